@@ -0,0 +1,111 @@
+/* Exercises the IPASIR interface of pumpkin: incremental clauses, assumptions, failed assumptions and termination. */
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "ipasir.h"
+
+static int num_failures = 0;
+
+#define CHECK(condition)                                                         \
+    do {                                                                         \
+        if (!(condition)) {                                                      \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+            num_failures++;                                                      \
+        }                                                                        \
+    } while (0)
+
+static void add_clause(void *solver, const int32_t *literals) {
+    for (; *literals != 0; literals++) {
+        ipasir_add(solver, *literals);
+    }
+    ipasir_add(solver, 0);
+}
+
+static int terminate_immediately(void *data) {
+    int *num_calls = data;
+    (*num_calls)++;
+    return 1;
+}
+
+/* pigeons i = 0..n and holes j = 0..n-1, where variable i * n + j + 1 means that pigeon i is in hole j */
+static void add_pigeon_hole_clauses(void *solver, int n) {
+    for (int i = 0; i <= n; i++) {
+        for (int j = 0; j < n; j++) {
+            ipasir_add(solver, i * n + j + 1);
+        }
+        ipasir_add(solver, 0);
+    }
+    for (int j = 0; j < n; j++) {
+        for (int i = 0; i <= n; i++) {
+            for (int k = i + 1; k <= n; k++) {
+                int32_t clause[] = {-(i * n + j + 1), -(k * n + j + 1), 0};
+                add_clause(solver, clause);
+            }
+        }
+    }
+}
+
+int main(void) {
+    printf("c testing %s\n", ipasir_signature());
+
+    void *solver = ipasir_init();
+    int32_t clause_1[] = {1, 2, 0};
+    int32_t clause_2[] = {-1, 2, 0};
+    int32_t clause_3[] = {-2, 3, 0};
+    int32_t clause_4[] = {-4, 5, 0};
+    int32_t clause_5[] = {-5, 6, 0};
+    add_clause(solver, clause_1);
+    add_clause(solver, clause_2);
+    add_clause(solver, clause_3);
+    add_clause(solver, clause_4);
+    add_clause(solver, clause_5);
+
+    CHECK(ipasir_solve(solver) == 10);
+    CHECK(ipasir_val(solver, 2) == 2);
+    CHECK(ipasir_val(solver, -3) == 3);
+
+    /* the failed assumptions are the ones that imply the violated assumption */
+    ipasir_assume(solver, 4);
+    ipasir_assume(solver, 7);
+    ipasir_assume(solver, -6);
+    CHECK(ipasir_solve(solver) == 20);
+    CHECK(ipasir_failed(solver, 4));
+    CHECK(ipasir_failed(solver, -6));
+    CHECK(!ipasir_failed(solver, 7));
+
+    /* assumptions only hold for a single call */
+    CHECK(ipasir_solve(solver) == 10);
+    ipasir_assume(solver, -3);
+    CHECK(ipasir_solve(solver) == 20);
+    ipasir_assume(solver, 4);
+    CHECK(ipasir_solve(solver) == 10);
+    CHECK(ipasir_val(solver, 6) == 6);
+
+    /* clauses added after solving are kept */
+    int32_t clause_6[] = {-6, 0};
+    add_clause(solver, clause_6);
+    CHECK(ipasir_solve(solver) == 10);
+    CHECK(ipasir_val(solver, 4) == -4);
+    int32_t clause_7[] = {-3, 0};
+    add_clause(solver, clause_7);
+    CHECK(ipasir_solve(solver) == 20);
+    CHECK(ipasir_solve(solver) == 20);
+    ipasir_release(solver);
+
+    /* a terminate callback that returns nonzero interrupts solving */
+    solver = ipasir_init();
+    add_pigeon_hole_clauses(solver, 8);
+    int num_calls = 0;
+    ipasir_set_terminate(solver, &num_calls, terminate_immediately);
+    CHECK(ipasir_solve(solver) == 0);
+    CHECK(num_calls > 0);
+    ipasir_set_terminate(solver, NULL, NULL);
+    ipasir_release(solver);
+
+    if (num_failures > 0) {
+        fprintf(stderr, "%d checks failed\n", num_failures);
+        return EXIT_FAILURE;
+    }
+    printf("c all checks passed\n");
+    return EXIT_SUCCESS;
+}
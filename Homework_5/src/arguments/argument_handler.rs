@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::env;
 
+use crate::basic_types::PumpkinError;
+
 pub struct ArgumentHandler {
     categories: HashMap<String, Category>,
     arguments_integer: HashMap<String, IntegerEntry>,
@@ -9,6 +11,12 @@ pub struct ArgumentHandler {
     arguments_bool: HashMap<String, BoolEntry>,
 }
 
+impl Default for ArgumentHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ArgumentHandler {
     pub fn new() -> ArgumentHandler {
         ArgumentHandler {
@@ -21,7 +29,7 @@ impl ArgumentHandler {
     }
 
     pub fn print_if_empty_arguments_and_exit(&self) {
-        if env::args().skip(0).count() <= 1 {
+        if env::args().count() <= 1 {
             println!("----------------------");
             println!("No arguments found. Pass the arguments '-help' to see info.");
             std::process::exit(0);
@@ -37,8 +45,8 @@ impl ArgumentHandler {
         println!("Displaying argument help information.");
         for entry in &self.categories {
             println!("----------------------");
-            println!("Category: {}. {}", *entry.0, (*entry.1).short_description);
-            for arg in &(*entry.1).arguments {
+            println!("Category: {}. {}", *entry.0, entry.1.short_description);
+            for arg in &entry.1.arguments {
                 match arg.data_type {
                     DataType::Integer => {
                         let arg_info = self.arguments_integer.get(&arg.name).unwrap();
@@ -93,7 +101,7 @@ impl ArgumentHandler {
         for entry in &self.categories {
             println!("----------------------");
             println!("Category: {}.", *entry.0);
-            for arg in &(*entry.1).arguments {
+            for arg in &entry.1.arguments {
                 match arg.data_type {
                     DataType::Integer => {
                         let arg_info = self.arguments_integer.get(&arg.name).unwrap();
@@ -124,7 +132,7 @@ impl ArgumentHandler {
         for entry in &self.categories {
             println!("----------------------");
             println!("Category: {}.", *entry.0);
-            for arg in &(*entry.1).arguments {
+            for arg in &entry.1.arguments {
                 match arg.data_type {
                     DataType::Integer => {
                         let arg_info = self.arguments_integer.get(&arg.name).unwrap();
@@ -190,105 +198,152 @@ impl ArgumentHandler {
         }
     }
 
-    pub fn set_integer_argument(&mut self, argument_name: &str, value: i64) {
-        assert!(self.arguments_integer.contains_key(argument_name));
-
-        let entry = self.arguments_integer.get_mut(argument_name).unwrap();
-
-        assert!(
-            entry.min_value <= value && value <= entry.max_value,
-            "The value '{value}' supplied for argument '{argument_name}' is not within the allowed range [{}, {}]",
-            entry.min_value,
-            entry.max_value
-        );
+    pub fn set_integer_argument(
+        &mut self,
+        argument_name: &str,
+        value: i64,
+    ) -> Result<(), PumpkinError> {
+        let entry = self
+            .arguments_integer
+            .get_mut(argument_name)
+            .ok_or_else(|| PumpkinError::invalid_argument(argument_name, "Unknown argument."))?;
+
+        if value < entry.min_value || entry.max_value < value {
+            return Err(PumpkinError::invalid_argument(
+                argument_name,
+                format!(
+                    "The value '{value}' is not within the allowed range [{}, {}].",
+                    entry.min_value, entry.max_value
+                ),
+            ));
+        }
 
         entry.current_value = value;
+        Ok(())
     }
 
-    pub fn set_float_argument(&mut self, argument_name: &str, value: f64) {
-        assert!(self.arguments_float.contains_key(argument_name));
-
-        let entry = self.arguments_float.get_mut(argument_name).unwrap();
-
-        assert!(
-            entry.min_value <= value && value <= entry.max_value,
-            "The value '{value}' supplied for argument '{argument_name}' is not within the allowed range [{}, {}]",
-            entry.min_value,
-            entry.max_value
-        );
+    pub fn set_float_argument(
+        &mut self,
+        argument_name: &str,
+        value: f64,
+    ) -> Result<(), PumpkinError> {
+        let entry = self
+            .arguments_float
+            .get_mut(argument_name)
+            .ok_or_else(|| PumpkinError::invalid_argument(argument_name, "Unknown argument."))?;
+
+        if !(entry.min_value <= value && value <= entry.max_value) {
+            return Err(PumpkinError::invalid_argument(
+                argument_name,
+                format!(
+                    "The value '{value}' is not within the allowed range [{}, {}].",
+                    entry.min_value, entry.max_value
+                ),
+            ));
+        }
 
         entry.current_value = value;
+        Ok(())
     }
 
-    pub fn set_string_argument(&mut self, argument_name: &str, value: &str) {
-        assert!(self.arguments_string.contains_key(argument_name));
-
-        let arg_info = self.arguments_string.get_mut(argument_name).unwrap();
-
-        assert!(
-            arg_info.allowed_values.is_empty() ||
-            arg_info.allowed_values.iter().any(|s| *s == value),
-            "The value '{value}' supplied for argument '{argument_name}' is not within the allowed values ({{{}}}).",
-            arg_info.allowed_values.join(", ")
-        );
+    pub fn set_string_argument(
+        &mut self,
+        argument_name: &str,
+        value: &str,
+    ) -> Result<(), PumpkinError> {
+        let arg_info = self
+            .arguments_string
+            .get_mut(argument_name)
+            .ok_or_else(|| PumpkinError::invalid_argument(argument_name, "Unknown argument."))?;
+
+        if !arg_info.allowed_values.is_empty()
+            && !arg_info.allowed_values.iter().any(|s| *s == value)
+        {
+            return Err(PumpkinError::invalid_argument(
+                argument_name,
+                format!(
+                    "The value '{value}' is not within the allowed values ({{{}}}).",
+                    arg_info.allowed_values.join(", ")
+                ),
+            ));
+        }
 
         arg_info.current_value = value.to_string();
+        Ok(())
     }
 
-    pub fn set_bool_argument(&mut self, argument_name: &str, value: bool) {
-        assert!(self.arguments_bool.contains_key(argument_name));
-
+    pub fn set_bool_argument(
+        &mut self,
+        argument_name: &str,
+        value: bool,
+    ) -> Result<(), PumpkinError> {
         self.arguments_bool
             .get_mut(argument_name)
-            .unwrap()
+            .ok_or_else(|| PumpkinError::invalid_argument(argument_name, "Unknown argument."))?
             .current_value = value;
+        Ok(())
     }
 
-    pub fn parse_command_line_arguments(&mut self) {
+    pub fn parse_command_line_arguments(&mut self) -> Result<(), PumpkinError> {
         //skip the first argument since it does not contain user arguments
-        for argument in env::args().skip(1) {
-            assert!(!argument.is_empty(), "Found empty command line argument.");
-            assert!(
-                argument.starts_with('-'),
-                "Each command line argument is expected to start with '-'."
-            );
-            assert!(
-                argument.find('=').is_some(),
-                "Found command line argument without '='"
-            );
-
-            let argument_name = &argument.to_string()[1..argument.find('=').unwrap()];
-            let raw_argument_value = &argument.to_string()[(argument.find('=').unwrap() + 1)..];
-            let argument_type = self.get_argument_type(argument_name);
-            assert!(argument_type.is_some(), "Unknown argument: {argument_name}");
-
-            match argument_type.unwrap() {
+        self.parse_arguments(env::args().skip(1))
+    }
+
+    //parses arguments of the form '-[name]=[value]', e.g., '-time-limit=60'
+    pub fn parse_arguments(
+        &mut self,
+        arguments: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<(), PumpkinError> {
+        for argument in arguments {
+            let argument = argument.as_ref();
+            let Some((argument_name, raw_argument_value)) = argument
+                .strip_prefix('-')
+                .and_then(|argument| argument.split_once('='))
+            else {
+                return Err(PumpkinError::invalid_argument(
+                    argument,
+                    "Each command line argument is expected to have the form '-[name]=[value]'.",
+                ));
+            };
+
+            let argument_type = self.get_argument_type(argument_name).ok_or_else(|| {
+                PumpkinError::invalid_argument(argument_name, "Unknown argument.")
+            })?;
+
+            match argument_type {
                 DataType::Integer => {
-                    let value = raw_argument_value.parse::<i64>().unwrap_or_else(|_| {
-                        panic!("{}", format!("Cannot convert argument value '{raw_argument_value}' into an integer.")) 
-                    });
-                    self.set_integer_argument(argument_name, value);
+                    let value = raw_argument_value.parse::<i64>().map_err(|_| {
+                        PumpkinError::invalid_argument(
+                            argument_name,
+                            format!("Cannot convert argument value '{raw_argument_value}' into an integer."),
+                        )
+                    })?;
+                    self.set_integer_argument(argument_name, value)?;
                 }
                 DataType::Float => {
-                    let value = raw_argument_value.parse::<f64>().unwrap_or_else(|_| {
-                        panic!("{}", format!("Cannot convert argument value '{raw_argument_value}' into a float.")) 
-                    });
-
-                    self.set_float_argument(argument_name, value);
+                    let value = raw_argument_value.parse::<f64>().map_err(|_| {
+                        PumpkinError::invalid_argument(
+                            argument_name,
+                            format!("Cannot convert argument value '{raw_argument_value}' into a float."),
+                        )
+                    })?;
+                    self.set_float_argument(argument_name, value)?;
                 }
                 DataType::String => {
-                    self.set_string_argument(argument_name, raw_argument_value);
+                    self.set_string_argument(argument_name, raw_argument_value)?;
                 }
                 DataType::Bool => {
-                    let value = raw_argument_value.parse::<bool>().unwrap_or_else(|_| {
-                        panic!("{}", format!("Cannot convert argument value '{raw_argument_value}' into a bool. 
-                             Remember to use 'true' and 'false' for bools rather than numeric values.")) 
-                    });
-
-                    self.set_bool_argument(argument_name, value);
+                    let value = raw_argument_value.parse::<bool>().map_err(|_| {
+                        PumpkinError::invalid_argument(
+                            argument_name,
+                            format!("Cannot convert argument value '{raw_argument_value}' into a bool. Remember to use 'true' and 'false' for bools rather than numeric values."),
+                        )
+                    })?;
+                    self.set_bool_argument(argument_name, value)?;
                 }
             }
         }
+        Ok(())
     }
 
     pub fn define_new_category(&mut self, category_name: &str, short_description: &str) {
@@ -353,7 +408,7 @@ impl ArgumentHandler {
     ) {
         self.basic_checks_on_input(argument_name, category_name, short_description);
         assert!(
-            allowed_values.is_empty() || allowed_values.iter().any(|s| *s == default_value),
+            allowed_values.is_empty() || allowed_values.contains(&default_value),
             "Default value must be within the allowed values for the parameter '{}'.",
             argument_name
         );
@@ -0,0 +1,203 @@
+use crate::pumpkin_asserts::pumpkin_assert_simple;
+
+use super::{IntegerVariable, Predicate};
+
+//a view on an integer variable x that represents the value 'scale * x + offset'
+//  views allow posting constraints on simple expressions such as 'x + 5' or '-x' without creating new variables
+//  negation is a view with a negative scale, which means that bounds of the view correspond to the opposite bounds of x,
+//      e.g., the lower bound of '-x' is minus the upper bound of x
+//predicates over views are mapped to predicates over the underlying variable, see 'lower_bound_predicate' and friends
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AffineView {
+    integer_variable: IntegerVariable,
+    scale: i32,
+    offset: i32,
+}
+
+impl AffineView {
+    pub fn new(integer_variable: IntegerVariable, scale: i32, offset: i32) -> AffineView {
+        pumpkin_assert_simple!(scale != 0, "The scale of a view cannot be zero.");
+        AffineView {
+            integer_variable,
+            scale,
+            offset,
+        }
+    }
+
+    pub fn get_integer_variable(&self) -> IntegerVariable {
+        self.integer_variable
+    }
+
+    pub fn get_scale(&self) -> i32 {
+        self.scale
+    }
+
+    pub fn get_offset(&self) -> i32 {
+        self.offset
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.scale == 1 && self.offset == 0
+    }
+
+    //returns true if the view increases when the underlying variable increases
+    //  in that case the lower bound of the view corresponds to the lower bound of the variable
+    pub fn preserves_bound_direction(&self) -> bool {
+        self.scale > 0
+    }
+
+    //returns true if the view takes values that fit in an i32 for all values of the underlying variable in [lower_bound, upper_bound]
+    //  views are only meaningful over such domains, see 'ConstraintSatisfactionSolver::add_propagator'
+    pub fn fits_domain(&self, lower_bound: i32, upper_bound: i32) -> bool {
+        [lower_bound, upper_bound]
+            .into_iter()
+            .all(|value| i32::try_from(self.transform_value_i64(value)).is_ok())
+    }
+
+    //returns the view 'scale * (current view)'
+    pub fn scaled(&self, scale: i32) -> AffineView {
+        let (new_scale, new_offset) = self
+            .scale
+            .checked_mul(scale)
+            .zip(self.offset.checked_mul(scale))
+            .unwrap_or_else(|| panic!("Scaling the view {} by {} overflows.", self, scale));
+        AffineView::new(self.integer_variable, new_scale, new_offset)
+    }
+
+    //returns the view '(current view) + offset'
+    pub fn offset_by(&self, offset: i32) -> AffineView {
+        let new_offset = self
+            .offset
+            .checked_add(offset)
+            .unwrap_or_else(|| panic!("Offsetting the view {} by {} overflows.", self, offset));
+        AffineView::new(self.integer_variable, self.scale, new_offset)
+    }
+
+    //returns the value of the view when the underlying variable takes the given value
+    //  the value is expected to fit in an i32, which holds for values in the domain of the variable, see 'fits_domain'
+    pub fn transform_value(&self, value: i32) -> i32 {
+        let view_value = self.transform_value_i64(value);
+        pumpkin_assert_simple!(
+            i32::try_from(view_value).is_ok(),
+            "The value {} of the view {} does not fit in an i32.",
+            view_value,
+            self
+        );
+        view_value as i32
+    }
+
+    //the computations of the view are done with i64, so that they cannot overflow for i32 values
+    fn transform_value_i64(&self, value: i32) -> i64 {
+        self.scale as i64 * value as i64 + self.offset as i64
+    }
+
+    //returns the value of the underlying variable for which the view takes the given value
+    //  returns None if no such integer value exists
+    pub fn inverse_value(&self, view_value: i32) -> Option<i32> {
+        let shifted_value = view_value as i64 - self.offset as i64;
+        if shifted_value % self.scale as i64 == 0 {
+            i32::try_from(shifted_value / self.scale as i64).ok()
+        } else {
+            None
+        }
+    }
+
+    //returns the predicate over the underlying variable that is equivalent to [view >= lower_bound]
+    pub fn lower_bound_predicate(&self, lower_bound: i32) -> Predicate {
+        let shifted_bound = lower_bound as i64 - self.offset as i64;
+        if self.scale > 0 {
+            Predicate::LowerBound {
+                integer_variable: self.integer_variable,
+                lower_bound: saturate(div_ceil(shifted_bound, self.scale as i64)),
+            }
+        } else {
+            Predicate::UpperBound {
+                integer_variable: self.integer_variable,
+                upper_bound: saturate(div_floor(shifted_bound, self.scale as i64)),
+            }
+        }
+    }
+
+    //returns the predicate over the underlying variable that is equivalent to [view <= upper_bound]
+    pub fn upper_bound_predicate(&self, upper_bound: i32) -> Predicate {
+        let shifted_bound = upper_bound as i64 - self.offset as i64;
+        if self.scale > 0 {
+            Predicate::UpperBound {
+                integer_variable: self.integer_variable,
+                upper_bound: saturate(div_floor(shifted_bound, self.scale as i64)),
+            }
+        } else {
+            Predicate::LowerBound {
+                integer_variable: self.integer_variable,
+                lower_bound: saturate(div_ceil(shifted_bound, self.scale as i64)),
+            }
+        }
+    }
+
+    //returns the predicate over the underlying variable that is equivalent to [view == value]
+    //  returns None if the view can never take the value
+    pub fn equality_predicate(&self, value: i32) -> Option<Predicate> {
+        self.inverse_value(value)
+            .map(|equality_constant| Predicate::Equal {
+                integer_variable: self.integer_variable,
+                equality_constant,
+            })
+    }
+
+    //returns the predicate over the underlying variable that is equivalent to [view != value]
+    //  returns None if the view can never take the value, i.e., the predicate always holds
+    pub fn not_equal_predicate(&self, value: i32) -> Option<Predicate> {
+        self.inverse_value(value)
+            .map(|not_equal_constant| Predicate::NotEqual {
+                integer_variable: self.integer_variable,
+                not_equal_constant,
+            })
+    }
+}
+
+impl From<IntegerVariable> for AffineView {
+    fn from(integer_variable: IntegerVariable) -> Self {
+        AffineView::new(integer_variable, 1, 0)
+    }
+}
+
+impl std::ops::Neg for AffineView {
+    type Output = AffineView;
+    fn neg(self) -> AffineView {
+        self.scaled(-1)
+    }
+}
+
+impl std::fmt::Display for AffineView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.scale, self.offset) {
+            (1, 0) => write!(f, "{}", self.integer_variable),
+            (-1, 0) => write!(f, "-{}", self.integer_variable),
+            (scale, 0) => write!(f, "{}*{}", scale, self.integer_variable),
+            (scale, offset) => write!(f, "{}*{} + {}", scale, self.integer_variable, offset),
+        }
+    }
+}
+
+//bounds beyond the range of i32 are clamped, which does not change the meaning of the predicate since domains fit in an i32
+fn saturate(value: i64) -> i32 {
+    value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+fn div_floor(numerator: i64, denominator: i64) -> i64 {
+    let quotient = numerator / denominator;
+    if numerator % denominator != 0 && ((numerator < 0) != (denominator < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn div_ceil(numerator: i64, denominator: i64) -> i64 {
+    let quotient = numerator / denominator;
+    if numerator % denominator != 0 && ((numerator < 0) == (denominator < 0)) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
@@ -1,6 +1,7 @@
-use super::Literal;
+use super::{Literal, Predicate};
 
 pub enum BranchingDecision {
     Assumption { assumption_literal: Literal },
     StandardDecision { decision_literal: Literal },
+    IntegerDecision { decision_predicate: Predicate },
 }
@@ -1,4 +1,4 @@
-use super::Literal;
+use super::{estimate_vec_memory_usage, Literal};
 use crate::pumpkin_asserts::*;
 
 pub struct Clause {
@@ -29,6 +29,10 @@ impl Clause {
         self.literals.len() as u32
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.literals.is_empty()
+    }
+
     pub fn is_learned(&self) -> bool {
         self.is_learned
     }
@@ -57,9 +61,16 @@ impl Clause {
     //note that this does _not_ delete the clause, it simply marks it as if it was deleted
     //  to delete a clause, use the ClauseManager
     //  could restrict access of this method in the future
+    //the literals are released, since a deleted clause is only kept so that its reference can be reused
     pub fn mark_deleted(&mut self) {
         pumpkin_assert_moderate!(!self.is_deleted);
         self.is_deleted = true;
+        self.literals = vec![];
+    }
+
+    //only counts the literals, since the clause itself is stored by the clause allocator
+    pub fn estimate_memory_usage(&self) -> usize {
+        estimate_vec_memory_usage(&self.literals)
     }
 
     pub fn mark_protection_against_deletion(&mut self) {
@@ -0,0 +1,381 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+};
+
+use super::PumpkinError;
+
+//the header line 'p [format] [num variables] [num clauses]', followed by '[top weight]' for the wcnf format
+pub(crate) struct DimacsHeader {
+    pub num_variables: u64,
+    pub num_clauses: u64,
+    pub top_weight: Option<u64>,
+}
+
+//a streaming parser for DIMACS cnf and wcnf files shared by the readers of 'Pumpkin' and 'Instance'
+//  the input is read byte by byte through a buffer, so files of several GBs are never held in memory
+//  the parser is tolerant towards common deviations from the format:
+//      tokens may be separated by any whitespace, and clauses may span several lines since only the zero terminates a clause
+//      the last clause may omit its terminating zero
+//      a '%' ends the clauses, as in the SATLIB benchmarks, e.g., 'uf20-01.cnf', which end with '%' followed by '0'
+//      comments, i.e., tokens starting with 'c', run until the end of the line and may appear anywhere
+//      headers that do not match the number of variables or clauses in the file are reported as warnings
+//  malformed tokens are reported as a 'PumpkinError' with the line and column of the token, where columns are counted in bytes
+pub(crate) struct DimacsParser<R: Read> {
+    reader: BufReader<R>,
+    file_location: String,
+    header: DimacsHeader,
+    //the position of the next byte
+    line: usize,
+    column: usize,
+    //the position of the first token of the last clause that was read
+    clause_line: usize,
+    clause_column: usize,
+    num_clauses_read: u64,
+    max_variable_index: u64,
+    is_finished: bool,
+    warnings: Vec<String>,
+    word: Vec<u8>,
+}
+
+impl DimacsParser<File> {
+    pub fn open(file_location: &str, format: &str) -> Result<DimacsParser<File>, PumpkinError> {
+        let file = File::open(file_location).map_err(|error| PumpkinError::Io {
+            file_location: file_location.to_string(),
+            message: error.to_string(),
+        })?;
+        DimacsParser::new(file, file_location, format)
+    }
+}
+
+impl<R: Read> DimacsParser<R> {
+    //reads the header of the given format, i.e., 'cnf' or 'wcnf'
+    //  the file location is only used for reporting errors
+    pub fn new(
+        reader: R,
+        file_location: &str,
+        format: &str,
+    ) -> Result<DimacsParser<R>, PumpkinError> {
+        let mut parser = DimacsParser {
+            reader: BufReader::with_capacity(1 << 16, reader),
+            file_location: file_location.to_string(),
+            header: DimacsHeader {
+                num_variables: 0,
+                num_clauses: 0,
+                top_weight: None,
+            },
+            line: 1,
+            column: 1,
+            clause_line: 1,
+            clause_column: 1,
+            num_clauses_read: 0,
+            max_variable_index: 0,
+            is_finished: false,
+            warnings: vec![],
+            word: vec![],
+        };
+        parser.read_header(format)?;
+        Ok(parser)
+    }
+
+    pub fn header(&self) -> &DimacsHeader {
+        &self.header
+    }
+
+    //the largest variable index that has been read, which may exceed the number of variables in the header
+    pub fn max_variable_index(&self) -> u64 {
+        self.max_variable_index
+    }
+
+    //reads the next clause into the buffer, returning false once all clauses have been read
+    pub fn next_clause(&mut self, literals: &mut Vec<i64>) -> Result<bool, PumpkinError> {
+        literals.clear();
+        match self.next_integer()? {
+            Some(first_integer) => {
+                self.read_clause(first_integer, literals)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    //reads the weight and literals of the next clause of a wcnf file, returning None once all clauses have been read
+    pub fn next_weighted_clause(
+        &mut self,
+        literals: &mut Vec<i64>,
+    ) -> Result<Option<u64>, PumpkinError> {
+        literals.clear();
+        let Some((weight, line, column)) = self.next_integer()? else {
+            return Ok(None);
+        };
+        if weight <= 0 {
+            return Err(PumpkinError::parse_error(
+                line,
+                column,
+                format!("Expected a positive clause weight, found '{}'.", weight),
+            ));
+        }
+
+        match self.next_integer()? {
+            Some(first_integer) => self.read_clause(first_integer, literals)?,
+            None => self.finish_clause(line, column),
+        }
+        self.clause_line = line;
+        self.clause_column = column;
+        Ok(Some(weight as u64))
+    }
+
+    //an error pointing to the start of the last clause that was read
+    pub fn clause_error(&self, message: impl Into<String>) -> PumpkinError {
+        PumpkinError::parse_error(self.clause_line, self.clause_column, message)
+    }
+
+    //returns the warnings about the file, including mismatches with the header that are only known after reading all clauses
+    pub fn finish(mut self) -> Vec<String> {
+        if self.header.num_clauses != self.num_clauses_read {
+            self.warnings.push(format!(
+                "The header declares {} clauses but the file contains {}.",
+                self.header.num_clauses, self.num_clauses_read
+            ));
+        }
+        if self.max_variable_index < self.header.num_variables {
+            self.warnings.push(format!(
+                "The header declares {} variables but the largest variable index in the file is {}.",
+                self.header.num_variables, self.max_variable_index
+            ));
+        }
+        self.warnings
+    }
+}
+
+//methods for reading tokens
+impl<R: Read> DimacsParser<R> {
+    fn peek(&mut self) -> Result<Option<u8>, PumpkinError> {
+        match self.reader.fill_buf() {
+            Ok(buffer) => Ok(buffer.first().copied()),
+            Err(error) => Err(PumpkinError::Io {
+                file_location: self.file_location.clone(),
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    fn advance(&mut self, byte: u8) {
+        self.reader.consume(1);
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    //skips whitespace and comments
+    //  when reading the rest of a line, e.g., the header, the newline is kept and comments are not recognised
+    fn skip_whitespace(&mut self, is_within_line: bool) -> Result<(), PumpkinError> {
+        while let Some(byte) = self.peek()? {
+            match byte {
+                b'\n' if is_within_line => return Ok(()),
+                b'c' if !is_within_line => {
+                    while let Some(byte) = self.peek()? {
+                        if byte == b'\n' {
+                            break;
+                        }
+                        self.advance(byte);
+                    }
+                }
+                _ if byte.is_ascii_whitespace() => self.advance(byte),
+                _ => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+
+    //reads the bytes up to the next whitespace into the word buffer, which is reused to avoid allocating for every token
+    fn read_word(&mut self) -> Result<(), PumpkinError> {
+        self.word.clear();
+        while let Some(byte) = self.peek()? {
+            if byte.is_ascii_whitespace() {
+                break;
+            }
+            self.word.push(byte);
+            self.advance(byte);
+        }
+        Ok(())
+    }
+
+    //reads the next integer together with its position, or None if the clauses have ended
+    fn next_integer(&mut self) -> Result<Option<(i64, usize, usize)>, PumpkinError> {
+        if self.is_finished {
+            return Ok(None);
+        }
+
+        self.skip_whitespace(false)?;
+        match self.peek()? {
+            Some(b'%') | None => {
+                self.is_finished = true;
+                Ok(None)
+            }
+            Some(_) => self.read_integer().map(Some),
+        }
+    }
+
+    fn read_integer(&mut self) -> Result<(i64, usize, usize), PumpkinError> {
+        let (line, column) = (self.line, self.column);
+        self.read_word()?;
+
+        let (is_negative, digits) = match self.word.split_first() {
+            Some((b'-', digits)) => (true, digits),
+            _ => (false, self.word.as_slice()),
+        };
+        let mut value = if digits.is_empty() { None } else { Some(0u64) };
+        for digit in digits {
+            value = match value {
+                Some(value) if digit.is_ascii_digit() => Some(value * 10 + (digit - b'0') as u64),
+                _ => None,
+            };
+            //literals and weights need to fit the 32-bit variable indices of the solver
+            if value.is_some_and(|value| value > u32::MAX as u64) {
+                return Err(PumpkinError::parse_error(
+                    line,
+                    column,
+                    format!(
+                        "The integer '{}' is too large.",
+                        String::from_utf8_lossy(&self.word)
+                    ),
+                ));
+            }
+        }
+
+        match value {
+            Some(value) if is_negative => Ok((-(value as i64), line, column)),
+            Some(value) => Ok((value as i64, line, column)),
+            None if self.word == b"p" => Err(PumpkinError::parse_error(
+                line,
+                column,
+                "Unexpected header after the first clause.",
+            )),
+            None => Err(PumpkinError::parse_error(
+                line,
+                column,
+                format!(
+                    "Expected an integer, found '{}'.",
+                    String::from_utf8_lossy(&self.word)
+                ),
+            )),
+        }
+    }
+
+    fn read_header(&mut self, format: &str) -> Result<(), PumpkinError> {
+        let has_top_weight = format == "wcnf";
+        let expected_header = if has_top_weight {
+            "p wcnf [num variables] [num clauses] [top weight]"
+        } else {
+            "p cnf [num variables] [num clauses]"
+        };
+
+        self.skip_whitespace(false)?;
+        let (line, column) = (self.line, self.column);
+        self.read_word()?;
+        let is_header = self.word == b"p" && {
+            self.skip_whitespace(true)?;
+            self.read_word()?;
+            self.word == format.as_bytes()
+        };
+        if !is_header {
+            return Err(PumpkinError::parse_error(
+                line,
+                column,
+                format!("Expected the header '{}'.", expected_header),
+            ));
+        }
+
+        let mut fields = vec![];
+        loop {
+            self.skip_whitespace(true)?;
+            match self.peek()? {
+                Some(b'\n') | None => break,
+                Some(_) => {
+                    let (integer, line, column) = self.read_integer()?;
+                    if integer < 0 {
+                        return Err(PumpkinError::parse_error(
+                            line,
+                            column,
+                            format!("Expected a nonnegative integer, found '{}'.", integer),
+                        ));
+                    }
+                    fields.push(integer as u64);
+                }
+            }
+        }
+
+        let num_fields = if has_top_weight { 3 } else { 2 };
+        if fields.len() < num_fields {
+            return Err(PumpkinError::parse_error(
+                line,
+                column,
+                format!(
+                    "Expected the header '{}', found {} numbers.",
+                    expected_header,
+                    fields.len()
+                ),
+            ));
+        } else if fields.len() > num_fields {
+            self.warnings.push(format!(
+                "Ignoring the {} additional numbers in the header.",
+                fields.len() - num_fields
+            ));
+        }
+
+        self.header = DimacsHeader {
+            num_variables: fields[0],
+            num_clauses: fields[1],
+            top_weight: fields.get(2).copied().filter(|_| has_top_weight),
+        };
+        Ok(())
+    }
+
+    //reads the literals up to the terminating zero, where the first integer has already been read
+    fn read_clause(
+        &mut self,
+        first_integer: (i64, usize, usize),
+        literals: &mut Vec<i64>,
+    ) -> Result<(), PumpkinError> {
+        let (_, line, column) = first_integer;
+        let mut next_integer = Some(first_integer);
+        while let Some((literal, _, _)) = next_integer {
+            if literal == 0 {
+                self.finish_clause(line, column);
+                return Ok(());
+            }
+            self.record_variable_index(literal.unsigned_abs());
+            literals.push(literal);
+            next_integer = self.next_integer()?;
+        }
+
+        self.warnings.push(format!(
+            "The clause starting at line {} is not terminated by a zero.",
+            line
+        ));
+        self.finish_clause(line, column);
+        Ok(())
+    }
+
+    fn finish_clause(&mut self, line: usize, column: usize) {
+        self.clause_line = line;
+        self.clause_column = column;
+        self.num_clauses_read += 1;
+    }
+
+    fn record_variable_index(&mut self, variable_index: u64) {
+        if variable_index > self.header.num_variables
+            && self.max_variable_index <= self.header.num_variables
+        {
+            self.warnings.push(format!(
+                "The header declares {} variables but the file uses variable {}, additional variables are created.",
+                self.header.num_variables, variable_index
+            ));
+        }
+        self.max_variable_index = self.max_variable_index.max(variable_index);
+    }
+}
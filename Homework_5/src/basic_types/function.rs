@@ -14,6 +14,12 @@ pub struct Function {
     constant_term: u64,
 }
 
+impl Default for Function {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Function {
     pub fn new() -> Function {
         Function {
@@ -59,11 +65,13 @@ impl Function {
         self.constant_term += value;
     }
 
-    pub fn get_weighted_literals(&self) -> std::collections::hash_map::Iter<Literal, u64> {
+    pub fn get_weighted_literals(&self) -> std::collections::hash_map::Iter<'_, Literal, u64> {
         self.weighted_literals.iter()
     }
 
-    pub fn get_weighted_integers(&self) -> std::collections::hash_map::Iter<IntegerVariable, u64> {
+    pub fn get_weighted_integers(
+        &self,
+    ) -> std::collections::hash_map::Iter<'_, IntegerVariable, u64> {
         self.weighted_integers.iter()
     }
 
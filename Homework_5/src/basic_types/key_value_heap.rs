@@ -6,6 +6,9 @@ It allows increasing/decreasing the values of its entries
 
 //the implementation could be more efficient, currently more comparisons are done than necessary when sifting, and possibly the recursion could be unrolled
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::Random;
 use crate::pumpkin_asserts::{pumpkin_assert_moderate, pumpkin_assert_simple};
 
 #[derive(Default)]
@@ -61,6 +64,21 @@ impl KeyValueHeap {
         }
     }
 
+    //sets the value of the element of 'key', which may be smaller than its current value, unlike with 'increment'
+    //  O(logn)
+    pub fn set_value(&mut self, key: u32, value: f64) {
+        let position = self.map_key_to_position[key as usize];
+        let old_value = self.values[position as usize];
+        self.values[position as usize] = value;
+        if self.is_key_present(key) {
+            if value > old_value {
+                self.sift_up(position);
+            } else {
+                self.sift_down(position);
+            }
+        }
+    }
+
     //Restores the entry with key 'key' to the heap if the key is not present, otherwise does nothing
     //  its value is the previous value used before 'delete_key' was called.
     //  O(logn)
@@ -123,7 +141,7 @@ impl KeyValueHeap {
     //  the random_seed controls the randomisation procedure in the following way:
     //      random_seed == -2: the order of the keys initially in the tree structure will be in a fixed increasing order
     //      random_seed == -1: the random_seed will be replaced by the current time, and then randomises the order
-    //      random_seed > 0: the randomises the order using the given seen
+    //      random_seed >= 0: randomises the order using the given seed
     pub fn reset(&mut self, random_seed: i64) {
         pumpkin_assert_simple!(random_seed >= -2);
 
@@ -138,8 +156,22 @@ impl KeyValueHeap {
             *iter.1 = iter.0 as u32;
         }
 
-        if random_seed != -2 {
-            todo!("Random seeding not implemented yet.");
+        if random_seed == -2 {
+            return;
+        }
+
+        let random_seed = if random_seed == -1 {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_nanos() as u64)
+        } else {
+            random_seed as u64
+        };
+
+        //all values are zero, so any order of the keys is a valid heap
+        Random::new(random_seed).shuffle(&mut self.map_position_to_key);
+        for (position, key) in self.map_position_to_key.iter().enumerate() {
+            self.map_key_to_position[*key as usize] = position as u32;
         }
     }
 
@@ -179,14 +211,15 @@ impl KeyValueHeap {
     }
 
     fn is_heap_locally(&self, position: u32) -> bool {
-        //either the node is a left, or it satisfies the heap property (the value of the parent is at least as large as the values of its child)
+        //either the node is a leaf, or it satisfies the heap property (the value of the parent is at least as large as the values of its child)
         let left_child_position = KeyValueHeap::get_left_child_position(position);
         let right_child_position = KeyValueHeap::get_right_child_position(position);
 
         self.is_leaf(position)
             || (self.values[position as usize] >= self.values[left_child_position as usize]
-                && right_child_position < self.end_position
-                && self.values[position as usize] >= self.values[right_child_position as usize])
+                && (right_child_position >= self.end_position
+                    || self.values[position as usize]
+                        >= self.values[right_child_position as usize]))
     }
 
     fn is_leaf(&self, position: u32) -> bool {
@@ -0,0 +1,16 @@
+use std::mem::size_of;
+
+//helpers for estimating the memory used by the data structures of the solver, see 'ConstraintSatisfactionSolver::estimate_memory_usage'
+//  the estimates count the allocated capacity of vectors, but not the overhead of the allocator
+
+pub fn estimate_vec_memory_usage<T>(vector: &Vec<T>) -> usize {
+    vector.capacity() * size_of::<T>()
+}
+
+pub fn estimate_nested_vec_memory_usage<T>(vectors: &Vec<Vec<T>>) -> usize {
+    estimate_vec_memory_usage(vectors)
+        + vectors
+            .iter()
+            .map(|vector| estimate_vec_memory_usage(vector))
+            .sum::<usize>()
+}
@@ -1,8 +1,10 @@
+mod affine_view;
 mod branching_decision;
 mod clause;
 mod clause_addition_outcome;
 mod clause_reference;
 mod csp_solver_execution_flag;
+mod dimacs_parser;
 mod enqueue_status;
 mod file_format;
 mod function;
@@ -10,6 +12,8 @@ mod instance;
 mod integer_variable;
 mod key_value_heap;
 mod literal;
+mod memory_usage;
+mod model_execution_flag;
 mod predicate;
 mod propagation_status_clausal;
 mod propagation_status_cp;
@@ -17,18 +21,25 @@ mod propagation_status_cp_one_step;
 mod propagator_identifier;
 mod propositional_conjunction;
 mod propositional_variable;
+mod pumpkin_error;
 mod pumpkin_execution_flag;
+mod random;
+mod search_annotation;
 mod solution;
+mod solution_enumeration_flag;
 mod solution_tracker;
 mod solution_value_pair;
+mod solver_statistics;
 mod stopwatch;
 mod weighted_literal;
 
+pub use affine_view::AffineView;
 pub use branching_decision::BranchingDecision;
 pub use clause::Clause;
 pub use clause_addition_outcome::ClauseAdditionOutcome;
 pub use clause_reference::ClauseReference;
 pub use csp_solver_execution_flag::CSPSolverExecutionFlag;
+pub(crate) use dimacs_parser::DimacsParser;
 pub use enqueue_status::EnqueueStatus;
 pub use file_format::FileFormat;
 pub use function::Function;
@@ -37,6 +48,8 @@ pub use integer_variable::IntegerVariable;
 pub use integer_variable::IntegerVariableGeneratorIterator;
 pub use key_value_heap::KeyValueHeap;
 pub use literal::Literal;
+pub use memory_usage::{estimate_nested_vec_memory_usage, estimate_vec_memory_usage};
+pub use model_execution_flag::ModelExecutionFlag;
 pub use predicate::Predicate;
 pub use propagation_status_clausal::PropagationStatusClausal;
 pub use propagation_status_cp::PropagationStatusCP;
@@ -45,9 +58,16 @@ pub use propagator_identifier::PropagatorIdentifier;
 pub use propositional_conjunction::PropositionalConjunction;
 pub use propositional_variable::PropositionalVariable;
 pub use propositional_variable::PropositionalVariableGeneratorIterator;
+pub use pumpkin_error::PumpkinError;
 pub use pumpkin_execution_flag::PumpkinExecutionFlag;
+pub use random::Random;
+pub use search_annotation::IntegerValueSelectionStrategy;
+pub use search_annotation::IntegerVariableSelectionStrategy;
+pub use search_annotation::SearchAnnotation;
 pub use solution::Solution;
+pub use solution_enumeration_flag::SolutionEnumerationFlag;
 pub use solution_tracker::SolutionTracker;
 pub use solution_value_pair::SolutionValuePair;
+pub use solver_statistics::{PropagatorStatistics, SolverStatistics};
 pub use stopwatch::Stopwatch;
 pub use weighted_literal::WeightedLiteral;
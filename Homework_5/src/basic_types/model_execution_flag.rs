@@ -0,0 +1,11 @@
+use super::Solution;
+
+//the outcome of 'Model::solve'
+//  satisfaction problems report their solution as feasible, whereas optimisation problems report it as optimal once no better solution exists
+//  an optimisation problem that times out after finding a solution reports the best solution found as feasible
+pub enum ModelExecutionFlag {
+    Optimal { optimal_solution: Solution },
+    Feasible { feasible_solution: Solution },
+    Infeasible,
+    Timeout,
+}
@@ -1,6 +1,6 @@
 use super::IntegerVariable;
 
-#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy)]
 pub enum Predicate {
     LowerBound {
         integer_variable: IntegerVariable,
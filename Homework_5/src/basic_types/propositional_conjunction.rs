@@ -5,6 +5,12 @@ pub struct PropositionalConjunction {
     predicates_in_conjunction: Vec<Predicate>,
 }
 
+impl Default for PropositionalConjunction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PropositionalConjunction {
     pub fn new() -> PropositionalConjunction {
         PropositionalConjunction {
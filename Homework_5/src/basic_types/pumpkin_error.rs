@@ -0,0 +1,90 @@
+//an error reported by the library entry points instead of aborting, e.g., when reading a malformed file or constructing an invalid model
+//  lines and columns of parse errors are one-based, as reported by text editors
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PumpkinError {
+    Io {
+        file_location: String,
+        message: String,
+    },
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    InvalidArgument {
+        argument: String,
+        message: String,
+    },
+    InvalidModel {
+        message: String,
+    },
+    UnsupportedFileFormat {
+        message: String,
+    },
+}
+
+impl PumpkinError {
+    pub fn parse_error(line: usize, column: usize, message: impl Into<String>) -> PumpkinError {
+        PumpkinError::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_argument(argument: &str, message: impl Into<String>) -> PumpkinError {
+        PumpkinError::InvalidArgument {
+            argument: argument.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_model(message: impl Into<String>) -> PumpkinError {
+        PumpkinError::InvalidModel {
+            message: message.into(),
+        }
+    }
+
+    pub fn unsupported_file_format(message: impl Into<String>) -> PumpkinError {
+        PumpkinError::UnsupportedFileFormat {
+            message: message.into(),
+        }
+    }
+
+    //prefixes the message with the context in which the error was found, e.g., the constraint of a model
+    pub fn with_context(mut self, context: &str) -> PumpkinError {
+        match &mut self {
+            PumpkinError::Io { message, .. }
+            | PumpkinError::Parse { message, .. }
+            | PumpkinError::InvalidArgument { message, .. }
+            | PumpkinError::InvalidModel { message }
+            | PumpkinError::UnsupportedFileFormat { message } => {
+                *message = format!("{}: {}", context, message)
+            }
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for PumpkinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PumpkinError::Io {
+                file_location,
+                message,
+            } => write!(f, "cannot read the file '{}': {}", file_location, message),
+            PumpkinError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            PumpkinError::InvalidArgument { argument, message } => {
+                write!(f, "argument '{}': {}", argument, message)
+            }
+            PumpkinError::InvalidModel { message }
+            | PumpkinError::UnsupportedFileFormat { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for PumpkinError {}
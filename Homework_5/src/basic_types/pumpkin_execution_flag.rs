@@ -1,6 +1,8 @@
 use super::Solution;
 
+//optimal solutions are only reported by optimisation, see 'Pumpkin::solve_with_lns'
 pub enum PumpkinExecutionFlag {
+    Optimal { optimal_solution: Solution },
     Feasible { feasible_solution: Solution },
     Infeasible,
     Timeout,
@@ -0,0 +1,38 @@
+//a small pseudo-random number generator for randomised decisions of the solver, e.g., the initial variable order and the neighbourhoods of large neighbourhood search
+//  uses the splitmix64 procedure, which avoids depending on a random number crate and gives the same sequence on every platform
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    //returns a number in [0, bound), where the bound needs to be positive
+    //  the modulo introduces a slight bias, which is negligible for the bounds used by the solver
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    //returns a number in [0, 1) with 53 random bits, the precision of f64
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    //shuffles the elements with the Fisher-Yates procedure
+    pub fn shuffle<T>(&mut self, elements: &mut [T]) {
+        for position in (1..elements.len()).rev() {
+            let other_position = self.next_below(position + 1);
+            elements.swap(position, other_position);
+        }
+    }
+}
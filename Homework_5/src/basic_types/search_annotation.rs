@@ -0,0 +1,106 @@
+use crate::{engine::AssignmentsInteger, pumpkin_asserts::pumpkin_assert_moderate};
+
+use super::{IntegerVariable, Predicate};
+
+//a search annotation describes how the solver branches over a group of integer variables
+//  similar to 'int_search' in MiniZinc, the annotation consists of the variables, a variable selection strategy, and a value selection strategy
+//  the solver considers annotations in the order they were added, i.e., the next annotation is only used once all variables of the previous annotations are fixed
+pub struct SearchAnnotation {
+    pub integer_variables: Vec<IntegerVariable>,
+    pub variable_selection_strategy: IntegerVariableSelectionStrategy,
+    pub value_selection_strategy: IntegerValueSelectionStrategy,
+}
+
+impl SearchAnnotation {
+    pub fn new(
+        integer_variables: Vec<IntegerVariable>,
+        variable_selection_strategy: IntegerVariableSelectionStrategy,
+        value_selection_strategy: IntegerValueSelectionStrategy,
+    ) -> SearchAnnotation {
+        SearchAnnotation {
+            integer_variables,
+            variable_selection_strategy,
+            value_selection_strategy,
+        }
+    }
+}
+
+//determines which unfixed variable of a search annotation is branched on next
+//  ties are broken by the order of the variables in the annotation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegerVariableSelectionStrategy {
+    //the first unfixed variable
+    InputOrder,
+    //the variable with the smallest domain size
+    FirstFail,
+    //the variable with the smallest ratio of domain size to weighted degree
+    //  the weighted degree of a variable is the sum of the weights of its propagators, where the weight of a propagator is one plus the number of conflicts it reported
+    DomWDeg,
+    //the variable with the smallest lower bound
+    Smallest,
+    //the variable with the largest upper bound
+    Largest,
+}
+
+//determines the decision that is posted on the selected variable
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegerValueSelectionStrategy {
+    //[x <= lb(x)], i.e., assign the smallest value
+    Min,
+    //[x >= ub(x)], i.e., assign the largest value
+    Max,
+    //[x <= (lb(x) + ub(x)) / 2], i.e., exclude the upper half of the domain
+    Split,
+    //[x >= (lb(x) + ub(x)) / 2 + 1], i.e., exclude the lower half of the domain
+    ReverseSplit,
+    //[x == v], where v is the middle value of the values in the domain
+    Median,
+}
+
+impl IntegerValueSelectionStrategy {
+    pub fn select_decision_predicate(
+        &self,
+        integer_variable: IntegerVariable,
+        assignments_integer: &AssignmentsInteger,
+    ) -> Predicate {
+        pumpkin_assert_moderate!(
+            !assignments_integer.is_integer_variable_assigned(integer_variable),
+            "Cannot branch on an assigned integer variable."
+        );
+
+        let lower_bound = assignments_integer.get_lower_bound(integer_variable);
+        let upper_bound = assignments_integer.get_upper_bound(integer_variable);
+        let middle_value = lower_bound + (upper_bound - lower_bound) / 2;
+
+        match self {
+            IntegerValueSelectionStrategy::Min => Predicate::UpperBound {
+                integer_variable,
+                upper_bound: lower_bound,
+            },
+            IntegerValueSelectionStrategy::Max => Predicate::LowerBound {
+                integer_variable,
+                lower_bound: upper_bound,
+            },
+            IntegerValueSelectionStrategy::Split => Predicate::UpperBound {
+                integer_variable,
+                upper_bound: middle_value,
+            },
+            IntegerValueSelectionStrategy::ReverseSplit => Predicate::LowerBound {
+                integer_variable,
+                lower_bound: middle_value + 1,
+            },
+            IntegerValueSelectionStrategy::Median => {
+                let values_in_domain: Vec<i32> = (lower_bound..=upper_bound)
+                    .filter(|value| {
+                        assignments_integer.is_value_in_domain(integer_variable, *value)
+                    })
+                    .collect();
+
+                Predicate::Equal {
+                    integer_variable,
+                    equality_constant: values_in_domain[(values_in_domain.len() - 1) / 2],
+                }
+            }
+        }
+    }
+}
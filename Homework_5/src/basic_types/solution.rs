@@ -73,6 +73,21 @@ impl Solution {
         Solution::update_integer_values(&mut self.integer_values, assignments_integer);
     }
 
+    //the value line of the DIMACS output, e.g., "v 1 -2 3 0"
+    //  the root variable at index zero is not part of the problem, and the line is terminated by zero
+    pub fn format_dimacs_value_line(&self) -> String {
+        let mut line = String::from("v");
+        for (index, &truth_value) in self.truth_values.iter().enumerate().skip(1) {
+            if truth_value {
+                line.push_str(&format!(" {}", index));
+            } else {
+                line.push_str(&format!(" -{}", index));
+            }
+        }
+        line.push_str(" 0");
+        line
+    }
+
     pub fn get_literal_value(&self, literal: Literal) -> bool {
         if literal.is_positive() {
             self.truth_values[literal.get_propositional_variable()]
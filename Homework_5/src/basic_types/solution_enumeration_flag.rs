@@ -0,0 +1,15 @@
+pub enum SolutionEnumerationFlag {
+    AllSolutionsFound { num_solutions: u64 },
+    SolutionLimitReached { num_solutions: u64 },
+    Timeout { num_solutions: u64 },
+}
+
+impl SolutionEnumerationFlag {
+    pub fn num_solutions(&self) -> u64 {
+        match self {
+            SolutionEnumerationFlag::AllSolutionsFound { num_solutions }
+            | SolutionEnumerationFlag::SolutionLimitReached { num_solutions }
+            | SolutionEnumerationFlag::Timeout { num_solutions } => *num_solutions,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+//a snapshot of the counters of the solver, see 'ConstraintSatisfactionSolver::get_statistics'
+//  the statistics can be printed as name-value pairs, see 'get_entries', or written as a JSON object for benchmark scripts, see 'to_json'
+#[derive(Clone, Debug, Default)]
+pub struct SolverStatistics {
+    pub num_decisions: u64,
+    pub num_conflicts: u64,
+    pub num_propagations: u64,
+    pub num_restarts: u64,
+    //backtracks that undo only the conflict level rather than backjumping, see 'SolverOptions::chronological_backtracking'
+    pub num_chronological_backtracks: u64,
+    //rephases overwrite the saved phases at restarts, see 'RephasingStrategy'
+    pub num_rephases: u64,
+    //flips of local search, both before the search and when rephasing, see 'LocalSearch'
+    pub num_local_search_flips: u64,
+    pub num_ticks: u64,
+    //learned clauses include unit clauses, which are not stored in the clause database
+    pub num_learned_clauses: u64,
+    pub num_unit_clauses_learned: u64,
+    //the literal block distance of learned clauses at the time they were learned
+    pub average_learned_clause_lbd: f64,
+    pub num_deleted_learned_clauses: u64,
+    pub num_explanation_clauses: u64,
+    pub sat_propagation_time: Duration,
+    pub cp_propagation_time: Duration,
+    //see 'ConstraintSatisfactionSolver::estimate_memory_usage'
+    pub estimated_memory_usage_in_bytes: u64,
+    //learned clauses exchanged with other solvers, see 'ClauseSharing'
+    pub num_exported_shared_clauses: u64,
+    pub num_imported_shared_clauses: u64,
+    //propagators with the same name are aggregated, ordered by name
+    pub propagators: Vec<PropagatorStatistics>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PropagatorStatistics {
+    pub name: String,
+    pub num_propagators: u64,
+    pub num_calls: u64,
+    //the number of domain changes made by the propagators
+    pub num_propagations: u64,
+    pub num_conflicts: u64,
+}
+
+impl SolverStatistics {
+    //returns the statistics as name-value pairs in a fixed order, where the propagator statistics are prefixed by the name of the propagator
+    //  spaces in the names of propagators are replaced by underscores, so that names consist of a single word
+    pub fn get_entries(&self) -> Vec<(String, String)> {
+        let mut entries = self.get_solver_entries();
+        for propagator in &self.propagators {
+            let propagator_name = propagator.name.replace(' ', "_");
+            for (name, value) in [
+                ("propagators", propagator.num_propagators),
+                ("calls", propagator.num_calls),
+                ("propagations", propagator.num_propagations),
+                ("conflicts", propagator.num_conflicts),
+            ] {
+                entries.push((format!("{}_{}", propagator_name, name), value.to_string()));
+            }
+        }
+        entries
+    }
+
+    //the times are given in seconds, and the propagator statistics form an array of objects
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\n");
+        for (name, value) in self.get_solver_entries() {
+            json.push_str(&format!("  \"{}\": {},\n", name, value));
+        }
+
+        let propagators: Vec<String> = self
+            .propagators
+            .iter()
+            .map(|propagator| {
+                format!(
+                    "    {{\"name\": \"{}\", \"propagators\": {}, \"calls\": {}, \"propagations\": {}, \"conflicts\": {}}}",
+                    escape_json_string(&propagator.name),
+                    propagator.num_propagators,
+                    propagator.num_calls,
+                    propagator.num_propagations,
+                    propagator.num_conflicts
+                )
+            })
+            .collect();
+        if propagators.is_empty() {
+            json.push_str("  \"propagators\": []\n}\n");
+        } else {
+            json.push_str(&format!(
+                "  \"propagators\": [\n{}\n  ]\n}}\n",
+                propagators.join(",\n")
+            ));
+        }
+        json
+    }
+
+    //the statistics that do not belong to a single propagator, where all values are numbers
+    fn get_solver_entries(&self) -> Vec<(String, String)> {
+        vec![
+            ("decisions", self.num_decisions.to_string()),
+            ("conflicts", self.num_conflicts.to_string()),
+            ("propagations", self.num_propagations.to_string()),
+            ("restarts", self.num_restarts.to_string()),
+            (
+                "chronological_backtracks",
+                self.num_chronological_backtracks.to_string(),
+            ),
+            ("rephases", self.num_rephases.to_string()),
+            (
+                "local_search_flips",
+                self.num_local_search_flips.to_string(),
+            ),
+            ("ticks", self.num_ticks.to_string()),
+            ("learned_clauses", self.num_learned_clauses.to_string()),
+            (
+                "unit_clauses_learned",
+                self.num_unit_clauses_learned.to_string(),
+            ),
+            (
+                "average_learned_clause_lbd",
+                format!("{:.2}", self.average_learned_clause_lbd),
+            ),
+            (
+                "deleted_learned_clauses",
+                self.num_deleted_learned_clauses.to_string(),
+            ),
+            (
+                "explanation_clauses",
+                self.num_explanation_clauses.to_string(),
+            ),
+            (
+                "sat_propagation_time",
+                format!("{:.3}", self.sat_propagation_time.as_secs_f64()),
+            ),
+            (
+                "cp_propagation_time",
+                format!("{:.3}", self.cp_propagation_time.as_secs_f64()),
+            ),
+            (
+                "estimated_memory_mb",
+                format!(
+                    "{:.1}",
+                    self.estimated_memory_usage_in_bytes as f64 / (1 << 20) as f64
+                ),
+            ),
+            (
+                "shared_clauses_exported",
+                self.num_exported_shared_clauses.to_string(),
+            ),
+            (
+                "shared_clauses_imported",
+                self.num_imported_shared_clauses.to_string(),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+    }
+}
+
+fn escape_json_string(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(|character| match character {
+            '"' | '\\' => vec!['\\', character],
+            _ => vec![character],
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::basic_types::Literal;
+
+//the endpoint of a solver for exchanging learned clauses with the other solvers of a group, see 'PortfolioSolver'
+//  each solver owns one endpoint, with a queue for receiving clauses and a sender to the queue of every other solver of the group
+//  the queues are the channels of the standard library, which do not block the sender, so exporting never waits for the other solvers
+//  shared clauses are only meaningful if all solvers of the group hold the same constraints over the same variables,
+//  e.g., solvers that read the same file in the same way
+pub struct ClauseSharing {
+    senders: Vec<Sender<Vec<Literal>>>,
+    receiver: Receiver<Vec<Literal>>,
+    max_clause_length: usize,
+    max_clause_lbd: u32,
+    pub num_exported_clauses: u64,
+    pub num_imported_clauses: u64,
+}
+
+impl ClauseSharing {
+    //creates the endpoints of a group of solvers, where learned clauses are shared if both their length and their LBD are within the limits
+    //  unit clauses are always shared
+    pub fn create_group(
+        num_solvers: usize,
+        max_clause_length: usize,
+        max_clause_lbd: u32,
+    ) -> Vec<ClauseSharing> {
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..num_solvers).map(|_| channel()).unzip();
+
+        receivers
+            .into_iter()
+            .enumerate()
+            .map(|(index, receiver)| ClauseSharing {
+                senders: senders
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_index, _)| *other_index != index)
+                    .map(|(_, sender)| sender.clone())
+                    .collect(),
+                receiver,
+                max_clause_length,
+                max_clause_lbd,
+                num_exported_clauses: 0,
+                num_imported_clauses: 0,
+            })
+            .collect()
+    }
+
+    //sends the learned clause to the other solvers if it is short and has a low LBD
+    //  solvers that already finished are skipped
+    pub fn export_learned_clause(&mut self, literals: &[Literal], lbd: u32) {
+        if literals.len() > 1
+            && (literals.len() > self.max_clause_length || lbd > self.max_clause_lbd)
+        {
+            return;
+        }
+
+        self.num_exported_clauses += 1;
+        for sender in &self.senders {
+            let _ = sender.send(literals.to_vec());
+        }
+    }
+
+    //returns the clauses received since the last call, without waiting for new clauses
+    pub fn take_imported_clauses(&mut self) -> Vec<Vec<Literal>> {
+        let clauses: Vec<Vec<Literal>> = self.receiver.try_iter().collect();
+        self.num_imported_clauses += clauses.len() as u64;
+        clauses
+    }
+}
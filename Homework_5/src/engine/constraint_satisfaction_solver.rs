@@ -1,16 +1,24 @@
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use super::cp::CPEngineDataStructures;
 use super::sat::SATEngineDataStructures;
-use super::{AssignmentsInteger, AssignmentsPropositional, SATCPMediator};
-use crate::arguments::ArgumentHandler;
+use super::{
+    AssignmentsInteger, AssignmentsPropositional, ClauseSharing, SATCPMediator, SolutionIterator,
+    SolverOptions,
+};
 use crate::basic_types::{
-    BranchingDecision, CSPSolverExecutionFlag, ClauseAdditionOutcome, ClauseReference,
-    IntegerVariable, Literal, PropagationStatusCP, PropagationStatusClausal,
-    PropagationStatusOneStepCP, PropagatorIdentifier, PropositionalConjunction,
-    PropositionalVariable, Stopwatch,
+    estimate_vec_memory_usage, BranchingDecision, CSPSolverExecutionFlag, ClauseAdditionOutcome,
+    ClauseReference, IntegerVariable, Literal, Predicate, PropagationStatusCP,
+    PropagationStatusClausal, PropagationStatusOneStepCP, PropagatorIdentifier,
+    PropagatorStatistics, PropositionalConjunction, PropositionalVariable, PumpkinError,
+    SearchAnnotation, SolverStatistics, Stopwatch,
 };
 
-use crate::engine::DomainManager;
-use crate::propagators::ConstraintProgrammingPropagator;
+use crate::engine::{DebugHelper, DomainManager};
+use crate::propagators::{ConstraintProgrammingPropagator, GuardedPropagator};
 use crate::pumpkin_asserts::*;
 
 pub struct ConstraintSatisfactionSolver {
@@ -20,28 +28,34 @@ pub struct ConstraintSatisfactionSolver {
     cp_propagators: Vec<Box<dyn ConstraintProgrammingPropagator>>,
     sat_cp_mediator: SATCPMediator,
     seen: Vec<bool>,
+    trail_positions: Vec<usize>,
+    scope_selectors: Vec<IntegerVariable>,
     counters: Counters,
     internal_parameters: ConstraintSatisfactionSolverInternalParameters,
     stopwatch: Stopwatch,
+    termination_callback: Option<Box<dyn FnMut() -> bool + Send>>,
+    terminate_flag: Arc<AtomicBool>,
+    clause_sharing: Option<ClauseSharing>,
 }
 
 //methods that offer basic functionality
 impl ConstraintSatisfactionSolver {
-    pub fn new(argument_handler: &ArgumentHandler) -> ConstraintSatisfactionSolver {
+    pub fn new(options: &SolverOptions) -> ConstraintSatisfactionSolver {
         let mut csp_solver = ConstraintSatisfactionSolver {
             state: CSPSolverState::new(),
-            sat_data_structures: SATEngineDataStructures::new(argument_handler),
-            cp_data_structures: CPEngineDataStructures::new(argument_handler),
+            sat_data_structures: SATEngineDataStructures::new(options),
+            cp_data_structures: CPEngineDataStructures::new(options),
             cp_propagators: vec![],
             sat_cp_mediator: SATCPMediator::new(),
             seen: vec![],
-            counters: Counters::new(
-                argument_handler.get_integer_argument("num-conflicts-per-restart"),
-            ),
-            internal_parameters: ConstraintSatisfactionSolverInternalParameters::new(
-                argument_handler,
-            ),
+            trail_positions: vec![],
+            scope_selectors: vec![],
+            counters: Counters::new(options.num_conflicts_per_restart as i64),
+            internal_parameters: ConstraintSatisfactionSolverInternalParameters::new(options),
             stopwatch: Stopwatch::new(i64::MAX),
+            termination_callback: None,
+            terminate_flag: options.terminate_flag.clone(),
+            clause_sharing: None,
         };
 
         //we introduce a dummy variable set to true at the root level
@@ -70,21 +84,97 @@ impl ConstraintSatisfactionSolver {
         csp_solver
     }
 
+    //the selectors of the open scopes are assumed before the given assumptions, see 'push_scope'
+    //  in case a selector is violated, the constraints of the open scopes are infeasible regardless of the given assumptions,
+    //  which is reported as infeasible rather than infeasible under assumptions
+    //  the solver nevertheless remains in the infeasible under assumptions state, so it can be restored once the scopes are popped
     pub fn solve_under_assumptions(
         &mut self,
         assumptions: &[Literal],
         time_limit_in_seconds: i64,
     ) -> CSPSolverExecutionFlag {
-        self.initialise(assumptions, time_limit_in_seconds);
-        self.solve_internal()
+        let assumptions: Vec<Literal> = self
+            .scope_selectors
+            .iter()
+            .map(|selector| self.sat_cp_mediator.get_lower_bound_literal(*selector, 1))
+            .chain(assumptions.iter().copied())
+            .collect();
+
+        self.initialise(&assumptions, time_limit_in_seconds);
+        let execution_flag = self.solve_internal();
+
+        if let CSPSolverExecutionFlag::InfeasibleUnderAssumptions = execution_flag {
+            let num_selectors = self.scope_selectors.len();
+            if assumptions[..num_selectors].contains(&self.state.get_violated_assumption()) {
+                return CSPSolverExecutionFlag::Infeasible;
+            }
+        }
+        execution_flag
     }
 
+    //returns a subset of the assumptions that cannot be satisfied together, including the violated assumption
+    //  the implication graph is traversed backwards from the negation of the violated assumption
+    //  all decisions on the trail are assumptions at the time the violation is detected, so the decisions reached by the traversal form the core
+    //  literals assigned at the root hold regardless of the assumptions and are not traversed
+    //  the selectors of open scopes are not reported, see 'push_scope'
     pub fn extract_core(&mut self) -> Vec<Literal> {
         pumpkin_assert_simple!(
             self.state.is_infeasible_under_assumptions(),
             "Cannot extract core unless the solver is in the infeasible under assumption state."
         );
-        todo!();
+
+        let violated_assumption = self.state.get_violated_assumption();
+        let mut core = vec![violated_assumption];
+        let mut seen_variables: Vec<PropositionalVariable> = vec![];
+        let mut literals_to_explain = vec![!violated_assumption];
+        while let Some(literal) = literals_to_explain.pop() {
+            let variable = literal.get_propositional_variable();
+            let assignments = &self.sat_data_structures.assignments_propositional;
+            if self.seen[variable.index() as usize]
+                || assignments.is_literal_root_assignment(literal)
+            {
+                continue;
+            }
+            self.seen[variable.index() as usize] = true;
+            seen_variables.push(variable);
+
+            if !assignments.is_literal_propagated(literal) {
+                core.push(literal);
+                continue;
+            }
+
+            let reason_reference = self
+                .sat_cp_mediator
+                .get_propagation_reason_clause_reference(
+                    literal,
+                    &mut self.sat_data_structures,
+                    &self.cp_data_structures,
+                    &mut self.cp_propagators,
+                );
+            let reason = &self.sat_data_structures.clause_allocator[reason_reference];
+            literals_to_explain.extend(
+                reason
+                    .get_literal_slice()
+                    .iter()
+                    .filter(|reason_literal| {
+                        reason_literal.get_propositional_variable() != variable
+                    })
+                    .map(|reason_literal| !*reason_literal),
+            );
+        }
+
+        for variable in seen_variables {
+            self.seen[variable.index() as usize] = false;
+        }
+        self.sat_data_structures.clean_up_explanation_clauses();
+
+        let selector_literals: Vec<Literal> = self
+            .scope_selectors
+            .iter()
+            .map(|selector| self.sat_cp_mediator.get_lower_bound_literal(*selector, 1))
+            .collect();
+        core.retain(|literal| !selector_literals.contains(literal));
+        core
     }
 
     pub fn solve(&mut self, time_limit_in_seconds: i64) -> CSPSolverExecutionFlag {
@@ -99,6 +189,31 @@ impl ConstraintSatisfactionSolver {
             .reset(random_seed);
     }
 
+    //adds a search annotation, which makes the solver branch on the given integer variables
+    //  annotations are used in the order they are added, see 'SearchAnnotation'
+    //  after 'num-restarts-before-vsids' restarts the solver ignores the annotations and uses the branching heuristic
+    pub fn add_search_annotation(&mut self, search_annotation: SearchAnnotation) {
+        pumpkin_assert_simple!(self.state.is_ready());
+        self.cp_data_structures
+            .integer_variable_selector
+            .add_search_annotation(search_annotation);
+    }
+
+    //the callback is polled during search, and the search stops with a timeout as soon as it returns true
+    //  e.g., the IPASIR interface uses it for 'ipasir_set_terminate'
+    pub fn set_termination_callback(
+        &mut self,
+        termination_callback: Option<Box<dyn FnMut() -> bool + Send>>,
+    ) {
+        self.termination_callback = termination_callback;
+    }
+
+    //the solver exports its short learned clauses through the endpoint, and imports the clauses of the other solvers whenever it is at the root
+    //  see 'ClauseSharing' for the requirements on the solvers that share clauses
+    pub fn set_clause_sharing(&mut self, clause_sharing: Option<ClauseSharing>) {
+        self.clause_sharing = clause_sharing;
+    }
+
     pub fn get_state(&self) -> &CSPSolverState {
         &self.state
     }
@@ -108,16 +223,41 @@ impl ConstraintSatisfactionSolver {
             .create_new_propositional_variable(&mut self.sat_data_structures)
     }
 
+    //the encoding of integer variables requires nonnegative bounds with at least two values in the domain
+    //  fixed values can be modelled by fixing a 0-1 variable, see the FlatZinc translator
     pub fn create_new_integer_variable(
         &mut self,
         lower_bound: i32,
         upper_bound: i32,
-    ) -> IntegerVariable {
-        self.sat_cp_mediator.create_new_integer_variable(
+    ) -> Result<IntegerVariable, PumpkinError> {
+        if lower_bound < 0 {
+            return Err(PumpkinError::invalid_model(format!(
+                "Integer variables require a nonnegative lower bound, found [{}, {}].",
+                lower_bound, upper_bound
+            )));
+        }
+        if lower_bound >= upper_bound {
+            return Err(PumpkinError::invalid_model(format!(
+                "Integer variables require a lower bound below the upper bound, found [{}, {}].",
+                lower_bound, upper_bound
+            )));
+        }
+
+        Ok(self.sat_cp_mediator.create_new_integer_variable(
             lower_bound,
             upper_bound,
             &mut self.sat_data_structures,
             &mut self.cp_data_structures,
+        ))
+    }
+
+    //0-1 variables always have valid bounds, so unlike 'create_new_integer_variable' this cannot fail
+    pub fn create_new_zero_one_variable(&mut self) -> IntegerVariable {
+        self.sat_cp_mediator.create_new_integer_variable(
+            0,
+            1,
+            &mut self.sat_data_structures,
+            &mut self.cp_data_structures,
         )
     }
 
@@ -134,6 +274,109 @@ impl ConstraintSatisfactionSolver {
             .get_lower_bound_literal(integer_variable, lower_bound)
     }
 
+    //returns the literal that is true exactly when the predicate holds
+    //  predicates that are trivially true or false with respect to the initial domain map to the true or false literal
+    pub fn get_predicate_literal(&self, predicate: Predicate) -> Literal {
+        self.sat_cp_mediator.get_predicate_literal(predicate)
+    }
+
+    //ticks measure the work done by the solver independently of the machine, which makes them a reproducible alternative to the time limit
+    //  a tick is counted for every clause visited by the clausal propagator, every call to a CP propagator, and every resolution step in conflict analysis
+    pub fn get_num_ticks(&self) -> u64 {
+        self.counters.num_ticks
+            + self
+                .sat_data_structures
+                .clausal_propagator
+                .num_clause_visits
+            + self.sat_data_structures.num_local_search_flips
+    }
+
+    pub fn get_num_conflicts(&self) -> u64 {
+        self.counters.num_conflicts
+    }
+
+    //replaces the conflict budget of the options, which counts the conflicts since the solver was created
+    //  e.g., large neighbourhood search limits each iteration by setting the budget to the current number of conflicts plus the budget of the iteration
+    pub fn set_conflict_budget(&mut self, conflict_budget: u64) {
+        self.internal_parameters.conflict_budget = conflict_budget;
+    }
+
+    //propagators are aggregated by their name, e.g., all linear inequalities are reported together
+    pub fn get_statistics(&self) -> SolverStatistics {
+        let mut propagators: Vec<PropagatorStatistics> = vec![];
+        for (propagator, counters) in self
+            .cp_propagators
+            .iter()
+            .zip(&self.counters.propagator_counters)
+        {
+            let index =
+                match propagators.binary_search_by(|p| p.name.as_str().cmp(propagator.name())) {
+                    Ok(index) => index,
+                    Err(index) => {
+                        propagators.insert(
+                            index,
+                            PropagatorStatistics {
+                                name: propagator.name().to_string(),
+                                ..Default::default()
+                            },
+                        );
+                        index
+                    }
+                };
+            propagators[index].num_propagators += 1;
+            propagators[index].num_calls += counters.num_calls;
+            propagators[index].num_propagations += counters.num_propagations;
+            propagators[index].num_conflicts += counters.num_conflicts;
+        }
+
+        SolverStatistics {
+            num_decisions: self.counters.num_decisions,
+            num_conflicts: self.counters.num_conflicts,
+            num_propagations: self.counters.num_propagations,
+            num_restarts: self.counters.num_restarts,
+            num_chronological_backtracks: self.counters.num_chronological_backtracks,
+            num_rephases: self.sat_data_structures.num_rephases,
+            num_local_search_flips: self.sat_data_structures.num_local_search_flips,
+            num_ticks: self.get_num_ticks(),
+            num_learned_clauses: self.counters.num_learned_clauses,
+            num_unit_clauses_learned: self.counters.num_unit_clauses_learned,
+            average_learned_clause_lbd: if self.counters.num_learned_clauses > 0 {
+                self.counters.sum_learned_clause_lbd as f64
+                    / self.counters.num_learned_clauses as f64
+            } else {
+                0.0
+            },
+            num_deleted_learned_clauses: self.sat_data_structures.num_deleted_learned_clauses,
+            num_explanation_clauses: self.sat_data_structures.num_explanation_clauses,
+            sat_propagation_time: self.counters.sat_propagation_time,
+            cp_propagation_time: self.counters.cp_propagation_time,
+            estimated_memory_usage_in_bytes: self.estimate_memory_usage() as u64,
+            num_exported_shared_clauses: self
+                .clause_sharing
+                .as_ref()
+                .map_or(0, |clause_sharing| clause_sharing.num_exported_clauses),
+            num_imported_shared_clauses: self
+                .clause_sharing
+                .as_ref()
+                .map_or(0, |clause_sharing| clause_sharing.num_imported_clauses),
+            propagators,
+        }
+    }
+
+    //estimates the memory in bytes of the data structures that grow with the instance and the search
+    //  i.e., the clause database, the watch lists, the mappings between literals and predicates, and the trails
+    //  the memory of CP propagators is not included
+    pub fn estimate_memory_usage(&self) -> usize {
+        self.sat_data_structures.estimate_memory_usage()
+            + self
+                .cp_data_structures
+                .assignments_integer
+                .estimate_memory_usage()
+            + self.sat_cp_mediator.estimate_memory_usage()
+            + estimate_vec_memory_usage(&self.seen)
+            + estimate_vec_memory_usage(&self.trail_positions)
+    }
+
     pub fn get_integer_assignments(&self) -> &AssignmentsInteger {
         &self.cp_data_structures.assignments_integer
     }
@@ -170,11 +413,178 @@ impl ConstraintSatisfactionSolver {
         }
     }
 
+    //the solver can be restored after a solution, a violated assumption, or a timeout, after which the search can be resumed
+    //  the solver may also be ready above the root after blocking a solution, see 'add_blocking_clause_for_current_solution'
     pub fn restore_state_at_root(&mut self) {
-        pumpkin_assert_simple!(self.state.has_solution() && self.get_decision_level() > 0);
+        pumpkin_assert_simple!(
+            self.state.is_ready()
+                || self.state.has_solution()
+                || self.state.is_infeasible_under_assumptions()
+                || self.state.timeout()
+        );
+
+        if self.get_decision_level() > 0 {
+            self.backtrack(0);
+        }
+        if !self.state.is_ready() {
+            self.state.declare_ready();
+        }
+    }
+
+    //opens a new scope: clauses and propagators added until the matching 'pop_scope' are only active while the scope is open
+    //  each scope has a fresh 0-1 selector variable, and the scope is active when [selector >= 1] holds
+    //  clauses are extended with the negated selector literal, and propagators are wrapped in a 'GuardedPropagator'
+    //  the selectors of all open scopes are passed as the first assumptions when solving
+    pub fn push_scope(&mut self) {
+        pumpkin_assert_simple!(self.state.is_ready() && self.get_decision_level() == 0);
+
+        let selector = self.create_new_zero_one_variable();
+        self.scope_selectors.push(selector);
+    }
+
+    //closes the most recently opened scope, permanently disabling the clauses and propagators added in the scope
+    //  the selector is fixed to zero at the root, after which the clauses of the scope, including learned clauses that depend on the scope, are satisfied and removed
+    //  the guarded propagators stay in the solver but never propagate again
+    pub fn pop_scope(&mut self) {
+        pumpkin_assert_simple!(self.state.is_ready() && self.get_decision_level() == 0);
+        pumpkin_assert_simple!(
+            !self.scope_selectors.is_empty(),
+            "Cannot pop a scope without an open scope."
+        );
+
+        let selector = self.scope_selectors.pop().unwrap();
+        let selector_literal = self.sat_cp_mediator.get_lower_bound_literal(selector, 1);
+
+        let outcome = self.add_unguarded_unit_clause(!selector_literal);
+        pumpkin_assert_simple!(outcome == ClauseAdditionOutcome::NoConflictDetected);
+
+        self.sat_data_structures
+            .remove_clauses_containing_literal(!selector_literal);
+    }
+
+    pub fn num_open_scopes(&self) -> usize {
+        self.scope_selectors.len()
+    }
+
+    //the literal that, when added to a clause, makes the clause part of the innermost open scope
+    //  note that scopes are nested, so guarding with the innermost selector suffices
+    fn get_scope_guard_literal(&self) -> Option<Literal> {
+        self.scope_selectors
+            .last()
+            .map(|selector| !self.sat_cp_mediator.get_lower_bound_literal(*selector, 1))
+    }
+
+    //excludes the current solution, restricted to the given variables, from the search space
+    //  a clause is added that forces at least one of the given variables to take a different value
+    //  the clause is falsified by the solution, so the solver backjumps to the level where the clause propagates, as is done for learned clauses
+    //  the next call to solve continues the search from that level rather than from the root, provided the assumptions do not change
+    //  the outcome is infeasible if no other solution exists with respect to the given variables, in which case the solver is declared infeasible
+    pub fn add_blocking_clause_for_current_solution(
+        &mut self,
+        propositional_variables: &[PropositionalVariable],
+        integer_variables: &[IntegerVariable],
+    ) -> ClauseAdditionOutcome {
+        pumpkin_assert_simple!(
+            self.state.has_solution(),
+            "Cannot block a solution without a solution in the solver."
+        );
+
+        let assignments_propositional = &self.sat_data_structures.assignments_propositional;
+        let assignments_integer = &self.cp_data_structures.assignments_integer;
+        let mut blocking_literals: Vec<Literal> = propositional_variables
+            .iter()
+            .map(|variable| {
+                Literal::new(
+                    *variable,
+                    !assignments_propositional.is_variable_assigned_true(*variable),
+                )
+            })
+            .chain(integer_variables.iter().map(|integer_variable| {
+                self.sat_cp_mediator.get_inequality_literal(
+                    *integer_variable,
+                    assignments_integer.get_assigned_value(*integer_variable),
+                )
+            }))
+            .collect();
+
+        //an empty projection means that all solutions are considered equal, so there is no other solution
+        if blocking_literals.is_empty() {
+            self.restore_state_at_root();
+            self.state.declare_infeasible();
+            return ClauseAdditionOutcome::Infeasible;
+        }
+
+        if let Some(guard_literal) = self.get_scope_guard_literal() {
+            blocking_literals.push(guard_literal);
+        }
+
+        //the literals are sorted by decision level, so that the first two literals are the ones that become unassigned last when backtracking
+        //  these are the literals that are watched
+        blocking_literals.sort_by_key(|literal| {
+            std::cmp::Reverse(assignments_propositional.get_literal_assignment_level(*literal))
+        });
+        let highest_level =
+            assignments_propositional.get_literal_assignment_level(blocking_literals[0]);
+        let second_highest_level = blocking_literals.get(1).map_or(0, |literal| {
+            assignments_propositional.get_literal_assignment_level(*literal)
+        });
 
-        self.backtrack(0);
         self.state.declare_ready();
+
+        //all literals are false at the root, so there is no other solution
+        let outcome = if highest_level == 0 {
+            self.backtrack(0);
+            ClauseAdditionOutcome::Infeasible
+        }
+        //the clause is unit at the root, in which case it is added as a root assignment
+        else if second_highest_level == 0 {
+            self.backtrack(0);
+            self.sat_data_structures
+                .add_permanent_clause(blocking_literals)
+        }
+        //several literals are assigned at the highest level, after undoing that level the clause has at least two unassigned literals
+        else if highest_level == second_highest_level {
+            self.backtrack(highest_level - 1);
+            self.sat_data_structures
+                .add_clause_after_backtracking_unchecked(blocking_literals, false);
+            ClauseAdditionOutcome::NoConflictDetected
+        }
+        //the clause propagates its first literal at the second highest level
+        else {
+            self.backtrack(second_highest_level);
+            let propagated_literal = blocking_literals[0];
+            let clause_reference = self
+                .sat_data_structures
+                .add_clause_after_backtracking_unchecked(blocking_literals, false);
+            self.sat_data_structures
+                .assignments_propositional
+                .enqueue_propagated_literal_at_level(
+                    propagated_literal,
+                    clause_reference.id,
+                    second_highest_level,
+                );
+            ClauseAdditionOutcome::NoConflictDetected
+        };
+
+        if outcome == ClauseAdditionOutcome::Infeasible {
+            self.state.declare_infeasible();
+        }
+        outcome
+    }
+
+    //returns an iterator over the solutions of the solver, see 'SolutionIterator'
+    pub fn solutions(
+        &mut self,
+        propositional_variables: Vec<PropositionalVariable>,
+        integer_variables: Vec<IntegerVariable>,
+        time_limit_in_seconds: i64,
+    ) -> SolutionIterator<'_> {
+        SolutionIterator::new(
+            self,
+            propositional_variables,
+            integer_variables,
+            time_limit_in_seconds,
+        )
     }
 }
 
@@ -186,27 +596,72 @@ impl ConstraintSatisfactionSolver {
             .assignments_propositional
             .num_propositional_variables() as usize;
 
+        //the search only continues from the current decision level if the assumptions are the same as in the previous call, see 'add_blocking_clause_for_current_solution'
+        //  otherwise the decisions on the trail no longer correspond to the assumptions
+        if self.get_decision_level() > 0 && self.sat_data_structures.assumptions != assumptions {
+            self.backtrack(0);
+        }
+
         self.state.declare_solving();
         self.stopwatch.reset(time_limit_in_seconds);
         self.sat_data_structures.assumptions = assumptions.to_owned();
         self.seen.resize(num_propositional_variables, false);
+        self.trail_positions.resize(num_propositional_variables, 0);
 
         self.counters.num_conflicts_until_restart =
             self.internal_parameters.num_conflicts_per_restart as i64;
+        self.counters.next_memory_check = self.counters.num_conflicts;
     }
 
     fn solve_internal(&mut self) -> CSPSolverExecutionFlag {
         loop {
-            if self.stopwatch.get_remaining_time_budget() <= 0 {
+            if self.stopwatch.get_remaining_time_budget() <= 0
+                || self.is_termination_requested()
+                || self.is_budget_exhausted()
+                || self.is_memory_limit_exceeded()
+            {
                 self.state.declare_timeout();
                 return CSPSolverExecutionFlag::Timeout;
             }
 
+            if self.get_decision_level() == 0
+                && self.is_propagation_complete()
+                && self.import_shared_clauses() == ClauseAdditionOutcome::Infeasible
+            {
+                self.state.declare_infeasible();
+                return CSPSolverExecutionFlag::Infeasible;
+            }
+
             self.propagate_enqueued();
+            self.sat_data_structures
+                .notify_branching_heuristic_of_assignments();
 
             if self.state.no_conflict() {
                 if self.should_restart() {
+                    //the whole trail is conflict-free, since propagation finished without a conflict
+                    let num_assignments = self
+                        .sat_data_structures
+                        .assignments_propositional
+                        .trail
+                        .len();
+                    self.sat_data_structures
+                        .update_target_and_best_phases(num_assignments);
                     self.backtrack(0);
+                    self.counters.num_restarts += 1;
+                    self.sat_data_structures
+                        .rephase_if_needed(self.counters.num_conflicts);
+                    //clauses shared by other solvers are imported at the root, see the start of the loop
+                    if self.clause_sharing.is_some() {
+                        continue;
+                    }
+                }
+
+                //local search initialises the phases before the first decision, see 'SolverOptions::local_search'
+                if self.internal_parameters.local_search
+                    && self.sat_data_structures.num_local_search_runs == 0
+                    && self.get_decision_level() == 0
+                {
+                    self.sat_data_structures.run_local_search();
                 }
 
                 self.sat_data_structures
@@ -216,7 +671,7 @@ impl ConstraintSatisfactionSolver {
                     .assignments_integer
                     .increase_decision_level();
 
-                match self.sat_data_structures.get_next_branching_decision() {
+                match self.get_next_branching_decision() {
                     Some(branching_decision) => match branching_decision {
                         BranchingDecision::Assumption { assumption_literal } => {
                             //Case 1: the assumption is unassigned, assign it
@@ -242,6 +697,7 @@ impl ConstraintSatisfactionSolver {
                             }
                             //Case 3: the assumption literal is in conflict with the input assumption
                             //  which means the instance is infeasible under the current assumptions
+                            //  the assumption is falsified at the root, by propagation, or by an earlier assumption that is its negation, e.g., [a, ~a]
                             else {
                                 pumpkin_assert_moderate!(
                                     self.sat_data_structures
@@ -251,7 +707,11 @@ impl ConstraintSatisfactionSolver {
                                         || self
                                             .sat_data_structures
                                             .assignments_propositional
-                                            .is_literal_propagated(assumption_literal),
+                                            .is_literal_propagated(assumption_literal)
+                                        || self
+                                            .sat_data_structures
+                                            .assumptions
+                                            .contains(&!assumption_literal),
                                 );
 
                                 self.state
@@ -265,6 +725,17 @@ impl ConstraintSatisfactionSolver {
                                 .assignments_propositional
                                 .enqueue_decision_literal(decision_literal);
                         }
+                        BranchingDecision::IntegerDecision { decision_predicate } => {
+                            //the decision is made on the literal of the predicate
+                            //  the integer domains are updated when the propositional trail is synchronised during propagation
+                            self.counters.num_decisions += 1;
+                            let decision_literal = self
+                                .sat_cp_mediator
+                                .get_predicate_literal(decision_predicate);
+                            self.sat_data_structures
+                                .assignments_propositional
+                                .enqueue_decision_literal(decision_literal);
+                        }
                     },
                     None => {
                         self.state.declare_solution_found();
@@ -272,17 +743,23 @@ impl ConstraintSatisfactionSolver {
                     }
                 }
             } else {
-                if self
-                    .sat_data_structures
-                    .assignments_propositional
-                    .is_at_the_root_level()
-                {
+                let conflict_reference = self.get_conflict_clause();
+                let conflict_level = self.get_conflict_level(conflict_reference);
+                if conflict_level == 0 {
+                    self.sat_data_structures.clean_up_explanation_clauses();
                     self.state.declare_infeasible();
                     return CSPSolverExecutionFlag::Infeasible;
                 }
 
-                let conflict_reference = self.get_conflict_clause();
-                let analysis_result = self.analyse_conflict(conflict_reference);
+                //the assignments before the conflict level are conflict-free
+                let num_conflict_free_assignments =
+                    self.sat_data_structures
+                        .assignments_propositional
+                        .trail_delimiter[conflict_level as usize - 1] as usize;
+                self.sat_data_structures
+                    .update_target_and_best_phases(num_conflict_free_assignments);
+
+                let analysis_result = self.analyse_conflict(conflict_reference, conflict_level);
                 self.counters.num_unit_clauses_learned +=
                     (analysis_result.learned_literals.len() == 1) as u64;
                 self.process_conflict_analysis_result(analysis_result);
@@ -290,9 +767,6 @@ impl ConstraintSatisfactionSolver {
                 self.state.declare_solving();
 
                 self.sat_data_structures.decay_clause_activities();
-                self.sat_data_structures
-                    .propositional_variable_selector
-                    .decay_activities();
             }
         }
     }
@@ -301,6 +775,16 @@ impl ConstraintSatisfactionSolver {
     //i.e., adds the learned clause to the database, backtracks, enqueues the propagated literal, and updates internal data structures for simple moving averages
     //note that no propagation is done, this is left to the solver
     fn process_conflict_analysis_result(&mut self, analysis_result: ConflictAnalysisResult) {
+        //the LBD is computed before backtracking, while all literals of the learned clause are still assigned
+        let lbd = self
+            .sat_data_structures
+            .compute_lbd_for_literals(&analysis_result.learned_literals);
+        self.counters.num_learned_clauses += 1;
+        self.counters.sum_learned_clause_lbd += lbd as u64;
+        if let Some(clause_sharing) = &mut self.clause_sharing {
+            clause_sharing.export_learned_clause(&analysis_result.learned_literals, lbd);
+        }
+
         //unit clauses are treated in a special way: they are added as decision literals at decision level 0
         if analysis_result.learned_literals.len() == 1 {
             self.backtrack(0);
@@ -320,7 +804,21 @@ impl ConstraintSatisfactionSolver {
             //int lbd = state_.ComputeLBD(&analysis_result_.learned_clause_literals[0] + 1, analysis_result_.learned_clause_literals.size() - 1);
             //state_.UpdateMovingAveragesForRestarts(lbd);
 
-            self.backtrack(analysis_result.backjump_level);
+            //chronological backtracking only undoes the conflict level when backjumping would undo many decision levels
+            //  the propagated literal is then assigned out of order at the backjump level, see 'AssignmentsPropositional::synchronise'
+            let backjump_level = analysis_result.backjump_level;
+            let backtrack_level = if self.internal_parameters.chronological_backtracking
+                && analysis_result.conflict_level - backjump_level
+                    > self
+                        .internal_parameters
+                        .chronological_backtracking_threshold
+            {
+                self.counters.num_chronological_backtracks += 1;
+                analysis_result.conflict_level - 1
+            } else {
+                backjump_level
+            };
+            self.backtrack(backtrack_level);
 
             let propagated_literal = analysis_result.learned_literals[0];
 
@@ -330,16 +828,32 @@ impl ConstraintSatisfactionSolver {
 
             self.sat_data_structures
                 .assignments_propositional
-                .enqueue_propagated_literal(propagated_literal, learned_clause_reference.id);
+                .enqueue_propagated_literal_at_level(
+                    propagated_literal,
+                    learned_clause_reference.id,
+                    backjump_level,
+                );
         }
     }
 
+    //the conflict level is the highest decision level of the literals in the conflict clause
+    //  this is the current decision level, unless literals were assigned out of order with chronological backtracking
+    fn get_conflict_level(&self, conflict_reference: ClauseReference) -> u32 {
+        let assignments = &self.sat_data_structures.assignments_propositional;
+        self.sat_data_structures.clause_allocator[conflict_reference]
+            .get_literal_slice()
+            .iter()
+            .map(|literal| assignments.get_literal_assignment_level(*literal))
+            .max()
+            .unwrap()
+    }
+
     fn get_conflict_clause(&mut self) -> ClauseReference {
         pumpkin_assert_simple!(self.state.conflict_detected());
         if self.state.is_clausal_conflict() {
             self.state.get_conflict_clause_reference()
         } else {
-            let failure_literals = self
+            let mut failure_literals: Vec<Literal> = self
                 .state
                 .get_conflict_reason_cp()
                 .clone()
@@ -347,11 +861,123 @@ impl ConstraintSatisfactionSolver {
                 .map(|p| !self.sat_cp_mediator.get_predicate_literal(p))
                 .collect();
 
+            //clauses need at least two literals, adding the false literal does not change the meaning of the clause
+            if failure_literals.len() < 2 {
+                failure_literals.push(self.sat_cp_mediator.false_literal);
+            }
+
             self.sat_data_structures
                 .add_explanation_clause_unchecked(failure_literals)
         }
     }
 
+    //assumptions take precedence over other decisions
+    //  afterwards the solver branches according to the search annotations until all annotated variables are fixed,
+    //  and then uses the branching heuristic for the remaining propositional variables, see 'BranchingHeuristic'
+    //  once the solver restarted 'num_restarts_before_vsids' times, the search annotations are ignored
+    fn get_next_branching_decision(&mut self) -> Option<BranchingDecision> {
+        if let Some(assumption_literal) = self.sat_data_structures.peek_next_assumption_literal() {
+            return Some(BranchingDecision::Assumption { assumption_literal });
+        }
+
+        if self.counters.num_restarts < self.internal_parameters.num_restarts_before_vsids {
+            if let Some(integer_decision) = self.cp_data_structures.get_next_branching_decision() {
+                return Some(integer_decision);
+            }
+        }
+
+        self.sat_data_structures.get_next_branching_decision()
+    }
+
+    fn is_termination_requested(&mut self) -> bool {
+        self.terminate_flag.load(Ordering::Relaxed)
+            || self
+                .termination_callback
+                .as_mut()
+                .is_some_and(|termination_callback| termination_callback())
+    }
+
+    //the budgets count the conflicts, decisions, and propagations since the solver was created, see 'SolverOptions'
+    fn is_budget_exhausted(&self) -> bool {
+        self.counters.num_conflicts >= self.internal_parameters.conflict_budget
+            || self.counters.num_decisions >= self.internal_parameters.decision_budget
+            || self.counters.num_propagations >= self.internal_parameters.propagation_budget
+            || self.get_num_ticks() >= self.internal_parameters.tick_limit
+    }
+
+    //adds the clauses received from other solvers as learned clauses, which requires the solver to be at the root with complete propagation
+    //  the clauses are simplified with respect to the root assignment, where unit clauses are enqueued at the root and left for the solver to propagate
+    //  clauses over variables that do not exist in this solver are ignored
+    fn import_shared_clauses(&mut self) -> ClauseAdditionOutcome {
+        pumpkin_assert_simple!(self.get_decision_level() == 0 && self.is_propagation_complete());
+
+        let Some(clause_sharing) = &mut self.clause_sharing else {
+            return ClauseAdditionOutcome::NoConflictDetected;
+        };
+        let num_propositional_variables = self
+            .sat_data_structures
+            .assignments_propositional
+            .num_propositional_variables();
+
+        //clauses with at least two literals are added before enqueuing unit clauses, since adding clauses requires propagation to be complete
+        let mut unit_clauses = vec![];
+        for literals in clause_sharing.take_imported_clauses() {
+            if literals.iter().any(|literal| {
+                literal.get_propositional_variable().index() >= num_propositional_variables
+            }) {
+                continue;
+            }
+
+            let literals = SATEngineDataStructures::preprocess_clause(
+                literals,
+                &self.sat_data_structures.assignments_propositional,
+            );
+            if literals.is_empty() {
+                return ClauseAdditionOutcome::Infeasible;
+            } else if literals.len() == 1 {
+                unit_clauses.push(literals[0]);
+            } else {
+                self.sat_data_structures
+                    .add_clause_unchecked(literals, true);
+            }
+        }
+
+        for unit_clause in unit_clauses {
+            let assignments = &mut self.sat_data_structures.assignments_propositional;
+            if assignments.is_literal_assigned_false(unit_clause) {
+                return ClauseAdditionOutcome::Infeasible;
+            } else if assignments.is_literal_unassigned(unit_clause) {
+                assignments.enqueue_decision_literal(unit_clause);
+            }
+        }
+        ClauseAdditionOutcome::NoConflictDetected
+    }
+
+    //the memory is checked at the start of the search and then periodically after conflicts, since estimating it visits all clauses
+    //  when the limit is exceeded, the learned clause database is reduced at the root, and the limit is only considered exceeded if that does not suffice
+    fn is_memory_limit_exceeded(&mut self) -> bool {
+        if self.internal_parameters.memory_limit_in_bytes == usize::MAX
+            || self.counters.num_conflicts < self.counters.next_memory_check
+        {
+            return false;
+        }
+        self.counters.next_memory_check =
+            self.counters.num_conflicts + self.internal_parameters.num_conflicts_per_memory_check;
+
+        if self.estimate_memory_usage() <= self.internal_parameters.memory_limit_in_bytes {
+            return false;
+        }
+
+        if self.get_decision_level() > 0 {
+            self.backtrack(0);
+        }
+        self.sat_data_structures
+            .reduce_learned_clause_database_aggressively(
+                self.internal_parameters.min_num_learned_clauses,
+            );
+        self.estimate_memory_usage() > self.internal_parameters.memory_limit_in_bytes
+    }
+
     fn should_restart(&self) -> bool {
         pumpkin_assert_moderate!(
             self.counters.num_conflicts_until_restart > 0 || self.get_decision_level() > 0
@@ -363,26 +989,36 @@ impl ConstraintSatisfactionSolver {
         true
     }
 
+    //undoes the decision levels above the backtrack level
+    //  with chronological backtracking, literals assigned out of order at or below the backtrack level remain on the propositional trail,
+    //  whereas the integer trail is undone by decision level, so the predicates of these literals are applied again during the next propagation
     fn backtrack(&mut self, backtrack_level: u32) {
         pumpkin_assert_simple!(backtrack_level < self.get_decision_level());
 
+        let num_unchanged_propositional_assignments =
+            self.sat_data_structures
+                .assignments_propositional
+                .trail_delimiter[backtrack_level as usize] as usize;
         self.sat_data_structures.backtrack(backtrack_level);
         self.cp_data_structures.backtrack(backtrack_level);
         //  note that sat_cp_mediator sync should be called after the sat/cp data structures backtrack
         self.sat_cp_mediator.synchronise(
-            &self.sat_data_structures.assignments_propositional,
+            num_unchanged_propositional_assignments,
             &self.cp_data_structures.assignments_integer,
         );
-        //for now all propagators are called to synchronise
-        //  in the future this will be improved in two ways:
-        //      allow incremental synchronisation
-        //      only call the subset of propagators that were notified since last backtrack
-        for propagator_id in 0..self.cp_propagators.len() {
+        //only the propagators that were notified above the backtrack level are called to synchronise
+        //  the internal state of the other propagators was not changed at the undone decision levels
+        //  propagators restore their state incrementally, e.g., trailed values are reverted using the decision levels of the domains, see 'TrailedVec'
+        let notified_propagators = self
+            .cp_data_structures
+            .watch_list_cp
+            .take_propagators_notified_above(backtrack_level);
+        for propagator_identifier in notified_propagators {
             let domains = DomainManager::new(
-                propagator_id,
+                propagator_identifier.id as usize,
                 &mut self.cp_data_structures.assignments_integer,
             );
-            self.cp_propagators[propagator_id].synchronise(&domains);
+            self.cp_propagators[propagator_identifier.id as usize].synchronise(&domains);
         }
 
         if backtrack_level == 0 {
@@ -391,13 +1027,126 @@ impl ConstraintSatisfactionSolver {
 
             self.counters.num_conflicts_until_restart =
                 self.internal_parameters.num_conflicts_per_restart as i64;
-
-            self.counters.num_restarts += 1;
         }
     }
 
-    fn analyse_conflict(&mut self, _conflict_reference: ClauseReference) -> ConflictAnalysisResult {
-        todo!()
+    //computes the learned clause according to the first unique implication point (1UIP) scheme
+    //  the literals of the conflict clause are resolved with the reasons of their propagation, most recently assigned first,
+    //  until a single literal of the conflict level remains
+    //  the conflict level is below the current decision level if literals were assigned out of order, see 'get_conflict_level'
+    //  the negation of that literal is placed at the zero-th position of the learned clause, and it will be propagated after backjumping
+    //note that literals of the current decision level are processed in order of their trail position using a heap rather than by walking the trail backwards
+    //  this is because the explanations of CP propagators may use weaker predicates than the ones on the trail,
+    //      e.g., [x >= 1] rather than [x >= 5], and the literal of the weaker predicate is assigned by the clausal propagator after the explained propagation
+    fn analyse_conflict(
+        &mut self,
+        conflict_reference: ClauseReference,
+        conflict_level: u32,
+    ) -> ConflictAnalysisResult {
+        pumpkin_assert_simple!(conflict_level > 0);
+
+        //record the trail positions of the literals assigned since the conflict level started, which includes all literals of the conflict level
+        let assignments = &self.sat_data_structures.assignments_propositional;
+        let level_start_position =
+            assignments.trail_delimiter[conflict_level as usize - 1] as usize;
+        for position in level_start_position..assignments.trail.len() {
+            let variable = assignments.trail[position].get_propositional_variable();
+            self.trail_positions[variable.index() as usize] = position;
+        }
+
+        //the zero-th position is reserved for the negated 1UIP literal
+        let mut learned_literals = vec![assignments.true_literal];
+        let mut seen_variables: Vec<PropositionalVariable> = vec![];
+        let mut current_level_positions: BinaryHeap<usize> = BinaryHeap::new();
+
+        let mut next_clause_reference = conflict_reference;
+        let mut resolved_variable: Option<PropositionalVariable> = None;
+        loop {
+            self.counters.num_ticks += 1;
+            self.sat_data_structures
+                .update_clause_lbd_and_bump_activity(next_clause_reference);
+
+            let clause = &self.sat_data_structures.clause_allocator[next_clause_reference];
+            for &literal in clause.get_literal_slice() {
+                let variable = literal.get_propositional_variable();
+                let assignments = &self.sat_data_structures.assignments_propositional;
+                pumpkin_assert_moderate!(
+                    Some(variable) == resolved_variable
+                        || assignments.is_literal_assigned_false(literal),
+                    "Literals in the conflict clause and the reasons are expected to be false."
+                );
+
+                if Some(variable) == resolved_variable
+                    || self.seen[variable.index() as usize]
+                    || assignments.is_literal_root_assignment(literal)
+                {
+                    continue;
+                }
+
+                self.seen[variable.index() as usize] = true;
+                seen_variables.push(variable);
+
+                if assignments.get_literal_assignment_level(literal) == conflict_level {
+                    current_level_positions.push(self.trail_positions[variable.index() as usize]);
+                } else {
+                    learned_literals.push(literal);
+                }
+            }
+
+            pumpkin_assert_simple!(
+                !current_level_positions.is_empty(),
+                "The conflict is expected to contain a literal of the conflict level."
+            );
+
+            let position = current_level_positions.pop().unwrap();
+            let trail_literal = self.sat_data_structures.assignments_propositional.trail[position];
+
+            //the remaining literal of the current decision level is the 1UIP
+            if current_level_positions.is_empty() {
+                learned_literals[0] = !trail_literal;
+                break;
+            }
+
+            next_clause_reference = self
+                .sat_cp_mediator
+                .get_propagation_reason_clause_reference(
+                    trail_literal,
+                    &mut self.sat_data_structures,
+                    &self.cp_data_structures,
+                    &mut self.cp_propagators,
+                );
+            resolved_variable = Some(trail_literal.get_propositional_variable());
+        }
+
+        for variable in seen_variables {
+            self.seen[variable.index() as usize] = false;
+            self.sat_data_structures
+                .propositional_variable_selector
+                .on_conflict_variable(variable);
+        }
+        //the heuristic is informed before backjumping, so that it can reward the assignments that are undone with the conflict
+        self.sat_data_structures
+            .propositional_variable_selector
+            .on_conflict();
+
+        self.sat_data_structures.clean_up_explanation_clauses();
+
+        //the literal with the highest decision level is placed at the first position, so that it is watched together with the propagated literal
+        let mut backjump_level = 0;
+        if learned_literals.len() > 1 {
+            let assignments = &self.sat_data_structures.assignments_propositional;
+            let highest_level_index = (1..learned_literals.len())
+                .max_by_key(|i| assignments.get_literal_assignment_level(learned_literals[*i]))
+                .unwrap();
+            learned_literals.swap(1, highest_level_index);
+            backjump_level = assignments.get_literal_assignment_level(learned_literals[1]);
+        }
+
+        ConflictAnalysisResult {
+            learned_literals,
+            backjump_level,
+            conflict_level,
+        }
     }
 
     fn propagate_enqueued(&mut self) {
@@ -411,9 +1160,13 @@ impl ConstraintSatisfactionSolver {
                 .synchronise_propositional_trail_based_on_integer_trail(
                     &mut self.sat_data_structures.assignments_propositional,
                     &self.cp_data_structures.assignments_integer,
+                    &mut self.cp_propagators,
+                    self.internal_parameters.chronological_backtracking,
                 );
 
+            let time_start = Instant::now();
             let propagation_status_clausal = self.sat_data_structures.propagate_clauses();
+            self.counters.sat_propagation_time += time_start.elapsed();
 
             if let PropagationStatusClausal::ConflictDetected { reason_code } =
                 propagation_status_clausal
@@ -434,7 +1187,9 @@ impl ConstraintSatisfactionSolver {
             //propagate boolean propagators - todo add these special-case propagators
 
             //propagate (conventional) CP propagators
+            let time_start = Instant::now();
             let propagation_status_one_step_cp = self.propagate_cp_one_step();
+            self.counters.cp_propagation_time += time_start.elapsed();
 
             match propagation_status_one_step_cp {
                 PropagationStatusOneStepCP::ConflictDetected {
@@ -444,9 +1199,22 @@ impl ConstraintSatisfactionSolver {
                         .synchronise_propositional_trail_based_on_integer_trail(
                             &mut self.sat_data_structures.assignments_propositional,
                             &self.cp_data_structures.assignments_integer,
+                            &mut self.cp_propagators,
+                            self.internal_parameters.chronological_backtracking,
                         );
 
-                    self.state.declare_cp_conflict(conflict_reason);
+                    //the explanation may contain predicates that are implied by the trail but whose literals are not yet assigned
+                    //  e.g., [x >= 1] when [x >= 5] was just placed on the trail
+                    //  clausal propagation assigns these literals, which is needed for conflict analysis
+                    //  in case clausal propagation detects a conflict by itself, that conflict is used instead
+                    if let PropagationStatusClausal::ConflictDetected { reason_code } =
+                        self.sat_data_structures.propagate_clauses()
+                    {
+                        self.state
+                            .declare_clausal_conflict(ClauseReference { id: reason_code });
+                    } else {
+                        self.state.declare_cp_conflict(conflict_reason);
+                    }
                     break;
                 }
                 PropagationStatusOneStepCP::PropagationHappened => {
@@ -468,6 +1236,16 @@ impl ConstraintSatisfactionSolver {
                 .assignments_propositional
                 .num_assigned_propositional_variables() as u64
                 - num_assigned_variables_old as u64;
+
+        //Only check fixed point propagation if there was no reported conflict.
+        pumpkin_assert_extreme!(
+            self.state.conflict_detected()
+                || DebugHelper::debug_fixed_point_propagation(
+                    &self.cp_data_structures.assignments_integer,
+                    &self.sat_data_structures,
+                    &self.cp_propagators,
+                )
+        );
     }
 
     fn propagate_cp_one_step(&mut self) -> PropagationStatusOneStepCP {
@@ -484,10 +1262,25 @@ impl ConstraintSatisfactionSolver {
             );
 
             let propagation_status_cp = propagator.propagate(&mut domains);
+            self.counters.num_ticks += 1;
+            let propagator_counters =
+                &mut self.counters.propagator_counters[propagator_identifier.id as usize];
+            propagator_counters.num_calls += 1;
 
             match propagation_status_cp {
                 //if there was a conflict, then stop any further propagation and proceed to conflict analysis
                 PropagationStatusCP::ConflictDetected { failure_reason } => {
+                    pumpkin_assert_advanced!(DebugHelper::debug_reported_failure(
+                        &self.cp_data_structures.assignments_integer,
+                        &failure_reason,
+                        propagator.as_ref(),
+                        propagator_identifier,
+                    ));
+
+                    propagator_counters.num_conflicts += 1;
+                    self.cp_data_structures
+                        .integer_variable_selector
+                        .bump_propagator_weight(propagator_identifier);
                     return PropagationStatusOneStepCP::ConflictDetected { failure_reason };
                 }
                 PropagationStatusCP::NoConflictDetected => {
@@ -498,34 +1291,18 @@ impl ConstraintSatisfactionSolver {
                         .assignments_integer
                         .num_trail_entries()
                         - num_predicates_on_trail_before;
+                    propagator_counters.num_propagations += num_propagations_done as u64;
 
                     if num_propagations_done > 0 {
                         //notify other propagators
-                        //  note that during propagators, predicates are placed on the assignment_integer trail
+                        //  note that during propagation, predicates are placed on the assignment_integer trail
                         //      but no notifying is done for propagators
                         //  this is because the propagator does not have all the info on which propagators to notify when propagating
-                        //here we do the notification by removing the predicates from the trail, and apply them in the same order
-                        //  but this time notify all propagators of the relevant changes
-                        //  note that even the propagator that did the changes needs to be notified
-                        //  since propagators are not required to propagate until a fixed point in one step
-
-                        //the current solution of copying from the trail, popping, and reapplying is not ideal
-                        //  todo think about better ways
-                        let propagations = self
-                            .cp_data_structures
-                            .assignments_integer
-                            .get_last_predicates_on_trail(num_propagations_done);
-                        self.cp_data_structures
-                            .assignments_integer
-                            .undo_trail(num_propagations_done);
-
-                        for predicate in propagations {
-                            self.cp_data_structures.apply_predicate(
-                                &predicate,
-                                Some(propagator_identifier),
-                                &mut self.cp_propagators,
-                            );
-                        }
+                        //here the new trail entries are turned into domain events, merged per integer variable, and dispatched to the watching propagators
+                        self.cp_data_structures.notify_propagators_of_trail_entries(
+                            num_predicates_on_trail_before,
+                            &mut self.cp_propagators,
+                        );
 
                         return PropagationStatusOneStepCP::PropagationHappened;
                     }
@@ -538,9 +1315,29 @@ impl ConstraintSatisfactionSolver {
 
 //methods for adding constraints (propagators and clauses)
 impl ConstraintSatisfactionSolver {
-    pub fn add_propagator(&mut self, propagator_to_add: Box<dyn ConstraintProgrammingPropagator>) {
+    //adds the propagator and propagates at the root
+    //  the outcome is infeasible if the propagator detects a conflict at the root, in which case the solver is declared infeasible
+    pub fn add_propagator(
+        &mut self,
+        propagator_to_add: Box<dyn ConstraintProgrammingPropagator>,
+    ) -> ClauseAdditionOutcome {
         pumpkin_assert_simple!(propagator_to_add.priority() <= 3, "The propagator priority exceeds 3. Currently we only support values up to 3, but this can easily be changed if there is a good reason.");
 
+        let propagator_to_add: Box<dyn ConstraintProgrammingPropagator> =
+            match self.scope_selectors.last() {
+                Some(selector) => Box::new(GuardedPropagator::new(propagator_to_add, *selector)),
+                None => propagator_to_add,
+            };
+
+        //literals set by clauses at the root may not yet be reflected in the integer domains
+        //  they are synchronised first, otherwise the new propagator could place a predicate on the integer trail whose literal is already assigned
+        self.sat_cp_mediator
+            .synchronise_integer_trail_based_on_propositional_trail(
+                &self.sat_data_structures.assignments_propositional,
+                &mut self.cp_data_structures,
+                &mut self.cp_propagators,
+            );
+
         self.sat_data_structures
             .clause_allocator
             .reduce_id_limit_by_one();
@@ -549,6 +1346,9 @@ impl ConstraintSatisfactionSolver {
             id: self.cp_propagators.len() as u32,
         };
         self.cp_propagators.push(propagator_to_add);
+        self.counters
+            .propagator_counters
+            .push(PropagatorCounters::default());
 
         let new_propagator = &mut self.cp_propagators[new_propagator_id.id as usize];
         let mut domains = DomainManager::new(
@@ -560,32 +1360,101 @@ impl ConstraintSatisfactionSolver {
             .watch_list_cp
             .add_watches_for_propagator(new_propagator.as_ref(), new_propagator_id);
 
+        //views whose values do not fit in an i32 are rejected, since domain events and propagators compute with the values of views as i32
+        //  checking at the root suffices, since domains only shrink
+        let watched_integer_variables = new_propagator
+            .get_views_to_watch_for_lower_bound_changes()
+            .into_iter()
+            .chain(new_propagator.get_views_to_watch_for_upper_bound_changes())
+            .chain(new_propagator.get_views_to_watch_for_domain_hole_changes())
+            .map(|view| {
+                let integer_variable = view.get_integer_variable();
+                pumpkin_assert_simple!(
+                    view.fits_domain(
+                        domains.get_lower_bound(integer_variable),
+                        domains.get_upper_bound(integer_variable)
+                    ),
+                    "The view {} of the {} takes values that do not fit in an i32.",
+                    view,
+                    new_propagator.name()
+                );
+                integer_variable
+            })
+            .collect();
+        self.cp_data_structures
+            .integer_variable_selector
+            .register_propagator(new_propagator_id, watched_integer_variables);
+
+        let num_predicates_on_trail_before = domains.num_trail_entries();
+
         new_propagator.initialise_at_root(&mut domains);
 
         let root_status = new_propagator.initialise_at_root(&mut domains);
 
-        pumpkin_assert_simple!(root_status.no_conflict(), "For now we crash when adding a new propagator that detects a conflict at the root node, even though this is not necessarily an error. Should handle better in the future.");
+        if root_status.conflict_detected() {
+            self.state.declare_infeasible();
+            return ClauseAdditionOutcome::Infeasible;
+        }
+
+        //the root propagations were done without notifying other propagators, so the watching propagators are notified now
+        //  this is the same procedure as in 'propagate_cp_one_step'
+        self.cp_data_structures.notify_propagators_of_trail_entries(
+            num_predicates_on_trail_before,
+            &mut self.cp_propagators,
+        );
 
         self.propagate_enqueued();
-        pumpkin_assert_simple!(self.state.no_conflict(), "Root conflict detected after adding propagator, for now we crash the program but this may not necessarily be an error.");
+        if self.state.conflict_detected() {
+            self.state.declare_infeasible();
+            return ClauseAdditionOutcome::Infeasible;
+        }
+        ClauseAdditionOutcome::NoConflictDetected
     }
 
-    pub fn add_permanent_clause(&mut self, literals: Vec<Literal>) -> ClauseAdditionOutcome {
+    //clauses added while a scope is open are guarded by the selector of the scope, see 'push_scope'
+    pub fn add_permanent_clause(&mut self, mut literals: Vec<Literal>) -> ClauseAdditionOutcome {
+        if let Some(guard_literal) = self.get_scope_guard_literal() {
+            literals.push(guard_literal);
+        }
         self.sat_data_structures.add_permanent_clause(literals)
     }
 
     pub fn add_permanent_implication_unchecked(&mut self, lhs: Literal, rhs: Literal) {
-        self.sat_data_structures
-            .add_permanent_implication_unchecked(lhs, rhs);
+        match self.get_scope_guard_literal() {
+            Some(guard_literal) => {
+                self.sat_data_structures
+                    .add_permanent_ternary_clause_unchecked(!lhs, rhs, guard_literal);
+            }
+            None => self
+                .sat_data_structures
+                .add_permanent_implication_unchecked(lhs, rhs),
+        }
     }
 
     pub fn add_permanent_ternary_clause_unchecked(&mut self, a: Literal, b: Literal, c: Literal) {
-        self.sat_data_structures
-            .add_permanent_ternary_clause_unchecked(a, b, c);
+        match self.get_scope_guard_literal() {
+            Some(guard_literal) => {
+                self.sat_data_structures
+                    .add_clause_unchecked(vec![a, b, c, guard_literal], false);
+            }
+            None => self
+                .sat_data_structures
+                .add_permanent_ternary_clause_unchecked(a, b, c),
+        }
     }
 
     pub fn add_unit_clause(&mut self, unit_clause: Literal) -> ClauseAdditionOutcome {
+        match self.get_scope_guard_literal() {
+            Some(guard_literal) => self
+                .sat_data_structures
+                .add_permanent_clause(vec![unit_clause, guard_literal]),
+            None => self.add_unguarded_unit_clause(unit_clause),
+        }
+    }
+
+    fn add_unguarded_unit_clause(&mut self, unit_clause: Literal) -> ClauseAdditionOutcome {
         pumpkin_assert_simple!(self.get_decision_level() == 0);
+
         pumpkin_assert_simple!(self.is_propagation_complete());
 
         //if the literal representing the unit clause is unassigned, assign it
@@ -652,6 +1521,25 @@ struct Counters {
     pub num_unit_clauses_learned: u64,
     pub num_conflicts_until_restart: i64, //in case the solver gets into a chain of conflicts, this value could go get negative
     pub num_restarts: u64,
+    //backtracks to the level below the conflict level instead of the backjump level, see 'process_conflict_analysis_result'
+    pub num_chronological_backtracks: u64,
+    //propagator calls and resolution steps during conflict analysis, see 'get_num_ticks'
+    pub num_ticks: u64,
+    pub num_learned_clauses: u64,
+    pub sum_learned_clause_lbd: u64,
+    pub sat_propagation_time: Duration,
+    pub cp_propagation_time: Duration,
+    //indexed by the identifier of the propagator
+    pub propagator_counters: Vec<PropagatorCounters>,
+    //the number of conflicts at which the memory limit is checked next, see 'is_memory_limit_exceeded'
+    pub next_memory_check: u64,
+}
+
+#[derive(Default)]
+struct PropagatorCounters {
+    pub num_calls: u64,
+    pub num_propagations: u64,
+    pub num_conflicts: u64,
 }
 
 impl Counters {
@@ -663,6 +1551,14 @@ impl Counters {
             num_unit_clauses_learned: 0,
             num_conflicts_until_restart,
             num_restarts: 0,
+            num_chronological_backtracks: 0,
+            num_ticks: 0,
+            num_learned_clauses: 0,
+            sum_learned_clause_lbd: 0,
+            sat_propagation_time: Duration::ZERO,
+            cp_propagation_time: Duration::ZERO,
+            propagator_counters: vec![],
+            next_memory_check: 0,
         }
     }
 }
@@ -670,6 +1566,7 @@ impl Counters {
 pub struct ConflictAnalysisResult {
     pub learned_literals: Vec<Literal>,
     pub backjump_level: u32,
+    pub conflict_level: u32,
 }
 
 #[derive(Default)]
@@ -781,7 +1678,9 @@ impl CSPSolverState {
     }
 
     fn declare_ready(&mut self) {
-        pumpkin_assert_simple!(self.has_solution());
+        pumpkin_assert_simple!(
+            self.has_solution() || self.is_infeasible_under_assumptions() || self.timeout()
+        );
         self.internal_state = CSPSolverStateInternal::Ready;
     }
 
@@ -823,16 +1722,38 @@ impl CSPSolverState {
 
 pub struct ConstraintSatisfactionSolverInternalParameters {
     pub num_conflicts_per_restart: u64,
+    pub num_restarts_before_vsids: u64,
+    pub conflict_budget: u64,
+    pub decision_budget: u64,
+    pub propagation_budget: u64,
+    pub tick_limit: u64,
+    pub memory_limit_in_bytes: usize,
+    pub num_conflicts_per_memory_check: u64,
+    //the learned clause database is not reduced below this size when the memory limit is reached
+    pub min_num_learned_clauses: u64,
+    pub local_search: bool,
+    pub chronological_backtracking: bool,
+    pub chronological_backtracking_threshold: u32,
 }
 
 impl ConstraintSatisfactionSolverInternalParameters {
-    pub fn new(
-        argument_handler: &ArgumentHandler,
-    ) -> ConstraintSatisfactionSolverInternalParameters {
+    pub fn new(options: &SolverOptions) -> ConstraintSatisfactionSolverInternalParameters {
         ConstraintSatisfactionSolverInternalParameters {
-            num_conflicts_per_restart: argument_handler
-                .get_integer_argument("num-conflicts-per-restart")
-                as u64,
+            num_conflicts_per_restart: options.num_conflicts_per_restart,
+            num_restarts_before_vsids: options.num_restarts_before_vsids,
+            conflict_budget: options.conflict_budget,
+            decision_budget: options.decision_budget,
+            propagation_budget: options.propagation_budget,
+            tick_limit: options.tick_limit,
+            memory_limit_in_bytes: match options.memory_limit_in_megabytes {
+                u64::MAX => usize::MAX,
+                memory_limit => (memory_limit as usize).saturating_mul(1 << 20),
+            },
+            num_conflicts_per_memory_check: 1000,
+            min_num_learned_clauses: 100,
+            local_search: options.local_search,
+            chronological_backtracking: options.chronological_backtracking,
+            chronological_backtracking_threshold: options.chronological_backtracking_threshold,
         }
     }
 }
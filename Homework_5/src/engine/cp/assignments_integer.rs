@@ -1,6 +1,7 @@
 use crate::{
     basic_types::{
-        IntegerVariable, IntegerVariableGeneratorIterator, Predicate, PropagatorIdentifier,
+        estimate_vec_memory_usage, IntegerVariable, IntegerVariableGeneratorIterator, Predicate,
+        PropagatorIdentifier,
     },
     pumpkin_asserts::*,
 };
@@ -10,10 +11,19 @@ pub struct AssignmentsInteger {
     state: AssignmentsIntegerInternalState,
     current_decision_level: u32,
     trail_delimiter: Vec<u32>, //[i] is the position where the i-th decision level ends (exclusive) on the trail
+    //[i] identifies the (i+1)-th decision level, the stamp of a level is new each time the level is started, see 'TrailedVec'
+    decision_level_stamps: Vec<u64>,
+    num_decision_levels_started: u64,
     trail: Vec<ConstraintProgrammingTrailEntry>,
     domains: Vec<IntegerDomainExplicit>, //[integer_variable.id][j] indicates if value j is in the domain of the integer variable
 }
 
+impl Default for AssignmentsInteger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AssignmentsInteger {
     pub fn new() -> AssignmentsInteger {
         AssignmentsInteger {
@@ -21,19 +31,46 @@ impl AssignmentsInteger {
             current_decision_level: 0,
             trail: vec![],
             trail_delimiter: Vec::new(),
+            decision_level_stamps: vec![],
+            num_decision_levels_started: 0,
             domains: vec![],
         }
     }
 
+    pub fn estimate_memory_usage(&self) -> usize {
+        estimate_vec_memory_usage(&self.trail_delimiter)
+            + estimate_vec_memory_usage(&self.trail)
+            + estimate_vec_memory_usage(&self.domains)
+            + self
+                .domains
+                .iter()
+                .map(|domain| estimate_vec_memory_usage(&domain.is_value_in_domain))
+                .sum::<usize>()
+    }
+
     pub fn increase_decision_level(&mut self) {
         self.current_decision_level += 1;
         self.trail_delimiter.push(self.trail.len() as u32);
+        self.num_decision_levels_started += 1;
+        self.decision_level_stamps
+            .push(self.num_decision_levels_started);
     }
 
     pub fn get_decision_level(&self) -> u32 {
         self.current_decision_level
     }
 
+    //the stamp changes when the decision level is undone and started again, so it tells whether a change made at the level is still valid
+    //  the root level is never undone and has stamp zero
+    pub fn get_decision_level_stamp(&self, decision_level: u32) -> u64 {
+        pumpkin_assert_moderate!(decision_level <= self.current_decision_level);
+        if decision_level == 0 {
+            0
+        } else {
+            self.decision_level_stamps[decision_level as usize - 1]
+        }
+    }
+
     pub fn num_integer_variables(&self) -> u32 {
         self.domains.len() as u32
     }
@@ -50,6 +87,10 @@ impl AssignmentsInteger {
         self.trail[index].predicate
     }
 
+    pub fn get_trail_entry(&self, index: usize) -> ConstraintProgrammingTrailEntry {
+        self.trail[index]
+    }
+
     pub fn get_last_entry_on_trail(&self) -> ConstraintProgrammingTrailEntry {
         *self.trail.last().unwrap()
     }
@@ -144,10 +185,7 @@ impl AssignmentsInteger {
     pub fn get_bound_predicates(&self, integer_variables: &[IntegerVariable]) -> Vec<Predicate> {
         self.get_lower_bound_predicates(integer_variables)
             .into_iter()
-            .chain(
-                self.get_upper_bound_predicates(integer_variables)
-                    .into_iter(),
-            )
+            .chain(self.get_upper_bound_predicates(integer_variables))
             .collect()
     }
 
@@ -160,6 +198,14 @@ impl AssignmentsInteger {
             && self.domains[integer_variable].is_value_in_domain[value as usize]
     }
 
+    //returns the number of values in the domain
+    //  note that this takes time linear in the difference between the bounds
+    pub fn get_domain_size(&self, integer_variable: IntegerVariable) -> u32 {
+        (self.get_lower_bound(integer_variable)..=self.get_upper_bound(integer_variable))
+            .filter(|value| self.domains[integer_variable].is_value_in_domain[*value as usize])
+            .count() as u32
+    }
+
     pub fn is_integer_variable_assigned(&self, integer_variable: IntegerVariable) -> bool {
         self.get_lower_bound(integer_variable) == self.get_upper_bound(integer_variable)
     }
@@ -317,6 +363,14 @@ impl AssignmentsInteger {
         let old_lower_bound = self.get_lower_bound(integer_variable);
         let old_upper_bound = self.get_upper_bound(integer_variable);
 
+        //removing the only value empties the domain
+        if old_lower_bound == old_upper_bound {
+            self.state = AssignmentsIntegerInternalState::Conflict {
+                conflicting_predicate: predicate,
+            };
+            return DomainOperationOutcome::Failure;
+        }
+
         self.trail.push(ConstraintProgrammingTrailEntry {
             predicate,
             old_lower_bound,
@@ -328,26 +382,31 @@ impl AssignmentsInteger {
 
         domain.is_value_in_domain[removed_value_from_domain as usize] = false;
 
+        //the bounds move past values that are not in the domain, but not past each other
+        //  a bound may itself be a removed value for a moment, e.g., after tightening the lower bound to a removed value,
+        //  until the clausal propagator moves the bound, see the equality literals in 'SATCPMediator::create_new_integer_variable'
+
         //adjust the lower bound
         if old_lower_bound == removed_value_from_domain {
             //set the lower bound to the next value
             //  note that the lower bound might increase by more than one, if the values greater than 'not_equal_constant' are also not in the domain
-            while domain.is_value_in_domain[domain.lower_bound as usize] {
+            while domain.lower_bound < domain.upper_bound
+                && !domain.is_value_in_domain[domain.lower_bound as usize]
+            {
                 domain.lower_bound += 1;
-
-                pumpkin_assert_moderate!(domain.debug_bounds_check());
             }
         }
         //adjust the upper bound
         if old_upper_bound == removed_value_from_domain {
             //set the upper bound to the next value
-            //  note that the upper bound might increase by more than one, if the values lower than 'not_equal_constant' are also not in the domain
-            while domain.is_value_in_domain[domain.upper_bound as usize] {
+            //  note that the upper bound might decrease by more than one, if the values lower than 'not_equal_constant' are also not in the domain
+            while domain.upper_bound > domain.lower_bound
+                && !domain.is_value_in_domain[domain.upper_bound as usize]
+            {
                 domain.upper_bound -= 1;
-
-                pumpkin_assert_moderate!(domain.debug_bounds_check());
             }
         }
+        pumpkin_assert_moderate!(domain.lower_bound <= domain.upper_bound);
         DomainOperationOutcome::Success
     }
 
@@ -454,9 +513,14 @@ impl AssignmentsInteger {
 
             self.domains[integer_variable].lower_bound = popped_entry.old_lower_bound;
             self.domains[integer_variable].upper_bound = popped_entry.old_upper_bound;
-
-            pumpkin_assert_moderate!(self.domains[integer_variable].debug_bounds_check());
         }
+
+        //the bounds are only checked once all entries are undone
+        //  in between, a bound may be a value whose removal is undone by an entry that is popped later
+        pumpkin_assert_moderate!(self
+            .domains
+            .iter()
+            .all(|domain| domain.debug_bounds_check()));
     }
 
     pub fn synchronise(&mut self, new_decision_level: u32) {
@@ -468,6 +532,8 @@ impl AssignmentsInteger {
         self.undo_trail(num_trail_entries_to_remove);
         self.current_decision_level = new_decision_level;
         self.trail_delimiter.truncate(new_decision_level as usize);
+        self.decision_level_stamps
+            .truncate(new_decision_level as usize);
 
         if self.is_conflict() {
             self.restore_state_to_ok();
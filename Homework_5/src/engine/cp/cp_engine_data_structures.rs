@@ -1,23 +1,30 @@
 use crate::{
-    arguments::ArgumentHandler,
-    basic_types::{IntegerVariable, Predicate, PropagatorIdentifier},
+    basic_types::{BranchingDecision, IntegerVariable, Predicate, PropagatorIdentifier},
+    engine::SolverOptions,
     propagators::ConstraintProgrammingPropagator,
 };
 
-use super::{AssignmentsInteger, DomainOperationOutcome, PropagatorQueue, WatchListCP};
+use super::{
+    AssignmentsInteger, DomainEvents, DomainOperationOutcome, IntegerVariableSelector,
+    PropagatorQueue, WatchListCP,
+};
 
 pub struct CPEngineDataStructures {
     pub assignments_integer: AssignmentsInteger,
     pub watch_list_cp: WatchListCP,
     pub propagator_queue: PropagatorQueue,
+    pub integer_variable_selector: IntegerVariableSelector,
+    domain_events: DomainEvents,
 }
 
 impl CPEngineDataStructures {
-    pub fn new(_argument_handler: &ArgumentHandler) -> CPEngineDataStructures {
+    pub fn new(_options: &SolverOptions) -> CPEngineDataStructures {
         CPEngineDataStructures {
             assignments_integer: AssignmentsInteger::new(),
             watch_list_cp: WatchListCP::new(),
             propagator_queue: PropagatorQueue::new(5),
+            integer_variable_selector: IntegerVariableSelector::new(),
+            domain_events: DomainEvents::new(),
         }
     }
 
@@ -25,6 +32,13 @@ impl CPEngineDataStructures {
         self.assignments_integer.synchronise(backtrack_level);
         self.propagator_queue.clear();
     }
+
+    //returns the next decision according to the search annotations, or None if all annotated variables are fixed
+    pub fn get_next_branching_decision(&self) -> Option<BranchingDecision> {
+        self.integer_variable_selector
+            .get_next_decision_predicate(&self.assignments_integer)
+            .map(|decision_predicate| BranchingDecision::IntegerDecision { decision_predicate })
+    }
 }
 
 //methods for motifying the domains of variables
@@ -262,4 +276,62 @@ impl CPEngineDataStructures {
     pub fn does_predicate_hold(&self, predicate: &Predicate) -> bool {
         self.assignments_integer.does_predicate_hold(predicate)
     }
+
+    //notifies the watching propagators of the domain changes of the trail entries from the start position onwards
+    //  the entries were placed on the trail without notification, e.g., by a propagator through the 'DomainManager'
+    //  the changes are merged per integer variable, so each watching propagator is notified once per bound, from the old bound to the current bound
+    //      and values removed from the domain are only reported as holes if they are within the current bounds
+    //  note that the propagator that placed the entries is notified as well, since propagators are not required to propagate until a fixed point
+    pub fn notify_propagators_of_trail_entries(
+        &mut self,
+        start_position: usize,
+        cp_propagators: &mut [Box<dyn ConstraintProgrammingPropagator>],
+    ) {
+        for position in start_position..self.assignments_integer.num_trail_entries() {
+            self.domain_events
+                .record(&self.assignments_integer.get_trail_entry(position));
+        }
+
+        for event in self.domain_events.take_events() {
+            let integer_variable = event.integer_variable;
+            let new_lower_bound = self.assignments_integer.get_lower_bound(integer_variable);
+            let new_upper_bound = self.assignments_integer.get_upper_bound(integer_variable);
+
+            if event.old_lower_bound < new_lower_bound {
+                self.watch_list_cp
+                    .notify_lower_bound_subscribed_propagators(
+                        integer_variable,
+                        event.old_lower_bound,
+                        new_lower_bound,
+                        cp_propagators,
+                        &mut self.propagator_queue,
+                        &mut self.assignments_integer,
+                    );
+            }
+
+            if event.old_upper_bound > new_upper_bound {
+                self.watch_list_cp
+                    .notify_upper_bound_subscribed_propagators(
+                        integer_variable,
+                        event.old_upper_bound,
+                        new_upper_bound,
+                        cp_propagators,
+                        &mut self.propagator_queue,
+                        &mut self.assignments_integer,
+                    );
+            }
+
+            for removed_value in event.removed_values {
+                if new_lower_bound < removed_value && removed_value < new_upper_bound {
+                    self.watch_list_cp.notify_hole_subscribed_propagators(
+                        integer_variable,
+                        removed_value,
+                        cp_propagators,
+                        &mut self.propagator_queue,
+                        &mut self.assignments_integer,
+                    );
+                }
+            }
+        }
+    }
 }
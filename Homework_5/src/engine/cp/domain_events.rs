@@ -0,0 +1,73 @@
+use crate::basic_types::{IntegerVariable, Predicate};
+
+use super::ConstraintProgrammingTrailEntry;
+
+//queues the domain changes that have not been dispatched to the watching propagators yet, merged per integer variable
+//  the changes of an integer variable are kept as the bounds before its first change, together with the values removed from its domain
+//  the bound events are then reported from the old bound to the current bound, see 'CPEngineDataStructures::notify_propagators_of_trail_entries'
+pub struct DomainEvents {
+    events: Vec<IntegerVariableEvents>,
+    event_positions: Vec<usize>, //[i] is the position of the events of the i-th integer variable in 'events', or usize::MAX if it has no queued events
+}
+
+pub struct IntegerVariableEvents {
+    pub integer_variable: IntegerVariable,
+    pub old_lower_bound: i32,
+    pub old_upper_bound: i32,
+    pub removed_values: Vec<i32>,
+}
+
+impl Default for DomainEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DomainEvents {
+    pub fn new() -> DomainEvents {
+        DomainEvents {
+            events: vec![],
+            event_positions: vec![],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn record(&mut self, trail_entry: &ConstraintProgrammingTrailEntry) {
+        let integer_variable = trail_entry.predicate.get_integer_variable();
+        let variable_index = integer_variable.id as usize;
+        if variable_index >= self.event_positions.len() {
+            self.event_positions.resize(variable_index + 1, usize::MAX);
+        }
+
+        //only the first change of the variable determines the old bounds
+        if self.event_positions[variable_index] == usize::MAX {
+            self.event_positions[variable_index] = self.events.len();
+            self.events.push(IntegerVariableEvents {
+                integer_variable,
+                old_lower_bound: trail_entry.old_lower_bound,
+                old_upper_bound: trail_entry.old_upper_bound,
+                removed_values: vec![],
+            });
+        }
+
+        if let Predicate::NotEqual {
+            not_equal_constant, ..
+        } = trail_entry.predicate
+        {
+            self.events[self.event_positions[variable_index]]
+                .removed_values
+                .push(not_equal_constant);
+        }
+    }
+
+    //removes all queued events, in the order in which the integer variables were first changed
+    pub fn take_events(&mut self) -> Vec<IntegerVariableEvents> {
+        for event in &self.events {
+            self.event_positions[event.integer_variable.id as usize] = usize::MAX;
+        }
+        std::mem::take(&mut self.events)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::basic_types::{IntegerVariable, Predicate, PropagatorIdentifier};
+use crate::{
+    basic_types::{AffineView, Predicate, PropagatorIdentifier},
+    pumpkin_asserts::pumpkin_assert_simple,
+};
 
 use super::{assignments_integer::DomainOperationOutcome, AssignmentsInteger};
 
@@ -12,7 +15,7 @@ impl DomainManager<'_> {
     pub fn new(
         propagator_index: usize,
         assignments_integer: &'_ mut AssignmentsInteger,
-    ) -> DomainManager {
+    ) -> DomainManager<'_> {
         DomainManager {
             propagator_identifier: Some(PropagatorIdentifier {
                 id: propagator_index as u32,
@@ -24,107 +27,178 @@ impl DomainManager<'_> {
     pub fn num_trail_entries(&self) -> usize {
         self.assignments_integer.num_trail_entries()
     }
+
+    //propagators can use the decision level to keep an undo log of their internal state, see 'ConstraintProgrammingPropagator::synchronise'
+    pub fn get_decision_level(&self) -> u32 {
+        self.assignments_integer.get_decision_level()
+    }
+
+    //used by trailed values to detect changes made at decision levels that have been undone since, see 'TrailedVec'
+    pub fn get_decision_level_stamp(&self, decision_level: u32) -> u64 {
+        self.assignments_integer
+            .get_decision_level_stamp(decision_level)
+    }
 }
 
 //methods for getting info about the domains
+//  the methods accept views, and plain integer variables are treated as identity views
+//  bounds are given in terms of the view, whereas the returned predicates are over the underlying integer variable
 impl DomainManager<'_> {
-    pub fn get_lower_bound(&self, integer_variable: IntegerVariable) -> i32 {
-        self.assignments_integer.get_lower_bound(integer_variable)
+    pub fn get_lower_bound(&self, view: impl Into<AffineView>) -> i32 {
+        let view = view.into();
+        let integer_variable = view.get_integer_variable();
+        if view.preserves_bound_direction() {
+            view.transform_value(self.assignments_integer.get_lower_bound(integer_variable))
+        } else {
+            view.transform_value(self.assignments_integer.get_upper_bound(integer_variable))
+        }
     }
 
-    pub fn get_upper_bound(&self, integer_variable: IntegerVariable) -> i32 {
-        self.assignments_integer.get_upper_bound(integer_variable)
+    pub fn get_upper_bound(&self, view: impl Into<AffineView>) -> i32 {
+        let view = view.into();
+        let integer_variable = view.get_integer_variable();
+        if view.preserves_bound_direction() {
+            view.transform_value(self.assignments_integer.get_upper_bound(integer_variable))
+        } else {
+            view.transform_value(self.assignments_integer.get_lower_bound(integer_variable))
+        }
     }
 
-    pub fn get_lower_bound_predicate(&self, integer_variable: IntegerVariable) -> Predicate {
-        self.assignments_integer
-            .get_lower_bound_predicate(integer_variable)
+    pub fn get_lower_bound_predicate(&self, view: impl Into<AffineView>) -> Predicate {
+        let view = view.into();
+        view.lower_bound_predicate(self.get_lower_bound(view))
     }
 
-    pub fn get_upper_bound_predicate(&self, integer_variable: IntegerVariable) -> Predicate {
-        self.assignments_integer
-            .get_upper_bound_predicate(integer_variable)
+    pub fn get_upper_bound_predicate(&self, view: impl Into<AffineView>) -> Predicate {
+        let view = view.into();
+        view.upper_bound_predicate(self.get_upper_bound(view))
     }
 
-    pub fn get_lower_bound_predicates(
+    pub fn get_lower_bound_predicates<View: Into<AffineView> + Copy>(
         &self,
-        integer_variables: &[IntegerVariable],
+        views: &[View],
     ) -> Vec<Predicate> {
-        self.assignments_integer
-            .get_lower_bound_predicates(integer_variables)
+        views
+            .iter()
+            .map(|view| self.get_lower_bound_predicate(*view))
+            .collect()
     }
 
-    pub fn get_upper_bound_predicates(
+    pub fn get_upper_bound_predicates<View: Into<AffineView> + Copy>(
         &self,
-        integer_variables: &[IntegerVariable],
+        views: &[View],
     ) -> Vec<Predicate> {
-        self.assignments_integer
-            .get_upper_bound_predicates(integer_variables)
+        views
+            .iter()
+            .map(|view| self.get_upper_bound_predicate(*view))
+            .collect()
     }
 
-    pub fn get_bound_predicates(&self, integer_variables: &[IntegerVariable]) -> Vec<Predicate> {
-        self.assignments_integer
-            .get_bound_predicates(integer_variables)
+    pub fn get_bound_predicates<View: Into<AffineView> + Copy>(
+        &self,
+        views: &[View],
+    ) -> Vec<Predicate> {
+        self.get_lower_bound_predicates(views)
+            .into_iter()
+            .chain(self.get_upper_bound_predicates(views))
+            .collect()
     }
 
-    pub fn is_value_in_domain(&self, integer_variable: IntegerVariable, value: i32) -> bool {
-        self.assignments_integer
-            .is_value_in_domain(integer_variable, value)
+    pub fn is_value_in_domain(&self, view: impl Into<AffineView>, value: i32) -> bool {
+        let view = view.into();
+        match view.inverse_value(value) {
+            Some(variable_value) => self
+                .assignments_integer
+                .is_value_in_domain(view.get_integer_variable(), variable_value),
+            None => false,
+        }
     }
 
-    pub fn is_integer_variable_assigned(&self, integer_variable: IntegerVariable) -> bool {
+    pub fn is_integer_variable_assigned(&self, view: impl Into<AffineView>) -> bool {
         self.assignments_integer
-            .is_integer_variable_assigned(integer_variable)
+            .is_integer_variable_assigned(view.into().get_integer_variable())
     }
 }
 
 //methods to change the domains
+//  bounds of views are rounded towards the values the view can actually take,
+//      e.g., tightening the lower bound of '2*x' to 5 tightens the lower bound of x to 3
 impl DomainManager<'_> {
     pub fn tighten_lower_bound(
         &mut self,
-        integer_variable: IntegerVariable,
+        view: impl Into<AffineView>,
         new_lower_bound: i32,
     ) -> DomainOperationOutcome {
-        self.assignments_integer.tighten_lower_bound_no_notify(
-            integer_variable,
-            new_lower_bound,
-            self.propagator_identifier,
-        )
+        let predicate = view.into().lower_bound_predicate(new_lower_bound);
+        self.apply_bound_predicate(predicate)
     }
 
     pub fn tighten_upper_bound(
         &mut self,
-        integer_variable: IntegerVariable,
+        view: impl Into<AffineView>,
         new_upper_bound: i32,
     ) -> DomainOperationOutcome {
-        self.assignments_integer.tighten_upper_bound_no_notify(
-            integer_variable,
-            new_upper_bound,
-            self.propagator_identifier,
-        )
+        let predicate = view.into().upper_bound_predicate(new_upper_bound);
+        self.apply_bound_predicate(predicate)
     }
 
     pub fn make_assignment(
         &mut self,
-        integer_variable: IntegerVariable,
+        view: impl Into<AffineView>,
         assigned_value: i32,
     ) -> DomainOperationOutcome {
+        let view = view.into();
+        let variable_value = view.inverse_value(assigned_value);
+        pumpkin_assert_simple!(
+            variable_value.is_some(),
+            "Cannot assign a value to a view that the view can never take."
+        );
+
         self.assignments_integer.make_assignment_no_notify(
-            integer_variable,
-            assigned_value,
+            view.get_integer_variable(),
+            variable_value.unwrap(),
             self.propagator_identifier,
         )
     }
 
     pub fn remove_value_from_domain(
         &mut self,
-        integer_variable: IntegerVariable,
+        view: impl Into<AffineView>,
         removed_value_from_domain: i32,
     ) -> DomainOperationOutcome {
+        let view = view.into();
+        let variable_value = view.inverse_value(removed_value_from_domain);
+        pumpkin_assert_simple!(
+            variable_value.is_some(),
+            "Cannot remove a value from a view that the view can never take."
+        );
+
         self.assignments_integer.remove_value_from_domain_no_notify(
-            integer_variable,
-            removed_value_from_domain,
+            view.get_integer_variable(),
+            variable_value.unwrap(),
             self.propagator_identifier,
         )
     }
+
+    fn apply_bound_predicate(&mut self, predicate: Predicate) -> DomainOperationOutcome {
+        match predicate {
+            Predicate::LowerBound {
+                integer_variable,
+                lower_bound,
+            } => self.assignments_integer.tighten_lower_bound_no_notify(
+                integer_variable,
+                lower_bound,
+                self.propagator_identifier,
+            ),
+            Predicate::UpperBound {
+                integer_variable,
+                upper_bound,
+            } => self.assignments_integer.tighten_upper_bound_no_notify(
+                integer_variable,
+                upper_bound,
+                self.propagator_identifier,
+            ),
+            _ => unreachable!("Expected a bound predicate."),
+        }
+    }
 }
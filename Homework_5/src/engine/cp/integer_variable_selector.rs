@@ -0,0 +1,149 @@
+use crate::basic_types::{
+    IntegerVariable, IntegerVariableSelectionStrategy, Predicate, PropagatorIdentifier,
+    SearchAnnotation,
+};
+
+use super::AssignmentsInteger;
+
+//selects the next integer decision based on the search annotations
+//  the annotations are considered in order, and the first annotation with an unfixed variable determines the decision
+//the selector also keeps track of the propagator weights used by the dom/wdeg strategy
+pub struct IntegerVariableSelector {
+    search_annotations: Vec<SearchAnnotation>,
+    propagator_weights: Vec<u64>, //[i] is one plus the number of conflicts reported by the i-th propagator
+    propagator_variables: Vec<Vec<IntegerVariable>>, //[i] are the integer variables watched by the i-th propagator
+    variable_propagators: Vec<Vec<PropagatorIdentifier>>, //[x] are the propagators that watch the integer variable x
+}
+
+impl Default for IntegerVariableSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntegerVariableSelector {
+    pub fn new() -> IntegerVariableSelector {
+        IntegerVariableSelector {
+            search_annotations: vec![],
+            propagator_weights: vec![],
+            propagator_variables: vec![],
+            variable_propagators: vec![],
+        }
+    }
+
+    pub fn add_search_annotation(&mut self, search_annotation: SearchAnnotation) {
+        self.search_annotations.push(search_annotation);
+    }
+
+    pub fn has_search_annotations(&self) -> bool {
+        !self.search_annotations.is_empty()
+    }
+
+    //registers the variables of a newly added propagator, which is used to compute weighted degrees
+    //  propagators are expected to be registered in the order of their identifiers
+    pub fn register_propagator(
+        &mut self,
+        propagator_identifier: PropagatorIdentifier,
+        mut integer_variables: Vec<IntegerVariable>,
+    ) {
+        integer_variables.sort_unstable_by_key(|integer_variable| integer_variable.id);
+        integer_variables.dedup();
+
+        for &integer_variable in &integer_variables {
+            if integer_variable.id as usize >= self.variable_propagators.len() {
+                self.variable_propagators
+                    .resize(integer_variable.id as usize + 1, vec![]);
+            }
+            self.variable_propagators[integer_variable].push(propagator_identifier);
+        }
+
+        self.propagator_weights.push(1);
+        self.propagator_variables.push(integer_variables);
+    }
+
+    pub fn bump_propagator_weight(&mut self, propagator_identifier: PropagatorIdentifier) {
+        self.propagator_weights[propagator_identifier.id as usize] += 1;
+    }
+
+    //returns the next decision according to the search annotations
+    //  returns None if all annotated variables are fixed
+    pub fn get_next_decision_predicate(
+        &self,
+        assignments_integer: &AssignmentsInteger,
+    ) -> Option<Predicate> {
+        self.search_annotations
+            .iter()
+            .find_map(|search_annotation| {
+                self.select_variable(search_annotation, assignments_integer)
+                    .map(|integer_variable| {
+                        (integer_variable, search_annotation.value_selection_strategy)
+                    })
+            })
+            .map(|(integer_variable, value_selection_strategy)| {
+                value_selection_strategy
+                    .select_decision_predicate(integer_variable, assignments_integer)
+            })
+    }
+
+    fn select_variable(
+        &self,
+        search_annotation: &SearchAnnotation,
+        assignments_integer: &AssignmentsInteger,
+    ) -> Option<IntegerVariable> {
+        let unfixed_variables = search_annotation
+            .integer_variables
+            .iter()
+            .copied()
+            .filter(|x| !assignments_integer.is_integer_variable_assigned(*x));
+
+        //returns the unfixed variable with the lowest score
+        //  note that 'min_by' returns the first variable in case of ties
+        let select_minimum = |score: &dyn Fn(IntegerVariable) -> f64| {
+            unfixed_variables
+                .clone()
+                .min_by(|x, y| score(*x).partial_cmp(&score(*y)).unwrap())
+        };
+
+        match search_annotation.variable_selection_strategy {
+            IntegerVariableSelectionStrategy::InputOrder => unfixed_variables.clone().next(),
+            IntegerVariableSelectionStrategy::FirstFail => {
+                select_minimum(&|x| assignments_integer.get_domain_size(x) as f64)
+            }
+            IntegerVariableSelectionStrategy::DomWDeg => select_minimum(&|x| {
+                assignments_integer.get_domain_size(x) as f64
+                    / self.get_weighted_degree(x, assignments_integer).max(1) as f64
+            }),
+            IntegerVariableSelectionStrategy::Smallest => {
+                select_minimum(&|x| assignments_integer.get_lower_bound(x) as f64)
+            }
+            IntegerVariableSelectionStrategy::Largest => {
+                select_minimum(&|x| -assignments_integer.get_upper_bound(x) as f64)
+            }
+        }
+    }
+
+    //the weighted degree is the sum of the weights of the propagators of the variable
+    //  only propagators with at least one other unfixed variable are counted
+    fn get_weighted_degree(
+        &self,
+        integer_variable: IntegerVariable,
+        assignments_integer: &AssignmentsInteger,
+    ) -> u64 {
+        if integer_variable.id as usize >= self.variable_propagators.len() {
+            return 0;
+        }
+
+        self.variable_propagators[integer_variable]
+            .iter()
+            .filter(|propagator_identifier| {
+                self.propagator_variables[propagator_identifier.id as usize]
+                    .iter()
+                    .any(|y| {
+                        *y != integer_variable
+                            && !assignments_integer.is_integer_variable_assigned(*y)
+                    })
+            })
+            .map(|propagator_identifier| self.propagator_weights[propagator_identifier.id as usize])
+            .sum()
+    }
+}
@@ -1,13 +1,24 @@
 mod assignments_integer;
 mod cp_engine_data_structures;
+mod domain_events;
 mod domain_manager;
+mod integer_variable_selector;
+mod notified_propagators;
 mod propagator_queue;
+mod trailed_values;
 mod watch_list_cp;
 
 pub use assignments_integer::AssignmentsInteger;
 pub use assignments_integer::ConstraintProgrammingTrailEntry;
 pub use assignments_integer::DomainOperationOutcome;
 pub use cp_engine_data_structures::CPEngineDataStructures;
+pub use domain_events::DomainEvents;
+pub use domain_events::IntegerVariableEvents;
 pub use domain_manager::DomainManager;
+pub use integer_variable_selector::IntegerVariableSelector;
+pub use notified_propagators::NotifiedPropagators;
 pub use propagator_queue::PropagatorQueue;
+pub use trailed_values::TrailedInteger;
+pub use trailed_values::TrailedVec;
+pub use watch_list_cp::ViewWatcher;
 pub use watch_list_cp::WatchListCP;
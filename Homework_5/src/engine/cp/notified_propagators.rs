@@ -0,0 +1,65 @@
+use crate::basic_types::PropagatorIdentifier;
+
+//keeps track of the propagators that were notified of domain changes at each decision level
+//  when backtracking, only the propagators notified above the backtrack level need to synchronise, see 'ConstraintSatisfactionSolver::backtrack'
+//  notifications at the root are not recorded, since the root level is never undone
+pub struct NotifiedPropagators {
+    propagators_per_level: Vec<Vec<PropagatorIdentifier>>, //[i] contains the propagators notified at decision level i+1
+    last_recorded_level: Vec<u32>, //[i] is the decision level at which the i-th propagator was last recorded, or zero if it is not recorded above the root
+}
+
+impl Default for NotifiedPropagators {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotifiedPropagators {
+    pub fn new() -> NotifiedPropagators {
+        NotifiedPropagators {
+            propagators_per_level: vec![],
+            last_recorded_level: vec![],
+        }
+    }
+
+    pub fn record(&mut self, propagator_identifier: PropagatorIdentifier, decision_level: u32) {
+        if decision_level == 0 {
+            return;
+        }
+
+        let propagator_index = propagator_identifier.id as usize;
+        if propagator_index >= self.last_recorded_level.len() {
+            self.last_recorded_level.resize(propagator_index + 1, 0);
+        }
+        //the propagator is recorded at most once per decision level
+        //  it may still be recorded twice if it was recorded at a level that got undone and is then notified again at a lower level
+        if self.last_recorded_level[propagator_index] == decision_level {
+            return;
+        }
+        self.last_recorded_level[propagator_index] = decision_level;
+
+        if self.propagators_per_level.len() < decision_level as usize {
+            self.propagators_per_level
+                .resize(decision_level as usize, vec![]);
+        }
+        self.propagators_per_level[decision_level as usize - 1].push(propagator_identifier);
+    }
+
+    //removes and returns the propagators that were notified above the backtrack level, each propagator is returned once
+    pub fn take_propagators_notified_above(
+        &mut self,
+        backtrack_level: u32,
+    ) -> Vec<PropagatorIdentifier> {
+        let mut propagators: Vec<PropagatorIdentifier> = vec![];
+        while self.propagators_per_level.len() > backtrack_level as usize {
+            propagators.extend(self.propagators_per_level.pop().unwrap());
+        }
+        propagators.sort_by_key(|propagator_identifier| propagator_identifier.id);
+        propagators.dedup();
+
+        for propagator_identifier in &propagators {
+            self.last_recorded_level[propagator_identifier.id as usize] = 0;
+        }
+        propagators
+    }
+}
@@ -0,0 +1,103 @@
+use super::DomainManager;
+
+//integers of the internal state of propagators that revert automatically when the solver backtracks
+//  each change above the root is logged together with the decision level at which it was made and the stamp of that level
+//  a change is undone once its decision level is undone, which is detected when the level is above the current decision level,
+//      or when the stamp of the level differs because the level was undone and started again, see 'AssignmentsInteger::get_decision_level_stamp'
+//  undoing is done lazily on the next access, so propagators do not need to restore trailed values in 'synchronise'
+//note that the decision levels are taken from the domains that the solver gives to the propagator
+pub struct TrailedVec {
+    values: Vec<i64>,
+    undo_log: Vec<TrailedChange>,
+}
+
+//a single trailed integer, see 'TrailedVec'
+pub struct TrailedInteger {
+    values: TrailedVec,
+}
+
+struct TrailedChange {
+    index: usize,
+    old_value: i64,
+    decision_level: u32,
+    decision_level_stamp: u64,
+}
+
+impl TrailedVec {
+    pub fn new(initial_values: Vec<i64>) -> TrailedVec {
+        TrailedVec {
+            values: initial_values,
+            undo_log: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn read(&mut self, index: usize, domains: &DomainManager) -> i64 {
+        self.undo_changes_of_undone_levels(domains);
+        self.values[index]
+    }
+
+    pub fn assign(&mut self, index: usize, new_value: i64, domains: &DomainManager) {
+        self.undo_changes_of_undone_levels(domains);
+
+        //changes at the root are never undone, so they are not logged
+        let decision_level = domains.get_decision_level();
+        if decision_level > 0 {
+            self.undo_log.push(TrailedChange {
+                index,
+                old_value: self.values[index],
+                decision_level,
+                decision_level_stamp: domains.get_decision_level_stamp(decision_level),
+            });
+        }
+        self.values[index] = new_value;
+    }
+
+    pub fn add(&mut self, index: usize, delta: i64, domains: &DomainManager) {
+        let new_value = self.read(index, domains) + delta;
+        self.assign(index, new_value, domains);
+    }
+
+    //the log is ordered by decision level, and all changes after an undone change were also undone
+    //  this holds since changes are only logged after the log has been cleaned up, so the undone changes are at the end of the log
+    fn undo_changes_of_undone_levels(&mut self, domains: &DomainManager) {
+        let current_decision_level = domains.get_decision_level();
+        while let Some(change) = self.undo_log.last() {
+            let is_undone = change.decision_level > current_decision_level
+                || domains.get_decision_level_stamp(change.decision_level)
+                    != change.decision_level_stamp;
+            if !is_undone {
+                break;
+            }
+            self.values[change.index] = change.old_value;
+            self.undo_log.pop();
+        }
+    }
+}
+
+impl TrailedInteger {
+    pub fn new(initial_value: i64) -> TrailedInteger {
+        TrailedInteger {
+            values: TrailedVec::new(vec![initial_value]),
+        }
+    }
+
+    pub fn read(&mut self, domains: &DomainManager) -> i64 {
+        self.values.read(0, domains)
+    }
+
+    pub fn assign(&mut self, new_value: i64, domains: &DomainManager) {
+        self.values.assign(0, new_value, domains);
+    }
+
+    pub fn add(&mut self, delta: i64, domains: &DomainManager) {
+        self.values.add(0, delta, domains);
+    }
+}
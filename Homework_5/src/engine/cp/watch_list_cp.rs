@@ -1,19 +1,28 @@
 use crate::{
-    basic_types::{EnqueueStatus, IntegerVariable, PropagatorIdentifier},
+    basic_types::{AffineView, EnqueueStatus, IntegerVariable, PropagatorIdentifier},
     propagators::ConstraintProgrammingPropagator,
-    pumpkin_asserts::pumpkin_assert_moderate,
 };
 
-use super::{AssignmentsInteger, DomainManager, PropagatorQueue};
+use super::{AssignmentsInteger, DomainManager, NotifiedPropagators, PropagatorQueue};
 
 pub struct WatchListCP {
-    watchers: Vec<WatcherCP>, //[i] contains propagator ids of propagators that watch domain changes of the i-th integer variable
+    watchers: Vec<WatcherCP>, //[i] contains the propagators, together with their views, that watch domain changes of the i-th integer variable
+    notified_propagators: NotifiedPropagators, //the propagators that were notified at each decision level, used to only synchronise those when backtracking
 }
 
 //public functions
+impl Default for WatchListCP {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WatchListCP {
     pub fn new() -> WatchListCP {
-        WatchListCP { watchers: vec![] }
+        WatchListCP {
+            watchers: vec![],
+            notified_propagators: NotifiedPropagators::new(),
+        }
     }
 
     pub fn grow(&mut self) {
@@ -29,99 +38,136 @@ impl WatchListCP {
         propagator: &dyn ConstraintProgrammingPropagator,
         propagator_identifier: PropagatorIdentifier,
     ) {
-        for integer_variable in propagator.get_integer_variables_to_watch_for_lower_bound_changes()
+        //note that for views that reverse the bound direction, e.g., negation,
+        //  lower bound changes of the view are upper bound changes of the underlying variable, and vice versa
+        //the local id of a view is its position in the list of views returned by the propagator for the event
+        //a view that is listed more than once for the same event, e.g., x in x * x = s, is watched once, with the local id of its first occurrence
+        //  so the propagator is notified once per event, and propagators that index their data by local id should merge such views, see 'LinearInequalityPropagator'
+        for (local_id, view) in propagator
+            .get_views_to_watch_for_lower_bound_changes()
+            .into_iter()
+            .enumerate()
         {
-            self.watch_lower_bound_domain_changes(integer_variable, propagator_identifier);
+            let watcher = ViewWatcher {
+                propagator_identifier,
+                view,
+                local_id,
+            };
+            if view.preserves_bound_direction() {
+                self.watch_lower_bound_domain_changes(watcher);
+            } else {
+                self.watch_upper_bound_domain_changes(watcher);
+            }
         }
 
-        for integer_variable in propagator.get_integer_variables_to_watch_for_upper_bound_changes()
+        for (local_id, view) in propagator
+            .get_views_to_watch_for_upper_bound_changes()
+            .into_iter()
+            .enumerate()
         {
-            self.watch_upper_bound_domain_changes(integer_variable, propagator_identifier);
+            let watcher = ViewWatcher {
+                propagator_identifier,
+                view,
+                local_id,
+            };
+            if view.preserves_bound_direction() {
+                self.watch_upper_bound_domain_changes(watcher);
+            } else {
+                self.watch_lower_bound_domain_changes(watcher);
+            }
         }
 
-        for integer_variable in propagator.get_integer_variables_to_watch_for_domain_hole_changes()
+        for (local_id, view) in propagator
+            .get_views_to_watch_for_domain_hole_changes()
+            .into_iter()
+            .enumerate()
         {
-            self.watch_hole_domain_changes(integer_variable, propagator_identifier);
+            self.watch_hole_domain_changes(ViewWatcher {
+                propagator_identifier,
+                view,
+                local_id,
+            });
         }
     }
 
-    pub fn get_lower_bound_watchers(
-        &self,
-        integer_variable: IntegerVariable,
-    ) -> &[PropagatorIdentifier] {
+    //the propagators that need to synchronise when backtracking, i.e., that were notified of a domain change above the backtrack level
+    pub fn take_propagators_notified_above(
+        &mut self,
+        backtrack_level: u32,
+    ) -> Vec<PropagatorIdentifier> {
+        self.notified_propagators
+            .take_propagators_notified_above(backtrack_level)
+    }
+
+    pub fn get_lower_bound_watchers(&self, integer_variable: IntegerVariable) -> &[ViewWatcher] {
         &self.watchers[integer_variable].lower_bound_watchers
     }
 
-    pub fn get_upper_bound_watchers(
-        &self,
-        integer_variable: IntegerVariable,
-    ) -> &[PropagatorIdentifier] {
+    pub fn get_upper_bound_watchers(&self, integer_variable: IntegerVariable) -> &[ViewWatcher] {
         &self.watchers[integer_variable].upper_bound_watchers
     }
 
-    pub fn get_hole_domain_watchers(
-        &self,
-        integer_variable: IntegerVariable,
-    ) -> &[PropagatorIdentifier] {
+    pub fn get_hole_domain_watchers(&self, integer_variable: IntegerVariable) -> &[ViewWatcher] {
         &self.watchers[integer_variable].hole_watchers
     }
 }
 
 //private functions
+//  watchers are stored based on the domain event of the underlying integer variable, together with the view of the propagator
 impl WatchListCP {
-    fn watch_lower_bound_domain_changes(
-        &mut self,
-        integer_variable: IntegerVariable,
-        propagator_id: PropagatorIdentifier,
-    ) {
-        pumpkin_assert_moderate!(
-            !self.watchers[integer_variable]
-                .lower_bound_watchers
-                .contains(&propagator_id),
-            "Already watching the variable for lower bound changes, for now we consider it an error to request a watch of an already watched variable."
-        );
-
-        self.watchers[integer_variable]
+    fn watch_lower_bound_domain_changes(&mut self, watcher: ViewWatcher) {
+        let view = watcher.view;
+        if self.watchers[view.get_integer_variable()]
             .lower_bound_watchers
-            .push(propagator_id);
+            .iter()
+            .any(|other| {
+                other.propagator_identifier == watcher.propagator_identifier && other.view == view
+            })
+        {
+            return;
+        }
+
+        self.watchers[view.get_integer_variable()]
+            .lower_bound_watchers
+            .push(watcher);
     }
 
-    fn watch_upper_bound_domain_changes(
-        &mut self,
-        integer_variable: IntegerVariable,
-        propagator_id: PropagatorIdentifier,
-    ) {
-        pumpkin_assert_moderate!(
-            !self.watchers[integer_variable]
-                .upper_bound_watchers
-                .contains(&propagator_id),
-                "Already watching the variable for upper bound changes, for now we consider it an error to request a watch of an already watched variable."
-        );
-
-        self.watchers[integer_variable]
+    fn watch_upper_bound_domain_changes(&mut self, watcher: ViewWatcher) {
+        let view = watcher.view;
+        if self.watchers[view.get_integer_variable()]
             .upper_bound_watchers
-            .push(propagator_id);
+            .iter()
+            .any(|other| {
+                other.propagator_identifier == watcher.propagator_identifier && other.view == view
+            })
+        {
+            return;
+        }
+
+        self.watchers[view.get_integer_variable()]
+            .upper_bound_watchers
+            .push(watcher);
     }
 
-    fn watch_hole_domain_changes(
-        &mut self,
-        integer_variable: IntegerVariable,
-        propagator_id: PropagatorIdentifier,
-    ) {
-        pumpkin_assert_moderate!(
-            !self.watchers[integer_variable]
-                .hole_watchers
-                .contains(&propagator_id),
-                "Already watching the variable for hole changes, for now we consider it an error to request a watch of an already watched variable."
-        );
-
-        self.watchers[integer_variable]
+    fn watch_hole_domain_changes(&mut self, watcher: ViewWatcher) {
+        let view = watcher.view;
+        if self.watchers[view.get_integer_variable()]
             .hole_watchers
-            .push(propagator_id);
+            .iter()
+            .any(|other| {
+                other.propagator_identifier == watcher.propagator_identifier && other.view == view
+            })
+        {
+            return;
+        }
+
+        self.watchers[view.get_integer_variable()]
+            .hole_watchers
+            .push(watcher);
     }
 
     pub fn notify_lower_bound_subscribed_propagators(
-        &self,
+        &mut self,
         integer_variable: IntegerVariable,
         old_lower_bound: i32,
         new_lower_bound: i32,
@@ -129,26 +175,43 @@ impl WatchListCP {
         propagator_queue: &mut PropagatorQueue,
         assignments_integer: &mut AssignmentsInteger,
     ) {
-        for propagator_identifier in &self.watchers[integer_variable].lower_bound_watchers {
+        for watcher in &self.watchers[integer_variable].lower_bound_watchers {
+            let propagator_identifier = watcher.propagator_identifier;
+            let view = watcher.view;
             let propagator = &mut propagators_cp[propagator_identifier.id as usize];
+            self.notified_propagators.record(
+                propagator_identifier,
+                assignments_integer.get_decision_level(),
+            );
             let domains =
                 DomainManager::new(propagator_identifier.id as usize, assignments_integer);
 
-            let enqueue_status = propagator.notify_lower_bound_integer_variable_change(
-                integer_variable,
-                old_lower_bound,
-                new_lower_bound,
-                &domains,
-            );
+            let enqueue_status = if view.preserves_bound_direction() {
+                propagator.notify_lower_bound_change_with_local_id(
+                    watcher.local_id,
+                    view,
+                    view.transform_value(old_lower_bound),
+                    view.transform_value(new_lower_bound),
+                    &domains,
+                )
+            } else {
+                propagator.notify_upper_bound_change_with_local_id(
+                    watcher.local_id,
+                    view,
+                    view.transform_value(old_lower_bound),
+                    view.transform_value(new_lower_bound),
+                    &domains,
+                )
+            };
 
             if let EnqueueStatus::ShouldEnqueue = enqueue_status {
-                propagator_queue.enqueue_propagator(*propagator_identifier, propagator.priority());
+                propagator_queue.enqueue_propagator(propagator_identifier, propagator.priority());
             }
         }
     }
 
     pub fn notify_upper_bound_subscribed_propagators(
-        &self,
+        &mut self,
         integer_variable: IntegerVariable,
         old_upper_bound: i32,
         new_upper_bound: i32,
@@ -156,53 +219,86 @@ impl WatchListCP {
         propagator_queue: &mut PropagatorQueue,
         assignments_integer: &mut AssignmentsInteger,
     ) {
-        for propagator_identifier in &self.watchers[integer_variable].upper_bound_watchers {
+        for watcher in &self.watchers[integer_variable].upper_bound_watchers {
+            let propagator_identifier = watcher.propagator_identifier;
+            let view = watcher.view;
             let propagator = &mut propagators_cp[propagator_identifier.id as usize];
+            self.notified_propagators.record(
+                propagator_identifier,
+                assignments_integer.get_decision_level(),
+            );
             let domains =
                 DomainManager::new(propagator_identifier.id as usize, assignments_integer);
 
-            let enqueue_status = propagator.notify_upper_bound_integer_variable_change(
-                integer_variable,
-                old_upper_bound,
-                new_upper_bound,
-                &domains,
-            );
+            let enqueue_status = if view.preserves_bound_direction() {
+                propagator.notify_upper_bound_change_with_local_id(
+                    watcher.local_id,
+                    view,
+                    view.transform_value(old_upper_bound),
+                    view.transform_value(new_upper_bound),
+                    &domains,
+                )
+            } else {
+                propagator.notify_lower_bound_change_with_local_id(
+                    watcher.local_id,
+                    view,
+                    view.transform_value(old_upper_bound),
+                    view.transform_value(new_upper_bound),
+                    &domains,
+                )
+            };
 
             if let EnqueueStatus::ShouldEnqueue = enqueue_status {
-                propagator_queue.enqueue_propagator(*propagator_identifier, propagator.priority());
+                propagator_queue.enqueue_propagator(propagator_identifier, propagator.priority());
             }
         }
     }
 
     pub fn notify_hole_subscribed_propagators(
-        &self,
+        &mut self,
         integer_variable: IntegerVariable,
         removed_value_from_domain: i32,
         propagators_cp: &mut [Box<dyn ConstraintProgrammingPropagator>],
         propagator_queue: &mut PropagatorQueue,
         assignments_integer: &mut AssignmentsInteger,
     ) {
-        for propagator_identifier in &self.watchers[integer_variable].hole_watchers {
+        for watcher in &self.watchers[integer_variable].hole_watchers {
+            let propagator_identifier = watcher.propagator_identifier;
+            let view = watcher.view;
             let propagator = &mut propagators_cp[propagator_identifier.id as usize];
+            self.notified_propagators.record(
+                propagator_identifier,
+                assignments_integer.get_decision_level(),
+            );
             let domains =
                 DomainManager::new(propagator_identifier.id as usize, assignments_integer);
 
-            let enqueue_status = propagator.notify_domain_hole_integer_variable_change(
-                integer_variable,
-                removed_value_from_domain,
+            let enqueue_status = propagator.notify_domain_hole_change_with_local_id(
+                watcher.local_id,
+                view,
+                view.transform_value(removed_value_from_domain),
                 &domains,
             );
 
             if let EnqueueStatus::ShouldEnqueue = enqueue_status {
-                propagator_queue.enqueue_propagator(*propagator_identifier, propagator.priority());
+                propagator_queue.enqueue_propagator(propagator_identifier, propagator.priority());
             }
         }
     }
 }
 
+//a propagator that watches a view of the integer variable
+//  the local id is the position of the view in the list of watched views of the propagator for the event, see 'add_watches_for_propagator'
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ViewWatcher {
+    pub propagator_identifier: PropagatorIdentifier,
+    pub view: AffineView,
+    pub local_id: usize,
+}
+
 #[derive(Default)]
 struct WatcherCP {
-    pub lower_bound_watchers: Vec<PropagatorIdentifier>,
-    pub upper_bound_watchers: Vec<PropagatorIdentifier>,
-    pub hole_watchers: Vec<PropagatorIdentifier>,
+    pub lower_bound_watchers: Vec<ViewWatcher>,
+    pub upper_bound_watchers: Vec<ViewWatcher>,
+    pub hole_watchers: Vec<ViewWatcher>,
 }
@@ -0,0 +1,223 @@
+use crate::{
+    basic_types::{Predicate, PropagationStatusCP, PropagatorIdentifier, PropositionalConjunction},
+    engine::cp::DomainManager,
+    propagators::ConstraintProgrammingPropagator,
+    pumpkin_asserts::*,
+};
+
+use super::{
+    cp::{AssignmentsInteger, DomainOperationOutcome},
+    SATEngineDataStructures,
+};
+
+//debug checks of the propagators, meant to be called within pumpkin asserts, see 'pumpkin_asserts.rs'
+//  the checks rely on 'ConstraintProgrammingPropagator::debug_propagate_from_scratch', and apply to every CP propagator
+//  each method either panics with a description of the bug, or returns true
+pub struct DebugHelper {}
+
+impl DebugHelper {
+    //this method is only to be called after the solver completed propagation until a fixed point and no conflict were detected
+    //  the point is to check whether there is a propagator that missed a propagation or failure
+    //  additionally checks whether the internal data structures of the clausal propagator are okay and consistent with the assignments_propositional
+    pub fn debug_fixed_point_propagation(
+        assignments_integer: &AssignmentsInteger,
+        sat_data_structures: &SATEngineDataStructures,
+        propagators_cp: &[Box<dyn ConstraintProgrammingPropagator>],
+    ) -> bool {
+        let mut assignments_integer_clone = assignments_integer.clone();
+        //check whether constraint programming propagators missed anything
+        //  ask each propagator to propagate from scratch, and check whether any new propagations took place
+        //  if a new propagation took place, then the main propagation loop missed at least one propagation, indicating buggy behaviour
+        //  note that it could still be that the main propagation loop propagates more than it should
+        //      however this will not be detected with this debug check
+        //      instead such behaviour may be detected when debug-checking the reason for propagation
+        for (propagator_id, propagator) in propagators_cp.iter().enumerate() {
+            let num_entries_on_trail_before_propagation =
+                assignments_integer_clone.num_trail_entries();
+
+            let mut domains = DomainManager::new(propagator_id, &mut assignments_integer_clone);
+            let propagation_status_cp = propagator.debug_propagate_from_scratch(&mut domains);
+
+            if let PropagationStatusCP::ConflictDetected { ref failure_reason } =
+                propagation_status_cp
+            {
+                panic!("Propagator '{}' with id '{}' seems to have missed a conflict in its regular propagation algorithms! Aborting!\nExpected reason: {}", propagator.name(), propagator_id, failure_reason);
+            }
+
+            let num_missed_propagations = assignments_integer_clone.num_trail_entries()
+                - num_entries_on_trail_before_propagation;
+            pumpkin_assert_eq_simple!(num_missed_propagations, 0,
+                "Propagator '{}' with id '{}' propagated {} predicates after calling debug_propagate_from_scratch, meaning it missed propagations in its regular 'propagate' method. Aborting!",
+                propagator.name(), propagator_id, num_missed_propagations);
+        }
+        //then check the clausal propagator
+        pumpkin_assert_simple!(sat_data_structures.clausal_propagator.debug_check_state(
+            &sat_data_structures.assignments_propositional,
+            &sat_data_structures.clause_allocator
+        ));
+        true
+    }
+
+    pub fn debug_reported_failure(
+        assignments_integer: &AssignmentsInteger,
+        failure_reason: &PropositionalConjunction,
+        propagator: &dyn ConstraintProgrammingPropagator,
+        propagator_id: PropagatorIdentifier,
+    ) -> bool {
+        //the failure reason is only checked to reproduce the failure
+        //  the reason is not checked to be minimal, e.g., by negating one of its predicates and expecting no failure,
+        //      since this may fail for correct reasons, e.g., if the constraint cannot be satisfied in the initial domains
+        let mut assignments_integer_clone =
+            DebugHelper::debug_create_empty_assignment_integers_clone(assignments_integer);
+
+        let reason_predicates: Vec<Predicate> = failure_reason.clone().into_iter().collect();
+        let adding_predicates_was_successful =
+            DebugHelper::debug_add_predicates_to_assignment_integers(
+                &mut assignments_integer_clone,
+                &reason_predicates,
+            );
+
+        if adding_predicates_was_successful {
+            //  now propagate using the debug propagation method
+            let mut domains =
+                DomainManager::new(propagator_id.id as usize, &mut assignments_integer_clone);
+            let debug_propagation_status_cp = propagator.debug_propagate_from_scratch(&mut domains);
+
+            assert!(
+                debug_propagation_status_cp.conflict_detected(),
+                "Debug propagation could not reproduce the conflict reported by the propagator '{}' with id '{}'.\nThe reported failure: {}",
+                propagator.name(), propagator_id.id, failure_reason
+            );
+        } else {
+            //if even adding the predicates failed, the method adding the predicates would have printed debug info already
+            //  so we just need to add more information to indicate where the failure happened
+            panic!(
+                "Bug detected for '{}' propagator with id '{}' after a failure reason was given by the propagator.",
+                propagator.name(), propagator_id.id
+            );
+        }
+        true
+    }
+
+    //note that the reason may contain predicates over the propagated integer variable
+    //  e.g., the absolute value propagator explains [x >= 3] using [x >= -2] and [z >= 3] for |x| = z
+    pub fn debug_propagator_reason(
+        propagated_predicate: Predicate,
+        reason: &PropositionalConjunction,
+        assignments_integer: &AssignmentsInteger,
+        propagator: &dyn ConstraintProgrammingPropagator,
+        propagator_id: PropagatorIdentifier,
+    ) -> bool {
+        //two checks are done
+        //  Check #1. Does setting the predicates from the reason indeed lead to the propagation?
+        {
+            let mut assignments_integer_clone =
+                DebugHelper::debug_create_empty_assignment_integers_clone(assignments_integer);
+
+            let reason_predicates: Vec<Predicate> = reason.clone().into_iter().collect();
+            let adding_predicates_was_successful =
+                DebugHelper::debug_add_predicates_to_assignment_integers(
+                    &mut assignments_integer_clone,
+                    &reason_predicates,
+                );
+
+            if adding_predicates_was_successful {
+                //  now propagate using the debug propagation method
+                let mut domains =
+                    DomainManager::new(propagator_id.id as usize, &mut assignments_integer_clone);
+                let debug_propagation_status_cp =
+                    propagator.debug_propagate_from_scratch(&mut domains);
+
+                //a reason that leads to a conflict implies any predicate, e.g., [x*x = 11] for the propagation [x <= 3]
+                //  so there is nothing to check in that case
+                if debug_propagation_status_cp.conflict_detected() {
+                    return true;
+                }
+
+                assert!(
+                    assignments_integer_clone.does_predicate_hold(&propagated_predicate),
+                    "Debug propagation could not obtain the propagated predicate given the provided reason.\nPropagator: '{}'\nPropagator id: {}\nReported reason: {}\nReported propagation: {}",
+                    propagator.name(), propagator_id.id, reason, propagated_predicate
+                );
+            } else {
+                //if even adding the predicates failed, the method adding the predicates would have printed debug info already
+                //  so we just need to add more information to indicate where the failure happened
+                panic!(
+                    "Bug detected for '{}' propagator with id '{}' after a reason was given by the propagator.",
+                    propagator.name(), propagator_id.id
+                );
+            }
+        }
+
+        //  Check #2. Does setting the predicates from reason while having the negated propagated predicate lead to failure?
+        //      this idea is by Graeme Gange in the context of debugging lazy explanations
+        //          and is closely related to reverse unit propagation
+        {
+            let mut assignments_integer_clone =
+                DebugHelper::debug_create_empty_assignment_integers_clone(assignments_integer);
+
+            //the reason can be added, otherwise check #1 would have failed
+            let reason_predicates: Vec<Predicate> = reason.clone().into_iter().collect();
+            DebugHelper::debug_add_predicates_to_assignment_integers(
+                &mut assignments_integer_clone,
+                &reason_predicates,
+            );
+            let outcome =
+                assignments_integer_clone.apply_predicate_no_notify(&!propagated_predicate, None);
+
+            if let DomainOperationOutcome::Success = outcome {
+                //  now propagate using the debug propagation method
+                let mut domains =
+                    DomainManager::new(propagator_id.id as usize, &mut assignments_integer_clone);
+                let debug_propagation_status_cp =
+                    propagator.debug_propagate_from_scratch(&mut domains);
+
+                assert!(
+                    debug_propagation_status_cp.conflict_detected(),
+                    "Debug propagation could not obtain a failure by setting the reason and negating the propagated predicate.\nPropagator: '{}'\nPropagator id: '{}'.\nThe reported reason: {}\nReported propagated predicate: {}",
+                    propagator.name(), propagator_id.id, reason, propagated_predicate
+                );
+            }
+            //otherwise the negated predicate contradicts the reason without any propagation
+            //  which means the reason trivially implies the propagated predicate, e.g., the reason [x >= 5] for the propagation [x >= 3]
+        }
+        true
+    }
+}
+
+//methods that serve as small utility functions
+impl DebugHelper {
+    //the clone has the initial domains of the integer variables, i.e., the domains before any predicate was placed on the trail, including root propagations
+    fn debug_create_empty_assignment_integers_clone(
+        assignments_integer: &AssignmentsInteger,
+    ) -> AssignmentsInteger {
+        let mut assignments_integer_clone = assignments_integer.clone();
+        let num_trail_entries = assignments_integer_clone.num_trail_entries();
+        assignments_integer_clone.undo_trail(num_trail_entries);
+        assignments_integer_clone
+    }
+
+    fn debug_add_predicates_to_assignment_integers(
+        assignments_integer: &mut AssignmentsInteger,
+        predicates: &[Predicate],
+    ) -> bool {
+        for predicate in predicates {
+            let outcome = assignments_integer.apply_predicate_no_notify(predicate, None);
+            match outcome {
+                DomainOperationOutcome::Success => {
+                    //do nothing, everything is okay
+                }
+                DomainOperationOutcome::Failure => {
+                    //trivial failure, this is unexpected
+                    //  e.g., this can happen if the propagator reported [x >= a] and [x <= a-1]
+                    println!(
+                        "Trivial failure detected in the given predicates.\nFailure detected after trying to apply '{}'.",
+                        predicate
+                    );
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
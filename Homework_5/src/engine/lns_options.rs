@@ -0,0 +1,74 @@
+use crate::arguments::ArgumentHandler;
+
+//the options of large neighbourhood search, see 'Pumpkin::solve_with_lns'
+//  the default values are also the defaults of the command line arguments, see 'Pumpkin::create_argument_handler'
+#[derive(Clone, Debug)]
+pub struct LnsOptions {
+    pub neighbourhood: LnsNeighbourhood,
+    //each iteration stops after this many conflicts, after which the neighbourhood is considered too hard
+    pub num_conflicts_per_iteration: u64,
+    //the fraction of the problem variables that is left free in an iteration, where the other variables are fixed to their value in the best solution
+    //  the fraction adapts during search, growing by the adaptation factor when a neighbourhood contains no better solution,
+    //  and shrinking by the adaptation factor when the conflict budget of an iteration is exhausted, but never below the minimum
+    pub initial_relaxed_fraction: f64,
+    pub min_relaxed_fraction: f64,
+    pub adaptation_factor: f64,
+    pub random_seed: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LnsNeighbourhood {
+    //relaxes a random subset of the problem variables
+    Random,
+    //relaxes a random window of consecutive problem variables, where the window wraps around
+    //  variables that are numbered close together are often related in the model, so this tends to relax related variables together
+    Sequential,
+}
+
+impl Default for LnsOptions {
+    fn default() -> Self {
+        LnsOptions {
+            neighbourhood: LnsNeighbourhood::Random,
+            num_conflicts_per_iteration: 2000,
+            initial_relaxed_fraction: 0.2,
+            min_relaxed_fraction: 0.01,
+            adaptation_factor: 1.2,
+            random_seed: 0,
+        }
+    }
+}
+
+impl LnsOptions {
+    //with these options no variable is fixed and the conflict budget is unlimited, so the search is a complete search that tightens the objective after every solution
+    pub fn complete_search() -> LnsOptions {
+        LnsOptions {
+            num_conflicts_per_iteration: u64::MAX,
+            initial_relaxed_fraction: 1.0,
+            ..Default::default()
+        }
+    }
+
+    //without the 'lns' argument, the options describe a complete search
+    pub fn from_argument_handler(argument_handler: &ArgumentHandler) -> LnsOptions {
+        if !argument_handler.get_bool_argument("lns") {
+            return LnsOptions::complete_search();
+        }
+
+        let neighbourhood = match argument_handler
+            .get_string_argument("lns-neighbourhood")
+            .as_str()
+        {
+            "sequential" => LnsNeighbourhood::Sequential,
+            _ => LnsNeighbourhood::Random,
+        };
+
+        LnsOptions {
+            neighbourhood,
+            num_conflicts_per_iteration: argument_handler
+                .get_integer_argument("lns-conflicts-per-iteration")
+                as u64,
+            random_seed: argument_handler.get_integer_argument("random-seed").max(0) as u64,
+            ..Default::default()
+        }
+    }
+}
@@ -1,11 +1,25 @@
+mod clause_sharing;
 mod constraint_satisfaction_solver;
 mod cp;
+mod debug_helper;
+mod lns_options;
+mod model;
+mod portfolio_solver;
 mod pumpkin;
 mod sat;
 mod sat_cp_mediator;
+mod solution_iterator;
+mod solver_options;
 
 pub use self::pumpkin::Pumpkin;
+pub use clause_sharing::ClauseSharing;
 pub use constraint_satisfaction_solver::ConstraintSatisfactionSolver;
 pub use cp::*;
+pub use debug_helper::DebugHelper;
+pub use lns_options::{LnsNeighbourhood, LnsOptions};
+pub use model::Model;
+pub use portfolio_solver::PortfolioSolver;
 pub use sat::*;
 pub use sat_cp_mediator::SATCPMediator;
+pub use solution_iterator::SolutionIterator;
+pub use solver_options::{SolverOptions, SolverOptionsBuilder};
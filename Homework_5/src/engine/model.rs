@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use crate::{
+    basic_types::{
+        AffineView, CSPSolverExecutionFlag, ClauseAdditionOutcome, Function, IntegerVariable,
+        Literal, ModelExecutionFlag, PumpkinError, Solution, Stopwatch,
+    },
+    propagators::{
+        AllDifferentPropagator, ConstraintProgrammingPropagator, LinearLessOrEqualPropagator,
+    },
+};
+
+use super::{ConstraintSatisfactionSolver, SolverOptions};
+
+//a model built through code rather than read from a file, for using the solver as a library
+//  variables and constraints are passed directly to the constraint satisfaction solver
+//  an objective given with 'minimize' is compiled into an integer variable that equals the objective, which is then tightened after every solution
+//      the tightened bounds are added in a scope that is popped once solving is done, so the model can be solved again, e.g., after adding constraints
+//  infeasibility detected while adding constraints is recorded and reported by 'solve', so constraints can be added without checking outcomes
+pub struct Model {
+    csp_solver: ConstraintSatisfactionSolver,
+    options: SolverOptions,
+    is_infeasible: bool,
+    //maps the literals created with 'new_bool' to their 0-1 variable, which allows using them in linear terms of the objective
+    zero_one_variables: HashMap<Literal, IntegerVariable>,
+    objective: Option<ModelObjective>,
+}
+
+struct ModelObjective {
+    function: Function,
+    //equals the objective without its constant term, or None if the objective is constant
+    objective_variable: Option<IntegerVariable>,
+}
+
+impl Model {
+    pub fn new(options: SolverOptions) -> Model {
+        Model {
+            csp_solver: ConstraintSatisfactionSolver::new(&options),
+            options,
+            is_infeasible: false,
+            zero_one_variables: HashMap::new(),
+            objective: None,
+        }
+    }
+
+    pub fn new_bool(&mut self) -> Literal {
+        let zero_one_variable = self.csp_solver.create_new_zero_one_variable();
+        let literal = self
+            .csp_solver
+            .get_lower_bound_literal(zero_one_variable, 1);
+        self.zero_one_variables.insert(literal, zero_one_variable);
+        literal
+    }
+
+    //the solver supports nonnegative domains with at least two values, see 'ConstraintSatisfactionSolver::create_new_integer_variable'
+    pub fn new_int(
+        &mut self,
+        lower_bound: i32,
+        upper_bound: i32,
+    ) -> Result<IntegerVariable, PumpkinError> {
+        self.csp_solver
+            .create_new_integer_variable(lower_bound, upper_bound)
+    }
+
+    //returns the literal that is true exactly when the integer variable is at least the value
+    pub fn get_lower_bound_literal(
+        &self,
+        integer_variable: IntegerVariable,
+        value: i32,
+    ) -> Literal {
+        self.csp_solver
+            .get_lower_bound_literal(integer_variable, value)
+    }
+
+    pub fn add_clause(&mut self, literals: Vec<Literal>) {
+        if self.is_infeasible {
+            return;
+        }
+
+        if literals.is_empty()
+            || self.csp_solver.add_permanent_clause(literals) == ClauseAdditionOutcome::Infeasible
+        {
+            self.is_infeasible = true;
+        }
+    }
+
+    //adds the constraint 'sum coefficient * view <= right_hand_side'
+    pub fn add_linear<View: Into<AffineView>>(
+        &mut self,
+        terms: Vec<(i32, View)>,
+        right_hand_side: i32,
+    ) {
+        let terms: Vec<AffineView> = terms
+            .into_iter()
+            .filter(|(coefficient, _)| *coefficient != 0)
+            .map(|(coefficient, view)| view.into().scaled(coefficient))
+            .collect();
+
+        if terms.is_empty() {
+            self.is_infeasible |= right_hand_side < 0;
+            return;
+        }
+        self.add_propagator(Box::new(LinearLessOrEqualPropagator::new(
+            terms,
+            right_hand_side,
+        )));
+    }
+
+    pub fn add_all_different<View: Into<AffineView>>(&mut self, variables: Vec<View>) {
+        self.add_propagator(Box::new(AllDifferentPropagator::new(variables)));
+    }
+
+    //the solver looks for a solution that minimises the function instead of any solution
+    //  literals that were not created with 'new_bool' are linked to a fresh 0-1 variable
+    //  the weights and the largest value of the objective need to fit in 32 bits, since the propagators compute with 32-bit bounds
+    pub fn minimize(&mut self, function: Function) -> Result<(), PumpkinError> {
+        let to_i32 = |value: u64| {
+            i32::try_from(value).map_err(|_| {
+                PumpkinError::invalid_model(format!(
+                    "The objective exceeds the supported range, found the value {}.",
+                    value
+                ))
+            })
+        };
+
+        //no variables can be created once the solver is infeasible, and 'solve' reports infeasibility regardless of the objective
+        if self.is_infeasible {
+            self.objective = Some(ModelObjective {
+                function,
+                objective_variable: None,
+            });
+            return Ok(());
+        }
+
+        let mut terms = vec![];
+        for (literal, weight) in function.get_weighted_literals() {
+            let zero_one_variable = self.get_zero_one_variable(*literal);
+            terms.push((to_i32(*weight)?, AffineView::from(zero_one_variable)));
+        }
+        for (integer_variable, weight) in function.get_weighted_integers() {
+            terms.push((to_i32(*weight)?, AffineView::from(*integer_variable)));
+        }
+        terms.retain(|(weight, _)| *weight != 0);
+
+        //the objective variable spans the values the terms can take, which are nonnegative since both weights and domains are
+        let (mut lower_bound, mut upper_bound) = (0u64, 0u64);
+        for (weight, view) in &terms {
+            let integer_variable = view.get_integer_variable();
+            let assignments_integer = self.csp_solver.get_integer_assignments();
+            lower_bound +=
+                *weight as u64 * assignments_integer.get_lower_bound(integer_variable) as u64;
+            upper_bound +=
+                *weight as u64 * assignments_integer.get_upper_bound(integer_variable) as u64;
+        }
+        let lower_bound = to_i32(lower_bound)?;
+        let upper_bound = to_i32(upper_bound)?;
+
+        let objective_variable = if lower_bound < upper_bound {
+            let objective_variable = self.new_int(lower_bound, upper_bound)?;
+            let objective_view = AffineView::from(objective_variable);
+
+            //the equality 'sum terms = objective' is posted as two inequalities
+            let mut less_or_equal: Vec<(i32, AffineView)> = terms.clone();
+            less_or_equal.push((-1, objective_view));
+            self.add_linear(less_or_equal, 0);
+
+            let mut greater_or_equal: Vec<(i32, AffineView)> = terms
+                .iter()
+                .map(|(weight, view)| (-weight, *view))
+                .collect();
+            greater_or_equal.push((1, objective_view));
+            self.add_linear(greater_or_equal, 0);
+
+            Some(objective_variable)
+        } else {
+            None
+        };
+
+        self.objective = Some(ModelObjective {
+            function,
+            objective_variable,
+        });
+        Ok(())
+    }
+
+    //returns the value of the objective in the solution including its constant term, or None if there is no objective
+    pub fn get_objective_value(&self, solution: &Solution) -> Option<u64> {
+        self.objective.as_ref().map(|objective| {
+            objective.function.evaluate_solution(solution) + objective.function.get_constant_term()
+        })
+    }
+
+    //solves the model within the time limit of the options
+    //  a model with a constant objective is optimal as soon as a solution is found
+    pub fn solve(&mut self) -> ModelExecutionFlag {
+        if self.is_infeasible {
+            return ModelExecutionFlag::Infeasible;
+        }
+
+        let stopwatch = Stopwatch::new(self.options.time_limit_in_seconds);
+        self.csp_solver
+            .reset_variable_selection(self.options.random_seed);
+
+        let objective_variable = match &self.objective {
+            None => {
+                return match self.solve_once(&stopwatch) {
+                    Some(Some(solution)) => ModelExecutionFlag::Feasible {
+                        feasible_solution: solution,
+                    },
+                    Some(None) => ModelExecutionFlag::Infeasible,
+                    None => ModelExecutionFlag::Timeout,
+                };
+            }
+            Some(objective) => objective.objective_variable,
+        };
+        let Some(objective_variable) = objective_variable else {
+            return match self.solve_once(&stopwatch) {
+                Some(Some(solution)) => ModelExecutionFlag::Optimal {
+                    optimal_solution: solution,
+                },
+                Some(None) => ModelExecutionFlag::Infeasible,
+                None => ModelExecutionFlag::Timeout,
+            };
+        };
+
+        //the improving bounds only hold for this call, so they are added in a scope
+        //  once the scope is violated, i.e., no better solution exists, the solver reports infeasibility under the scope, see 'solve_once'
+        self.csp_solver.push_scope();
+        let mut best_solution: Option<Solution> = None;
+        let is_search_complete = loop {
+            let solution = match self.solve_once(&stopwatch) {
+                Some(Some(solution)) => solution,
+                Some(None) => break true,
+                None => break false,
+            };
+
+            //the next solution needs to improve upon the current one
+            let improvement_literal = self
+                .csp_solver
+                .get_lower_bound_literal(objective_variable, solution[objective_variable]);
+            best_solution = Some(solution);
+            if self
+                .csp_solver
+                .add_permanent_clause(vec![!improvement_literal])
+                == ClauseAdditionOutcome::Infeasible
+            {
+                self.is_infeasible = true;
+                break true;
+            }
+        };
+
+        //the scope cannot be popped if the model itself is infeasible, in which case the model is not solved again
+        if !self.is_infeasible {
+            self.csp_solver.pop_scope();
+        }
+
+        match (best_solution, is_search_complete) {
+            (Some(solution), true) => ModelExecutionFlag::Optimal {
+                optimal_solution: solution,
+            },
+            (Some(solution), false) => ModelExecutionFlag::Feasible {
+                feasible_solution: solution,
+            },
+            (None, true) => ModelExecutionFlag::Infeasible,
+            (None, false) => ModelExecutionFlag::Timeout,
+        }
+    }
+}
+
+//private methods
+impl Model {
+    fn add_propagator(&mut self, propagator: Box<dyn ConstraintProgrammingPropagator>) {
+        if self.is_infeasible {
+            return;
+        }
+
+        if self.csp_solver.add_propagator(propagator) == ClauseAdditionOutcome::Infeasible {
+            self.is_infeasible = true;
+        }
+    }
+
+    //returns Some(solution) if a solution is found, Some(None) if the model is infeasible, and None on a timeout
+    //  the solver is restored at the root afterwards, so constraints can be added after solving
+    //  infeasibility due to the constraints of an open scope is reported as Some(None), but does not make the model infeasible
+    fn solve_once(&mut self, stopwatch: &Stopwatch) -> Option<Option<Solution>> {
+        match self.csp_solver.solve(stopwatch.get_remaining_time_budget()) {
+            CSPSolverExecutionFlag::Feasible => {
+                let solution = Solution::new(
+                    self.csp_solver.get_propositional_assignments(),
+                    self.csp_solver.get_integer_assignments(),
+                );
+                self.csp_solver.restore_state_at_root();
+                Some(Some(solution))
+            }
+            CSPSolverExecutionFlag::Infeasible => {
+                if self
+                    .csp_solver
+                    .get_state()
+                    .is_infeasible_under_assumptions()
+                {
+                    self.csp_solver.restore_state_at_root();
+                } else {
+                    self.is_infeasible = true;
+                }
+                Some(None)
+            }
+            CSPSolverExecutionFlag::Timeout => {
+                self.csp_solver.restore_state_at_root();
+                None
+            }
+            CSPSolverExecutionFlag::InfeasibleUnderAssumptions => unreachable!(),
+        }
+    }
+
+    //returns a 0-1 variable that is one exactly when the literal is true
+    fn get_zero_one_variable(&mut self, literal: Literal) -> IntegerVariable {
+        if let Some(zero_one_variable) = self.zero_one_variables.get(&literal) {
+            return *zero_one_variable;
+        }
+
+        let zero_one_variable = self.csp_solver.create_new_zero_one_variable();
+        let variable_literal = self
+            .csp_solver
+            .get_lower_bound_literal(zero_one_variable, 1);
+        self.add_clause(vec![!literal, variable_literal]);
+        self.add_clause(vec![literal, !variable_literal]);
+        self.zero_one_variables.insert(literal, zero_one_variable);
+        zero_one_variable
+    }
+}
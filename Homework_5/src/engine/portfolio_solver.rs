@@ -0,0 +1,155 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    sync::Arc,
+    thread,
+};
+
+use crate::{
+    basic_types::{FileFormat, PumpkinError, PumpkinExecutionFlag, SolverStatistics},
+    pumpkin_asserts::pumpkin_assert_simple,
+};
+
+use super::{ClauseSharing, LearnedClauseSortingStrategy, Pumpkin, SolverOptions};
+
+//solves an instance with several diversified solvers in parallel, one thread per solver, and reports the first definitive answer
+//  the solvers differ in their random seed, restart frequency, learned clause sorting strategy, and initial phase, see 'get_worker_options'
+//  learned unit clauses and short learned clauses with a low LBD are shared between the solvers, see 'ClauseSharing'
+//  once a solver finds a solution or proves infeasibility, the other solvers are stopped through their termination callback
+//  the first solver keeps the given options and is the only one that prints while reading the file
+pub struct PortfolioSolver {
+    workers: Vec<Pumpkin>,
+    random_seeds: Vec<i64>,
+    //the index of the solver that found the answer, or usize::MAX while no solver has found it
+    winner_index: Arc<AtomicUsize>,
+}
+
+impl PortfolioSolver {
+    pub fn new(options: &SolverOptions, num_threads: usize) -> PortfolioSolver {
+        pumpkin_assert_simple!(num_threads > 0, "The portfolio needs at least one thread.");
+
+        //clauses are shared if they have at most eight literals and an LBD of at most two, following common practice in parallel SAT solvers
+        let clause_sharing_group = ClauseSharing::create_group(num_threads, 8, 2);
+        let winner_index = Arc::new(AtomicUsize::new(usize::MAX));
+
+        let mut workers = vec![];
+        let mut random_seeds = vec![];
+        for (index, clause_sharing) in clause_sharing_group.into_iter().enumerate() {
+            let worker_options = PortfolioSolver::get_worker_options(options, index);
+            let mut worker = Pumpkin::new(&worker_options);
+            worker.set_clause_sharing(Some(clause_sharing));
+
+            let winner_index = winner_index.clone();
+            worker.set_termination_callback(Some(Box::new(move || {
+                winner_index.load(Ordering::Relaxed) != usize::MAX
+            })));
+            if index > 0 {
+                worker.disable_logging();
+            }
+
+            workers.push(worker);
+            random_seeds.push(worker_options.random_seed);
+        }
+
+        PortfolioSolver {
+            workers,
+            random_seeds,
+            winner_index,
+        }
+    }
+
+    //every solver reads the file, so that all solvers have the same variables, which is required for sharing clauses
+    pub fn read_file(
+        &mut self,
+        file_location: &str,
+        file_format: FileFormat,
+    ) -> Result<(), PumpkinError> {
+        for worker in &mut self.workers {
+            worker.read_file(file_location, file_format)?;
+        }
+        Ok(())
+    }
+
+    //the portfolio can only be solved once, like 'Pumpkin::solve'
+    pub fn solve(&mut self) -> PumpkinExecutionFlag {
+        for (worker, random_seed) in self.workers.iter_mut().zip(&self.random_seeds) {
+            worker.reset_variable_selection(*random_seed);
+        }
+
+        let winner_index = &self.winner_index;
+        let execution_flags: Vec<PumpkinExecutionFlag> = thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .workers
+                .iter_mut()
+                .enumerate()
+                .map(|(index, worker)| {
+                    scope.spawn(move || {
+                        let execution_flag = worker.solve();
+                        if !matches!(execution_flag, PumpkinExecutionFlag::Timeout) {
+                            //only the first answer is recorded, the other solvers stop at their next check
+                            let _ = winner_index.compare_exchange(
+                                usize::MAX,
+                                index,
+                                Ordering::Relaxed,
+                                Ordering::Relaxed,
+                            );
+                        }
+                        execution_flag
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        match self.get_winner_index() {
+            Some(winner_index) => execution_flags.into_iter().nth(winner_index).unwrap(),
+            None => PumpkinExecutionFlag::Timeout,
+        }
+    }
+
+    //returns the index of the solver whose answer was reported, or None if all solvers timed out
+    pub fn get_winner_index(&self) -> Option<usize> {
+        match self.winner_index.load(Ordering::Relaxed) {
+            usize::MAX => None,
+            winner_index => Some(winner_index),
+        }
+    }
+
+    //the statistics of the solver whose answer was reported, or of the first solver if all solvers timed out
+    pub fn get_statistics(&self) -> SolverStatistics {
+        self.workers[self.get_winner_index().unwrap_or(0)].get_statistics()
+    }
+
+    pub fn num_threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    //the solver with the given index uses the given options with some options changed depending on the index
+    //  the first solver uses the given options unchanged
+    pub fn get_worker_options(options: &SolverOptions, index: usize) -> SolverOptions {
+        let mut worker_options = options.clone();
+        if index == 0 {
+            return worker_options;
+        }
+
+        //the seed -2 keeps the default order of the variables, which the other solvers replace by a random order
+        worker_options.random_seed = options.random_seed.max(0) + index as i64;
+        worker_options.num_conflicts_per_restart = match index % 3 {
+            0 => options.num_conflicts_per_restart,
+            1 => options.num_conflicts_per_restart.saturating_mul(2),
+            _ => (options.num_conflicts_per_restart / 2).max(1),
+        };
+        if index % 2 == 1 {
+            worker_options.learned_clause_sorting_strategy =
+                match options.learned_clause_sorting_strategy {
+                    LearnedClauseSortingStrategy::Lbd => LearnedClauseSortingStrategy::Activity,
+                    LearnedClauseSortingStrategy::Activity => LearnedClauseSortingStrategy::Lbd,
+                };
+        }
+        worker_options.initial_phase = options.initial_phase ^ ((index / 2) % 2 == 1);
+        worker_options
+    }
+}
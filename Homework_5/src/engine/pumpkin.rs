@@ -1,12 +1,18 @@
-use std::{fs, time::Instant};
+use std::{fs::File, time::Instant};
 
 use crate::{
     arguments::ArgumentHandler,
     basic_types::{
-        CSPSolverExecutionFlag, FileFormat, Function, Literal, PropositionalVariable,
-        PumpkinExecutionFlag, Solution, Stopwatch,
+        AffineView, CSPSolverExecutionFlag, ClauseAdditionOutcome, DimacsParser, FileFormat,
+        Function, IntegerVariable, Literal, PropositionalVariable, PumpkinError,
+        PumpkinExecutionFlag, Random, Solution, SolutionEnumerationFlag, SolverStatistics,
+        Stopwatch,
     },
-    engine::{ConstraintSatisfactionSolver, SATEngineDataStructures},
+    engine::{
+        ClauseSharing, ConstraintSatisfactionSolver, LnsNeighbourhood, LnsOptions,
+        SATEngineDataStructures, SolverOptions,
+    },
+    propagators::LinearLessOrEqualPropagator,
     pumpkin_asserts::pumpkin_assert_simple,
 };
 
@@ -14,24 +20,50 @@ pub struct Pumpkin {
     csp_solver: ConstraintSatisfactionSolver,
     objective_function: Function,
     stopwatch: Stopwatch,
+    problem_variables: Vec<PropositionalVariable>, //the variables read from the file, as opposed to auxiliary variables created by the solver
+    is_logging_enabled: bool,
+    //the conflict budget of the options, which large neighbourhood search restores after limiting its iterations
+    conflict_budget: u64,
 }
 
 impl Pumpkin {
-    pub fn new(argument_handler: &ArgumentHandler) -> Pumpkin {
+    pub fn new(options: &SolverOptions) -> Pumpkin {
         Pumpkin {
-            csp_solver: ConstraintSatisfactionSolver::new(argument_handler),
+            csp_solver: ConstraintSatisfactionSolver::new(options),
             objective_function: Function::new(),
-            stopwatch: Stopwatch::new(argument_handler.get_integer_argument("time-limit")),
+            stopwatch: Stopwatch::new(options.time_limit_in_seconds),
+            problem_variables: vec![],
+            is_logging_enabled: true,
+            conflict_budget: options.conflict_budget,
         }
     }
 
+    //stops printing 'c' lines about reading the file and solving, e.g., for all but one solver of a portfolio
+    pub fn disable_logging(&mut self) {
+        self.is_logging_enabled = false;
+    }
+
+    //see 'ConstraintSatisfactionSolver::set_termination_callback'
+    pub fn set_termination_callback(
+        &mut self,
+        termination_callback: Option<Box<dyn FnMut() -> bool + Send>>,
+    ) {
+        self.csp_solver
+            .set_termination_callback(termination_callback);
+    }
+
+    //see 'ConstraintSatisfactionSolver::set_clause_sharing'
+    pub fn set_clause_sharing(&mut self, clause_sharing: Option<ClauseSharing>) {
+        self.csp_solver.set_clause_sharing(clause_sharing);
+    }
+
     pub fn solve(&mut self) -> PumpkinExecutionFlag {
         pumpkin_assert_simple!(self.csp_solver.get_state().is_ready());
 
-        println!(
+        self.log(format!(
             "c basic initialisation took {} seconds.",
             self.stopwatch.get_elapsed_time()
-        );
+        ));
 
         //set phasing saving to an optimistic version, where objective literals are being set to zero
         let optimistic_phases: Vec<Literal> = self
@@ -61,128 +93,323 @@ impl Pumpkin {
         }
     }
 
+    //enumerates solutions that differ in the values of the problem variables, calling 'solution_callback' for each solution as soon as it is found
+    //  the enumeration stops once 'max_num_solutions' solutions have been found, all solutions have been found, or the time limit is reached
+    pub fn enumerate_solutions(
+        &mut self,
+        max_num_solutions: u64,
+        mut solution_callback: impl FnMut(&Solution),
+    ) -> SolutionEnumerationFlag {
+        pumpkin_assert_simple!(self.csp_solver.get_state().is_ready());
+        pumpkin_assert_simple!(
+            self.objective_function.is_empty(),
+            "Solution enumeration is not supported for optimisation problems."
+        );
+
+        let mut solutions = self.csp_solver.solutions(
+            self.problem_variables.clone(),
+            vec![],
+            self.stopwatch.get_remaining_time_budget(),
+        );
+
+        let mut num_solutions = 0;
+        while num_solutions < max_num_solutions {
+            match solutions.next() {
+                Some(solution) => {
+                    num_solutions += 1;
+                    solution_callback(&solution);
+                }
+                None if solutions.has_enumerated_all_solutions() => {
+                    return SolutionEnumerationFlag::AllSolutionsFound { num_solutions }
+                }
+                None => return SolutionEnumerationFlag::Timeout { num_solutions },
+            }
+        }
+        SolutionEnumerationFlag::SolutionLimitReached { num_solutions }
+    }
+
     pub fn reset_variable_selection(&mut self, random_seed: i64) {
         self.csp_solver.reset_variable_selection(random_seed);
     }
+
+    pub fn get_statistics(&self) -> SolverStatistics {
+        self.csp_solver.get_statistics()
+    }
+
+    //minimises the objective read from a wcnf file with large neighbourhood search, calling 'solution_callback' with every improving solution and its objective value
+    //  each iteration fixes part of the problem variables to their value in the best solution through assumptions, and searches the remainder under a conflict budget
+    //  the solver is reused across iterations, so learned clauses are kept
+    //  the objective is bounded by a single integer variable, whose upper bound is tightened at the root after every improvement
+    //  the best solution is optimal once the solver is infeasible regardless of the assumptions, which is guaranteed to be detected once the neighbourhood covers all variables
+    //  the objective value counts the selector variables of soft clauses, so it may exceed the weight of the violated soft clauses until the solution is optimal
+    //  the weights of the objective need to sum to at most i32::MAX, since the constraints on the objective compute with 32-bit values
+    pub fn solve_with_lns(
+        &mut self,
+        lns_options: &LnsOptions,
+        mut solution_callback: impl FnMut(&Solution, u64),
+    ) -> Result<PumpkinExecutionFlag, PumpkinError> {
+        let objective_variable = self.create_objective_variable()?;
+
+        let best_solution = match self.solve() {
+            PumpkinExecutionFlag::Feasible { feasible_solution } => feasible_solution,
+            execution_flag => return Ok(execution_flag),
+        };
+        self.csp_solver.restore_state_at_root();
+
+        let execution_flag = self.improve_with_lns(
+            best_solution,
+            objective_variable,
+            lns_options,
+            &mut solution_callback,
+        );
+        self.csp_solver.set_conflict_budget(self.conflict_budget);
+        Ok(execution_flag)
+    }
+
+    fn log(&self, message: String) {
+        if self.is_logging_enabled {
+            println!("{}", message);
+        }
+    }
+}
+
+//methods for large neighbourhood search, see 'solve_with_lns'
+impl Pumpkin {
+    fn improve_with_lns(
+        &mut self,
+        mut best_solution: Solution,
+        objective_variable: IntegerVariable,
+        lns_options: &LnsOptions,
+        solution_callback: &mut impl FnMut(&Solution, u64),
+    ) -> PumpkinExecutionFlag {
+        let mut random = Random::new(lns_options.random_seed);
+        let mut relaxed_fraction = lns_options
+            .initial_relaxed_fraction
+            .clamp(lns_options.min_relaxed_fraction, 1.0);
+        let mut is_improved = true;
+
+        loop {
+            //only solutions that improve upon the best solution are accepted from now on
+            if is_improved {
+                let best_value = self.objective_function.evaluate_solution(&best_solution);
+                solution_callback(
+                    &best_solution,
+                    best_value + self.objective_function.get_constant_term(),
+                );
+                let improvement_literal = self
+                    .csp_solver
+                    .get_lower_bound_literal(objective_variable, best_value as i32);
+                if best_value == 0
+                    || self
+                        .csp_solver
+                        .add_permanent_clause(vec![!improvement_literal])
+                        == ClauseAdditionOutcome::Infeasible
+                {
+                    return PumpkinExecutionFlag::Optimal {
+                        optimal_solution: best_solution,
+                    };
+                }
+                is_improved = false;
+            }
+
+            let assumptions = self.select_fixed_literals(
+                &best_solution,
+                relaxed_fraction,
+                lns_options.neighbourhood,
+                &mut random,
+            );
+            let iteration_conflict_budget = self
+                .csp_solver
+                .get_num_conflicts()
+                .saturating_add(lns_options.num_conflicts_per_iteration)
+                .min(self.conflict_budget);
+            self.csp_solver
+                .set_conflict_budget(iteration_conflict_budget);
+
+            let csp_execution_flag = self
+                .csp_solver
+                .solve_under_assumptions(&assumptions, self.stopwatch.get_remaining_time_budget());
+            if let CSPSolverExecutionFlag::Infeasible = csp_execution_flag {
+                return PumpkinExecutionFlag::Optimal {
+                    optimal_solution: best_solution,
+                };
+            }
+            if let CSPSolverExecutionFlag::Feasible = csp_execution_flag {
+                best_solution = Solution::new(
+                    self.csp_solver.get_propositional_assignments(),
+                    self.csp_solver.get_integer_assignments(),
+                );
+                is_improved = true;
+            }
+            self.csp_solver.restore_state_at_root();
+
+            match csp_execution_flag {
+                //the neighbourhood contains no better solution, so more variables are relaxed
+                CSPSolverExecutionFlag::InfeasibleUnderAssumptions => {
+                    relaxed_fraction = (relaxed_fraction * lns_options.adaptation_factor).min(1.0);
+                }
+                //the neighbourhood is too hard, so fewer variables are relaxed
+                //  unless the search stopped for another reason than the budget of the iteration, e.g., the time limit
+                CSPSolverExecutionFlag::Timeout => {
+                    if self.stopwatch.get_remaining_time_budget() <= 0
+                        || self.csp_solver.get_num_conflicts() < iteration_conflict_budget
+                        || iteration_conflict_budget == self.conflict_budget
+                    {
+                        return PumpkinExecutionFlag::Feasible {
+                            feasible_solution: best_solution,
+                        };
+                    }
+                    relaxed_fraction = (relaxed_fraction / lns_options.adaptation_factor)
+                        .max(lns_options.min_relaxed_fraction);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    //creates an integer variable that is at least the objective, where each weighted literal is linked to a fresh 0-1 variable
+    //  only the upper bound of the variable is tightened, so the constraint 'sum weight * variable <= objective' suffices
+    fn create_objective_variable(&mut self) -> Result<IntegerVariable, PumpkinError> {
+        let weighted_literals = self
+            .objective_function
+            .get_function_as_weighted_literals_vector(&self.csp_solver);
+
+        let sum_of_weights: u64 = weighted_literals
+            .iter()
+            .map(|weighted_literal| weighted_literal.weight)
+            .sum();
+        if sum_of_weights > i32::MAX as u64 {
+            return Err(PumpkinError::invalid_model(format!(
+                "The weights of the objective exceed the supported range, found the sum {}.",
+                sum_of_weights
+            )));
+        }
+
+        let mut objective_terms = vec![];
+        for weighted_literal in weighted_literals {
+            let zero_one_variable = self.csp_solver.create_new_zero_one_variable();
+            let variable_literal = self
+                .csp_solver
+                .get_lower_bound_literal(zero_one_variable, 1);
+            self.csp_solver
+                .add_permanent_clause(vec![!weighted_literal.literal, variable_literal]);
+            self.csp_solver
+                .add_permanent_clause(vec![weighted_literal.literal, !variable_literal]);
+            objective_terms
+                .push(AffineView::from(zero_one_variable).scaled(weighted_literal.weight as i32));
+        }
+
+        //the domain needs at least two values, see 'ConstraintSatisfactionSolver::create_new_integer_variable'
+        let objective_variable = self
+            .csp_solver
+            .create_new_integer_variable(0, (sum_of_weights as i32).max(1))?;
+        objective_terms.push(AffineView::from(objective_variable).scaled(-1));
+        self.csp_solver
+            .add_propagator(Box::new(LinearLessOrEqualPropagator::new(
+                objective_terms,
+                0,
+            )));
+        Ok(objective_variable)
+    }
+
+    //returns the literals that fix the problem variables outside the neighbourhood to their value in the best solution
+    fn select_fixed_literals(
+        &self,
+        best_solution: &Solution,
+        relaxed_fraction: f64,
+        neighbourhood: LnsNeighbourhood,
+        random: &mut Random,
+    ) -> Vec<Literal> {
+        let num_variables = self.problem_variables.len();
+        let num_relaxed = (relaxed_fraction * num_variables as f64).ceil() as usize;
+        if num_relaxed >= num_variables {
+            return vec![];
+        }
+
+        let fixed_indices: Vec<usize> = match neighbourhood {
+            LnsNeighbourhood::Random => {
+                let mut indices: Vec<usize> = (0..num_variables).collect();
+                random.shuffle(&mut indices);
+                indices.truncate(num_variables - num_relaxed);
+                indices
+            }
+            LnsNeighbourhood::Sequential => {
+                let start = random.next_below(num_variables) + num_relaxed;
+                (start..(start + num_variables - num_relaxed))
+                    .map(|index| index % num_variables)
+                    .collect()
+            }
+        };
+
+        fixed_indices
+            .into_iter()
+            .map(|index| {
+                let variable = self.problem_variables[index];
+                Literal::new(variable, best_solution[variable])
+            })
+            .collect()
+    }
 }
 
 //methods for reading files
 //  perhaps in the future these should be moved outside the solver?
 impl Pumpkin {
-    pub fn read_file(&mut self, file_location: &str, file_format: FileFormat) {
+    pub fn read_file(
+        &mut self,
+        file_location: &str,
+        file_format: FileFormat,
+    ) -> Result<(), PumpkinError> {
         let time_start = Instant::now();
 
         match file_format {
-            FileFormat::CnfDimacsPLine => self.read_cnf_p_line(file_location),
-            FileFormat::WcnfDimacsPLine => self.read_wcnf_p_line(file_location),
-            FileFormat::MaxSAT2022 => todo!(),
+            FileFormat::CnfDimacsPLine => self.read_cnf_p_line(file_location)?,
+            FileFormat::WcnfDimacsPLine => self.read_wcnf_p_line(file_location)?,
+            FileFormat::MaxSAT2022 => {
+                return Err(PumpkinError::UnsupportedFileFormat {
+                    message: "Reading the MaxSAT2022 format is not supported.".to_string(),
+                })
+            }
         }
 
-        println!(
+        self.log(format!(
             "c reading file took {} seconds.",
             time_start.elapsed().as_secs()
-        );
+        ));
+        Ok(())
     }
 
-    fn read_wcnf_p_line(&mut self, file_location: &str) {
+    fn read_wcnf_p_line(&mut self, file_location: &str) -> Result<(), PumpkinError> {
         pumpkin_assert_simple!(
             self.objective_function.is_empty(),
             "Expected an empty objective function."
         );
 
-        //this is a slow method of reading, especially for large files (GBs) from the MaxSAT competition
-        //  but for now it will do
-
-        let file_contents = fs::read_to_string(file_location).unwrap_or_else(|_| {
-            panic!("Unable to read file with path: {}", file_location);
-        });
+        //the format is 'p wcnf [num variables] [num clauses] [top weight]', followed by the clauses
+        let mut parser = DimacsParser::open(file_location, "wcnf")?;
+        let num_variables = parser.header().num_variables;
+        let num_clauses = parser.header().num_clauses;
+        let top_weight = parser.header().top_weight.unwrap();
 
-        //skip comments
-        //  comments are lines that star with 'c'
-        let mut lines = file_contents.lines().filter(|line| !line.starts_with('c'));
-
-        //read the header line
-        //  the format is 'p wcnf [num variables] [num clauses] [top weight]
-        let mut header = lines.next().unwrap().split(' ');
-        let mut temp = header.next();
-        assert!(temp == Some("p"));
-        temp = header.next();
-        assert!(temp == Some("wcnf"));
-        let num_variables = header.next().unwrap().parse::<u64>().unwrap();
-        let num_clauses = header.next().unwrap().parse::<u64>().unwrap();
-        let top_weight = header.next().unwrap().parse::<u64>().unwrap();
-
-        let variables: Vec<PropositionalVariable> = (0..num_variables)
+        self.problem_variables = (0..num_variables)
             .map(|_i| self.csp_solver.create_new_propositional_variable())
             .collect();
 
-        println!("c reading file: {}", file_location);
-        println!("c num variables: {}", num_variables);
-        println!("c num clauses: {}", num_clauses);
-        println!("c top weight: {}", top_weight);
+        self.log(format!("c reading file: {}", file_location));
+        self.log(format!("c num variables: {}", num_variables));
+        self.log(format!("c num clauses: {}", num_clauses));
+        self.log(format!("c top weight: {}", top_weight));
 
-        let mut num_clauses_read = 0;
         //read clauses one by one
-        for line in lines {
-            let mut raw_integers = line
-                .split(' ')
-                .filter_map(|s| {
-                    //in case there are double spaces "  ", the split will return an empty string
-                    //  should do a more robust file reading algorithm but for now this will do
-                    if s.is_empty() {
-                        return None;
-                    }
-
-                    let integer = s.parse::<i64>().unwrap();
-                    if integer == 0 {
-                        None
-                    } else {
-                        Some(integer)
-                    }
-                })
-                .peekable();
+        let mut dimacs_literals = vec![];
+        while let Some(weight) = parser.next_weighted_clause(&mut dimacs_literals)? {
+            let mut literals = self.create_clause(&parser, &dimacs_literals)?;
 
-            if raw_integers.peek() == Some(&(top_weight as i64)) {
+            if weight == top_weight {
                 //hard clauses start with the top weight value
-                raw_integers.next(); //remove the first value from consideration
-                let literals: Vec<Literal> = raw_integers
-                    .filter_map(|v| match v.cmp(&0) {
-                        std::cmp::Ordering::Greater => Some(Literal::new(
-                            variables[v.unsigned_abs() as usize - 1], //minus one is important since in the vector the indicies are from zero, whereas the indexing is from 1 in the file
-                            true,
-                        )),
-                        std::cmp::Ordering::Less => Some(Literal::new(
-                            variables[v.unsigned_abs() as usize - 1], //minus one is important since in the vector the indicies are from zero, whereas the indexing is from 1 in the file
-                            false,
-                        )),
-                        std::cmp::Ordering::Equal => {
-                            panic!("Zero values are unexpected at this point")
-                        }
-                    })
-                    .collect();
-
                 self.csp_solver.add_permanent_clause(literals);
             } else {
                 //soft clause
 
-                let weight = raw_integers.next().unwrap() as u64;
-
-                let mut literals: Vec<Literal> = raw_integers
-                    .filter_map(|v| match v.cmp(&0) {
-                        std::cmp::Ordering::Greater => Some(Literal::new(
-                            variables[v.unsigned_abs() as usize - 1], //minus one is important since in the vector the indicies are from zero, whereas the indexing is from 1 in the file
-                            true,
-                        )),
-                        std::cmp::Ordering::Less => Some(Literal::new(
-                            variables[v.unsigned_abs() as usize - 1], //minus one is important since in the vector the indicies are from zero, whereas the indexing is from 1 in the file
-                            false,
-                        )),
-                        std::cmp::Ordering::Equal => {
-                            panic!("Zero values are unexpected at this point")
-                        }
-                    })
-                    .collect();
-
                 literals = SATEngineDataStructures::preprocess_clause(
                     literals,
                     self.csp_solver.get_propositional_assignments(),
@@ -216,79 +443,75 @@ impl Pumpkin {
                     self.csp_solver.add_permanent_clause(literals);
                 }
             }
+        }
 
-            num_clauses_read += 1;
+        for warning in parser.finish() {
+            self.log(format!("c warning: {}", warning));
         }
-        assert!(
-            num_clauses == num_clauses_read,
-            "Num of clauses in the file does not match the header."
-        );
+        Ok(())
     }
 
-    fn read_cnf_p_line(&mut self, file_location: &str) {
-        //this is a slow method of reading, especially for large files (GBs) from the MaxSAT competition
-        //  but for now it will do
-
-        let file_contents = fs::read_to_string(file_location).unwrap_or_else(|_| {
-            panic!("Unable to read file with path: {}", file_location);
-        });
-
-        //skip comments
-        //  comments are lines that star with 'c'
-        let mut lines = file_contents.lines().filter(|line| !line.starts_with('c'));
-
-        //read the header line
-        //  the format is 'p cnf [num variables] [num clauses]
-        let mut header = lines.next().unwrap().split(' ');
-        let mut temp = header.next();
-        assert!(temp == Some("p"));
-        temp = header.next();
-        assert!(temp == Some("cnf"));
-        let num_variables = header.next().unwrap().parse::<u64>().unwrap();
-        let num_clauses = header.next().unwrap().parse::<u64>().unwrap();
-
-        let variables: Vec<PropositionalVariable> = (0..num_variables)
+    fn read_cnf_p_line(&mut self, file_location: &str) -> Result<(), PumpkinError> {
+        //the format is 'p cnf [num variables] [num clauses]', followed by the clauses
+        let mut parser = DimacsParser::open(file_location, "cnf")?;
+        let num_variables = parser.header().num_variables;
+        let num_clauses = parser.header().num_clauses;
+
+        self.problem_variables = (0..num_variables)
             .map(|_i| self.csp_solver.create_new_propositional_variable())
             .collect();
 
-        println!("c reading file: {}", file_location);
-        println!("c num variables: {}", num_variables);
-        println!("c num clauses: {}", num_clauses);
+        self.log(format!("c reading file: {}", file_location));
+        self.log(format!("c num variables: {}", num_variables));
+        self.log(format!("c num clauses: {}", num_clauses));
 
-        let mut num_clauses_read = 0;
         //read clauses one by one
-        for line in lines {
-            let literals: Vec<Literal> = line
-                .split(' ')
-                .filter_map(|s| {
-                    let variable_index = s.parse::<i64>().unwrap();
-                    match variable_index.cmp(&0) {
-                        std::cmp::Ordering::Equal => None,
-                        std::cmp::Ordering::Greater => Some(Literal::new(
-                            variables[variable_index.unsigned_abs() as usize - 1], //minus one is important since in the vector the indicies are from zero, whereas the indexing is from 1 in the file
-                            true,
-                        )),
-                        std::cmp::Ordering::Less => Some(Literal::new(
-                            variables[variable_index.unsigned_abs() as usize - 1], //minus one is important since in the vector the indicies are from zero, whereas the indexing is from 1 in the file
-                            false,
-                        )),
-                    }
-                })
-                .collect();
-
+        let mut dimacs_literals = vec![];
+        while parser.next_clause(&mut dimacs_literals)? {
+            let literals = self.create_clause(&parser, &dimacs_literals)?;
             self.csp_solver.add_permanent_clause(literals);
+        }
 
-            num_clauses_read += 1;
+        for warning in parser.finish() {
+            self.log(format!("c warning: {}", warning));
         }
-        assert!(
-            num_clauses == num_clauses_read,
-            "Num of clauses in the file does not match the header."
-        );
+        Ok(())
+    }
+
+    //converts the literals of the file into literals of the solver
+    //  variables beyond the number declared in the header are created on demand
+    //  the empty clause is rejected since the solver expects clauses to contain at least one literal
+    fn create_clause(
+        &mut self,
+        parser: &DimacsParser<File>,
+        dimacs_literals: &[i64],
+    ) -> Result<Vec<Literal>, PumpkinError> {
+        if dimacs_literals.is_empty() {
+            return Err(parser.clause_error("Empty clauses are not supported."));
+        }
+
+        while (self.problem_variables.len() as u64) < parser.max_variable_index() {
+            let variable = self.csp_solver.create_new_propositional_variable();
+            self.problem_variables.push(variable);
+        }
+
+        Ok(dimacs_literals
+            .iter()
+            .map(|literal| {
+                Literal::new(
+                    self.problem_variables[literal.unsigned_abs() as usize - 1], //minus one is important since in the vector the indicies are from zero, whereas the indexing is from 1 in the file
+                    *literal > 0,
+                )
+            })
+            .collect())
     }
 }
 
 impl Pumpkin {
+    //the defaults of the arguments that configure the solver are taken from 'SolverOptions'
     pub fn create_argument_handler() -> ArgumentHandler {
+        let default_options = SolverOptions::default();
+        let default_lns_options = LnsOptions::default();
         let mut argument_handler = ArgumentHandler::new();
 
         argument_handler.define_new_category("General", "todo");
@@ -304,26 +527,68 @@ impl Pumpkin {
         argument_handler.define_integer_argument(
             "time-limit",
             "General",
-            "Maximum runtime in seconds. In the current implementation should be used for indicative purposes only, todo.",
-            i64::MAX,
+            "Maximum runtime in seconds. The limit is checked between search steps, and the solver can also be interrupted with SIGINT or SIGTERM.",
+            default_options.time_limit_in_seconds,
             0,
             i64::MAX
         );
 
+        argument_handler.define_integer_argument(
+            "threads",
+            "General",
+            "Number of threads. With more than one thread, CNF files are solved by a portfolio of differently configured solvers that share short learned clauses, where the first answer is reported. Other inputs and solution enumeration use a single thread.",
+            1,
+            1,
+            1 << 10,
+        );
+
         argument_handler.define_integer_argument(
             "num-conflicts-per-restart",
             "General",
             "Number of conflicts before each restart. This is a fixed-length restart strategy.",
-            4000,
+            default_options.num_conflicts_per_restart as i64,
+            0,
+            1 << 60,
+        );
+
+        argument_handler.define_integer_argument(
+            "num-restarts-before-vsids",
+            "General",
+            "Number of restarts during which the solver branches according to the search annotations, if any are given. Afterwards the solver switches to VSIDS.",
+            default_options.num_restarts_before_vsids as i64,
             0,
             1 << 60,
         );
 
+        argument_handler.define_bool_argument(
+            "all-solutions",
+            "General",
+            "If true, reports all solutions, where solutions are distinguished by the values of the variables in the file. Overrides 'num-solutions'.",
+            false,
+        );
+
+        argument_handler.define_integer_argument(
+            "num-solutions",
+            "General",
+            "Maximum number of solutions to report. Values larger than one enable solution enumeration, where solutions are distinguished by the values of the variables in the file.",
+            1,
+            1,
+            i64::MAX,
+        );
+
+        argument_handler.define_string_argument(
+            "solution-output-format",
+            "General",
+            "Format in which solutions are printed: 'v' lines as in the SAT competition, or the '----------' separated format of MiniZinc.",
+            "dimacs",
+            &["dimacs", "minizinc"],
+        );
+
         argument_handler.define_integer_argument
         ("threshold-learned-clauses",
         "General",
         "Threshold indicating the target number of learned clauses to be kept in the solver. This number could be exceeded temporarily but occassionally the solver will delete learned clauses.", 
-        4000,
+        default_options.threshold_learned_clauses as i64,
         0,
         1 << 60);
 
@@ -339,11 +604,159 @@ impl Pumpkin {
             "random-seed",
             "General",
             "Influences initial order of variables. todo example.",
+            default_options.random_seed,
             -2,
-            -2,
             i64::MAX,
         );
 
+        argument_handler.define_bool_argument(
+            "initial-phase",
+            "General",
+            "Truth value tried first for variables that have not been assigned before. Afterwards the solver uses phase saving.",
+            default_options.initial_phase,
+        );
+
+        argument_handler.define_string_argument(
+            "branching-heuristic",
+            "General",
+            "Heuristic that scores the propositional variables for decisions: VSIDS, conflict history-based branching (CHB), or learning-rate branching (LRB).",
+            "vsids",
+            &["vsids", "chb", "lrb"],
+        );
+
+        argument_handler.define_bool_argument(
+            "target-phases",
+            "General",
+            "If true, decisions follow the values of the longest conflict-free trail since the last rephase instead of the saved phases.",
+            default_options.target_phases,
+        );
+
+        argument_handler.define_string_argument(
+            "rephasing",
+            "General",
+            "Values given to the saved phases at restarts: the initial phase, its inversion, random values, the values of the longest conflict-free trail, the result of local search, or a cycle over these. 'none' disables rephasing.",
+            "none",
+            &["none", "original", "inverted", "random", "best", "walk", "cycle"],
+        );
+
+        argument_handler.define_integer_argument(
+            "num-conflicts-per-rephase",
+            "General",
+            "Number of conflicts before the first rephase. The interval between rephases grows by this number after every rephase.",
+            default_options.num_conflicts_per_rephase as i64,
+            1,
+            i64::MAX,
+        );
+
+        argument_handler.define_bool_argument(
+            "local-search",
+            "General",
+            "If true, ProbSAT local search over the clauses runs before the search, and its best assignment becomes the initial phases. Satisfiable instances are often solved by local search alone.",
+            default_options.local_search,
+        );
+
+        argument_handler.define_integer_argument(
+            "local-search-flips",
+            "General",
+            "Maximum number of flips of each local search, both before the search and when rephasing with walks.",
+            default_options.num_local_search_flips as i64,
+            0,
+            i64::MAX,
+        );
+
+        argument_handler.define_bool_argument(
+            "chronological-backtracking",
+            "General",
+            "If true, the solver only undoes the conflict level when the learned clause would backjump over more decision levels than the threshold.",
+            default_options.chronological_backtracking,
+        );
+
+        argument_handler.define_integer_argument(
+            "chronological-backtracking-threshold",
+            "General",
+            "Number of decision levels a backjump may undo before the solver backtracks chronologically instead.",
+            default_options.chronological_backtracking_threshold as i64,
+            0,
+            u32::MAX as i64,
+        );
+
+        argument_handler.define_bool_argument(
+            "lns",
+            "General",
+            "If true, wcnf files are optimised with large neighbourhood search, which fixes part of the variables to their value in the best solution and searches the rest under a conflict budget. Otherwise wcnf files are optimised with a complete search.",
+            false,
+        );
+
+        argument_handler.define_integer_argument(
+            "lns-conflicts-per-iteration",
+            "General",
+            "Conflict budget of each iteration of large neighbourhood search, after which fewer variables are left free in the next iteration.",
+            default_lns_options.num_conflicts_per_iteration as i64,
+            1,
+            i64::MAX,
+        );
+
+        argument_handler.define_string_argument(
+            "lns-neighbourhood",
+            "General",
+            "Variables left free in an iteration of large neighbourhood search: a random subset, or a random window of consecutively numbered variables.",
+            "random",
+            &["random", "sequential"],
+        );
+
+        argument_handler.define_integer_argument(
+            "conflict-budget",
+            "General",
+            "Maximum number of conflicts, after which the solver stops as if the time limit was reached. Unlike the time limit, budgets give deterministic runs. By default there is no limit.",
+            i64::MAX,
+            0,
+            i64::MAX,
+        );
+
+        argument_handler.define_integer_argument(
+            "decision-budget",
+            "General",
+            "Maximum number of decisions, after which the solver stops as if the time limit was reached. By default there is no limit.",
+            i64::MAX,
+            0,
+            i64::MAX,
+        );
+
+        argument_handler.define_integer_argument(
+            "propagation-budget",
+            "General",
+            "Maximum number of propagated literals, after which the solver stops as if the time limit was reached. By default there is no limit.",
+            i64::MAX,
+            0,
+            i64::MAX,
+        );
+
+        argument_handler.define_integer_argument(
+            "tick-limit",
+            "General",
+            "Maximum number of ticks, a deterministic measure of the work of the solver, after which the solver stops as if the time limit was reached. Ticks are reported in the statistics. By default there is no limit.",
+            i64::MAX,
+            0,
+            i64::MAX,
+        );
+
+        argument_handler.define_integer_argument(
+            "memory-limit",
+            "General",
+            "Approximate memory limit in megabytes for the clause database, the watch lists, the encoding of integer variables, and the trails. Once the limit is reached the learned clauses are reduced, and if that does not suffice the solver stops as if the time limit was reached. By default there is no limit.",
+            i64::MAX,
+            0,
+            i64::MAX,
+        );
+
+        argument_handler.define_string_argument(
+            "stats-file",
+            "General",
+            "If non-empty, the statistics of the solver are written as JSON to the file at exit, in addition to being printed.",
+            "",
+            &[],
+        );
+
         argument_handler
     }
 }
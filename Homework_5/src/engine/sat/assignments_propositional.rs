@@ -1,5 +1,6 @@
 use crate::basic_types::{
-    Literal, Predicate, PropositionalVariable, PropositionalVariableGeneratorIterator,
+    estimate_vec_memory_usage, Literal, Predicate, PropositionalVariable,
+    PropositionalVariableGeneratorIterator,
 };
 use crate::pumpkin_asserts::*;
 
@@ -12,6 +13,12 @@ pub struct AssignmentsPropositional {
     pub false_literal: Literal,
 }
 
+impl Default for AssignmentsPropositional {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AssignmentsPropositional {
     pub fn new() -> AssignmentsPropositional {
         let dummy_literal = Literal::new(PropositionalVariable::new(0), true);
@@ -30,6 +37,12 @@ impl AssignmentsPropositional {
         self.trail_delimiter.push(self.trail.len() as u32);
     }
 
+    pub fn estimate_memory_usage(&self) -> usize {
+        estimate_vec_memory_usage(&self.assignment_info)
+            + estimate_vec_memory_usage(&self.trail)
+            + estimate_vec_memory_usage(&self.trail_delimiter)
+    }
+
     pub fn get_decision_level(&self) -> u32 {
         self.current_decision_level
     }
@@ -161,13 +174,13 @@ impl AssignmentsPropositional {
         self.get_variable_reason_code(literal.get_propositional_variable())
     }
 
-    fn make_assignment(&mut self, true_literal: Literal, reason_code: u32) {
+    fn make_assignment(&mut self, true_literal: Literal, reason_code: u32, decision_level: u32) {
         pumpkin_assert_simple!(self.is_literal_unassigned(true_literal));
 
         self.assignment_info[true_literal.get_propositional_variable()] =
             PropositionalAssignmentInfo::Assigned {
                 truth_value: true_literal.is_positive(),
-                decision_level: self.get_decision_level(),
+                decision_level,
                 reason_code,
             };
 
@@ -183,22 +196,51 @@ impl AssignmentsPropositional {
     pub fn enqueue_decision_literal(&mut self, decision_literal: Literal) {
         pumpkin_assert_simple!(!self.is_literal_assigned(decision_literal));
 
-        self.make_assignment(decision_literal, 0);
+        self.make_assignment(decision_literal, 0, self.get_decision_level());
     }
 
     pub fn enqueue_propagated_literal(&mut self, propagated_literal: Literal, reason_code: u32) {
+        self.enqueue_propagated_literal_at_level(
+            propagated_literal,
+            reason_code,
+            self.get_decision_level(),
+        );
+    }
+
+    //assigns the literal out of order if the decision level is below the current decision level
+    //  this is used with chronological backtracking, where a propagated literal takes the highest level of the literals in its reason
+    //  the literal is placed at the end of the trail as usual, so the levels on the trail are no longer sorted
+    pub fn enqueue_propagated_literal_at_level(
+        &mut self,
+        propagated_literal: Literal,
+        reason_code: u32,
+        decision_level: u32,
+    ) {
         pumpkin_assert_simple!(!self.is_literal_assigned(propagated_literal));
         pumpkin_assert_ne_simple!(reason_code, 0);
+        pumpkin_assert_simple!(decision_level <= self.get_decision_level());
 
-        self.make_assignment(propagated_literal, reason_code);
+        self.make_assignment(propagated_literal, reason_code, decision_level);
     }
 
-    pub fn synchronise(&mut self, new_decision_level: u32) {
+    //undoes the assignments above the new decision level, and calls 'on_unassign' for each undone literal, most recent first
+    //  literals that were assigned out of order at or below the new decision level stay on the trail in their original order,
+    //  after the literals that were assigned before the next decision level started
+    pub fn synchronise(&mut self, new_decision_level: u32, mut on_unassign: impl FnMut(Literal)) {
         pumpkin_assert_simple!(new_decision_level < self.current_decision_level);
-        pumpkin_assert_simple!(
-            self.trail.len() == (self.trail_delimiter[new_decision_level as usize] as usize),
-            "It is expected that the solver would pop the trail before calling this method."
-        );
+
+        let num_kept_assignments = self.trail_delimiter[new_decision_level as usize] as usize;
+        let mut out_of_order_literals = vec![];
+        while self.trail.len() > num_kept_assignments {
+            let last_literal = self.trail.pop().unwrap();
+            if self.get_literal_assignment_level(last_literal) <= new_decision_level {
+                out_of_order_literals.push(last_literal);
+            } else {
+                self.undo_assignment(last_literal.get_propositional_variable());
+                on_unassign(last_literal);
+            }
+        }
+        self.trail.extend(out_of_order_literals.into_iter().rev());
 
         self.current_decision_level = new_decision_level;
         self.trail_delimiter.truncate(new_decision_level as usize);
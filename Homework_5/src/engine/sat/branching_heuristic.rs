@@ -0,0 +1,39 @@
+use crate::basic_types::{KeyValueHeap, PropositionalVariable};
+
+//computes the scores by which the propositional variable selector picks the next decision variable, see 'PropositionalVariableSelector'
+//  the scores are the values of the heap, which is owned by the selector, so that all heuristics share the lazy removal of assigned variables
+//  the solver calls the methods in the following order during a conflict:
+//      'on_conflict_variable' for every variable seen during conflict analysis, then 'on_conflict', and then 'on_unassign' while backjumping
+//  variables are unassigned both when backjumping after a conflict and when backtracking without a conflict, e.g., for restarts
+pub trait BranchingHeuristic: Send {
+    //adds a variable with index equal to the current number of variables
+    fn grow(&mut self);
+
+    //forgets the history of the search, after the selector set all scores to zero
+    fn reset(&mut self);
+
+    //the variable was assigned by a decision or a propagation, which is reported after propagation has finished
+    fn on_assign(&mut self, _variable: PropositionalVariable) {}
+
+    //the variable is about to be unassigned, and is placed back on the heap afterwards
+    //  its score may be changed through the heap, e.g., with the reward of the assignment
+    fn on_unassign(&mut self, _variable: PropositionalVariable, _heap: &mut KeyValueHeap) {}
+
+    //the variable appeared in a clause used during conflict analysis
+    fn on_conflict_variable(&mut self, variable: PropositionalVariable, heap: &mut KeyValueHeap);
+
+    //called once per conflict, after all variables of the conflict analysis have been reported
+    fn on_conflict(&mut self, heap: &mut KeyValueHeap);
+}
+
+//the heuristics that can be selected through the options, see 'SolverOptions::branching_heuristic'
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BranchingHeuristicType {
+    //variable state independent decaying sum, which bumps the variables seen during conflict analysis
+    #[default]
+    Vsids,
+    //conflict history-based branching, which rewards assignments of variables that recently took part in conflicts
+    Chb,
+    //learning-rate branching, which rewards assignments by the fraction of conflicts the variable took part in while assigned
+    Lrb,
+}
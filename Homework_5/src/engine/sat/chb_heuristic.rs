@@ -0,0 +1,80 @@
+use super::BranchingHeuristic;
+use crate::basic_types::{KeyValueHeap, PropositionalVariable};
+
+//conflict history-based branching, following Liang et al., 'Exponential Recency Weighted Average Branching Heuristic for SAT Solvers', AAAI 2016
+//  the score of a variable is an exponential moving average of the rewards of its assignments
+//  the reward is 1 / (conflicts since the variable last took part in a conflict + 1),
+//      multiplied by 0.9 if no conflict happened while the variable was assigned
+//  the step size of the moving average decreases with every conflict, from 0.4 to 0.06
+//  rewards are given when the variable is unassigned rather than after every propagation, which avoids revisiting the trail after each propagation
+pub struct ChbHeuristic {
+    num_conflicts: u64,
+    //the conflict in which the variable last took part, where conflicts are counted from one
+    last_conflicts: Vec<u64>,
+    //the number of conflicts at the time the variable was assigned
+    assignment_conflicts: Vec<u64>,
+    step_size: f64,
+    min_step_size: f64,
+    step_size_decrement: f64,
+}
+
+impl Default for ChbHeuristic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChbHeuristic {
+    pub fn new() -> ChbHeuristic {
+        ChbHeuristic {
+            num_conflicts: 0,
+            last_conflicts: vec![],
+            assignment_conflicts: vec![],
+            step_size: 0.4,
+            min_step_size: 0.06,
+            step_size_decrement: 1e-6,
+        }
+    }
+}
+
+impl BranchingHeuristic for ChbHeuristic {
+    fn grow(&mut self) {
+        self.last_conflicts.push(0);
+        self.assignment_conflicts.push(self.num_conflicts);
+    }
+
+    fn reset(&mut self) {
+        *self = ChbHeuristic {
+            last_conflicts: vec![0; self.last_conflicts.len()],
+            assignment_conflicts: vec![0; self.assignment_conflicts.len()],
+            ..ChbHeuristic::new()
+        };
+    }
+
+    fn on_assign(&mut self, variable: PropositionalVariable) {
+        self.assignment_conflicts[variable] = self.num_conflicts;
+    }
+
+    fn on_unassign(&mut self, variable: PropositionalVariable, heap: &mut KeyValueHeap) {
+        let multiplier = if self.assignment_conflicts[variable] < self.num_conflicts {
+            1.0
+        } else {
+            0.9
+        };
+        let reward = multiplier / (self.num_conflicts - self.last_conflicts[variable] + 1) as f64;
+
+        let score = heap.get_value(variable.index());
+        let new_score = (1.0 - self.step_size) * score + self.step_size * reward;
+        heap.set_value(variable.index(), new_score);
+    }
+
+    fn on_conflict_variable(&mut self, variable: PropositionalVariable, _heap: &mut KeyValueHeap) {
+        //the conflict is only counted in 'on_conflict', which comes after the variables of the conflict
+        self.last_conflicts[variable] = self.num_conflicts + 1;
+    }
+
+    fn on_conflict(&mut self, _heap: &mut KeyValueHeap) {
+        self.num_conflicts += 1;
+        self.step_size = (self.step_size - self.step_size_decrement).max(self.min_step_size);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::basic_types::estimate_vec_memory_usage;
 use crate::basic_types::Clause;
 use crate::basic_types::ClauseReference;
 use crate::basic_types::Literal;
@@ -9,6 +10,12 @@ pub struct ClauseAllocator {
     deleted_clause_ids: Vec<ClauseReference>,
 }
 
+impl Default for ClauseAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ClauseAllocator {
     pub fn new() -> ClauseAllocator {
         ClauseAllocator {
@@ -39,8 +46,7 @@ impl ClauseAllocator {
         } else {
             //reuse a clause reference from the deleted clause pool
             let clause_reference = self.deleted_clause_ids.pop().unwrap();
-            self.allocated_clauses[clause_reference.id as usize] =
-                Clause::new(literals, is_learned);
+            *self.get_mutable_clause(clause_reference) = Clause::new(literals, is_learned);
 
             pumpkin_assert_simple!(
                 clause_reference.id <= self.max_clause_id,
@@ -73,6 +79,16 @@ impl ClauseAllocator {
         self.deleted_clause_ids.push(clause_reference);
     }
 
+    pub fn estimate_memory_usage(&self) -> usize {
+        estimate_vec_memory_usage(&self.allocated_clauses)
+            + estimate_vec_memory_usage(&self.deleted_clause_ids)
+            + self
+                .allocated_clauses
+                .iter()
+                .map(|clause| clause.estimate_memory_usage())
+                .sum::<usize>()
+    }
+
     pub fn is_reason_code_linked_to_a_clause(&self, reason_code: u32) -> bool {
         reason_code <= self.max_clause_id
     }
@@ -0,0 +1,205 @@
+use crate::basic_types::{Literal, PropositionalVariable, Random};
+
+use super::AssignmentsPropositional;
+
+//ProbSAT local search over a set of clauses, used to find phases for the CDCL search, see 'SATEngineDataStructures::run_local_search'
+//  each step picks a random violated clause and flips one of its variables, where variables that violate fewer other clauses are more likely to be flipped
+//  the probability of a variable is proportional to (epsilon + break)^-cb, where 'break' is the number of clauses that become violated by the flip
+//  variables assigned at the root keep their value, so clauses satisfied at the root are left out and literals falsified at the root are removed
+pub struct LocalSearch {
+    clauses: Vec<Vec<Literal>>,
+    //the clauses in which the literal occurs, indexed by the code of the literal
+    occurrences: Vec<Vec<usize>>,
+    values: Vec<bool>,
+    num_true_literals: Vec<u32>,
+    unsatisfied_clauses: Vec<usize>,
+    //the position of each clause in 'unsatisfied_clauses', or usize::MAX if the clause is satisfied
+    unsatisfied_positions: Vec<usize>,
+    //the probability weight for each number of broken clauses, where larger numbers use the last weight
+    break_weights: Vec<f64>,
+    is_root_conflicting: bool,
+}
+
+pub struct LocalSearchResult {
+    //the assignment with the fewest violated clauses, indexed by the propositional variables
+    pub best_values: Vec<bool>,
+    pub num_unsatisfied_clauses: usize,
+    pub num_flips: u64,
+}
+
+impl LocalSearch {
+    pub fn new<'a>(
+        clauses: impl Iterator<Item = &'a [Literal]>,
+        assignments: &AssignmentsPropositional,
+    ) -> LocalSearch {
+        let num_variables = assignments.num_propositional_variables() as usize;
+        let mut local_search = LocalSearch {
+            clauses: vec![],
+            occurrences: vec![vec![]; 2 * num_variables],
+            values: vec![false; num_variables],
+            num_true_literals: vec![],
+            unsatisfied_clauses: vec![],
+            unsatisfied_positions: vec![],
+            //the constants of ProbSAT for 3-SAT
+            break_weights: (0..16)
+                .map(|num_breaks| (1.0 + num_breaks as f64).powf(-2.3))
+                .collect(),
+            is_root_conflicting: false,
+        };
+
+        for literals in clauses {
+            if literals.iter().any(|literal| {
+                assignments.is_literal_root_assignment(*literal)
+                    && assignments.is_literal_assigned_true(*literal)
+            }) {
+                continue;
+            }
+
+            let literals: Vec<Literal> = literals
+                .iter()
+                .copied()
+                .filter(|literal| !assignments.is_literal_root_assignment(*literal))
+                .collect();
+            if literals.is_empty() {
+                local_search.is_root_conflicting = true;
+                continue;
+            }
+
+            let clause_index = local_search.clauses.len();
+            for literal in &literals {
+                local_search.occurrences[literal.to_u32() as usize].push(clause_index);
+            }
+            local_search.clauses.push(literals);
+        }
+
+        local_search.num_true_literals = vec![0; local_search.clauses.len()];
+        local_search.unsatisfied_positions = vec![usize::MAX; local_search.clauses.len()];
+        local_search
+    }
+
+    pub fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    //starts from the initial values and flips until all clauses are satisfied or the number of flips is reached
+    //  the initial values are indexed by the propositional variables, and the values of variables assigned at the root are ignored
+    pub fn run(
+        &mut self,
+        initial_values: &[bool],
+        max_num_flips: u64,
+        random: &mut Random,
+    ) -> LocalSearchResult {
+        self.values.copy_from_slice(initial_values);
+        self.initialise_clause_states();
+
+        let mut best_values = self.values.clone();
+        let mut best_num_unsatisfied_clauses = self.get_num_unsatisfied_clauses();
+        let mut num_flips = 0;
+        let mut probabilities: Vec<f64> = vec![];
+        while num_flips < max_num_flips && !self.unsatisfied_clauses.is_empty() {
+            let clause_index =
+                self.unsatisfied_clauses[random.next_below(self.unsatisfied_clauses.len())];
+
+            //all literals of the clause are false, so flipping a variable makes its literal in the clause true
+            probabilities.clear();
+            for literal in &self.clauses[clause_index] {
+                let num_breaks = self.compute_num_breaks(literal.get_propositional_variable());
+                probabilities
+                    .push(self.break_weights[num_breaks.min(self.break_weights.len() - 1)]);
+            }
+
+            let mut threshold = random.next_f64() * probabilities.iter().sum::<f64>();
+            let mut selected_position = probabilities.len() - 1;
+            for (position, probability) in probabilities.iter().enumerate() {
+                if threshold < *probability {
+                    selected_position = position;
+                    break;
+                }
+                threshold -= probability;
+            }
+
+            self.flip(self.clauses[clause_index][selected_position].get_propositional_variable());
+            num_flips += 1;
+
+            if self.get_num_unsatisfied_clauses() < best_num_unsatisfied_clauses {
+                best_num_unsatisfied_clauses = self.get_num_unsatisfied_clauses();
+                best_values.copy_from_slice(&self.values);
+            }
+        }
+
+        LocalSearchResult {
+            best_values,
+            num_unsatisfied_clauses: best_num_unsatisfied_clauses,
+            num_flips,
+        }
+    }
+
+    //clauses violated at the root can never be satisfied, and are counted as unsatisfied by every assignment
+    fn get_num_unsatisfied_clauses(&self) -> usize {
+        self.unsatisfied_clauses.len() + self.is_root_conflicting as usize
+    }
+
+    fn initialise_clause_states(&mut self) {
+        self.unsatisfied_clauses.clear();
+        for clause_index in 0..self.clauses.len() {
+            let num_true_literals = self.clauses[clause_index]
+                .iter()
+                .filter(|literal| self.is_literal_true(**literal))
+                .count() as u32;
+            self.num_true_literals[clause_index] = num_true_literals;
+            self.unsatisfied_positions[clause_index] = usize::MAX;
+            if num_true_literals == 0 {
+                self.add_unsatisfied_clause(clause_index);
+            }
+        }
+    }
+
+    //the number of clauses in which the currently true literal of the variable is the only true literal
+    fn compute_num_breaks(&self, variable: PropositionalVariable) -> usize {
+        let true_literal = Literal::new(variable, self.values[variable]);
+        self.occurrences[true_literal.to_u32() as usize]
+            .iter()
+            .filter(|clause_index| self.num_true_literals[**clause_index] == 1)
+            .count()
+    }
+
+    fn flip(&mut self, variable: PropositionalVariable) {
+        let old_true_literal = Literal::new(variable, self.values[variable]);
+        self.values[variable] = !self.values[variable];
+
+        for position in 0..self.occurrences[old_true_literal.to_u32() as usize].len() {
+            let clause_index = self.occurrences[old_true_literal.to_u32() as usize][position];
+            self.num_true_literals[clause_index] -= 1;
+            if self.num_true_literals[clause_index] == 0 {
+                self.add_unsatisfied_clause(clause_index);
+            }
+        }
+
+        for position in 0..self.occurrences[(!old_true_literal).to_u32() as usize].len() {
+            let clause_index = self.occurrences[(!old_true_literal).to_u32() as usize][position];
+            self.num_true_literals[clause_index] += 1;
+            if self.num_true_literals[clause_index] == 1 {
+                self.remove_unsatisfied_clause(clause_index);
+            }
+        }
+    }
+
+    fn is_literal_true(&self, literal: Literal) -> bool {
+        self.values[literal.get_propositional_variable()] == literal.is_positive()
+    }
+
+    fn add_unsatisfied_clause(&mut self, clause_index: usize) {
+        self.unsatisfied_positions[clause_index] = self.unsatisfied_clauses.len();
+        self.unsatisfied_clauses.push(clause_index);
+    }
+
+    //swaps the clause with the last unsatisfied clause before removing it
+    fn remove_unsatisfied_clause(&mut self, clause_index: usize) {
+        let position = self.unsatisfied_positions[clause_index];
+        let last_clause_index = *self.unsatisfied_clauses.last().unwrap();
+        self.unsatisfied_clauses[position] = last_clause_index;
+        self.unsatisfied_positions[last_clause_index] = position;
+        self.unsatisfied_clauses.pop();
+        self.unsatisfied_positions[clause_index] = usize::MAX;
+    }
+}
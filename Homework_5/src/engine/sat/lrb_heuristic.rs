@@ -0,0 +1,78 @@
+use super::BranchingHeuristic;
+use crate::basic_types::{KeyValueHeap, PropositionalVariable};
+
+//learning-rate branching, following Liang et al., 'Learning Rate Based Branching Heuristic for SAT Solvers', SAT 2016
+//  the score of a variable is an exponential moving average of the learning rates of its assignments
+//  the learning rate of an assignment is the fraction of the conflicts during the assignment in which the variable took part
+//  the step size of the moving average decreases with every conflict, from 0.4 to 0.06
+//  assignments that lasted no conflict do not change the score
+pub struct LrbHeuristic {
+    num_conflicts: u64,
+    //the number of conflicts at the time the variable was assigned
+    assignment_conflicts: Vec<u64>,
+    //the number of conflicts the variable took part in since it was assigned
+    num_participations: Vec<u64>,
+    step_size: f64,
+    min_step_size: f64,
+    step_size_decrement: f64,
+}
+
+impl Default for LrbHeuristic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LrbHeuristic {
+    pub fn new() -> LrbHeuristic {
+        LrbHeuristic {
+            num_conflicts: 0,
+            assignment_conflicts: vec![],
+            num_participations: vec![],
+            step_size: 0.4,
+            min_step_size: 0.06,
+            step_size_decrement: 1e-6,
+        }
+    }
+}
+
+impl BranchingHeuristic for LrbHeuristic {
+    fn grow(&mut self) {
+        self.assignment_conflicts.push(self.num_conflicts);
+        self.num_participations.push(0);
+    }
+
+    fn reset(&mut self) {
+        *self = LrbHeuristic {
+            assignment_conflicts: vec![0; self.assignment_conflicts.len()],
+            num_participations: vec![0; self.num_participations.len()],
+            ..LrbHeuristic::new()
+        };
+    }
+
+    fn on_assign(&mut self, variable: PropositionalVariable) {
+        self.assignment_conflicts[variable] = self.num_conflicts;
+        self.num_participations[variable] = 0;
+    }
+
+    fn on_unassign(&mut self, variable: PropositionalVariable, heap: &mut KeyValueHeap) {
+        let interval = self.num_conflicts - self.assignment_conflicts[variable];
+        if interval == 0 {
+            return;
+        }
+
+        let learning_rate = self.num_participations[variable] as f64 / interval as f64;
+        let score = heap.get_value(variable.index());
+        let new_score = (1.0 - self.step_size) * score + self.step_size * learning_rate;
+        heap.set_value(variable.index(), new_score);
+    }
+
+    fn on_conflict_variable(&mut self, variable: PropositionalVariable, _heap: &mut KeyValueHeap) {
+        self.num_participations[variable] += 1;
+    }
+
+    fn on_conflict(&mut self, _heap: &mut KeyValueHeap) {
+        self.num_conflicts += 1;
+        self.step_size = (self.step_size - self.step_size_decrement).max(self.min_step_size);
+    }
+}
@@ -1,11 +1,24 @@
 mod assignments_propositional;
+mod branching_heuristic;
+mod chb_heuristic;
 mod clause_allocator;
+mod local_search;
+mod lrb_heuristic;
 mod propositional_value_selector;
 mod propositional_variable_selector;
 mod sat_engine_data_structures;
+mod vsids_heuristic;
 
 pub use assignments_propositional::AssignmentsPropositional;
+pub use branching_heuristic::{BranchingHeuristic, BranchingHeuristicType};
+pub use chb_heuristic::ChbHeuristic;
 pub use clause_allocator::ClauseAllocator;
+pub use local_search::{LocalSearch, LocalSearchResult};
+pub use lrb_heuristic::LrbHeuristic;
 pub use propositional_value_selector::PropositionalValueSelector;
+pub use propositional_value_selector::RephaseKind;
+pub use propositional_value_selector::RephasingStrategy;
 pub use propositional_variable_selector::PropositionalVariableSelector;
+pub use sat_engine_data_structures::LearnedClauseSortingStrategy;
 pub use sat_engine_data_structures::SATEngineDataStructures;
+pub use vsids_heuristic::VsidsHeuristic;
@@ -1,30 +1,124 @@
-use crate::basic_types::PropositionalVariable;
+use crate::basic_types::{Literal, PropositionalVariable, Random};
 
+//selects the truth value of decision variables
+//  by default the value is the saved phase, i.e., the last value the variable had before it was unassigned
+//  with target phases, the value is taken from the longest conflict-free trail since the last rephase, if the variable was on that trail
+//  the longest conflict-free trail since the last rephase to the best phases is kept as the best phases, which rephasing can copy into the saved phases
+//  frozen values, see 'update_and_freeze', take precedence over all of the above
 pub struct PropositionalValueSelector {
     truth_values: Vec<CandidateTruthAssignment>,
+    initial_value: bool,
+    use_target_phases: bool,
+    num_target_assignments: usize,
+    num_best_assignments: usize,
 }
 
 struct CandidateTruthAssignment {
     pub value: bool,
     pub frozen: bool,
+    pub target_value: Option<bool>,
+    pub best_value: Option<bool>,
+}
+
+//the values that rephasing assigns to the saved phases of variables that are not frozen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RephaseKind {
+    Original,
+    Inverted,
+    Random,
+    Best,
+    //the saved phases are improved by local search, see 'SATEngineDataStructures::run_local_search'
+    Walk,
+}
+
+//decides which kind of rephasing is done at the i-th rephase, see 'get_rephase_kind'
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RephasingStrategy {
+    #[default]
+    None,
+    Original,
+    Inverted,
+    Random,
+    Best,
+    Walk,
+    //cycles through the kinds similar to CaDiCaL, starting with the original and inverted phases,
+    //  after which the best phases alternate with the other kinds, each followed by local search
+    Cycle,
+}
+
+impl RephasingStrategy {
+    pub fn get_rephase_kind(&self, rephase_index: u64) -> Option<RephaseKind> {
+        const CYCLE: [RephaseKind; 9] = [
+            RephaseKind::Best,
+            RephaseKind::Walk,
+            RephaseKind::Random,
+            RephaseKind::Best,
+            RephaseKind::Walk,
+            RephaseKind::Original,
+            RephaseKind::Best,
+            RephaseKind::Walk,
+            RephaseKind::Inverted,
+        ];
+
+        match self {
+            RephasingStrategy::None => None,
+            RephasingStrategy::Original => Some(RephaseKind::Original),
+            RephasingStrategy::Inverted => Some(RephaseKind::Inverted),
+            RephasingStrategy::Random => Some(RephaseKind::Random),
+            RephasingStrategy::Best => Some(RephaseKind::Best),
+            RephasingStrategy::Walk => Some(RephaseKind::Walk),
+            RephasingStrategy::Cycle => match rephase_index {
+                0 => Some(RephaseKind::Original),
+                1 => Some(RephaseKind::Inverted),
+                _ => Some(CYCLE[(rephase_index as usize - 2) % CYCLE.len()]),
+            },
+        }
+    }
+}
+
+impl Default for PropositionalValueSelector {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PropositionalValueSelector {
     pub fn new() -> PropositionalValueSelector {
+        PropositionalValueSelector::with_initial_value(false)
+    }
+
+    //new variables are assigned the initial value when first selected
+    pub fn with_initial_value(initial_value: bool) -> PropositionalValueSelector {
         PropositionalValueSelector {
             truth_values: vec![],
+            initial_value,
+            use_target_phases: false,
+            num_target_assignments: 0,
+            num_best_assignments: 0,
         }
     }
 
+    pub fn with_target_phases(mut self, use_target_phases: bool) -> PropositionalValueSelector {
+        self.use_target_phases = use_target_phases;
+        self
+    }
+
     pub fn grow(&mut self) {
         self.truth_values.push(CandidateTruthAssignment {
-            value: false,
+            value: self.initial_value,
             frozen: false,
+            target_value: None,
+            best_value: None,
         });
     }
 
     pub fn select_value(&self, variable: PropositionalVariable) -> bool {
-        self.truth_values[variable].value
+        let candidate = &self.truth_values[variable];
+        if self.use_target_phases && !candidate.frozen {
+            candidate.target_value.unwrap_or(candidate.value)
+        } else {
+            candidate.value
+        }
     }
 
     pub fn update_if_not_frozen(&mut self, variable: PropositionalVariable, new_truth_value: bool) {
@@ -38,4 +132,50 @@ impl PropositionalValueSelector {
         self.truth_values[variable].value = new_truth_value;
         self.truth_values[variable].frozen = true;
     }
+
+    //the literals are assigned without a conflict, e.g., the trail before the decision level of a conflict
+    //  the literals become the target phases if there are more than on the longest conflict-free trail seen since the last rephase,
+    //  and similarly the best phases if there are more than since the last rephase to the best phases
+    pub fn update_target_and_best_phases(&mut self, conflict_free_literals: &[Literal]) {
+        if conflict_free_literals.len() > self.num_target_assignments {
+            self.num_target_assignments = conflict_free_literals.len();
+            for literal in conflict_free_literals {
+                self.truth_values[literal.get_propositional_variable()].target_value =
+                    Some(literal.is_positive());
+            }
+        }
+
+        if conflict_free_literals.len() > self.num_best_assignments {
+            self.num_best_assignments = conflict_free_literals.len();
+            for literal in conflict_free_literals {
+                self.truth_values[literal.get_propositional_variable()].best_value =
+                    Some(literal.is_positive());
+            }
+        }
+    }
+
+    //overwrites the saved phases of the variables that are not frozen, and forgets the target phases
+    //  variables without a best phase keep their saved phase when rephasing to the best phases, after which new best phases are collected
+    pub fn rephase(&mut self, rephase_kind: RephaseKind, random: &mut Random) {
+        for candidate in &mut self.truth_values {
+            candidate.target_value = None;
+            if candidate.frozen {
+                continue;
+            }
+
+            candidate.value = match rephase_kind {
+                RephaseKind::Original => self.initial_value,
+                RephaseKind::Inverted => !self.initial_value,
+                RephaseKind::Random => random.next_u64() & 1 == 1,
+                RephaseKind::Best => candidate.best_value.unwrap_or(candidate.value),
+                //local search has already written its values, see 'SATEngineDataStructures::rephase_if_needed'
+                RephaseKind::Walk => candidate.value,
+            };
+        }
+
+        self.num_target_assignments = 0;
+        if rephase_kind == RephaseKind::Best {
+            self.num_best_assignments = 0;
+        }
+    }
 }
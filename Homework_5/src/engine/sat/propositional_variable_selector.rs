@@ -1,52 +1,69 @@
-use super::AssignmentsPropositional;
+use super::{
+    AssignmentsPropositional, BranchingHeuristic, BranchingHeuristicType, ChbHeuristic,
+    LrbHeuristic, VsidsHeuristic,
+};
 use crate::basic_types::{KeyValueHeap, PropositionalVariable};
 
+//selects the unassigned variable with the highest score, where the scores are computed by the branching heuristic
 pub struct PropositionalVariableSelector {
     heap: KeyValueHeap,
-    increment: f64,
-    max_threshold: f64,
-    decay_factor: f64,
+    heuristic: Box<dyn BranchingHeuristic>,
+}
+
+impl Default for PropositionalVariableSelector {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PropositionalVariableSelector {
     pub fn new() -> PropositionalVariableSelector {
+        PropositionalVariableSelector::with_heuristic(BranchingHeuristicType::Vsids)
+    }
+
+    pub fn with_heuristic(heuristic_type: BranchingHeuristicType) -> PropositionalVariableSelector {
+        let heuristic: Box<dyn BranchingHeuristic> = match heuristic_type {
+            BranchingHeuristicType::Vsids => Box::new(VsidsHeuristic::new()),
+            BranchingHeuristicType::Chb => Box::new(ChbHeuristic::new()),
+            BranchingHeuristicType::Lrb => Box::new(LrbHeuristic::new()),
+        };
         PropositionalVariableSelector {
             heap: KeyValueHeap::new(),
-            increment: 1.0,
-            max_threshold: 1e100,
-            decay_factor: 0.95,
+            heuristic,
         }
     }
 
     pub fn reset(&mut self, random_seed: i64) {
         self.heap.reset(random_seed);
-        self.increment = 1.0;
+        self.heuristic.reset();
     }
 
-    pub fn bump_activity(&mut self, variable: PropositionalVariable) {
-        //scale the activities if the values are too large
-        let activity = self.heap.get_value(variable.index());
-        if activity + self.increment >= self.max_threshold {
-            self.heap.divide_values(self.max_threshold);
-            self.increment /= self.max_threshold;
-        }
-        //now perform the standard bumping
-        self.heap.increment(variable.index(), self.increment);
+    pub fn get_score(&self, variable: PropositionalVariable) -> f64 {
+        self.heap.get_value(variable.index())
+    }
+
+    pub fn on_assign(&mut self, variable: PropositionalVariable) {
+        self.heuristic.on_assign(variable);
     }
 
+    //the variable is unassigned, so it is placed back on the heap
     pub fn restore(&mut self, variable: PropositionalVariable) {
+        self.heuristic.on_unassign(variable, &mut self.heap);
         self.heap.restore_key(variable.index());
     }
 
-    pub fn decay_activities(&mut self) {
-        //note that decaying activities is implemented as increasing the 'increment'
-        //  so that future bumps are more impactful
-        //  this is cheaper than dividing each activity value
-        self.increment *= 1.0 / self.decay_factor;
+    pub fn on_conflict_variable(&mut self, variable: PropositionalVariable) {
+        self.heuristic
+            .on_conflict_variable(variable, &mut self.heap);
+    }
+
+    pub fn on_conflict(&mut self) {
+        self.heuristic.on_conflict(&mut self.heap);
     }
 
     pub fn grow(&mut self) {
         self.heap.grow(0.0);
+        self.heuristic.grow();
     }
 
     pub fn peek_next_variable(
@@ -1,15 +1,15 @@
 use crate::{
-    arguments::ArgumentHandler,
     basic_types::{
-        BranchingDecision, ClauseAdditionOutcome, ClauseReference, Literal,
-        PropagationStatusClausal,
+        estimate_vec_memory_usage, BranchingDecision, ClauseAdditionOutcome, ClauseReference,
+        Literal, PropagationStatusClausal, Random,
     },
+    engine::SolverOptions,
     propagators::ClausalPropagator,
 };
 
 use super::{
-    AssignmentsPropositional, ClauseAllocator, PropositionalValueSelector,
-    PropositionalVariableSelector,
+    AssignmentsPropositional, ClauseAllocator, LocalSearch, PropositionalValueSelector,
+    PropositionalVariableSelector, RephaseKind, RephasingStrategy,
 };
 
 use crate::pumpkin_asserts::*;
@@ -24,12 +24,22 @@ pub struct SATEngineDataStructures {
     pub learned_clauses: Vec<ClauseReference>,
     pub explanation_clauses: Vec<ClauseReference>,
     pub assumptions: Vec<Literal>,
+    pub num_deleted_learned_clauses: u64,
+    pub num_explanation_clauses: u64,
+    pub num_rephases: u64,
+    pub num_local_search_runs: u64,
+    pub num_local_search_flips: u64,
     parameters: SATDataStructuresInternalParameters,
     clause_bump_increment: f32,
+    //the number of conflicts at which the next rephase is due, see 'rephase_if_needed'
+    next_rephase: u64,
+    //the length of the prefix of the trail whose assignments were reported to the branching heuristic, see 'notify_branching_heuristic_of_assignments'
+    num_notified_assignments: usize,
+    random: Random,
 }
 
 impl SATEngineDataStructures {
-    pub fn new(argument_handler: &ArgumentHandler) -> SATEngineDataStructures {
+    pub fn new(options: &SolverOptions) -> SATEngineDataStructures {
         SATEngineDataStructures {
             assignments_propositional: AssignmentsPropositional::new(),
             clausal_propagator: ClausalPropagator::new(),
@@ -37,14 +47,45 @@ impl SATEngineDataStructures {
             permanent_clauses: vec![],
             learned_clauses: vec![],
             explanation_clauses: vec![],
-            propositional_variable_selector: PropositionalVariableSelector::new(),
-            propositional_value_selector: PropositionalValueSelector::new(),
+            propositional_variable_selector: PropositionalVariableSelector::with_heuristic(
+                options.branching_heuristic,
+            ),
+            propositional_value_selector: PropositionalValueSelector::with_initial_value(
+                options.initial_phase,
+            )
+            .with_target_phases(options.target_phases),
             assumptions: vec![],
-            parameters: SATDataStructuresInternalParameters::new(argument_handler),
+            num_deleted_learned_clauses: 0,
+            num_explanation_clauses: 0,
+            num_rephases: 0,
+            num_local_search_runs: 0,
+            num_local_search_flips: 0,
+            parameters: SATDataStructuresInternalParameters::new(options),
             clause_bump_increment: 1.0,
+            next_rephase: options.num_conflicts_per_rephase,
+            num_notified_assignments: 0,
+            random: Random::new(options.random_seed.max(0) as u64),
         }
     }
 
+    //the clause database, the watch lists, and the propositional trail, which dominate the memory of the SAT part
+    pub fn estimate_memory_usage(&self) -> usize {
+        self.clause_allocator.estimate_memory_usage()
+            + self.clausal_propagator.estimate_memory_usage()
+            + self.assignments_propositional.estimate_memory_usage()
+            + estimate_vec_memory_usage(&self.permanent_clauses)
+            + estimate_vec_memory_usage(&self.learned_clauses)
+            + estimate_vec_memory_usage(&self.explanation_clauses)
+    }
+
+    //reduces the target size of the learned clause database and removes clauses accordingly, which frees memory when the memory limit is reached
+    //  the target is not reduced below the given minimum
+    pub fn reduce_learned_clause_database_aggressively(&mut self, min_num_learned_clauses: u64) {
+        self.parameters.num_learned_clauses_max =
+            (self.parameters.num_learned_clauses_max / 2).max(min_num_learned_clauses);
+        self.shrink_learned_clause_database_if_needed();
+    }
+
     pub fn propagate_clauses(&mut self) -> PropagationStatusClausal {
         self.clausal_propagator.propagate(
             &mut self.assignments_propositional,
@@ -81,7 +122,9 @@ impl SATEngineDataStructures {
                 self.assignments_propositional
                     .enqueue_decision_literal(literals[0]);
                 let outcome = self.propagate_clauses();
-                pumpkin_assert_simple!(outcome.no_conflict(), "Conflict detected at the root level, this is not an error but for now we do not handle this case properly so we abort.");
+                if !outcome.no_conflict() {
+                    return ClauseAdditionOutcome::Infeasible;
+                }
             }
         } else {
             //standard case - the clause has at least two unassigned literals
@@ -96,17 +139,36 @@ impl SATEngineDataStructures {
         literals: Vec<Literal>,
         is_learned: bool,
     ) -> ClauseReference {
-        pumpkin_assert_ne_moderate!(literals.len(), 0);
+        //learned clauses are added right after backtracking, when literals kept on the trail by chronological backtracking may not have been propagated again
         pumpkin_assert_moderate!(
-            self.clausal_propagator
-                .is_propagation_complete(self.assignments_propositional.trail.len()),
+            is_learned
+                || self
+                    .clausal_propagator
+                    .is_propagation_complete(self.assignments_propositional.trail.len()),
             "Adding clauses is currently only possible once all propagation has been done."
         );
 
+        self.add_clause_after_backtracking_unchecked(literals, is_learned)
+    }
+
+    //adds a clause right after backtracking, e.g., a learned clause or a clause that blocks a solution
+    //  literals kept on the trail by chronological backtracking may not have been propagated again at this point
+    //  the caller is responsible for placing the literals to be watched at the first two positions
+    pub fn add_clause_after_backtracking_unchecked(
+        &mut self,
+        literals: Vec<Literal>,
+        is_learned: bool,
+    ) -> ClauseReference {
+        pumpkin_assert_ne_moderate!(literals.len(), 0);
+
         let clause_reference = self.clause_allocator.create_clause(literals, is_learned);
         let clause = self.clause_allocator.get_clause(clause_reference);
 
-        self.permanent_clauses.push(clause_reference);
+        if is_learned {
+            self.learned_clauses.push(clause_reference);
+        } else {
+            self.permanent_clauses.push(clause_reference);
+        }
         self.clausal_propagator
             .start_watching_clause_unchecked(clause, clause_reference);
 
@@ -124,6 +186,7 @@ impl SATEngineDataStructures {
             .create_clause(explanation_literals, false);
 
         self.explanation_clauses.push(clause_reference);
+        self.num_explanation_clauses += 1;
 
         clause_reference
     }
@@ -180,12 +243,14 @@ impl SATEngineDataStructures {
         //the clauses at the back of the array are the 'bad' clauses
         let mut num_clauses_to_remove =
             self.learned_clauses.len() as u64 - self.parameters.num_learned_clauses_max;
-        for i in 0..self.learned_clauses.len() {
+        //the clauses are visited from back to front, so that the swap-and-pop below only moves clauses that were already visited
+        let mut i_rev = self.learned_clauses.len();
+        while i_rev > 0 {
             if num_clauses_to_remove == 0 {
                 break;
             }
 
-            let i_rev = self.learned_clauses.len() - 1 - i;
+            i_rev -= 1;
             let clause_reference = self.learned_clauses[i_rev];
 
             if self.clause_allocator[clause_reference].is_protected_aganst_deletion() {
@@ -208,6 +273,7 @@ impl SATEngineDataStructures {
             );
             //  finally delete the clause
             self.clause_allocator.delete_clause(clause_reference);
+            self.num_deleted_learned_clauses += 1;
 
             num_clauses_to_remove -= 1;
         }
@@ -217,12 +283,15 @@ impl SATEngineDataStructures {
             .debug_check_state(&self.assignments_propositional, &self.clause_allocator));
     }
 
-    fn peek_next_assumption_literal(&self) -> Option<Literal> {
-        assert!(
-            self.assumptions.is_empty(),
-            "Assumptions are not yet supported!"
-        );
-        None
+    //by convention the i-th assumption literal is decided at decision level i+1
+    //  so the next assumption is determined by the current decision level, which has already been increased for the upcoming decision
+    pub fn peek_next_assumption_literal(&self) -> Option<Literal> {
+        let decision_level = self.assignments_propositional.get_decision_level() as usize;
+        if decision_level > 0 && decision_level <= self.assumptions.len() {
+            Some(self.assumptions[decision_level - 1])
+        } else {
+            None
+        }
     }
 
     pub fn get_next_branching_decision(&mut self) -> Option<BranchingDecision> {
@@ -250,32 +319,166 @@ impl SATEngineDataStructures {
         }
     }
 
+    //the assignments before the decision level of a conflict, or the whole trail when backtracking without a conflict, are conflict-free
+    //  the assignments at the root are left out, since they are the same on every trail
+    pub fn update_target_and_best_phases(&mut self, num_conflict_free_assignments: usize) {
+        if !self.parameters.use_target_phases
+            && self.parameters.rephasing_strategy == RephasingStrategy::None
+        {
+            return;
+        }
+
+        let num_root_assignments = match self.assignments_propositional.trail_delimiter.first() {
+            Some(num_root_assignments) => *num_root_assignments as usize,
+            None => self.assignments_propositional.trail.len(),
+        };
+        if num_conflict_free_assignments > num_root_assignments {
+            self.propositional_value_selector
+                .update_target_and_best_phases(
+                    &self.assignments_propositional.trail
+                        [num_root_assignments..num_conflict_free_assignments],
+                );
+        }
+    }
+
+    //rephases once the number of conflicts reaches the next rephase, after which the interval until the next rephase grows arithmetically
+    //  meant to be called at the root after a restart, returns true if the phases were changed
+    pub fn rephase_if_needed(&mut self, num_conflicts: u64) -> bool {
+        if num_conflicts < self.next_rephase {
+            return false;
+        }
+
+        let rephase_kind: Option<RephaseKind> = self
+            .parameters
+            .rephasing_strategy
+            .get_rephase_kind(self.num_rephases);
+        match rephase_kind {
+            Some(rephase_kind) => {
+                if rephase_kind == RephaseKind::Walk {
+                    self.run_local_search();
+                }
+                self.propositional_value_selector
+                    .rephase(rephase_kind, &mut self.random);
+                self.num_rephases += 1;
+                self.next_rephase = num_conflicts.saturating_add(
+                    self.parameters
+                        .num_conflicts_per_rephase
+                        .saturating_mul(self.num_rephases + 1),
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    //runs local search over the permanent clauses, starting from the current phases, and saves the best assignment found as the phases
+    //  the search keeps the assignments at the root, so it needs to be called at the root
+    //  returns true if the assignment satisfies all permanent clauses, in which case the search reaches it without conflicts unless other constraints are violated,
+    //  since decisions follow the phases and every propagated literal agrees with a satisfying assignment that agrees with the decisions
+    pub fn run_local_search(&mut self) -> bool {
+        pumpkin_assert_simple!(self.assignments_propositional.is_at_the_root_level());
+
+        let clause_allocator = &self.clause_allocator;
+        let mut local_search = LocalSearch::new(
+            self.permanent_clauses
+                .iter()
+                .map(|clause_reference| clause_allocator[*clause_reference].get_literal_slice()),
+            &self.assignments_propositional,
+        );
+        //the root variable, which is skipped when iterating over the variables, is assigned at the root and its value is ignored
+        let initial_values: Vec<bool> = std::iter::once(true)
+            .chain(
+                self.assignments_propositional
+                    .get_propositional_variables()
+                    .map(|variable| self.propositional_value_selector.select_value(variable)),
+            )
+            .collect();
+        let result = local_search.run(
+            &initial_values,
+            self.parameters.num_local_search_flips,
+            &mut self.random,
+        );
+        self.num_local_search_runs += 1;
+        self.num_local_search_flips += result.num_flips;
+
+        for variable in self.assignments_propositional.get_propositional_variables() {
+            if self
+                .assignments_propositional
+                .is_variable_unassigned(variable)
+            {
+                self.propositional_value_selector
+                    .update_if_not_frozen(variable, result.best_values[variable]);
+            }
+        }
+        result.num_unsatisfied_clauses == 0
+    }
+
+    //reports the assignments made since the last call to the branching heuristic, which is done after propagation rather than for every assignment
+    pub fn notify_branching_heuristic_of_assignments(&mut self) {
+        let trail = &self.assignments_propositional.trail;
+        for literal in &trail[self.num_notified_assignments.min(trail.len())..] {
+            self.propositional_variable_selector
+                .on_assign(literal.get_propositional_variable());
+        }
+        self.num_notified_assignments = trail.len();
+    }
+
     pub fn backtrack(&mut self, backtrack_level: u32) {
         pumpkin_assert_simple!(
             backtrack_level < self.assignments_propositional.get_decision_level()
         );
 
-        let num_assignments_for_removal = self.assignments_propositional.trail.len()
-            - self.assignments_propositional.trail_delimiter[backtrack_level as usize] as usize;
+        let num_unchanged_assignments =
+            self.assignments_propositional.trail_delimiter[backtrack_level as usize] as usize;
+
+        let variable_selector = &mut self.propositional_variable_selector;
+        let value_selector = &mut self.propositional_value_selector;
+        self.assignments_propositional
+            .synchronise(backtrack_level, |unassigned_literal| {
+                variable_selector.restore(unassigned_literal.get_propositional_variable());
+                value_selector.update_if_not_frozen(
+                    unassigned_literal.get_propositional_variable(),
+                    unassigned_literal.is_positive(),
+                );
+            });
+        self.num_notified_assignments = self
+            .num_notified_assignments
+            .min(self.assignments_propositional.trail.len());
 
-        for _i in 0..num_assignments_for_removal {
-            let last_literal = self.assignments_propositional.pop_trail();
+        //literals that were assigned out of order remain on the trail after backtracking, and are propagated again
+        //  this is needed because the clauses they propagated may have been watched by literals that are now unassigned
+        self.clausal_propagator
+            .synchronise(num_unchanged_assignments);
+    }
 
-            self.propositional_variable_selector
-                .restore(last_literal.get_propositional_variable());
+    //removes the permanent and learned clauses that contain the literal
+    //  meant for literals that are true at the root, in which case the clauses are satisfied and no longer needed
+    pub fn remove_clauses_containing_literal(&mut self, literal: Literal) {
+        pumpkin_assert_simple!(
+            self.assignments_propositional.is_at_the_root_level()
+                && self
+                    .assignments_propositional
+                    .is_literal_assigned_true(literal)
+        );
 
-            self.propositional_value_selector.update_if_not_frozen(
-                last_literal.get_propositional_variable(),
-                last_literal.is_positive(),
-            );
-        }
+        for clause_references in [&mut self.permanent_clauses, &mut self.learned_clauses] {
+            let clause_allocator = &mut self.clause_allocator;
+            let clausal_propagator = &mut self.clausal_propagator;
+            clause_references.retain(|clause_reference| {
+                let clause = &clause_allocator[*clause_reference];
+                if !clause.get_literal_slice().contains(&literal) {
+                    return true;
+                }
 
-        self.assignments_propositional.synchronise(backtrack_level);
+                clausal_propagator.remove_clause_consideration(clause, *clause_reference);
+                clause_allocator.delete_clause(*clause_reference);
+                false
+            });
+        }
 
-        self.clausal_propagator.synchronise(
-            self.assignments_propositional
-                .num_assigned_propositional_variables() as usize,
-        );
+        pumpkin_assert_extreme!(self
+            .clausal_propagator
+            .debug_check_state(&self.assignments_propositional, &self.clause_allocator));
     }
 
     pub fn clean_up_explanation_clauses(&mut self) {
@@ -359,7 +562,7 @@ impl SATEngineDataStructures {
 
     pub fn is_clausal_propagation_at_fixed_point(&self) -> bool {
         self.clausal_propagator
-            .is_propagation_complete(self.assignments_propositional.trail.len() as usize)
+            .is_propagation_complete(self.assignments_propositional.trail.len())
     }
 
     //does simple preprocessing, modifying the input vector of literals
@@ -440,36 +643,28 @@ struct SATDataStructuresInternalParameters {
     pub max_clause_activity: f32,
     pub clause_activity_decay_factor: f32,
     pub learned_clause_sorting_strategy: LearnedClauseSortingStrategy,
+    pub use_target_phases: bool,
+    pub rephasing_strategy: RephasingStrategy,
+    pub num_conflicts_per_rephase: u64,
+    pub num_local_search_flips: u64,
 }
 
 impl SATDataStructuresInternalParameters {
-    fn new(argument_handler: &ArgumentHandler) -> SATDataStructuresInternalParameters {
+    fn new(options: &SolverOptions) -> SATDataStructuresInternalParameters {
         SATDataStructuresInternalParameters {
-            num_learned_clauses_max: argument_handler
-                .get_integer_argument("threshold-learned-clauses")
-                as u64,
+            num_learned_clauses_max: options.threshold_learned_clauses,
             max_clause_activity: 1e20,
             clause_activity_decay_factor: 0.99,
-            learned_clause_sorting_strategy:
-                SATDataStructuresInternalParameters::parse_learned_clause_sorting_strategy(
-                    argument_handler,
-                ),
-        }
-    }
-
-    fn parse_learned_clause_sorting_strategy(
-        argument_handler: &ArgumentHandler,
-    ) -> LearnedClauseSortingStrategy {
-        let param = argument_handler.get_string_argument("learned-clause-sorting-strategy");
-        match param.as_str() {
-            "activity" => LearnedClauseSortingStrategy::Activity,
-            "lbd" => LearnedClauseSortingStrategy::Lbd,
-            _ => panic!("Unknown parameter given for the learned clause strategy: {}. See parameters for more details.", param)
+            learned_clause_sorting_strategy: options.learned_clause_sorting_strategy,
+            use_target_phases: options.target_phases,
+            rephasing_strategy: options.rephasing_strategy,
+            num_conflicts_per_rephase: options.num_conflicts_per_rephase,
+            num_local_search_flips: options.num_local_search_flips,
         }
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LearnedClauseSortingStrategy {
     Activity,
     #[default]
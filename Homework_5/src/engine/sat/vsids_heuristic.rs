@@ -0,0 +1,50 @@
+use super::BranchingHeuristic;
+use crate::basic_types::{KeyValueHeap, PropositionalVariable};
+
+pub struct VsidsHeuristic {
+    increment: f64,
+    max_threshold: f64,
+    decay_factor: f64,
+}
+
+impl Default for VsidsHeuristic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VsidsHeuristic {
+    pub fn new() -> VsidsHeuristic {
+        VsidsHeuristic {
+            increment: 1.0,
+            max_threshold: 1e100,
+            decay_factor: 0.95,
+        }
+    }
+}
+
+impl BranchingHeuristic for VsidsHeuristic {
+    fn grow(&mut self) {}
+
+    fn reset(&mut self) {
+        self.increment = 1.0;
+    }
+
+    fn on_conflict_variable(&mut self, variable: PropositionalVariable, heap: &mut KeyValueHeap) {
+        //scale the activities if the values are too large
+        let activity = heap.get_value(variable.index());
+        if activity + self.increment >= self.max_threshold {
+            heap.divide_values(self.max_threshold);
+            self.increment /= self.max_threshold;
+        }
+        //now perform the standard bumping
+        heap.increment(variable.index(), self.increment);
+    }
+
+    fn on_conflict(&mut self, _heap: &mut KeyValueHeap) {
+        //note that decaying activities is implemented as increasing the 'increment'
+        //  so that future bumps are more impactful
+        //  this is cheaper than dividing each activity value
+        self.increment *= 1.0 / self.decay_factor;
+    }
+}
@@ -1,8 +1,9 @@
 use crate::basic_types::{
-    ClauseReference, IntegerVariable, Literal, Predicate, PropagatorIdentifier,
-    PropositionalVariable,
+    estimate_nested_vec_memory_usage, estimate_vec_memory_usage, ClauseReference, IntegerVariable,
+    Literal, Predicate, PropagatorIdentifier, PropositionalVariable,
 };
 
+use crate::engine::DebugHelper;
 use crate::propagators::ConstraintProgrammingPropagator;
 use crate::pumpkin_asserts::*;
 
@@ -21,6 +22,12 @@ pub struct SATCPMediator {
     pub false_literal: Literal,
 }
 
+impl Default for SATCPMediator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SATCPMediator {
     pub fn new() -> SATCPMediator {
         let dummy_literal = Literal::new(PropositionalVariable::new(0), true);
@@ -35,6 +42,16 @@ impl SATCPMediator {
             false_literal: dummy_literal,
         }
     }
+
+    //the mappings between literals and predicates grow with the domains of the integer variables due to the eager encoding
+    pub fn estimate_memory_usage(&self) -> usize {
+        estimate_vec_memory_usage(&self.synchronised_literal_to_predicate)
+            + estimate_nested_vec_memory_usage(&self.mapping_integer_variable_to_equality_literals)
+            + estimate_nested_vec_memory_usage(
+                &self.mapping_integer_variable_to_lower_bound_literals,
+            )
+            + estimate_nested_vec_memory_usage(&self.mapping_literal_to_predicates)
+    }
 }
 
 //methods for synchronising trails
@@ -43,6 +60,8 @@ impl SATCPMediator {
         &mut self,
         assignments_propositional: &mut AssignmentsPropositional,
         assignments_integer: &AssignmentsInteger,
+        cp_propagators: &mut [Box<dyn ConstraintProgrammingPropagator>],
+        is_chronological_backtracking_enabled: bool,
     ) {
         //for each entry on the integer trail, we now add the equivalent propositional representation on the propositional trail
         //  note that only one literal per predicate will be stored
@@ -62,12 +81,53 @@ impl SATCPMediator {
             let reason_code =
                 self.convert_propagator_identifier_to_reason_code(propagator_identifier);
 
-            assignments_propositional.enqueue_propagated_literal(literal, reason_code);
+            //with chronological backtracking, the literals in the explanation may have been assigned out of order
+            //  so the literal is assigned at the highest level of these literals, like literals propagated by the clausal propagator
+            let decision_level = if is_chronological_backtracking_enabled {
+                self.get_propagation_level(
+                    predicate,
+                    propagator_identifier,
+                    assignments_propositional,
+                    cp_propagators,
+                )
+            } else {
+                assignments_propositional.get_decision_level()
+            };
+
+            assignments_propositional.enqueue_propagated_literal_at_level(
+                literal,
+                reason_code,
+                decision_level,
+            );
             self.synchronised_literal_to_predicate[literal] = predicate;
         }
         self.cp_trail_synced_position = assignments_integer.num_trail_entries();
     }
 
+    //the highest decision level of the literals in the explanation of the propagated predicate
+    //  literals of the explanation that are implied by the trail but not yet assigned by the clausal propagator count as the current decision level
+    fn get_propagation_level(
+        &self,
+        predicate: Predicate,
+        propagator_identifier: PropagatorIdentifier,
+        assignments_propositional: &AssignmentsPropositional,
+        cp_propagators: &mut [Box<dyn ConstraintProgrammingPropagator>],
+    ) -> u32 {
+        cp_propagators[propagator_identifier.id as usize]
+            .get_reason_for_propagation(predicate)
+            .into_iter()
+            .map(|reason_predicate| {
+                let literal = self.get_predicate_literal(reason_predicate);
+                if assignments_propositional.is_literal_assigned_true(literal) {
+                    assignments_propositional.get_literal_assignment_level(literal)
+                } else {
+                    assignments_propositional.get_decision_level()
+                }
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn synchronise_integer_trail_based_on_propositional_trail(
         &mut self,
         assignments_propositional: &AssignmentsPropositional,
@@ -90,7 +150,6 @@ impl SATCPMediator {
             .assignments_integer
             .num_integer_variables()
             == 0
-            || cp_propagators.is_empty()
         {
             self.sat_trail_synced_position = assignments_propositional.trail.len();
             return;
@@ -123,19 +182,22 @@ impl SATCPMediator {
         }
     }
 
+    //called after both trails have been backtracked, where the propositional trail is unchanged up to 'num_unchanged_propositional_assignments'
+    //  the literals after that position were assigned out of order, see 'AssignmentsPropositional::synchronise'
+    //  their predicates were removed from the integer trail, which backtracks by decision level, so they are synchronised again
     pub fn synchronise(
         &mut self,
-        assignments_propositional: &AssignmentsPropositional,
+        num_unchanged_propositional_assignments: usize,
         assignments_integer: &AssignmentsInteger,
     ) {
         pumpkin_assert_simple!(
-            self.sat_trail_synced_position >= assignments_propositional.trail.len()
+            self.sat_trail_synced_position >= num_unchanged_propositional_assignments
         );
         pumpkin_assert_simple!(
             self.cp_trail_synced_position >= assignments_integer.num_trail_entries()
         );
         self.cp_trail_synced_position = assignments_integer.num_trail_entries();
-        self.sat_trail_synced_position = assignments_propositional.trail.len();
+        self.sat_trail_synced_position = num_unchanged_propositional_assignments;
     }
 }
 
@@ -307,13 +369,13 @@ impl SATCPMediator {
         pumpkin_assert_simple!(equality_literals.len() == (upper_bound + 1) as usize);
 
         //	define equality literals
-        //		[x == value] <-> [x >= value] AND ~[x >= value]
+        //		[x == value] <-> [x >= value] AND ~[x >= value+1]
         //		recall from above that [x == lower_bound] and [x == upper_bound] are effectively defined by being set to the corresponding lower bound literals, and so are skipped
         for i in ((lower_bound + 1) as usize)..(upper_bound as usize) {
             //one side of the implication <-
             sat_data_structures.add_permanent_ternary_clause_unchecked(
                 !lower_bound_literals[i],
-                !lower_bound_literals[i + 1],
+                lower_bound_literals[i + 1],
                 equality_literals[i],
             );
             //the other side of the implication ->
@@ -387,12 +449,13 @@ impl SATCPMediator {
         integer_variable: IntegerVariable,
         lower_bound: i32,
     ) -> Literal {
-        if lower_bound as usize
+        //negative lower bounds need to be checked first, since casting them to usize wraps around
+        if lower_bound.is_negative() {
+            self.true_literal
+        } else if lower_bound as usize
             >= self.mapping_integer_variable_to_lower_bound_literals[integer_variable].len()
         {
             self.false_literal
-        } else if lower_bound.is_negative() {
-            self.true_literal
         } else {
             self.mapping_integer_variable_to_lower_bound_literals[integer_variable]
                 [lower_bound as usize]
@@ -472,7 +535,7 @@ impl SATCPMediator {
         &mut self,
         propagated_literal: Literal,
         sat_data_structures: &mut SATEngineDataStructures,
-        _cp_data_structures: &CPEngineDataStructures,
+        cp_data_structures: &CPEngineDataStructures,
         cp_propagators: &mut [Box<dyn ConstraintProgrammingPropagator>],
     ) -> ClauseReference {
         pumpkin_assert_moderate!(
@@ -500,14 +563,28 @@ impl SATCPMediator {
             let propagator = &mut cp_propagators[propagator_id.id as usize];
             let reason = propagator.get_reason_for_propagation(predicate);
 
+            pumpkin_assert_advanced!(DebugHelper::debug_propagator_reason(
+                predicate,
+                &reason,
+                &cp_data_structures.assignments_integer,
+                propagator.as_ref(),
+                propagator_id
+            ));
+
             //create the explanation clause
             //  allocate a fresh vector each time might be a performance bottleneck
             //  todo better ways
             //important to keep propagated literal at the zero-th position
-            let explanation_literals = std::iter::once(propagated_literal)
+            let mut explanation_literals: Vec<Literal> = std::iter::once(propagated_literal)
                 .chain(reason.into_iter().map(|p| !self.get_predicate_literal(p)))
                 .collect();
 
+            //an empty reason means the propagation holds unconditionally
+            //  clauses need at least two literals, so the false literal is added, which does not change the meaning of the clause
+            if explanation_literals.len() == 1 {
+                explanation_literals.push(self.false_literal);
+            }
+
             sat_data_structures.add_explanation_clause_unchecked(explanation_literals)
         }
     }
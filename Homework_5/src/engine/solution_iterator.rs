@@ -0,0 +1,90 @@
+use crate::basic_types::{
+    CSPSolverExecutionFlag, ClauseAdditionOutcome, IntegerVariable, PropositionalVariable,
+    Solution, Stopwatch,
+};
+
+use super::ConstraintSatisfactionSolver;
+
+//iterates over the solutions of the solver
+//  solutions are distinguished only by the values of the projection variables, i.e., two solutions that agree on the projection are considered the same
+//  before searching for the next solution, the previous solution is blocked by adding a clause over the projection variables
+//  the search resumes from the level where the blocking clause propagates, see 'ConstraintSatisfactionSolver::add_blocking_clause_for_current_solution'
+//the time limit applies to the enumeration as a whole rather than to each individual solution
+pub struct SolutionIterator<'a> {
+    csp_solver: &'a mut ConstraintSatisfactionSolver,
+    propositional_variables: Vec<PropositionalVariable>,
+    integer_variables: Vec<IntegerVariable>,
+    stopwatch: Stopwatch,
+    last_execution_flag: Option<CSPSolverExecutionFlag>,
+}
+
+impl SolutionIterator<'_> {
+    pub fn new(
+        csp_solver: &'_ mut ConstraintSatisfactionSolver,
+        propositional_variables: Vec<PropositionalVariable>,
+        integer_variables: Vec<IntegerVariable>,
+        time_limit_in_seconds: i64,
+    ) -> SolutionIterator<'_> {
+        SolutionIterator {
+            csp_solver,
+            propositional_variables,
+            integer_variables,
+            stopwatch: Stopwatch::new(time_limit_in_seconds),
+            last_execution_flag: None,
+        }
+    }
+
+    //true if the iterator stopped because there are no further solutions
+    //  as opposed to stopping because of the time limit
+    pub fn has_enumerated_all_solutions(&self) -> bool {
+        matches!(
+            self.last_execution_flag,
+            Some(CSPSolverExecutionFlag::Infeasible)
+        )
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self.last_execution_flag,
+            Some(CSPSolverExecutionFlag::Timeout)
+        )
+    }
+}
+
+impl Iterator for SolutionIterator<'_> {
+    type Item = Solution;
+
+    fn next(&mut self) -> Option<Solution> {
+        match self.last_execution_flag {
+            None => {}
+            Some(CSPSolverExecutionFlag::Feasible) => {
+                let outcome = self.csp_solver.add_blocking_clause_for_current_solution(
+                    &self.propositional_variables,
+                    &self.integer_variables,
+                );
+
+                if outcome == ClauseAdditionOutcome::Infeasible {
+                    self.last_execution_flag = Some(CSPSolverExecutionFlag::Infeasible);
+                    return None;
+                }
+            }
+            //the enumeration already finished
+            Some(_) => return None,
+        }
+
+        let execution_flag = self
+            .csp_solver
+            .solve(self.stopwatch.get_remaining_time_budget());
+
+        let solution = match execution_flag {
+            CSPSolverExecutionFlag::Feasible => Some(Solution::new(
+                self.csp_solver.get_propositional_assignments(),
+                self.csp_solver.get_integer_assignments(),
+            )),
+            _ => None,
+        };
+
+        self.last_execution_flag = Some(execution_flag);
+        solution
+    }
+}
@@ -0,0 +1,299 @@
+use std::sync::{atomic::AtomicBool, Arc};
+
+use crate::arguments::ArgumentHandler;
+
+use super::{BranchingHeuristicType, LearnedClauseSortingStrategy, RephasingStrategy};
+
+//the options of the solver, which allow using the solver as a library without going through the command line arguments
+//  the default values are also the defaults of the command line arguments, see 'Pumpkin::create_argument_handler'
+//  options are typically created with the builder, e.g., 'SolverOptions::builder().time_limit_in_seconds(10).build()'
+#[derive(Clone, Debug)]
+pub struct SolverOptions {
+    //the time limit is checked during search, where i64::MAX means no limit
+    pub time_limit_in_seconds: i64,
+    pub num_conflicts_per_restart: u64,
+    //restarts during which the solver follows the search annotations before switching to VSIDS
+    pub num_restarts_before_vsids: u64,
+    //the target number of learned clauses, which may be exceeded temporarily between clean-ups
+    pub threshold_learned_clauses: u64,
+    pub learned_clause_sorting_strategy: LearnedClauseSortingStrategy,
+    //influences the initial order of the variables, see 'ConstraintSatisfactionSolver::reset_variable_selection'
+    pub random_seed: i64,
+    //the truth value tried first for variables that have not been assigned yet, after which phase saving takes over
+    pub initial_phase: bool,
+    //the heuristic that scores the propositional variables for decisions, see 'BranchingHeuristic'
+    pub branching_heuristic: BranchingHeuristicType,
+    //if true, decisions follow the longest conflict-free trail since the last rephase rather than the saved phases, see 'PropositionalValueSelector'
+    pub target_phases: bool,
+    //rephasing overwrites the saved phases at restarts, where the interval between rephases grows by 'num_conflicts_per_rephase' after every rephase
+    pub rephasing_strategy: RephasingStrategy,
+    pub num_conflicts_per_rephase: u64,
+    //if true, local search over the clauses runs once before the first search to initialise the phases, see 'SATEngineDataStructures::run_local_search'
+    //  local search also runs when rephasing with walks, in both cases with at most 'num_local_search_flips' flips
+    pub local_search: bool,
+    pub num_local_search_flips: u64,
+    //if true, the solver backtracks chronologically, i.e., only undoes the conflict level,
+    //  when the learned clause would backjump over more than 'chronological_backtracking_threshold' decision levels
+    //  this avoids propagating the undone levels again, which is costly with many CP propagators
+    pub chronological_backtracking: bool,
+    pub chronological_backtracking_threshold: u32,
+    //the budgets limit the total number of conflicts, decisions, and propagations over the lifetime of the solver, where u64::MAX means no limit
+    //  unlike the time limit, budgets are deterministic, so runs with the same budget and seed end in the same state
+    pub conflict_budget: u64,
+    pub decision_budget: u64,
+    pub propagation_budget: u64,
+    //limits the work of the solver measured in ticks, see 'ConstraintSatisfactionSolver::get_num_ticks'
+    pub tick_limit: u64,
+    //once the estimated memory exceeds the limit, the learned clause database is reduced, and if that does not suffice the search stops as if the time limit was reached
+    //  see 'ConstraintSatisfactionSolver::estimate_memory_usage', where u64::MAX means no limit
+    pub memory_limit_in_megabytes: u64,
+    //the search stops with a timeout once the flag is set, e.g., from another thread or a signal handler
+    //  the flag is shared by clones of the options and is never reset by the solver
+    pub terminate_flag: Arc<AtomicBool>,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            time_limit_in_seconds: i64::MAX,
+            num_conflicts_per_restart: 4000,
+            num_restarts_before_vsids: 1 << 60,
+            threshold_learned_clauses: 4000,
+            learned_clause_sorting_strategy: LearnedClauseSortingStrategy::Lbd,
+            random_seed: -2,
+            initial_phase: false,
+            branching_heuristic: BranchingHeuristicType::Vsids,
+            target_phases: false,
+            rephasing_strategy: RephasingStrategy::None,
+            num_conflicts_per_rephase: 1000,
+            local_search: false,
+            num_local_search_flips: 100000,
+            chronological_backtracking: false,
+            chronological_backtracking_threshold: 100,
+            conflict_budget: u64::MAX,
+            decision_budget: u64::MAX,
+            propagation_budget: u64::MAX,
+            tick_limit: u64::MAX,
+            memory_limit_in_megabytes: u64::MAX,
+            terminate_flag: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl SolverOptions {
+    pub fn builder() -> SolverOptionsBuilder {
+        SolverOptionsBuilder {
+            options: SolverOptions::default(),
+        }
+    }
+
+    //maps the command line arguments onto the options
+    //  the argument handler has already checked the values against their allowed ranges
+    pub fn from_argument_handler(argument_handler: &ArgumentHandler) -> SolverOptions {
+        let learned_clause_sorting_strategy = match argument_handler
+            .get_string_argument("learned-clause-sorting-strategy")
+            .as_str()
+        {
+            "activity" => LearnedClauseSortingStrategy::Activity,
+            _ => LearnedClauseSortingStrategy::Lbd,
+        };
+        let branching_heuristic = match argument_handler
+            .get_string_argument("branching-heuristic")
+            .as_str()
+        {
+            "chb" => BranchingHeuristicType::Chb,
+            "lrb" => BranchingHeuristicType::Lrb,
+            _ => BranchingHeuristicType::Vsids,
+        };
+        let rephasing_strategy = match argument_handler.get_string_argument("rephasing").as_str() {
+            "original" => RephasingStrategy::Original,
+            "inverted" => RephasingStrategy::Inverted,
+            "random" => RephasingStrategy::Random,
+            "best" => RephasingStrategy::Best,
+            "walk" => RephasingStrategy::Walk,
+            "cycle" => RephasingStrategy::Cycle,
+            _ => RephasingStrategy::None,
+        };
+
+        SolverOptions {
+            time_limit_in_seconds: argument_handler.get_integer_argument("time-limit"),
+            num_conflicts_per_restart: argument_handler
+                .get_integer_argument("num-conflicts-per-restart")
+                as u64,
+            num_restarts_before_vsids: argument_handler
+                .get_integer_argument("num-restarts-before-vsids")
+                as u64,
+            threshold_learned_clauses: argument_handler
+                .get_integer_argument("threshold-learned-clauses")
+                as u64,
+            learned_clause_sorting_strategy,
+            random_seed: argument_handler.get_integer_argument("random-seed"),
+            initial_phase: argument_handler.get_bool_argument("initial-phase"),
+            branching_heuristic,
+            target_phases: argument_handler.get_bool_argument("target-phases"),
+            rephasing_strategy,
+            num_conflicts_per_rephase: argument_handler
+                .get_integer_argument("num-conflicts-per-rephase")
+                as u64,
+            local_search: argument_handler.get_bool_argument("local-search"),
+            num_local_search_flips: argument_handler.get_integer_argument("local-search-flips")
+                as u64,
+            chronological_backtracking: argument_handler
+                .get_bool_argument("chronological-backtracking"),
+            chronological_backtracking_threshold: argument_handler
+                .get_integer_argument("chronological-backtracking-threshold")
+                as u32,
+            conflict_budget: argument_handler.get_integer_argument("conflict-budget") as u64,
+            decision_budget: argument_handler.get_integer_argument("decision-budget") as u64,
+            propagation_budget: argument_handler.get_integer_argument("propagation-budget") as u64,
+            tick_limit: argument_handler.get_integer_argument("tick-limit") as u64,
+            memory_limit_in_megabytes: argument_handler.get_integer_argument("memory-limit") as u64,
+            terminate_flag: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+pub struct SolverOptionsBuilder {
+    options: SolverOptions,
+}
+
+impl SolverOptionsBuilder {
+    pub fn time_limit_in_seconds(mut self, time_limit_in_seconds: i64) -> SolverOptionsBuilder {
+        self.options.time_limit_in_seconds = time_limit_in_seconds;
+        self
+    }
+
+    pub fn num_conflicts_per_restart(
+        mut self,
+        num_conflicts_per_restart: u64,
+    ) -> SolverOptionsBuilder {
+        self.options.num_conflicts_per_restart = num_conflicts_per_restart;
+        self
+    }
+
+    pub fn num_restarts_before_vsids(
+        mut self,
+        num_restarts_before_vsids: u64,
+    ) -> SolverOptionsBuilder {
+        self.options.num_restarts_before_vsids = num_restarts_before_vsids;
+        self
+    }
+
+    pub fn threshold_learned_clauses(
+        mut self,
+        threshold_learned_clauses: u64,
+    ) -> SolverOptionsBuilder {
+        self.options.threshold_learned_clauses = threshold_learned_clauses;
+        self
+    }
+
+    pub fn learned_clause_sorting_strategy(
+        mut self,
+        learned_clause_sorting_strategy: LearnedClauseSortingStrategy,
+    ) -> SolverOptionsBuilder {
+        self.options.learned_clause_sorting_strategy = learned_clause_sorting_strategy;
+        self
+    }
+
+    pub fn random_seed(mut self, random_seed: i64) -> SolverOptionsBuilder {
+        self.options.random_seed = random_seed;
+        self
+    }
+
+    pub fn initial_phase(mut self, initial_phase: bool) -> SolverOptionsBuilder {
+        self.options.initial_phase = initial_phase;
+        self
+    }
+
+    pub fn branching_heuristic(
+        mut self,
+        branching_heuristic: BranchingHeuristicType,
+    ) -> SolverOptionsBuilder {
+        self.options.branching_heuristic = branching_heuristic;
+        self
+    }
+
+    pub fn target_phases(mut self, target_phases: bool) -> SolverOptionsBuilder {
+        self.options.target_phases = target_phases;
+        self
+    }
+
+    pub fn rephasing_strategy(
+        mut self,
+        rephasing_strategy: RephasingStrategy,
+    ) -> SolverOptionsBuilder {
+        self.options.rephasing_strategy = rephasing_strategy;
+        self
+    }
+
+    pub fn num_conflicts_per_rephase(
+        mut self,
+        num_conflicts_per_rephase: u64,
+    ) -> SolverOptionsBuilder {
+        self.options.num_conflicts_per_rephase = num_conflicts_per_rephase;
+        self
+    }
+
+    pub fn local_search(mut self, local_search: bool) -> SolverOptionsBuilder {
+        self.options.local_search = local_search;
+        self
+    }
+
+    pub fn num_local_search_flips(mut self, num_local_search_flips: u64) -> SolverOptionsBuilder {
+        self.options.num_local_search_flips = num_local_search_flips;
+        self
+    }
+
+    pub fn chronological_backtracking(
+        mut self,
+        chronological_backtracking: bool,
+    ) -> SolverOptionsBuilder {
+        self.options.chronological_backtracking = chronological_backtracking;
+        self
+    }
+
+    pub fn chronological_backtracking_threshold(
+        mut self,
+        chronological_backtracking_threshold: u32,
+    ) -> SolverOptionsBuilder {
+        self.options.chronological_backtracking_threshold = chronological_backtracking_threshold;
+        self
+    }
+
+    pub fn conflict_budget(mut self, conflict_budget: u64) -> SolverOptionsBuilder {
+        self.options.conflict_budget = conflict_budget;
+        self
+    }
+
+    pub fn decision_budget(mut self, decision_budget: u64) -> SolverOptionsBuilder {
+        self.options.decision_budget = decision_budget;
+        self
+    }
+
+    pub fn propagation_budget(mut self, propagation_budget: u64) -> SolverOptionsBuilder {
+        self.options.propagation_budget = propagation_budget;
+        self
+    }
+
+    pub fn tick_limit(mut self, tick_limit: u64) -> SolverOptionsBuilder {
+        self.options.tick_limit = tick_limit;
+        self
+    }
+
+    pub fn memory_limit_in_megabytes(
+        mut self,
+        memory_limit_in_megabytes: u64,
+    ) -> SolverOptionsBuilder {
+        self.options.memory_limit_in_megabytes = memory_limit_in_megabytes;
+        self
+    }
+
+    pub fn terminate_flag(mut self, terminate_flag: Arc<AtomicBool>) -> SolverOptionsBuilder {
+        self.options.terminate_flag = terminate_flag;
+        self
+    }
+
+    pub fn build(self) -> SolverOptions {
+        self.options
+    }
+}
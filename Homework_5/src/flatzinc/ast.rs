@@ -0,0 +1,112 @@
+//the abstract syntax tree of a FlatZinc model
+//  the parser only checks the syntax, the translator gives meaning to identifiers and constraints
+//  integers are kept as i64 so that the parser does not need to know about the limits of the solver
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Set(SetLiteral),
+    Array(Vec<Expression>),
+    Identifier(String),
+    //an element of an array, e.g., 'x[3]', where indices start at one
+    ArrayAccess(String, i64),
+    //annotations with arguments, e.g., 'int_search(x, input_order, indomain_min, complete)'
+    Call(String, Vec<Expression>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SetLiteral {
+    Range(i64, i64),
+    Values(Vec<i64>),
+}
+
+impl SetLiteral {
+    pub fn contains(&self, value: i64) -> bool {
+        match self {
+            SetLiteral::Range(lower_bound, upper_bound) => {
+                *lower_bound <= value && value <= *upper_bound
+            }
+            SetLiteral::Values(values) => values.contains(&value),
+        }
+    }
+
+    //returns the smallest and largest element of the set, or None if the set is empty
+    pub fn bounds(&self) -> Option<(i64, i64)> {
+        match self {
+            SetLiteral::Range(lower_bound, upper_bound) => {
+                if lower_bound <= upper_bound {
+                    Some((*lower_bound, *upper_bound))
+                } else {
+                    None
+                }
+            }
+            SetLiteral::Values(values) => Some((*values.iter().min()?, *values.iter().max()?)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VariableType {
+    Bool,
+    //an integer variable, optionally restricted to a domain
+    Int(Option<SetLiteral>),
+    Float,
+    SetOfInt,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation {
+    pub name: String,
+    pub arguments: Vec<Expression>,
+}
+
+//a declaration of a parameter, e.g., 'array [1..3] of int: a = [1, 2, 3];'
+//  the type of a parameter follows from its value, so it is not stored
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterDeclaration {
+    pub name: String,
+    pub value: Expression,
+}
+
+//a declaration of a variable or an array of variables
+//  for arrays, the assignment is an array literal with the elements of the array
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableDeclaration {
+    pub name: String,
+    pub variable_type: VariableType,
+    pub is_array: bool,
+    pub assignment: Option<Expression>,
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstraintItem {
+    pub name: String,
+    pub arguments: Vec<Expression>,
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolveGoal {
+    Satisfy,
+    Minimize(Expression),
+    Maximize(Expression),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveItem {
+    pub goal: SolveGoal,
+    pub annotations: Vec<Annotation>,
+}
+
+//items are kept in the order of the file, which is also the order in which they may refer to each other
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlatZincModel {
+    pub parameters: Vec<ParameterDeclaration>,
+    pub variables: Vec<VariableDeclaration>,
+    pub constraints: Vec<ConstraintItem>,
+    pub solve_item: SolveItem,
+}
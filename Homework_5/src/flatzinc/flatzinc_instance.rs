@@ -0,0 +1,149 @@
+use crate::basic_types::{AffineView, IntegerVariable, Solution};
+
+//the result of translating a FlatZinc model into the solver
+//  holds the information needed to solve the model and print its solutions, i.e., the objective and the output items
+pub struct FlatZincInstance {
+    //the view that is minimised, maximisation is expressed by minimising the negated objective
+    pub(crate) objective: Option<AffineView>,
+    pub(crate) is_maximisation: bool,
+    pub(crate) output_items: Vec<OutputItem>,
+    //true if the translation already detected that the model has no solution
+    pub(crate) is_infeasible: bool,
+    //the variable that is fixed to one and used to express constants as views, if any constant was needed
+    pub(crate) constant_variable: Option<IntegerVariable>,
+}
+
+//a variable or array annotated with 'output_var' or 'output_array'
+#[derive(Clone, Debug)]
+pub enum OutputItem {
+    Variable {
+        name: String,
+        value: OutputValue,
+    },
+    Array {
+        name: String,
+        index_sets: Vec<(i64, i64)>,
+        values: Vec<OutputValue>,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum OutputValue {
+    Int(AffineView),
+    //booleans are 0-1 views, where one means true
+    Bool(AffineView),
+}
+
+impl OutputValue {
+    fn view(&self) -> AffineView {
+        match self {
+            OutputValue::Int(view) | OutputValue::Bool(view) => *view,
+        }
+    }
+
+    fn value(&self, solution: &Solution) -> i64 {
+        let view = self.view();
+        view.transform_value(solution[view.get_integer_variable()]) as i64
+    }
+
+    fn format(&self, solution: &Solution) -> String {
+        let value = self.value(solution);
+        match self {
+            OutputValue::Int(_) => value.to_string(),
+            OutputValue::Bool(_) => (value == 1).to_string(),
+        }
+    }
+}
+
+impl FlatZincInstance {
+    pub fn is_optimisation_problem(&self) -> bool {
+        self.objective.is_some()
+    }
+
+    pub fn is_infeasible(&self) -> bool {
+        self.is_infeasible
+    }
+
+    //returns the value of the objective in the given solution, in terms of the original objective of the model
+    //  i.e., for maximisation problems the value is not negated
+    pub fn get_objective_value(&self, solution: &Solution) -> Option<i64> {
+        self.objective.map(|objective| {
+            let value =
+                objective.transform_value(solution[objective.get_integer_variable()]) as i64;
+            if self.is_maximisation {
+                -value
+            } else {
+                value
+            }
+        })
+    }
+
+    //the integer variables underlying the output, used to distinguish solutions when enumerating
+    pub fn get_output_variables(&self) -> Vec<IntegerVariable> {
+        let mut output_variables: Vec<IntegerVariable> = vec![];
+        for output_item in &self.output_items {
+            let values = match output_item {
+                OutputItem::Variable { value, .. } => std::slice::from_ref(value),
+                OutputItem::Array { values, .. } => values.as_slice(),
+            };
+            for value in values {
+                let integer_variable = value.view().get_integer_variable();
+                if Some(integer_variable) != self.constant_variable
+                    && !output_variables.contains(&integer_variable)
+                {
+                    output_variables.push(integer_variable);
+                }
+            }
+        }
+        output_variables
+    }
+
+    //returns the names and values of the output variables in the solution, in order of declaration, where booleans are zero or one
+    //  output arrays are skipped, they are used by MiniZinc but not by the other front-ends
+    pub fn get_output_values(&self, solution: &Solution) -> Vec<(&str, i64)> {
+        self.output_items
+            .iter()
+            .filter_map(|output_item| match output_item {
+                OutputItem::Variable { name, value } => {
+                    Some((name.as_str(), value.value(solution)))
+                }
+                OutputItem::Array { .. } => None,
+            })
+            .collect()
+    }
+
+    //formats the solution as expected by MiniZinc, i.e., one assignment per output item followed by '----------'
+    pub fn format_solution(&self, solution: &Solution) -> String {
+        let mut output = String::new();
+        for output_item in &self.output_items {
+            match output_item {
+                OutputItem::Variable { name, value } => {
+                    output.push_str(&format!("{} = {};\n", name, value.format(solution)));
+                }
+                OutputItem::Array {
+                    name,
+                    index_sets,
+                    values,
+                } => {
+                    let index_sets: Vec<String> = index_sets
+                        .iter()
+                        .map(|(lower_bound, upper_bound)| {
+                            format!("{}..{}", lower_bound, upper_bound)
+                        })
+                        .collect();
+                    let values: Vec<String> =
+                        values.iter().map(|value| value.format(solution)).collect();
+                    output.push_str(&format!(
+                        "{} = array{}d({}, [{}]);\n",
+                        name,
+                        index_sets.len(),
+                        index_sets.join(", "),
+                        values.join(", ")
+                    ));
+                }
+            }
+        }
+        output.push_str("----------");
+        output
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    basic_types::{
+        CSPSolverExecutionFlag, ClauseAdditionOutcome, PumpkinError, Solution,
+        SolutionEnumerationFlag, SolverStatistics, Stopwatch,
+    },
+    engine::{ConstraintSatisfactionSolver, SolverOptions},
+};
+
+use super::{translate_flatzinc, FlatZincInstance, FlatZincModel};
+
+//solves a FlatZinc model, reporting solutions as MiniZinc expects them
+//  satisfaction problems enumerate solutions that differ in the output variables
+//  optimisation problems repeatedly solve and tighten the objective bound until no better solution exists
+pub struct FlatZincSolver {
+    csp_solver: ConstraintSatisfactionSolver,
+    instance: FlatZincInstance,
+    stopwatch: Stopwatch,
+}
+
+impl FlatZincSolver {
+    pub fn new(
+        model: &FlatZincModel,
+        options: &SolverOptions,
+    ) -> Result<FlatZincSolver, PumpkinError> {
+        let mut csp_solver = ConstraintSatisfactionSolver::new(options);
+        let instance = translate_flatzinc(model, &mut csp_solver)?;
+        Ok(FlatZincSolver {
+            csp_solver,
+            instance,
+            stopwatch: Stopwatch::new(options.time_limit_in_seconds),
+        })
+    }
+
+    pub fn get_instance(&self) -> &FlatZincInstance {
+        &self.instance
+    }
+
+    pub fn get_statistics(&self) -> SolverStatistics {
+        self.csp_solver.get_statistics()
+    }
+
+    //calls 'solution_callback' for the reported solutions, together with the instance, which is used to format the solution
+    //  for satisfaction problems, at most 'max_num_solutions' solutions are reported
+    //  for optimisation problems, each improving solution is reported if 'report_intermediate_solutions' is set, and otherwise only the best solution once the search stops
+    //the returned flag reports all solutions found if the search was completed, which for optimisation problems means that the last reported solution is optimal
+    pub fn solve(
+        &mut self,
+        max_num_solutions: u64,
+        report_intermediate_solutions: bool,
+        mut solution_callback: impl FnMut(&FlatZincInstance, &Solution),
+    ) -> SolutionEnumerationFlag {
+        if self.instance.is_infeasible() {
+            return SolutionEnumerationFlag::AllSolutionsFound { num_solutions: 0 };
+        }
+
+        if self.instance.is_optimisation_problem() {
+            self.optimise(report_intermediate_solutions, solution_callback)
+        } else {
+            let mut solutions = self.csp_solver.solutions(
+                vec![],
+                self.instance.get_output_variables(),
+                self.stopwatch.get_remaining_time_budget(),
+            );
+
+            let mut num_solutions = 0;
+            while num_solutions < max_num_solutions {
+                match solutions.next() {
+                    Some(solution) => {
+                        num_solutions += 1;
+                        solution_callback(&self.instance, &solution);
+                    }
+                    None if solutions.has_enumerated_all_solutions() => {
+                        return SolutionEnumerationFlag::AllSolutionsFound { num_solutions }
+                    }
+                    None => return SolutionEnumerationFlag::Timeout { num_solutions },
+                }
+            }
+            SolutionEnumerationFlag::SolutionLimitReached { num_solutions }
+        }
+    }
+
+    fn optimise(
+        &mut self,
+        report_intermediate_solutions: bool,
+        mut solution_callback: impl FnMut(&FlatZincInstance, &Solution),
+    ) -> SolutionEnumerationFlag {
+        let objective = self.instance.objective.unwrap();
+        let mut best_solution: Option<Solution> = None;
+        let mut num_solutions = 0;
+
+        let is_search_complete = loop {
+            let execution_flag = self
+                .csp_solver
+                .solve(self.stopwatch.get_remaining_time_budget());
+
+            match execution_flag {
+                CSPSolverExecutionFlag::Feasible => {}
+                CSPSolverExecutionFlag::Infeasible => break true,
+                CSPSolverExecutionFlag::Timeout => break false,
+                _ => unreachable!(),
+            }
+
+            let solution = Solution::new(
+                self.csp_solver.get_propositional_assignments(),
+                self.csp_solver.get_integer_assignments(),
+            );
+            num_solutions += 1;
+            if report_intermediate_solutions {
+                solution_callback(&self.instance, &solution);
+            }
+
+            //the next solution needs to improve upon the current one
+            let objective_value =
+                objective.transform_value(solution[objective.get_integer_variable()]);
+            best_solution = Some(solution);
+
+            self.csp_solver.restore_state_at_root();
+            let improvement_literal = self
+                .csp_solver
+                .get_predicate_literal(objective.upper_bound_predicate(objective_value - 1));
+            if self
+                .csp_solver
+                .add_permanent_clause(vec![improvement_literal])
+                == ClauseAdditionOutcome::Infeasible
+            {
+                break true;
+            }
+        };
+
+        if !report_intermediate_solutions {
+            if let Some(best_solution) = &best_solution {
+                solution_callback(&self.instance, best_solution);
+            }
+        }
+
+        if is_search_complete {
+            SolutionEnumerationFlag::AllSolutionsFound { num_solutions }
+        } else {
+            SolutionEnumerationFlag::Timeout { num_solutions }
+        }
+    }
+}
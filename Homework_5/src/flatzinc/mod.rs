@@ -0,0 +1,11 @@
+mod ast;
+mod flatzinc_instance;
+mod flatzinc_solver;
+mod parser;
+mod translator;
+
+pub use ast::*;
+pub use flatzinc_instance::{FlatZincInstance, OutputItem, OutputValue};
+pub use flatzinc_solver::FlatZincSolver;
+pub use parser::parse_flatzinc;
+pub use translator::translate_flatzinc;
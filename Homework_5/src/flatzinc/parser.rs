@@ -0,0 +1,487 @@
+use crate::basic_types::PumpkinError;
+
+use super::{
+    Annotation, ConstraintItem, Expression, FlatZincModel, ParameterDeclaration, SetLiteral,
+    SolveGoal, SolveItem, VariableDeclaration, VariableType,
+};
+
+//parses a FlatZinc model given as a string
+//  the parser is a straightforward recursive descent parser over the tokens of the model
+//  predicate declarations are skipped, since the translator decides which constraints are supported
+pub fn parse_flatzinc(model: &str) -> Result<FlatZincModel, PumpkinError> {
+    let tokens = tokenise(model)?;
+    FlatZincParser {
+        tokens,
+        position: 0,
+        end_of_file: (
+            model.lines().count().max(1),
+            model.lines().last().map_or(0, |line| line.chars().count()) + 1,
+        ),
+    }
+    .parse_model()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Symbol(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Identifier(identifier) => write!(f, "'{}'", identifier),
+            Token::Int(value) => write!(f, "'{}'", value),
+            Token::Float(value) => write!(f, "'{}'", value),
+            Token::String(string) => write!(f, "\"{}\"", string),
+            Token::Symbol(symbol) => write!(f, "'{}'", symbol),
+        }
+    }
+}
+
+//symbols are matched in order, so longer symbols with a common prefix come first
+const SYMBOLS: [&str; 12] = ["::", "..", ":", ";", ",", "=", "[", "]", "(", ")", "{", "}"];
+
+//splits the model into tokens, each paired with its line and column
+//  columns are counted in characters, starting at one
+fn tokenise(model: &str) -> Result<Vec<(Token, usize, usize)>, PumpkinError> {
+    let characters: Vec<char> = model.chars().collect();
+    let mut tokens = vec![];
+    let mut line = 1;
+    let mut line_start = 0;
+    let mut i = 0;
+    while i < characters.len() {
+        let character = characters[i];
+        let column = i - line_start + 1;
+        if character == '\n' {
+            line += 1;
+            i += 1;
+            line_start = i;
+        } else if character.is_whitespace() {
+            i += 1;
+        }
+        //comments run until the end of the line
+        else if character == '%' {
+            while i < characters.len() && characters[i] != '\n' {
+                i += 1;
+            }
+        } else if character.is_ascii_alphabetic() || character == '_' {
+            let start = i;
+            while i < characters.len()
+                && (characters[i].is_ascii_alphanumeric() || characters[i] == '_')
+            {
+                i += 1;
+            }
+            let identifier: String = characters[start..i].iter().collect();
+            tokens.push((Token::Identifier(identifier), line, column));
+        } else if character.is_ascii_digit()
+            || (character == '-' && i + 1 < characters.len() && characters[i + 1].is_ascii_digit())
+        {
+            let (token, next_position) = tokenise_number(&characters, i, line, column)?;
+            tokens.push((token, line, column));
+            i = next_position;
+        } else if character == '"' {
+            let start = i + 1;
+            i += 1;
+            while i < characters.len() && characters[i] != '"' {
+                if characters[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            if i >= characters.len() {
+                return Err(PumpkinError::parse_error(
+                    line,
+                    column,
+                    "Unterminated string literal.",
+                ));
+            }
+            let string: String = characters[start..i].iter().collect();
+            tokens.push((Token::String(string), line, column));
+            i += 1;
+        } else {
+            let symbol = SYMBOLS.iter().find(|symbol| {
+                symbol
+                    .chars()
+                    .enumerate()
+                    .all(|(offset, c)| characters.get(i + offset) == Some(&c))
+            });
+            match symbol {
+                Some(symbol) => {
+                    tokens.push((Token::Symbol(symbol), line, column));
+                    i += symbol.len();
+                }
+                None => {
+                    return Err(PumpkinError::parse_error(
+                        line,
+                        column,
+                        format!("Unexpected character '{}'.", character),
+                    ))
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+//reads an integer or float starting at the given position, returning the token and the position after the number
+//  a '.' only continues the number if it is followed by a digit, since '1..3' is a range of integers
+fn tokenise_number(
+    characters: &[char],
+    start: usize,
+    line: usize,
+    column: usize,
+) -> Result<(Token, usize), PumpkinError> {
+    let is_digit_at = |i: usize| characters.get(i).is_some_and(|c| c.is_ascii_digit());
+
+    let mut i = start + 1;
+    while is_digit_at(i) {
+        i += 1;
+    }
+
+    let mut is_float = false;
+    if characters.get(i) == Some(&'.') && is_digit_at(i + 1) {
+        is_float = true;
+        i += 1;
+        while is_digit_at(i) {
+            i += 1;
+        }
+    }
+    if matches!(characters.get(i), Some('e') | Some('E')) {
+        let exponent_start = if matches!(characters.get(i + 1), Some('-') | Some('+')) {
+            i + 2
+        } else {
+            i + 1
+        };
+        if is_digit_at(exponent_start) {
+            is_float = true;
+            i = exponent_start;
+            while is_digit_at(i) {
+                i += 1;
+            }
+        }
+    }
+
+    let number: String = characters[start..i].iter().collect();
+    let token = if is_float {
+        number.parse::<f64>().map(Token::Float).ok()
+    } else {
+        number.parse::<i64>().map(Token::Int).ok()
+    };
+    token.map(|token| (token, i)).ok_or_else(|| {
+        PumpkinError::parse_error(line, column, format!("Invalid number '{}'.", number))
+    })
+}
+
+struct FlatZincParser {
+    tokens: Vec<(Token, usize, usize)>,
+    position: usize,
+    end_of_file: (usize, usize), //the line and column used when reporting errors at the end of the file
+}
+
+//methods for parsing items
+impl FlatZincParser {
+    fn parse_model(&mut self) -> Result<FlatZincModel, PumpkinError> {
+        let mut parameters = vec![];
+        let mut variables = vec![];
+        let mut constraints = vec![];
+        let mut solve_item = None;
+
+        while self.peek().is_some() {
+            if solve_item.is_some() {
+                return Err(self.error("Expected the end of the model after the solve item."));
+            }
+
+            if self.consume_identifier_if("predicate") {
+                self.skip_until_semicolon()?;
+            } else if self.consume_identifier_if("constraint") {
+                constraints.push(self.parse_constraint()?);
+            } else if self.consume_identifier_if("solve") {
+                solve_item = Some(self.parse_solve_item()?);
+            } else {
+                match self.parse_declaration()? {
+                    Declaration::Parameter(parameter) => parameters.push(parameter),
+                    Declaration::Variable(variable) => variables.push(variable),
+                }
+            }
+        }
+
+        Ok(FlatZincModel {
+            parameters,
+            variables,
+            constraints,
+            solve_item: solve_item.ok_or_else(|| self.error("The model has no solve item."))?,
+        })
+    }
+
+    fn parse_constraint(&mut self) -> Result<ConstraintItem, PumpkinError> {
+        let name = self.expect_identifier()?;
+        self.expect_symbol("(")?;
+        let arguments = self.parse_expression_list(")")?;
+        let annotations = self.parse_annotations()?;
+        self.expect_symbol(";")?;
+        Ok(ConstraintItem {
+            name,
+            arguments,
+            annotations,
+        })
+    }
+
+    fn parse_solve_item(&mut self) -> Result<SolveItem, PumpkinError> {
+        let annotations = self.parse_annotations()?;
+        let goal = if self.consume_identifier_if("satisfy") {
+            SolveGoal::Satisfy
+        } else if self.consume_identifier_if("minimize") {
+            SolveGoal::Minimize(self.parse_expression()?)
+        } else if self.consume_identifier_if("maximize") {
+            SolveGoal::Maximize(self.parse_expression()?)
+        } else {
+            return Err(self.error("Expected 'satisfy', 'minimize', or 'maximize'."));
+        };
+        self.expect_symbol(";")?;
+        Ok(SolveItem { goal, annotations })
+    }
+
+    //parses a parameter or variable declaration, including arrays
+    fn parse_declaration(&mut self) -> Result<Declaration, PumpkinError> {
+        let is_array = self.consume_identifier_if("array");
+        if is_array {
+            //the index set is either '1..n' or 'int', and is implied by the array literal
+            self.expect_symbol("[")?;
+            if !self.consume_identifier_if("int") {
+                self.parse_expression()?;
+            }
+            self.expect_symbol("]")?;
+            self.expect_identifier_keyword("of")?;
+        }
+
+        let is_variable = self.consume_identifier_if("var");
+        let variable_type = self.parse_type()?;
+        self.expect_symbol(":")?;
+        let name = self.expect_identifier()?;
+        let annotations = self.parse_annotations()?;
+        let assignment = if self.consume_symbol_if("=") {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+        self.expect_symbol(";")?;
+
+        if is_variable {
+            Ok(Declaration::Variable(VariableDeclaration {
+                name,
+                variable_type,
+                is_array,
+                assignment,
+                annotations,
+            }))
+        } else {
+            let value = assignment.ok_or_else(|| {
+                self.error(format!("The parameter '{}' is not assigned a value.", name))
+            })?;
+            Ok(Declaration::Parameter(ParameterDeclaration { name, value }))
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<VariableType, PumpkinError> {
+        if self.consume_identifier_if("bool") {
+            Ok(VariableType::Bool)
+        } else if self.consume_identifier_if("int") {
+            Ok(VariableType::Int(None))
+        } else if self.consume_identifier_if("float") {
+            Ok(VariableType::Float)
+        } else if self.consume_identifier_if("set") {
+            self.expect_identifier_keyword("of")?;
+            if !self.consume_identifier_if("int") {
+                self.parse_expression()?;
+            }
+            Ok(VariableType::SetOfInt)
+        } else {
+            match self.parse_expression()? {
+                Expression::Set(set) => Ok(VariableType::Int(Some(set))),
+                Expression::Float(_) => {
+                    //a float range such as '0.0..1.0'
+                    self.expect_symbol("..")?;
+                    self.parse_expression()?;
+                    Ok(VariableType::Float)
+                }
+                _ => Err(self.error("Expected a type.")),
+            }
+        }
+    }
+
+    //annotations are of the form ':: name' or ':: name(arguments)' and may be repeated
+    fn parse_annotations(&mut self) -> Result<Vec<Annotation>, PumpkinError> {
+        let mut annotations = vec![];
+        while self.consume_symbol_if("::") {
+            let name = self.expect_identifier()?;
+            let arguments = if self.consume_symbol_if("(") {
+                self.parse_expression_list(")")?
+            } else {
+                vec![]
+            };
+            annotations.push(Annotation { name, arguments });
+        }
+        Ok(annotations)
+    }
+
+    fn skip_until_semicolon(&mut self) -> Result<(), PumpkinError> {
+        while !self.consume_symbol_if(";") {
+            if self.next().is_none() {
+                return Err(self.error("Expected ';'."));
+            }
+        }
+        Ok(())
+    }
+}
+
+//methods for parsing expressions
+impl FlatZincParser {
+    fn parse_expression(&mut self) -> Result<Expression, PumpkinError> {
+        match self.next() {
+            Some(Token::Int(value)) => {
+                if self.consume_symbol_if("..") {
+                    match self.next() {
+                        Some(Token::Int(upper_bound)) => {
+                            Ok(Expression::Set(SetLiteral::Range(value, upper_bound)))
+                        }
+                        _ => Err(self.error("Expected an integer after '..'.")),
+                    }
+                } else {
+                    Ok(Expression::Int(value))
+                }
+            }
+            Some(Token::Float(value)) => Ok(Expression::Float(value)),
+            Some(Token::String(string)) => Ok(Expression::String(string)),
+            Some(Token::Symbol("{")) => {
+                let mut values = vec![];
+                for element in self.parse_expression_list("}")? {
+                    match element {
+                        Expression::Int(value) => values.push(value),
+                        _ => return Err(self.error("Expected an integer in the set literal.")),
+                    }
+                }
+                Ok(Expression::Set(SetLiteral::Values(values)))
+            }
+            Some(Token::Symbol("[")) => Ok(Expression::Array(self.parse_expression_list("]")?)),
+            Some(Token::Identifier(identifier)) => match identifier.as_str() {
+                "true" => Ok(Expression::Bool(true)),
+                "false" => Ok(Expression::Bool(false)),
+                _ => {
+                    if self.consume_symbol_if("[") {
+                        let index = match self.next() {
+                            Some(Token::Int(index)) => index,
+                            _ => return Err(self.error("Expected an integer index.")),
+                        };
+                        self.expect_symbol("]")?;
+                        Ok(Expression::ArrayAccess(identifier, index))
+                    } else if self.consume_symbol_if("(") {
+                        Ok(Expression::Call(
+                            identifier,
+                            self.parse_expression_list(")")?,
+                        ))
+                    } else {
+                        Ok(Expression::Identifier(identifier))
+                    }
+                }
+            },
+            Some(token) => Err(self.error(format!("Unexpected token {}.", token))),
+            None => Err(self.error("Unexpected end of the model.")),
+        }
+    }
+
+    //parses comma-separated expressions up to and including the closing symbol
+    fn parse_expression_list(
+        &mut self,
+        closing_symbol: &'static str,
+    ) -> Result<Vec<Expression>, PumpkinError> {
+        let mut expressions = vec![];
+        if self.consume_symbol_if(closing_symbol) {
+            return Ok(expressions);
+        }
+        loop {
+            expressions.push(self.parse_expression()?);
+            if self.consume_symbol_if(closing_symbol) {
+                return Ok(expressions);
+            }
+            self.expect_symbol(",")?;
+        }
+    }
+}
+
+//methods for inspecting and consuming tokens
+impl FlatZincParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn consume_symbol_if(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn consume_identifier_if(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Identifier(identifier)) if identifier == keyword) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), PumpkinError> {
+        if self.consume_symbol_if(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected '{}'.", symbol)))
+        }
+    }
+
+    fn expect_identifier_keyword(&mut self, keyword: &str) -> Result<(), PumpkinError> {
+        if self.consume_identifier_if(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected '{}'.", keyword)))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, PumpkinError> {
+        match self.peek() {
+            Some(Token::Identifier(identifier)) => {
+                let identifier = identifier.clone();
+                self.position += 1;
+                Ok(identifier)
+            }
+            _ => Err(self.error("Expected an identifier.")),
+        }
+    }
+
+    //creates an error at the line and column of the current token
+    fn error(&self, message: impl Into<String>) -> PumpkinError {
+        let (line, column) = self
+            .tokens
+            .get(self.position)
+            .map_or(self.end_of_file, |(_, line, column)| (*line, *column));
+        PumpkinError::parse_error(line, column, message)
+    }
+}
+
+enum Declaration {
+    Parameter(ParameterDeclaration),
+    Variable(VariableDeclaration),
+}
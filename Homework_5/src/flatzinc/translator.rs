@@ -0,0 +1,1262 @@
+use std::collections::HashMap;
+
+use crate::{
+    basic_types::{
+        AffineView, ClauseAdditionOutcome, IntegerValueSelectionStrategy, IntegerVariable,
+        IntegerVariableSelectionStrategy, Literal, Predicate, PumpkinError, SearchAnnotation,
+    },
+    engine::ConstraintSatisfactionSolver,
+    propagators::{
+        AbsoluteValuePropagator, AllDifferentPropagator, ConstraintProgrammingPropagator,
+        CumulativePropagator, DivisionPropagator, GuardedPropagator, LinearLessOrEqualPropagator,
+        MaximumPropagator, MinimumPropagator, ModuloPropagator, TimesPropagator,
+    },
+};
+
+use super::{
+    Annotation, ConstraintItem, Expression, FlatZincInstance, FlatZincModel, OutputItem,
+    OutputValue, SetLiteral, SolveGoal, VariableDeclaration, VariableType,
+};
+
+//translates the FlatZinc model into variables, clauses, and propagators of the solver
+//  every integer variable is represented by a view, and every boolean variable by a 0-1 view, where the literal [b >= 1] means true
+//  constants are views over a single variable that is fixed to one, so that constraints do not need to distinguish constants from variables
+//  the translation stops as soon as a constraint is found to be infeasible at the root, which is recorded in the returned instance
+pub fn translate_flatzinc(
+    model: &FlatZincModel,
+    csp_solver: &mut ConstraintSatisfactionSolver,
+) -> Result<FlatZincInstance, PumpkinError> {
+    FlatZincTranslator {
+        csp_solver,
+        parameters: HashMap::new(),
+        variables: HashMap::new(),
+        variable_arrays: HashMap::new(),
+        constant_variable: None,
+        is_infeasible: false,
+    }
+    .translate(model)
+}
+
+//the inequality 'terms[0] + ... + terms[n-1] <= right_hand_side'
+//  coefficients are part of the views, and each underlying variable occurs at most once
+#[derive(Clone, Debug)]
+struct LinearLessOrEqual {
+    terms: Vec<AffineView>,
+    right_hand_side: i64,
+}
+
+impl LinearLessOrEqual {
+    //the inequality that holds exactly when this inequality is violated, i.e., sum >= rhs + 1
+    fn negated(&self) -> LinearLessOrEqual {
+        LinearLessOrEqual {
+            terms: self.terms.iter().map(|term| -*term).collect(),
+            right_hand_side: -self.right_hand_side - 1,
+        }
+    }
+
+    //the inequality sum >= rhs, which together with this inequality gives sum == rhs
+    fn reversed(&self) -> LinearLessOrEqual {
+        LinearLessOrEqual {
+            terms: self.terms.iter().map(|term| -*term).collect(),
+            right_hand_side: -self.right_hand_side,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    LessOrEqual,
+    LessThan,
+}
+
+//the constraints that may be reified by appending '_reif' or '_imp' to the name
+const REIFIABLE_CONSTRAINTS: [&str; 15] = [
+    "int_eq",
+    "int_ne",
+    "int_le",
+    "int_lt",
+    "int_lin_eq",
+    "int_lin_ne",
+    "int_lin_le",
+    "int_lin_lt",
+    "bool_eq",
+    "bool_ne",
+    "bool_le",
+    "bool_lt",
+    "bool_clause",
+    "bool_lin_eq",
+    "set_in",
+];
+
+//how a constraint relates to its 0-1 reification view r, if any
+//  half reification (the '_imp' constraints) only enforces r -> constraint, full reification (the '_reif' constraints) enforces r <-> constraint
+#[derive(Clone, Copy, Debug)]
+enum Reification {
+    None,
+    Half(AffineView),
+    Full(AffineView),
+}
+
+struct FlatZincTranslator<'a> {
+    csp_solver: &'a mut ConstraintSatisfactionSolver,
+    parameters: HashMap<String, Expression>,
+    variables: HashMap<String, AffineView>,
+    variable_arrays: HashMap<String, Vec<Expression>>,
+    constant_variable: Option<IntegerVariable>,
+    is_infeasible: bool,
+}
+
+//methods for translating the items of the model
+impl FlatZincTranslator<'_> {
+    fn translate(mut self, model: &FlatZincModel) -> Result<FlatZincInstance, PumpkinError> {
+        for parameter in &model.parameters {
+            self.parameters
+                .insert(parameter.name.clone(), parameter.value.clone());
+        }
+
+        for variable in &model.variables {
+            self.declare_variable(variable)?;
+        }
+
+        for constraint in &model.constraints {
+            if self.is_infeasible {
+                break;
+            }
+            self.translate_constraint(constraint).map_err(|error| {
+                error.with_context(&format!("Constraint '{}'", constraint.name))
+            })?;
+        }
+
+        let (objective, is_maximisation) = match &model.solve_item.goal {
+            SolveGoal::Satisfy => (None, false),
+            SolveGoal::Minimize(objective) => (Some(self.view(objective)?), false),
+            SolveGoal::Maximize(objective) => (Some(-self.view(objective)?), true),
+        };
+
+        //the solver only accepts annotations when it is ready, which is not the case once it is declared infeasible
+        if !self.is_infeasible {
+            self.add_search_annotations(&model.solve_item.annotations)?;
+        }
+
+        let output_items = self.create_output_items(&model.variables)?;
+
+        Ok(FlatZincInstance {
+            objective,
+            is_maximisation,
+            output_items,
+            is_infeasible: self.is_infeasible,
+            constant_variable: self.constant_variable,
+        })
+    }
+
+    fn declare_variable(&mut self, declaration: &VariableDeclaration) -> Result<(), PumpkinError> {
+        if matches!(
+            declaration.variable_type,
+            VariableType::Float | VariableType::SetOfInt
+        ) {
+            return Err(PumpkinError::unsupported_file_format(format!(
+                "The variable '{}' is a float or set variable, which is not supported.",
+                declaration.name
+            )));
+        }
+
+        //arrays of variables are resolved element by element when used
+        if declaration.is_array {
+            let elements = match &declaration.assignment {
+                Some(Expression::Array(elements)) => elements.clone(),
+                _ => {
+                    return Err(PumpkinError::invalid_model(format!(
+                        "The array '{}' is expected to be assigned an array literal.",
+                        declaration.name
+                    )))
+                }
+            };
+            self.variable_arrays
+                .insert(declaration.name.clone(), elements);
+            return Ok(());
+        }
+
+        let view = match (&declaration.variable_type, &declaration.assignment) {
+            (_, Some(assignment)) => self.view(assignment)?,
+            (VariableType::Int(Some(domain)), None) => self.create_integer_view(domain)?,
+            (VariableType::Int(None), None) => {
+                return Err(PumpkinError::unsupported_file_format(format!(
+                    "The integer variable '{}' has no domain, which is not supported.",
+                    declaration.name
+                )))
+            }
+            _ => self.create_bool_view(),
+        };
+
+        if let VariableType::Int(Some(domain)) = &declaration.variable_type {
+            self.restrict_domain(view, domain)?;
+        }
+
+        self.variables.insert(declaration.name.clone(), view);
+        Ok(())
+    }
+
+    fn translate_constraint(&mut self, constraint: &ConstraintItem) -> Result<(), PumpkinError> {
+        //reified constraints are handled together with their base constraint, where the reification variable is the last argument
+        let (name, arguments, reification) = match (
+            constraint.name.strip_suffix("_reif"),
+            constraint.name.strip_suffix("_imp"),
+            constraint.arguments.split_last(),
+        ) {
+            (Some(name), _, Some((reification_variable, arguments))) => (
+                name,
+                arguments,
+                Reification::Full(self.view(reification_variable)?),
+            ),
+            (_, Some(name), Some((reification_variable, arguments))) => (
+                name,
+                arguments,
+                Reification::Half(self.view(reification_variable)?),
+            ),
+            _ => (
+                constraint.name.as_str(),
+                constraint.arguments.as_slice(),
+                Reification::None,
+            ),
+        };
+
+        if !matches!(reification, Reification::None) && !REIFIABLE_CONSTRAINTS.contains(&name) {
+            return Err(PumpkinError::unsupported_file_format(
+                "The constraint is not supported.",
+            ));
+        }
+
+        match name {
+            "int_eq" | "int_ne" | "int_le" | "int_lt" => {
+                check_num_arguments(arguments, 2)?;
+                let a = self.view(&arguments[0])?;
+                let b = self.view(&arguments[1])?;
+                self.post_comparison(get_comparison(name), vec![(1, a), (-1, b)], 0, reification)
+            }
+            "int_lin_eq" | "int_lin_ne" | "int_lin_le" | "int_lin_lt" | "bool_lin_eq"
+            | "bool_lin_le" => {
+                check_num_arguments(arguments, 3)?;
+                let coefficients = self.constant_array(&arguments[0])?;
+                let views = self.view_array(&arguments[1])?;
+                if coefficients.len() != views.len() {
+                    return Err(PumpkinError::invalid_model(
+                        "The number of coefficients and variables differ.",
+                    ));
+                }
+                let mut terms: Vec<(i64, AffineView)> =
+                    coefficients.into_iter().zip(views).collect();
+
+                //the right-hand side of the boolean linear constraints may be a variable
+                let right_hand_side = match self.constant(&arguments[2]) {
+                    Ok(right_hand_side) => right_hand_side,
+                    Err(_) => {
+                        terms.push((-1, self.view(&arguments[2])?));
+                        0
+                    }
+                };
+                self.post_comparison(get_comparison(name), terms, right_hand_side, reification)
+            }
+            "int_plus" => {
+                check_num_arguments(arguments, 3)?;
+                let [a, b, c] = self.views::<3>(arguments)?;
+                self.post_comparison(
+                    Comparison::Equal,
+                    vec![(1, a), (1, b), (-1, c)],
+                    0,
+                    reification,
+                )
+            }
+            "set_in" => {
+                check_num_arguments(arguments, 2)?;
+                let x = self.view(&arguments[0])?;
+                let set = self.set(&arguments[1])?;
+                self.post_set_in(x, &set, reification)
+            }
+            "bool_eq" | "bool_ne" | "bool_not" | "bool_xor"
+                if arguments.len() == 2 || !matches!(reification, Reification::None) =>
+            {
+                check_num_arguments(arguments, 2)?;
+                let a = self.literal(&arguments[0])?;
+                let b = self.literal(&arguments[1])?;
+                let is_equality = name == "bool_eq";
+                match reification {
+                    Reification::None => {
+                        let b = if is_equality { b } else { !b };
+                        self.add_equivalence(a, b);
+                    }
+                    Reification::Half(r) => {
+                        let r = self.view_literal(r);
+                        let b = if is_equality { b } else { !b };
+                        self.add_clause(vec![!r, !a, b]);
+                        self.add_clause(vec![!r, a, !b]);
+                    }
+                    Reification::Full(r) => {
+                        let r = self.view_literal(r);
+                        let r = if is_equality { !r } else { r };
+                        self.add_xor_reification(r, a, b);
+                    }
+                }
+                Ok(())
+            }
+            "bool_xor" => {
+                check_num_arguments(arguments, 3)?;
+                let [a, b, r] = self.literals::<3>(arguments)?;
+                self.add_xor_reification(r, a, b);
+                Ok(())
+            }
+            "bool_le" | "bool_lt" => {
+                check_num_arguments(arguments, 2)?;
+                let [a, b] = self.literals::<2>(arguments)?;
+                if name == "bool_le" {
+                    self.post_disjunction(vec![!a, b], reification);
+                } else {
+                    self.post_conjunction(vec![!a, b], reification);
+                }
+                Ok(())
+            }
+            "bool_and" | "bool_or" => {
+                check_num_arguments(arguments, 3)?;
+                let [a, b, _] = self.literals::<3>(arguments)?;
+                let r = Reification::Full(self.view(&arguments[2])?);
+                if name == "bool_and" {
+                    self.post_conjunction(vec![a, b], r);
+                } else {
+                    self.post_disjunction(vec![a, b], r);
+                }
+                Ok(())
+            }
+            "array_bool_and" | "array_bool_or" => {
+                check_num_arguments(arguments, 2)?;
+                let literals = self.literal_array(&arguments[0])?;
+                let r = Reification::Full(self.view(&arguments[1])?);
+                if name == "array_bool_and" {
+                    self.post_conjunction(literals, r);
+                } else {
+                    self.post_disjunction(literals, r);
+                }
+                Ok(())
+            }
+            "bool_clause" => {
+                check_num_arguments(arguments, 2)?;
+                let positive_literals = self.literal_array(&arguments[0])?;
+                let negative_literals = self.literal_array(&arguments[1])?;
+                let literals = positive_literals
+                    .into_iter()
+                    .chain(negative_literals.into_iter().map(|literal| !literal))
+                    .collect();
+                self.post_disjunction(literals, reification);
+                Ok(())
+            }
+            "array_bool_xor" => {
+                check_num_arguments(arguments, 1)?;
+                let literals = self.literal_array(&arguments[0])?;
+                //the parity is computed by chaining xors over fresh variables
+                let mut parity = self.false_literal();
+                for literal in literals {
+                    let new_parity = self.create_bool_view();
+                    let new_parity = self.view_literal(new_parity);
+                    self.add_xor_reification(new_parity, parity, literal);
+                    parity = new_parity;
+                }
+                self.add_clause(vec![parity]);
+                Ok(())
+            }
+            "bool2int" => {
+                check_num_arguments(arguments, 2)?;
+                let a = self.literal(&arguments[0])?;
+                let b = self.view(&arguments[1])?;
+                self.restrict_domain(b, &SetLiteral::Range(0, 1))?;
+                let b = self.view_literal(b);
+                self.add_equivalence(a, b);
+                Ok(())
+            }
+            "int_times" | "int_div" | "int_mod" | "int_min" | "int_max" => {
+                check_num_arguments(arguments, 3)?;
+                let [a, b, c] = self.views::<3>(arguments)?;
+                let propagator: Box<dyn ConstraintProgrammingPropagator> = match name {
+                    "int_times" => Box::new(TimesPropagator::new(a, b, c)),
+                    "int_div" => Box::new(DivisionPropagator::new(a, b, c)),
+                    "int_mod" => Box::new(ModuloPropagator::new(a, b, c)),
+                    "int_min" => Box::new(MinimumPropagator::new(vec![a, b], c)),
+                    _ => Box::new(MaximumPropagator::new(vec![a, b], c)),
+                };
+                self.add_propagator(propagator);
+                Ok(())
+            }
+            "int_abs" => {
+                check_num_arguments(arguments, 2)?;
+                let [a, b] = self.views::<2>(arguments)?;
+                self.add_propagator(Box::new(AbsoluteValuePropagator::new(a, b)));
+                Ok(())
+            }
+            "array_int_maximum" | "array_int_minimum" => {
+                check_num_arguments(arguments, 2)?;
+                let m = self.view(&arguments[0])?;
+                let views = self.view_array(&arguments[1])?;
+                if views.is_empty() {
+                    return Err(PumpkinError::invalid_model("The array is empty."));
+                }
+                if name == "array_int_maximum" {
+                    self.add_propagator(Box::new(MaximumPropagator::new(views, m)));
+                } else {
+                    self.add_propagator(Box::new(MinimumPropagator::new(views, m)));
+                }
+                Ok(())
+            }
+            "array_int_element" | "array_bool_element" => {
+                check_num_arguments(arguments, 3)?;
+                let index = self.view(&arguments[0])?;
+                let values = self.constant_array(&arguments[1])?;
+                let c = self.view(&arguments[2])?;
+                self.post_element(index, &values, c)
+            }
+            "array_var_int_element" | "array_var_bool_element" => {
+                check_num_arguments(arguments, 3)?;
+                let index = self.view(&arguments[0])?;
+                let views = self.view_array(&arguments[1])?;
+                let c = self.view(&arguments[2])?;
+                self.post_variable_element(index, &views, c)
+            }
+            //the global constraints below are declared natively in the MiniZinc library of pumpkin
+            "fzn_all_different_int" => {
+                check_num_arguments(arguments, 1)?;
+                let views = self.view_array(&arguments[0])?;
+                self.add_propagator(Box::new(AllDifferentPropagator::new(views)));
+                Ok(())
+            }
+            "pumpkin_cumulative" => {
+                check_num_arguments(arguments, 4)?;
+                let start_times = self.view_array(&arguments[0])?;
+                let durations = self.constant_array(&arguments[1])?;
+                let resource_usages = self.constant_array(&arguments[2])?;
+                let capacity = self.constant(&arguments[3])?;
+                if start_times.len() != durations.len()
+                    || start_times.len() != resource_usages.len()
+                {
+                    return Err(PumpkinError::invalid_model("The arrays differ in length."));
+                }
+                if durations.iter().chain(&resource_usages).any(|&v| v < 0) {
+                    return Err(PumpkinError::invalid_model(
+                        "Durations and resource usages must be nonnegative.",
+                    ));
+                }
+                self.add_propagator(Box::new(CumulativePropagator::new(
+                    start_times,
+                    durations
+                        .into_iter()
+                        .map(to_i32)
+                        .collect::<Result<_, _>>()?,
+                    resource_usages
+                        .into_iter()
+                        .map(to_i32)
+                        .collect::<Result<_, _>>()?,
+                    to_i32(capacity)?,
+                )));
+                Ok(())
+            }
+            _ => Err(PumpkinError::unsupported_file_format(
+                "The constraint is not supported.",
+            )),
+        }
+    }
+
+    fn add_search_annotations(&mut self, annotations: &[Annotation]) -> Result<(), PumpkinError> {
+        for annotation in annotations {
+            match annotation.name.as_str() {
+                "int_search" | "bool_search" if annotation.arguments.len() >= 3 => {
+                    let variable_selection_strategy = match get_identifier(&annotation.arguments[1])
+                    {
+                        Some("input_order") => IntegerVariableSelectionStrategy::InputOrder,
+                        Some("first_fail") => IntegerVariableSelectionStrategy::FirstFail,
+                        Some("dom_w_deg") => IntegerVariableSelectionStrategy::DomWDeg,
+                        Some("smallest") => IntegerVariableSelectionStrategy::Smallest,
+                        Some("largest") => IntegerVariableSelectionStrategy::Largest,
+                        //annotations are hints, so unsupported strategies are ignored
+                        _ => continue,
+                    };
+                    let value_selection_strategy = match get_identifier(&annotation.arguments[2]) {
+                        Some("indomain_min") | Some("indomain") => {
+                            IntegerValueSelectionStrategy::Min
+                        }
+                        Some("indomain_max") => IntegerValueSelectionStrategy::Max,
+                        Some("indomain_split") => IntegerValueSelectionStrategy::Split,
+                        Some("indomain_reverse_split") => {
+                            IntegerValueSelectionStrategy::ReverseSplit
+                        }
+                        Some("indomain_median") => IntegerValueSelectionStrategy::Median,
+                        _ => continue,
+                    };
+
+                    let mut integer_variables: Vec<IntegerVariable> = vec![];
+                    for view in self.view_array(&annotation.arguments[0])? {
+                        let integer_variable = view.get_integer_variable();
+                        if Some(integer_variable) != self.constant_variable
+                            && !integer_variables.contains(&integer_variable)
+                        {
+                            integer_variables.push(integer_variable);
+                        }
+                    }
+
+                    if !integer_variables.is_empty() {
+                        self.csp_solver.add_search_annotation(SearchAnnotation::new(
+                            integer_variables,
+                            variable_selection_strategy,
+                            value_selection_strategy,
+                        ));
+                    }
+                }
+                "seq_search" if annotation.arguments.len() == 1 => {
+                    let nested_annotations: Vec<Annotation> = match &annotation.arguments[0] {
+                        Expression::Array(elements) => elements
+                            .iter()
+                            .filter_map(|element| match element {
+                                Expression::Call(name, arguments) => Some(Annotation {
+                                    name: name.clone(),
+                                    arguments: arguments.clone(),
+                                }),
+                                _ => None,
+                            })
+                            .collect(),
+                        _ => vec![],
+                    };
+                    self.add_search_annotations(&nested_annotations)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn create_output_items(
+        &mut self,
+        variables: &[VariableDeclaration],
+    ) -> Result<Vec<OutputItem>, PumpkinError> {
+        let mut output_items = vec![];
+        for declaration in variables {
+            let is_bool = declaration.variable_type == VariableType::Bool;
+            let to_output_value = |view: AffineView| {
+                if is_bool {
+                    OutputValue::Bool(view)
+                } else {
+                    OutputValue::Int(view)
+                }
+            };
+
+            for annotation in &declaration.annotations {
+                match annotation.name.as_str() {
+                    "output_var" if !declaration.is_array => {
+                        let view = self.variables[&declaration.name];
+                        output_items.push(OutputItem::Variable {
+                            name: declaration.name.clone(),
+                            value: to_output_value(view),
+                        });
+                    }
+                    "output_array" if declaration.is_array => {
+                        let index_sets = match annotation.arguments.first() {
+                            Some(Expression::Array(index_sets)) => index_sets
+                                .iter()
+                                .map(|index_set| match index_set {
+                                    Expression::Set(set) => set.bounds().ok_or_else(|| {
+                                        PumpkinError::invalid_model(
+                                            "Empty index set in output_array.",
+                                        )
+                                    }),
+                                    _ => Err(PumpkinError::invalid_model(
+                                        "Expected index sets in output_array.",
+                                    )),
+                                })
+                                .collect::<Result<Vec<(i64, i64)>, PumpkinError>>()?,
+                            _ => {
+                                return Err(PumpkinError::invalid_model(
+                                    "Expected index sets in output_array.",
+                                ))
+                            }
+                        };
+                        let values = self
+                            .view_array(&Expression::Identifier(declaration.name.clone()))?
+                            .into_iter()
+                            .map(to_output_value)
+                            .collect();
+                        output_items.push(OutputItem::Array {
+                            name: declaration.name.clone(),
+                            index_sets,
+                            values,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(output_items)
+    }
+}
+
+//methods for posting constraints
+impl FlatZincTranslator<'_> {
+    //posts 'sum of coefficient * view <comparison> right_hand_side'
+    fn post_comparison(
+        &mut self,
+        comparison: Comparison,
+        terms: Vec<(i64, AffineView)>,
+        right_hand_side: i64,
+        reification: Reification,
+    ) -> Result<(), PumpkinError> {
+        let linear = self.normalise_linear(terms, right_hand_side)?;
+        match comparison {
+            Comparison::LessOrEqual => self.post_linear_less_or_equal(linear, reification),
+            Comparison::LessThan => self.post_linear_less_or_equal(
+                LinearLessOrEqual {
+                    right_hand_side: linear.right_hand_side - 1,
+                    ..linear
+                },
+                reification,
+            ),
+            Comparison::Equal => self.post_linear_equal(linear, reification),
+            //not equal is the negated reification of equality
+            Comparison::NotEqual => match reification {
+                Reification::None => {
+                    let false_view = self.constant_view(0)?;
+                    self.post_linear_equal(linear, Reification::Full(false_view))
+                }
+                Reification::Half(r) => {
+                    let is_equal = self.create_bool_view();
+                    self.post_linear_equal(linear, Reification::Full(is_equal))?;
+                    let (r, is_equal) = (self.view_literal(r), self.view_literal(is_equal));
+                    self.add_clause(vec![!r, !is_equal]);
+                    Ok(())
+                }
+                Reification::Full(r) => {
+                    self.post_linear_equal(linear, Reification::Full(negate_bool_view(r)))
+                }
+            },
+        }
+    }
+
+    fn post_linear_less_or_equal(
+        &mut self,
+        linear: LinearLessOrEqual,
+        reification: Reification,
+    ) -> Result<(), PumpkinError> {
+        //inequalities over at most one variable are expressed by a literal
+        if let Some(literal) = self.get_linear_literal(&linear) {
+            match reification {
+                Reification::None => self.add_clause(vec![literal]),
+                Reification::Half(r) => {
+                    let r = self.view_literal(r);
+                    self.add_clause(vec![!r, literal]);
+                }
+                Reification::Full(r) => {
+                    let r = self.view_literal(r);
+                    self.add_equivalence(r, literal);
+                }
+            }
+            return Ok(());
+        }
+
+        match reification {
+            Reification::None => self.add_linear_propagator(&linear, None),
+            Reification::Half(r) => self.add_linear_propagator(&linear, Some(r)),
+            Reification::Full(r) => {
+                self.add_linear_propagator(&linear, Some(r));
+                self.add_linear_propagator(&linear.negated(), Some(negate_bool_view(r)));
+            }
+        }
+        Ok(())
+    }
+
+    fn post_linear_equal(
+        &mut self,
+        linear: LinearLessOrEqual,
+        reification: Reification,
+    ) -> Result<(), PumpkinError> {
+        let reversed = linear.reversed();
+        match reification {
+            Reification::None | Reification::Half(_) => {
+                self.post_linear_less_or_equal(linear, reification)?;
+                self.post_linear_less_or_equal(reversed, reification)
+            }
+            Reification::Full(r) => {
+                //equalities over at most one variable are expressed by a literal
+                let equality_literal = match linear.terms.as_slice() {
+                    [] => Some(self.constant_literal(linear.right_hand_side == 0)),
+                    [view] => Some(self.get_equality_literal(*view, linear.right_hand_side)),
+                    _ => None,
+                };
+                if let Some(equality_literal) = equality_literal {
+                    let r = self.view_literal(r);
+                    self.add_equivalence(r, equality_literal);
+                    return Ok(());
+                }
+
+                let is_less_or_equal = self.create_bool_view();
+                let is_greater_or_equal = self.create_bool_view();
+                self.post_linear_less_or_equal(linear, Reification::Full(is_less_or_equal))?;
+                self.post_linear_less_or_equal(reversed, Reification::Full(is_greater_or_equal))?;
+                let literals = vec![
+                    self.view_literal(is_less_or_equal),
+                    self.view_literal(is_greater_or_equal),
+                ];
+                self.post_conjunction(literals, reification);
+                Ok(())
+            }
+        }
+    }
+
+    fn post_set_in(
+        &mut self,
+        x: AffineView,
+        set: &SetLiteral,
+        reification: Reification,
+    ) -> Result<(), PumpkinError> {
+        if let Reification::None = reification {
+            return self.restrict_domain(x, set);
+        }
+
+        match set {
+            SetLiteral::Range(lower_bound, upper_bound) => {
+                let literals = vec![
+                    self.get_lower_bound_literal(x, *lower_bound),
+                    !self.get_lower_bound_literal(x, *upper_bound + 1),
+                ];
+                self.post_conjunction(literals, reification);
+            }
+            SetLiteral::Values(values) => {
+                let literals = values
+                    .iter()
+                    .map(|value| self.get_equality_literal(x, *value))
+                    .collect();
+                self.post_disjunction(literals, reification);
+            }
+        }
+        Ok(())
+    }
+
+    //posts 'c == values[index]', where the index starts at one
+    fn post_element(
+        &mut self,
+        index: AffineView,
+        values: &[i64],
+        c: AffineView,
+    ) -> Result<(), PumpkinError> {
+        self.restrict_domain(index, &SetLiteral::Range(1, values.len() as i64))?;
+
+        for (i, value) in values.iter().enumerate() {
+            let index_literal = self.get_equality_literal(index, i as i64 + 1);
+            let value_literal = self.get_equality_literal(c, *value);
+            self.add_clause(vec![!index_literal, value_literal]);
+        }
+
+        //each value of c needs to be supported by an index
+        let (lower_bound, upper_bound) = self.get_bounds(c);
+        for value in lower_bound..=upper_bound {
+            let mut clause = vec![!self.get_equality_literal(c, value)];
+            for (i, _) in values.iter().enumerate().filter(|(_, v)| **v == value) {
+                clause.push(self.get_equality_literal(index, i as i64 + 1));
+            }
+            self.add_clause(clause);
+        }
+        Ok(())
+    }
+
+    //posts 'c == views[index]', where the index starts at one
+    //  each equality is half-reified by the literal [index == i]
+    fn post_variable_element(
+        &mut self,
+        index: AffineView,
+        views: &[AffineView],
+        c: AffineView,
+    ) -> Result<(), PumpkinError> {
+        self.restrict_domain(index, &SetLiteral::Range(1, views.len() as i64))?;
+
+        for (i, view) in views.iter().enumerate() {
+            let index_literal = self.get_equality_literal(index, i as i64 + 1);
+            let selector = self.create_bool_view();
+            let selector_literal = self.view_literal(selector);
+            self.add_equivalence(selector_literal, index_literal);
+            self.post_comparison(
+                Comparison::Equal,
+                vec![(1, c), (-1, *view)],
+                0,
+                Reification::Half(selector),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn post_disjunction(&mut self, literals: Vec<Literal>, reification: Reification) {
+        match reification {
+            Reification::None => self.add_clause(literals),
+            Reification::Half(r) => {
+                let r = self.view_literal(r);
+                self.add_clause(std::iter::once(!r).chain(literals).collect());
+            }
+            Reification::Full(r) => {
+                let r = self.view_literal(r);
+                for literal in &literals {
+                    self.add_clause(vec![r, !*literal]);
+                }
+                self.add_clause(std::iter::once(!r).chain(literals).collect());
+            }
+        }
+    }
+
+    fn post_conjunction(&mut self, literals: Vec<Literal>, reification: Reification) {
+        match reification {
+            Reification::None => {
+                for literal in literals {
+                    self.add_clause(vec![literal]);
+                }
+            }
+            Reification::Half(r) => {
+                let r = self.view_literal(r);
+                for literal in literals {
+                    self.add_clause(vec![!r, literal]);
+                }
+            }
+            Reification::Full(r) => {
+                let r = self.view_literal(r);
+                for literal in &literals {
+                    self.add_clause(vec![!r, *literal]);
+                }
+                self.add_clause(
+                    std::iter::once(r)
+                        .chain(literals.iter().map(|literal| !*literal))
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    fn add_equivalence(&mut self, a: Literal, b: Literal) {
+        self.add_clause(vec![!a, b]);
+        self.add_clause(vec![a, !b]);
+    }
+
+    //r <-> (a xor b)
+    fn add_xor_reification(&mut self, r: Literal, a: Literal, b: Literal) {
+        self.add_clause(vec![!r, a, b]);
+        self.add_clause(vec![!r, !a, !b]);
+        self.add_clause(vec![r, !a, b]);
+        self.add_clause(vec![r, a, !b]);
+    }
+
+    fn add_clause(&mut self, literals: Vec<Literal>) {
+        if self.is_infeasible {
+            return;
+        }
+
+        if literals.is_empty()
+            || self.csp_solver.add_permanent_clause(literals) == ClauseAdditionOutcome::Infeasible
+        {
+            self.is_infeasible = true;
+        }
+    }
+
+    //adds a propagator for the inequality, which is only active when the selector is one if a selector is given
+    fn add_linear_propagator(&mut self, linear: &LinearLessOrEqual, selector: Option<AffineView>) {
+        let propagator = Box::new(LinearLessOrEqualPropagator::new(
+            linear.terms.clone(),
+            clamp_to_i32(linear.right_hand_side),
+        ));
+
+        match selector {
+            None => self.add_propagator(propagator),
+            //constant selectors do not need a guard
+            Some(selector) if Some(selector.get_integer_variable()) == self.constant_variable => {
+                if selector.transform_value(1) == 1 {
+                    self.add_propagator(propagator);
+                }
+            }
+            Some(selector) => {
+                self.add_propagator(Box::new(GuardedPropagator::new(propagator, selector)))
+            }
+        }
+    }
+
+    fn add_propagator(&mut self, propagator: Box<dyn ConstraintProgrammingPropagator>) {
+        if self.is_infeasible {
+            return;
+        }
+
+        if self.csp_solver.add_propagator(propagator) == ClauseAdditionOutcome::Infeasible {
+            self.is_infeasible = true;
+        }
+    }
+
+    //restricts the view to the values of the set
+    fn restrict_domain(&mut self, view: AffineView, set: &SetLiteral) -> Result<(), PumpkinError> {
+        let (lower_bound, upper_bound) = match set.bounds() {
+            Some(bounds) => bounds,
+            None => {
+                self.is_infeasible = true;
+                return Ok(());
+            }
+        };
+
+        let lower_bound_literal = self.get_lower_bound_literal(view, lower_bound);
+        let upper_bound_literal = !self.get_lower_bound_literal(view, upper_bound + 1);
+        self.add_clause(vec![lower_bound_literal]);
+        self.add_clause(vec![upper_bound_literal]);
+
+        if let SetLiteral::Values(_) = set {
+            for value in lower_bound..=upper_bound {
+                if !set.contains(value) {
+                    let not_equal_literal = !self.get_equality_literal(view, value);
+                    self.add_clause(vec![not_equal_literal]);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+//methods for creating variables and literals
+impl FlatZincTranslator<'_> {
+    fn create_bool_view(&mut self) -> AffineView {
+        AffineView::from(self.csp_solver.create_new_zero_one_variable())
+    }
+
+    //creates a view whose initial domain spans the bounds of the set, see 'restrict_domain' for removing the holes
+    //  the solver only supports nonnegative domains, so negative domains are shifted by an offset
+    fn create_integer_view(&mut self, domain: &SetLiteral) -> Result<AffineView, PumpkinError> {
+        let (lower_bound, upper_bound) = match domain.bounds() {
+            Some(bounds) => bounds,
+            None => {
+                self.is_infeasible = true;
+                return self.constant_view(0);
+            }
+        };
+
+        let lower_bound = to_i32(lower_bound)?;
+        let upper_bound = to_i32(upper_bound)?;
+        if lower_bound == upper_bound {
+            self.constant_view(lower_bound as i64)
+        } else if lower_bound >= 0 {
+            Ok(AffineView::from(
+                self.csp_solver
+                    .create_new_integer_variable(lower_bound, upper_bound)?,
+            ))
+        } else {
+            let domain_size = to_i32(upper_bound as i64 - lower_bound as i64)?;
+            let integer_variable = self
+                .csp_solver
+                .create_new_integer_variable(0, domain_size)?;
+            Ok(AffineView::new(integer_variable, 1, lower_bound))
+        }
+    }
+
+    //constants are views over a variable fixed to one
+    fn constant_view(&mut self, value: i64) -> Result<AffineView, PumpkinError> {
+        let value = to_i32(value)?;
+        Ok(AffineView::new(self.get_constant_variable(), 1, value - 1))
+    }
+
+    fn get_constant_variable(&mut self) -> IntegerVariable {
+        if let Some(constant_variable) = self.constant_variable {
+            return constant_variable;
+        }
+
+        let constant_variable = self.csp_solver.create_new_zero_one_variable();
+        self.constant_variable = Some(constant_variable);
+        let literal = self
+            .csp_solver
+            .get_lower_bound_literal(constant_variable, 1);
+        self.add_clause(vec![literal]);
+        constant_variable
+    }
+
+    fn constant_literal(&mut self, value: bool) -> Literal {
+        //any lower bound of at most zero holds trivially, which gives the true literal of the solver
+        let integer_variable = self.get_constant_variable();
+        let true_literal = self
+            .csp_solver
+            .get_predicate_literal(Predicate::LowerBound {
+                integer_variable,
+                lower_bound: 0,
+            });
+        if value {
+            true_literal
+        } else {
+            !true_literal
+        }
+    }
+
+    fn false_literal(&mut self) -> Literal {
+        self.constant_literal(false)
+    }
+
+    //the literal [b >= 1] of a 0-1 view
+    fn view_literal(&self, view: AffineView) -> Literal {
+        self.csp_solver
+            .get_predicate_literal(view.lower_bound_predicate(1))
+    }
+
+    fn get_lower_bound_literal(&mut self, view: AffineView, lower_bound: i64) -> Literal {
+        if lower_bound <= i32::MIN as i64 {
+            self.constant_literal(true)
+        } else if lower_bound > i32::MAX as i64 {
+            self.constant_literal(false)
+        } else {
+            self.csp_solver
+                .get_predicate_literal(view.lower_bound_predicate(lower_bound as i32))
+        }
+    }
+
+    fn get_equality_literal(&mut self, view: AffineView, value: i64) -> Literal {
+        let predicate = i32::try_from(value)
+            .ok()
+            .and_then(|value| view.equality_predicate(value));
+        match predicate {
+            Some(predicate) => self.csp_solver.get_predicate_literal(predicate),
+            None => self.constant_literal(false),
+        }
+    }
+
+    //returns the literal that is equivalent to the inequality if it has at most one term
+    fn get_linear_literal(&mut self, linear: &LinearLessOrEqual) -> Option<Literal> {
+        match linear.terms.as_slice() {
+            [] => Some(self.constant_literal(linear.right_hand_side >= 0)),
+            [view] => Some(!self.get_lower_bound_literal(*view, linear.right_hand_side + 1)),
+            _ => None,
+        }
+    }
+
+    //returns the bounds of the view at the root
+    fn get_bounds(&self, view: AffineView) -> (i64, i64) {
+        let assignments = self.csp_solver.get_integer_assignments();
+        let integer_variable = view.get_integer_variable();
+        let a = view.transform_value(assignments.get_lower_bound(integer_variable)) as i64;
+        let b = view.transform_value(assignments.get_upper_bound(integer_variable)) as i64;
+        (a.min(b), a.max(b))
+    }
+
+    //rewrites 'sum of coefficient * view <= right_hand_side' into a sum of views over distinct variables, moving constants to the right-hand side
+    fn normalise_linear(
+        &self,
+        terms: Vec<(i64, AffineView)>,
+        right_hand_side: i64,
+    ) -> Result<LinearLessOrEqual, PumpkinError> {
+        let mut right_hand_side = right_hand_side;
+        let mut coefficients: Vec<(IntegerVariable, i64)> = vec![];
+        for (coefficient, view) in terms {
+            right_hand_side -= coefficient * view.get_offset() as i64;
+            let coefficient = coefficient * view.get_scale() as i64;
+            let integer_variable = view.get_integer_variable();
+
+            if Some(integer_variable) == self.constant_variable {
+                right_hand_side -= coefficient;
+            } else if let Some(entry) = coefficients
+                .iter_mut()
+                .find(|(variable, _)| *variable == integer_variable)
+            {
+                entry.1 += coefficient;
+            } else {
+                coefficients.push((integer_variable, coefficient));
+            }
+        }
+
+        let terms = coefficients
+            .into_iter()
+            .filter(|(_, coefficient)| *coefficient != 0)
+            .map(|(integer_variable, coefficient)| {
+                Ok(AffineView::new(integer_variable, to_i32(coefficient)?, 0))
+            })
+            .collect::<Result<Vec<AffineView>, PumpkinError>>()?;
+        Ok(LinearLessOrEqual {
+            terms,
+            right_hand_side,
+        })
+    }
+}
+
+//methods for resolving expressions
+//  identifiers refer to parameters, variables, or arrays, and array accesses are resolved to the element of the array
+impl FlatZincTranslator<'_> {
+    fn view(&mut self, expression: &Expression) -> Result<AffineView, PumpkinError> {
+        match expression {
+            Expression::Int(value) => self.constant_view(*value),
+            Expression::Bool(value) => self.constant_view(*value as i64),
+            Expression::Identifier(name) => {
+                if let Some(view) = self.variables.get(name) {
+                    Ok(*view)
+                } else if let Some(value) = self.parameters.get(name).cloned() {
+                    self.view(&value)
+                } else {
+                    Err(unknown_identifier(name))
+                }
+            }
+            Expression::ArrayAccess(name, index) => {
+                let element = self.array_element(name, *index)?;
+                self.view(&element)
+            }
+            _ => Err(PumpkinError::invalid_model(format!(
+                "Expected an integer or boolean, found {:?}.",
+                expression
+            ))),
+        }
+    }
+
+    fn views<const N: usize>(
+        &mut self,
+        arguments: &[Expression],
+    ) -> Result<[AffineView; N], PumpkinError> {
+        let views = arguments[..N]
+            .iter()
+            .map(|argument| self.view(argument))
+            .collect::<Result<Vec<AffineView>, PumpkinError>>()?;
+        Ok(views.try_into().unwrap())
+    }
+
+    fn literal(&mut self, expression: &Expression) -> Result<Literal, PumpkinError> {
+        let view = self.view(expression)?;
+        Ok(self.view_literal(view))
+    }
+
+    fn literals<const N: usize>(
+        &mut self,
+        arguments: &[Expression],
+    ) -> Result<[Literal; N], PumpkinError> {
+        let views = self.views::<N>(arguments)?;
+        Ok(views.map(|view| self.view_literal(view)))
+    }
+
+    fn constant(&self, expression: &Expression) -> Result<i64, PumpkinError> {
+        match expression {
+            Expression::Int(value) => Ok(*value),
+            Expression::Bool(value) => Ok(*value as i64),
+            Expression::Identifier(name) => match self.parameters.get(name) {
+                Some(value) => self.constant(value),
+                None => Err(PumpkinError::invalid_model(format!(
+                    "Expected a constant, found '{}'.",
+                    name
+                ))),
+            },
+            Expression::ArrayAccess(name, index) => {
+                self.constant(&self.array_element(name, *index)?)
+            }
+            _ => Err(PumpkinError::invalid_model(format!(
+                "Expected a constant, found {:?}.",
+                expression
+            ))),
+        }
+    }
+
+    fn set(&self, expression: &Expression) -> Result<SetLiteral, PumpkinError> {
+        match expression {
+            Expression::Set(set) => Ok(set.clone()),
+            Expression::Identifier(name) => match self.parameters.get(name) {
+                Some(value) => self.set(value),
+                None => Err(unknown_identifier(name)),
+            },
+            _ => Err(PumpkinError::invalid_model(format!(
+                "Expected a set, found {:?}.",
+                expression
+            ))),
+        }
+    }
+
+    fn array(&self, expression: &Expression) -> Result<Vec<Expression>, PumpkinError> {
+        match expression {
+            Expression::Array(elements) => Ok(elements.clone()),
+            Expression::Identifier(name) => {
+                if let Some(elements) = self.variable_arrays.get(name) {
+                    Ok(elements.clone())
+                } else if let Some(value) = self.parameters.get(name) {
+                    self.array(value)
+                } else {
+                    Err(unknown_identifier(name))
+                }
+            }
+            _ => Err(PumpkinError::invalid_model(format!(
+                "Expected an array, found {:?}.",
+                expression
+            ))),
+        }
+    }
+
+    fn array_element(&self, name: &str, index: i64) -> Result<Expression, PumpkinError> {
+        let elements = self.array(&Expression::Identifier(name.to_owned()))?;
+        usize::try_from(index - 1)
+            .ok()
+            .and_then(|i| elements.get(i).cloned())
+            .ok_or_else(|| {
+                PumpkinError::invalid_model(format!(
+                    "The index {} is out of bounds for '{}'.",
+                    index, name
+                ))
+            })
+    }
+
+    fn view_array(&mut self, expression: &Expression) -> Result<Vec<AffineView>, PumpkinError> {
+        self.array(expression)?
+            .iter()
+            .map(|element| self.view(element))
+            .collect()
+    }
+
+    fn literal_array(&mut self, expression: &Expression) -> Result<Vec<Literal>, PumpkinError> {
+        self.array(expression)?
+            .iter()
+            .map(|element| self.literal(element))
+            .collect()
+    }
+
+    fn constant_array(&self, expression: &Expression) -> Result<Vec<i64>, PumpkinError> {
+        self.array(expression)?
+            .iter()
+            .map(|element| self.constant(element))
+            .collect()
+    }
+}
+
+fn get_comparison(name: &str) -> Comparison {
+    if name.ends_with("_eq") {
+        Comparison::Equal
+    } else if name.ends_with("_ne") {
+        Comparison::NotEqual
+    } else if name.ends_with("_le") {
+        Comparison::LessOrEqual
+    } else {
+        Comparison::LessThan
+    }
+}
+
+//the view 1 - b, which is one exactly when the 0-1 view b is zero
+fn negate_bool_view(view: AffineView) -> AffineView {
+    (-view).offset_by(1)
+}
+
+fn get_identifier(expression: &Expression) -> Option<&str> {
+    match expression {
+        Expression::Identifier(identifier) => Some(identifier.as_str()),
+        _ => None,
+    }
+}
+
+fn check_num_arguments(arguments: &[Expression], num_arguments: usize) -> Result<(), PumpkinError> {
+    if arguments.len() == num_arguments {
+        Ok(())
+    } else {
+        Err(PumpkinError::invalid_model(format!(
+            "Expected {} arguments, found {}.",
+            num_arguments,
+            arguments.len()
+        )))
+    }
+}
+
+fn unknown_identifier(name: &str) -> PumpkinError {
+    PumpkinError::invalid_model(format!("Unknown identifier '{}'.", name))
+}
+
+fn to_i32(value: i64) -> Result<i32, PumpkinError> {
+    i32::try_from(value).map_err(|_| {
+        PumpkinError::invalid_model(format!("The value {} does not fit in 32 bits.", value))
+    })
+}
+
+fn clamp_to_i32(value: i64) -> i32 {
+    value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
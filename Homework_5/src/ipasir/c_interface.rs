@@ -0,0 +1,117 @@
+use std::ffi::{c_char, c_int, c_void};
+
+use super::{IpasirResult, IpasirSolver};
+
+//the C interface of the IPASIR standard for incremental SAT solvers, declared in 'ipasir/ipasir.h'
+//  the solver pointer is created by 'ipasir_init' and must be passed to 'ipasir_release' exactly once
+//  the functions follow the IPASIR semantics, see 'IpasirSolver' for how these map onto the solver
+
+/// Returns the name and version of the solver as a null-terminated string.
+#[no_mangle]
+pub extern "C" fn ipasir_signature() -> *const c_char {
+    concat!("pumpkin-", env!("CARGO_PKG_VERSION"), "\0")
+        .as_ptr()
+        .cast()
+}
+
+/// Creates a new solver, which is released with `ipasir_release`.
+#[no_mangle]
+pub extern "C" fn ipasir_init() -> *mut c_void {
+    Box::into_raw(Box::new(IpasirSolver::new())).cast()
+}
+
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_release(solver: *mut c_void) {
+    drop(Box::from_raw(solver.cast::<IpasirSolver>()));
+}
+
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_add(solver: *mut c_void, literal_or_zero: i32) {
+    get_solver(solver).add(literal_or_zero);
+}
+
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_assume(solver: *mut c_void, literal: i32) {
+    get_solver(solver).assume(literal);
+}
+
+/// Returns 10 if the clauses are satisfiable under the assumptions, 20 if they are not, and 0 if
+/// solving was interrupted.
+///
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_solve(solver: *mut c_void) -> c_int {
+    let result: IpasirResult = get_solver(solver).solve();
+    result as c_int
+}
+
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released, and the last call to
+/// `ipasir_solve` must have returned 10.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_val(solver: *mut c_void, literal: i32) -> i32 {
+    get_solver(solver).value(literal)
+}
+
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_failed(solver: *mut c_void, literal: i32) -> c_int {
+    get_solver(solver).failed(literal) as c_int
+}
+
+/// Sets a callback that is polled during solving, which interrupts solving by returning a nonzero
+/// value. A null callback removes the current one.
+///
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released, and the callback must
+/// be safe to call with the given data until it is replaced.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_terminate(
+    solver: *mut c_void,
+    data: *mut c_void,
+    terminate: Option<unsafe extern "C" fn(*mut c_void) -> c_int>,
+) {
+    let data = CallbackData(data);
+    let termination_callback = terminate.map(|terminate| {
+        Box::new(move || unsafe { terminate(data.get()) != 0 }) as Box<dyn FnMut() -> bool + Send>
+    });
+    get_solver(solver).set_terminate(termination_callback);
+}
+
+//the data of a callback is only passed back to the callback, so it may be sent to another thread together with the solver
+//  whether the callback itself may be called from another thread is up to the caller
+struct CallbackData(*mut c_void);
+
+unsafe impl Send for CallbackData {}
+
+impl CallbackData {
+    //the closure calls this method rather than reading the field, so that it captures the wrapper rather than the raw pointer
+    fn get(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+/// Learned clauses are not exported, so the callback is never called.
+///
+/// # Safety
+/// The solver must have been created by `ipasir_init` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_learn(
+    _solver: *mut c_void,
+    _data: *mut c_void,
+    _max_length: c_int,
+    _learn: Option<unsafe extern "C" fn(*mut c_void, *mut i32)>,
+) {
+}
+
+unsafe fn get_solver<'a>(solver: *mut c_void) -> &'a mut IpasirSolver {
+    &mut *solver.cast::<IpasirSolver>()
+}
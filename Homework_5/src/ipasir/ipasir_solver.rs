@@ -0,0 +1,157 @@
+use crate::{
+    basic_types::{CSPSolverExecutionFlag, ClauseAdditionOutcome, Literal, PropositionalVariable},
+    engine::{ConstraintSatisfactionSolver, SolverOptions},
+};
+
+//the outcome of 'IpasirSolver::solve', where the discriminants are the return codes of 'ipasir_solve'
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IpasirResult {
+    Satisfiable = 10,
+    Unsatisfiable = 20,
+    Interrupted = 0,
+}
+
+//an incremental SAT solver with the semantics of the IPASIR interface, on top of the constraint satisfaction solver
+//  literals are nonzero integers as in DIMACS, and propositional variables are created the first time they are used
+//  clauses are added literal by literal and terminated by zero, and assumptions only hold for the next call to 'solve'
+//  adding clauses or assumptions after solving restores the solver to the root, after which the values and failed assumptions of the previous call are no longer available
+pub struct IpasirSolver {
+    csp_solver: ConstraintSatisfactionSolver,
+    //the propositional variable of IPASIR variable i is stored at index i - 1
+    variables: Vec<PropositionalVariable>,
+    clause: Vec<Literal>,
+    assumptions: Vec<Literal>,
+    failed_assumptions: Vec<Literal>,
+    //set once the clauses are infeasible regardless of the assumptions, after which every call to solve reports unsatisfiability
+    is_infeasible: bool,
+}
+
+impl Default for IpasirSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IpasirSolver {
+    pub fn new() -> IpasirSolver {
+        IpasirSolver {
+            csp_solver: ConstraintSatisfactionSolver::new(&SolverOptions::default()),
+            variables: vec![],
+            clause: vec![],
+            assumptions: vec![],
+            failed_assumptions: vec![],
+            is_infeasible: false,
+        }
+    }
+
+    //adds the literal to the current clause, or adds the current clause to the solver if the literal is zero
+    pub fn add(&mut self, literal_or_zero: i32) {
+        self.restore_state_at_root();
+
+        if literal_or_zero != 0 {
+            let literal = self.get_literal(literal_or_zero);
+            self.clause.push(literal);
+            return;
+        }
+
+        let clause = std::mem::take(&mut self.clause);
+        if self.is_infeasible {
+            return;
+        }
+        if clause.is_empty()
+            || self.csp_solver.add_permanent_clause(clause) == ClauseAdditionOutcome::Infeasible
+        {
+            self.is_infeasible = true;
+        }
+    }
+
+    pub fn assume(&mut self, literal: i32) {
+        self.restore_state_at_root();
+        let literal = self.get_literal(literal);
+        self.assumptions.push(literal);
+    }
+
+    //solves under the assumptions added since the previous call, which are cleared afterwards
+    pub fn solve(&mut self) -> IpasirResult {
+        self.restore_state_at_root();
+        let assumptions = std::mem::take(&mut self.assumptions);
+        self.failed_assumptions.clear();
+
+        if self.is_infeasible {
+            return IpasirResult::Unsatisfiable;
+        }
+
+        match self
+            .csp_solver
+            .solve_under_assumptions(&assumptions, i64::MAX)
+        {
+            CSPSolverExecutionFlag::Feasible => IpasirResult::Satisfiable,
+            CSPSolverExecutionFlag::Infeasible => {
+                self.is_infeasible = true;
+                IpasirResult::Unsatisfiable
+            }
+            CSPSolverExecutionFlag::InfeasibleUnderAssumptions => {
+                self.failed_assumptions = self.csp_solver.extract_core();
+                IpasirResult::Unsatisfiable
+            }
+            CSPSolverExecutionFlag::Timeout => IpasirResult::Interrupted,
+        }
+    }
+
+    //returns the literal if it is true in the solution of the last call to solve, and its negation if it is false
+    //  zero is returned for variables that do not appear in the clauses, since either value is part of a solution
+    pub fn value(&self, literal: i32) -> i32 {
+        assert!(
+            self.csp_solver.get_state().has_solution(),
+            "Values are only available after the solver found a solution."
+        );
+        let index = literal.unsigned_abs() as usize;
+        if index == 0 || index > self.variables.len() {
+            return 0;
+        }
+
+        let variable = self.variables[index - 1];
+        let assignments = self.csp_solver.get_propositional_assignments();
+        if assignments.is_variable_assigned_true(variable) == (literal > 0) {
+            literal
+        } else {
+            -literal
+        }
+    }
+
+    //returns true if the assumption is part of the core found by the last call to solve, i.e., it was used to show unsatisfiability
+    pub fn failed(&self, literal: i32) -> bool {
+        let index = literal.unsigned_abs() as usize;
+        if index == 0 || index > self.variables.len() {
+            return false;
+        }
+        let literal = Literal::new(self.variables[index - 1], literal > 0);
+        self.failed_assumptions.contains(&literal)
+    }
+
+    //the callback is polled during solving, and solving is interrupted as soon as it returns true
+    pub fn set_terminate(&mut self, termination_callback: Option<Box<dyn FnMut() -> bool + Send>>) {
+        self.csp_solver
+            .set_termination_callback(termination_callback);
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.variables.len()
+    }
+
+    fn get_literal(&mut self, literal: i32) -> Literal {
+        let index = literal.unsigned_abs() as usize;
+        while self.variables.len() < index {
+            let variable = self.csp_solver.create_new_propositional_variable();
+            self.variables.push(variable);
+        }
+        Literal::new(self.variables[index - 1], literal > 0)
+    }
+
+    fn restore_state_at_root(&mut self) {
+        let state = self.csp_solver.get_state();
+        if state.has_solution() || state.is_infeasible_under_assumptions() || state.timeout() {
+            self.csp_solver.restore_state_at_root();
+        }
+    }
+}
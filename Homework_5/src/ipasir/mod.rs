@@ -0,0 +1,5 @@
+mod c_interface;
+mod ipasir_solver;
+
+pub use c_interface::*;
+pub use ipasir_solver::{IpasirResult, IpasirSolver};
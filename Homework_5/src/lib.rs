@@ -4,5 +4,8 @@ pub mod arguments;
 pub mod basic_types;
 pub mod encoders;
 pub mod engine;
+pub mod flatzinc;
+pub mod ipasir;
 pub mod propagators;
 pub mod pumpkin_asserts;
+pub mod xcsp3;
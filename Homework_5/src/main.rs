@@ -1,13 +1,24 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use pumpkin::arguments::ArgumentHandler;
 use pumpkin::basic_types::*;
 use pumpkin::engine::*;
+use pumpkin::flatzinc::{parse_flatzinc, FlatZincSolver};
+use pumpkin::xcsp3::{format_instantiation, read_xcsp3};
 
 fn main() {
     pumpkin::print_pumpkin_assert_warning_message!();
 
     let mut argument_handler = Pumpkin::create_argument_handler();
     argument_handler.print_help_summary_if_needed_and_exit();
-    argument_handler.parse_command_line_arguments();
+    if let Err(error) = argument_handler.parse_command_line_arguments() {
+        eprintln!("Error in the command line arguments: {}", error);
+        std::process::exit(1);
+    }
 
+    let options = SolverOptions::from_argument_handler(&argument_handler);
+    install_signal_handlers(options.terminate_flag.clone());
     let file_location = argument_handler.get_string_argument("file-location");
 
     if file_location.is_empty() {
@@ -15,36 +26,380 @@ fn main() {
         std::process::abort();
     }
 
-    let file_format = if file_location.ends_with(".cnf") {
+    //only CNF files are solved by a portfolio, other inputs are solved with a single thread
+    let num_threads = argument_handler.get_integer_argument("threads") as usize;
+    let is_cnf = file_location.ends_with(".cnf");
+    if num_threads > 1 && !is_cnf {
+        warn_single_thread("only CNF files are solved with multiple threads");
+    }
+
+    if file_location.ends_with(".fzn") {
+        solve_flatzinc(&argument_handler, &options, &file_location);
+        return;
+    }
+
+    if file_location.ends_with(".xml") {
+        solve_xcsp3(&argument_handler, &options, &file_location);
+        return;
+    }
+
+    if file_location.ends_with(".wcnf") {
+        solve_wcnf(&argument_handler, &options, &file_location);
+        return;
+    }
+
+    let file_format = if is_cnf {
         FileFormat::CnfDimacsPLine
     } else {
-        panic!("Unknown file format!")
+        eprintln!("Unknown file format of the file '{}'.", file_location);
+        std::process::exit(1);
     };
 
-    let mut pumpkin = Pumpkin::new(&argument_handler);
-    pumpkin.read_file(file_location.as_str(), file_format);
-    pumpkin.reset_variable_selection(argument_handler.get_integer_argument("random-seed"));
+    let is_minizinc_output =
+        argument_handler.get_string_argument("solution-output-format") == "minizinc";
+
+    let max_num_solutions = if argument_handler.get_bool_argument("all-solutions") {
+        u64::MAX
+    } else {
+        argument_handler.get_integer_argument("num-solutions") as u64
+    };
+
+    if num_threads > 1 && max_num_solutions == 1 {
+        solve_portfolio(
+            &argument_handler,
+            &options,
+            &file_location,
+            file_format,
+            num_threads,
+        );
+        return;
+    }
+    if num_threads > 1 {
+        warn_single_thread("solutions are enumerated with a single thread");
+    }
+
+    let mut pumpkin = Pumpkin::new(&options);
+    if let Err(error) = pumpkin.read_file(file_location.as_str(), file_format) {
+        eprintln!("Error in the file '{}': {}", file_location, error);
+        std::process::exit(1);
+    }
+    pumpkin.reset_variable_selection(options.random_seed);
+
+    if max_num_solutions > 1 {
+        let enumeration_flag = pumpkin.enumerate_solutions(max_num_solutions, |solution| {
+            print_solution(solution, is_minizinc_output)
+        });
+
+        println!("c num solutions: {}", enumeration_flag.num_solutions());
+        report_statistics(&pumpkin.get_statistics(), &argument_handler, "c ", ": ");
+        match enumeration_flag {
+            SolutionEnumerationFlag::AllSolutionsFound { num_solutions: 0 } => {
+                print_infeasible(is_minizinc_output)
+            }
+            SolutionEnumerationFlag::Timeout { num_solutions: 0 } => {
+                print_unknown(is_minizinc_output)
+            }
+            SolutionEnumerationFlag::AllSolutionsFound { .. } if is_minizinc_output => {
+                println!("==========")
+            }
+            _ => {
+                if !is_minizinc_output {
+                    println!("s SATISFIABLE");
+                }
+            }
+        }
+        return;
+    }
 
     let pumpkin_output = pumpkin.solve();
+    report_statistics(&pumpkin.get_statistics(), &argument_handler, "c ", ": ");
+    print_pumpkin_output(pumpkin_output, is_minizinc_output);
+}
+
+//minimises the weight of the violated soft clauses, printing the output following the conventions of the MaxSAT evaluation
+//  i.e., 'o' lines for improving solutions, an 's' line with the status, and the 'v' line with the best solution
+fn solve_wcnf(argument_handler: &ArgumentHandler, options: &SolverOptions, file_location: &str) {
+    let mut pumpkin = Pumpkin::new(options);
+    if let Err(error) = pumpkin.read_file(file_location, FileFormat::WcnfDimacsPLine) {
+        eprintln!("Error in the file '{}': {}", file_location, error);
+        std::process::exit(1);
+    }
+    pumpkin.reset_variable_selection(options.random_seed);
+
+    let lns_options = LnsOptions::from_argument_handler(argument_handler);
+    let pumpkin_output = pumpkin.solve_with_lns(&lns_options, |_solution, objective_value| {
+        println!("o {}", objective_value)
+    });
+    let pumpkin_output = match pumpkin_output {
+        Ok(pumpkin_output) => pumpkin_output,
+        Err(error) => {
+            eprintln!("Error in the file '{}': {}", file_location, error);
+            std::process::exit(1);
+        }
+    };
+
+    report_statistics(&pumpkin.get_statistics(), argument_handler, "c ", ": ");
+    match pumpkin_output {
+        PumpkinExecutionFlag::Feasible { feasible_solution } if is_interrupted() => {
+            println!("s UNKNOWN");
+            print_solution(&feasible_solution, false);
+        }
+        _ => print_pumpkin_output(pumpkin_output, false),
+    }
+}
+
+//solves the file with a portfolio of solvers, reporting the answer and the statistics of the solver that found the answer first
+fn solve_portfolio(
+    argument_handler: &ArgumentHandler,
+    options: &SolverOptions,
+    file_location: &str,
+    file_format: FileFormat,
+    num_threads: usize,
+) {
+    let mut portfolio = PortfolioSolver::new(options, num_threads);
+    if let Err(error) = portfolio.read_file(file_location, file_format) {
+        eprintln!("Error in the file '{}': {}", file_location, error);
+        std::process::exit(1);
+    }
+
+    let pumpkin_output = portfolio.solve();
+    println!("c threads: {}", portfolio.num_threads());
+    if let Some(winner_index) = portfolio.get_winner_index() {
+        println!("c winning thread: {}", winner_index);
+    }
+    report_statistics(&portfolio.get_statistics(), argument_handler, "c ", ": ");
 
+    let is_minizinc_output =
+        argument_handler.get_string_argument("solution-output-format") == "minizinc";
+    print_pumpkin_output(pumpkin_output, is_minizinc_output);
+}
+
+fn print_pumpkin_output(pumpkin_output: PumpkinExecutionFlag, is_minizinc_output: bool) {
     match pumpkin_output {
+        PumpkinExecutionFlag::Optimal { optimal_solution } => {
+            if !is_minizinc_output {
+                println!("s OPTIMUM FOUND");
+            }
+            print_solution(&optimal_solution, is_minizinc_output);
+        }
         PumpkinExecutionFlag::Feasible { feasible_solution } => {
-            println!("s SATISFIABLE");
-
-            let values = (0..feasible_solution.num_propositional_variables())
-                .map(|index| PropositionalVariable::new(index.try_into().unwrap()))
-                .map(|var| {
-                    if feasible_solution[var] {
-                        format!("{} ", var.index())
-                    } else {
-                        format!("-{} ", var.index())
-                    }
-                })
-                .collect::<String>();
-
-            println!("v {}", values);
-        }
-        PumpkinExecutionFlag::Infeasible => println!("s UNSATISFIABLE"),
-        PumpkinExecutionFlag::Timeout => println!("s UNKNOWN"),
+            if !is_minizinc_output {
+                println!("s SATISFIABLE");
+            }
+            print_solution(&feasible_solution, is_minizinc_output);
+        }
+        PumpkinExecutionFlag::Infeasible => print_infeasible(is_minizinc_output),
+        PumpkinExecutionFlag::Timeout => print_unknown(is_minizinc_output),
+    }
+}
+
+//solves the FlatZinc model, printing the output as MiniZinc expects it
+//  for optimisation problems, 'all-solutions' reports every improving solution rather than only the best one
+fn solve_flatzinc(
+    argument_handler: &ArgumentHandler,
+    options: &SolverOptions,
+    file_location: &str,
+) {
+    let solver = std::fs::read_to_string(file_location)
+        .map_err(|error| read_error(file_location, error))
+        .and_then(|model| parse_flatzinc(&model))
+        .and_then(|model| FlatZincSolver::new(&model, options));
+
+    let mut solver = match solver {
+        Ok(solver) => solver,
+        Err(error) => {
+            eprintln!("Error in FlatZinc file '{}': {}", file_location, error);
+            std::process::exit(1);
+        }
+    };
+
+    let is_all_solutions = argument_handler.get_bool_argument("all-solutions");
+    let max_num_solutions = if is_all_solutions {
+        u64::MAX
+    } else {
+        argument_handler.get_integer_argument("num-solutions") as u64
+    };
+
+    let enumeration_flag =
+        solver.solve(max_num_solutions, is_all_solutions, |instance, solution| {
+            println!("{}", instance.format_solution(solution))
+        });
+
+    //the statistics follow the MiniZinc conventions
+    report_statistics(
+        &solver.get_statistics(),
+        argument_handler,
+        "%%%mzn-stat: ",
+        "=",
+    );
+    println!("%%%mzn-stat-end");
+
+    match enumeration_flag {
+        SolutionEnumerationFlag::AllSolutionsFound { num_solutions: 0 } => print_infeasible(true),
+        SolutionEnumerationFlag::Timeout { num_solutions: 0 } => print_unknown(true),
+        SolutionEnumerationFlag::AllSolutionsFound { .. } => println!("=========="),
+        _ => {}
+    }
+}
+
+//solves the XCSP3 instance, printing the output following the conventions of the XCSP competition
+//  i.e., 'o' lines for improving solutions, an 's' line with the status, and the 'v' lines with the last solution
+fn solve_xcsp3(argument_handler: &ArgumentHandler, options: &SolverOptions, file_location: &str) {
+    let solver = std::fs::read_to_string(file_location)
+        .map_err(|error| read_error(file_location, error))
+        .and_then(|instance| read_xcsp3(&instance))
+        .and_then(|model| FlatZincSolver::new(&model, options));
+
+    let mut solver = match solver {
+        Ok(solver) => solver,
+        Err(error @ PumpkinError::UnsupportedFileFormat { .. }) => {
+            println!("c {}", error);
+            println!("s UNSUPPORTED");
+            return;
+        }
+        Err(error) => {
+            eprintln!("Error in XCSP3 file '{}': {}", file_location, error);
+            std::process::exit(1);
+        }
+    };
+
+    let mut instantiation = None;
+    let mut is_optimisation_problem = false;
+    let enumeration_flag = solver.solve(1, true, |instance, solution| {
+        if let Some(objective_value) = instance.get_objective_value(solution) {
+            println!("o {}", objective_value);
+        }
+        instantiation = Some(format_instantiation(instance, solution));
+        is_optimisation_problem = instance.is_optimisation_problem();
+    });
+
+    report_statistics(&solver.get_statistics(), argument_handler, "c ", ": ");
+
+    let Some(instantiation) = instantiation else {
+        match enumeration_flag {
+            SolutionEnumerationFlag::AllSolutionsFound { .. } => println!("s UNSATISFIABLE"),
+            _ => println!("s UNKNOWN"),
+        }
+        return;
+    };
+
+    match enumeration_flag {
+        SolutionEnumerationFlag::AllSolutionsFound { .. } if is_optimisation_problem => {
+            println!("s OPTIMUM FOUND")
+        }
+        _ if is_optimisation_problem && is_interrupted() => println!("s UNKNOWN"),
+        _ => println!("s SATISFIABLE"),
+    }
+    for line in instantiation.lines() {
+        println!("v {}", line);
+    }
+}
+
+fn read_error(file_location: &str, error: std::io::Error) -> PumpkinError {
+    PumpkinError::Io {
+        file_location: file_location.to_string(),
+        message: error.to_string(),
+    }
+}
+
+//prints each statistic on a line as 'prefix name separator value', and writes the statistics as JSON to the 'stats-file' if one is given
+fn report_statistics(
+    statistics: &SolverStatistics,
+    argument_handler: &ArgumentHandler,
+    prefix: &str,
+    separator: &str,
+) {
+    for (name, value) in statistics.get_entries() {
+        println!("{}{}{}{}", prefix, name, separator, value);
+    }
+
+    let stats_file = argument_handler.get_string_argument("stats-file");
+    if !stats_file.is_empty() {
+        if let Err(error) = std::fs::write(&stats_file, statistics.to_json()) {
+            eprintln!("Cannot write the statistics to '{}': {}", stats_file, error);
+        }
+    }
+}
+
+//the flag that the signal handlers set, which is the terminate flag of the solver options
+static SIGNAL_TERMINATE_FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+//on SIGINT or SIGTERM the search stops as if the time limit was reached, after which the best solution found so far is reported
+//  a second signal uses the default behaviour, which ends the process immediately
+#[cfg(unix)]
+fn install_signal_handlers(terminate_flag: Arc<AtomicBool>) {
+    //only async-signal-safe operations are allowed here, i.e., setting the flag
+    extern "C" fn handle_signal(_signal_number: libc::c_int) {
+        if let Some(terminate_flag) = SIGNAL_TERMINATE_FLAG.get() {
+            terminate_flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    if SIGNAL_TERMINATE_FLAG.set(terminate_flag).is_err() {
+        return;
+    }
+
+    //'SA_RESETHAND' restores the default handler once the signal is delivered
+    let handler: extern "C" fn(libc::c_int) = handle_signal;
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handler as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESETHAND | libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+        for signal_number in [libc::SIGINT, libc::SIGTERM] {
+            if libc::sigaction(signal_number, &action, std::ptr::null_mut()) != 0 {
+                eprintln!("Cannot install the handler for signal {}.", signal_number);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn install_signal_handlers(_terminate_flag: Arc<AtomicBool>) {}
+
+//the warning is written to the error stream, since the output of FlatZinc models is read by MiniZinc
+fn warn_single_thread(reason: &str) {
+    eprintln!(
+        "Warning: ignoring the 'threads' argument, {}. Solving with a single thread.",
+        reason
+    );
+}
+
+//whether a signal stopped the search, in which case the status of an optimisation problem is unknown
+//  the best solution found before the signal is still reported after the status line
+fn is_interrupted() -> bool {
+    SIGNAL_TERMINATE_FLAG
+        .get()
+        .is_some_and(|terminate_flag| terminate_flag.load(Ordering::Relaxed))
+}
+
+fn print_solution(solution: &Solution, is_minizinc_output: bool) {
+    if is_minizinc_output {
+        let variables = (0..solution.num_propositional_variables())
+            .map(|index| PropositionalVariable::new(index.try_into().unwrap()));
+
+        //the root variable at index zero is not part of the problem
+        for var in variables.skip(1) {
+            println!("x{} = {};", var.index(), solution[var]);
+        }
+        println!("----------");
+    } else {
+        println!("{}", solution.format_dimacs_value_line());
+    }
+}
+
+fn print_infeasible(is_minizinc_output: bool) {
+    if is_minizinc_output {
+        println!("=====UNSATISFIABLE=====");
+    } else {
+        println!("s UNSATISFIABLE");
+    }
+}
+
+fn print_unknown(is_minizinc_output: bool) {
+    if is_minizinc_output {
+        println!("=====UNKNOWN=====");
+    } else {
+        println!("s UNKNOWN");
     }
 }
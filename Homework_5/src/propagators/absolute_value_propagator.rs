@@ -0,0 +1,181 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{get_bounds, propagate_until_fixed_point, return_if_conflict},
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the constraint |x| = z.
+///
+/// The bounds of z follow from the sign of x. In the other direction, x lies in [-ub(z), ub(z)],
+/// and if one of the two sign regions of x is excluded, the lower bound of z bounds x away from
+/// zero in the remaining region.
+pub struct AbsoluteValuePropagator {
+    x: AffineView,
+    z: AffineView,
+    reason_store: ReasonStore,
+}
+
+impl AbsoluteValuePropagator {
+    pub fn new(x: impl Into<AffineView>, z: impl Into<AffineView>) -> AbsoluteValuePropagator {
+        AbsoluteValuePropagator {
+            x: x.into(),
+            z: z.into(),
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        x: AffineView,
+        z: AffineView,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            z,
+            0,
+            PropositionalConjunction::new()
+        ));
+
+        //bounds of z based on the bounds of x
+        let (x_lower_bound, x_upper_bound) = get_bounds(domains, x);
+        if x_lower_bound >= 0 {
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                z,
+                x_lower_bound,
+                vec![domains.get_lower_bound_predicate(x)].into(),
+            ));
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                z,
+                x_upper_bound,
+                vec![
+                    domains.get_upper_bound_predicate(x),
+                    x.lower_bound_predicate(0)
+                ]
+                .into(),
+            ));
+        } else if x_upper_bound <= 0 {
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                z,
+                -x_upper_bound,
+                vec![domains.get_upper_bound_predicate(x)].into(),
+            ));
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                z,
+                -x_lower_bound,
+                vec![
+                    domains.get_lower_bound_predicate(x),
+                    x.upper_bound_predicate(0)
+                ]
+                .into(),
+            ));
+        } else {
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                z,
+                x_upper_bound.max(-x_lower_bound),
+                domains.get_bound_predicates(&[x]).into(),
+            ));
+        }
+
+        //bounds of x based on the upper bound of z
+        let (z_lower_bound, z_upper_bound) = get_bounds(domains, z);
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            x,
+            -z_upper_bound,
+            vec![domains.get_upper_bound_predicate(z)].into(),
+        ));
+        return_if_conflict!(reason_store.tighten_upper_bound(
+            domains,
+            x,
+            z_upper_bound,
+            vec![domains.get_upper_bound_predicate(z)].into(),
+        ));
+
+        //if x cannot be in the negative region [-ub(z), -lb(z)], then it is at least lb(z), and vice versa
+        if z_lower_bound >= 1 {
+            let (x_lower_bound, x_upper_bound) = get_bounds(domains, x);
+            if x_lower_bound > -z_lower_bound {
+                return_if_conflict!(reason_store.tighten_lower_bound(
+                    domains,
+                    x,
+                    z_lower_bound,
+                    vec![
+                        domains.get_lower_bound_predicate(z),
+                        x.lower_bound_predicate((-z_lower_bound + 1) as i32)
+                    ]
+                    .into(),
+                ));
+            }
+            if x_upper_bound < z_lower_bound {
+                return_if_conflict!(reason_store.tighten_upper_bound(
+                    domains,
+                    x,
+                    -z_lower_bound,
+                    vec![
+                        domains.get_lower_bound_predicate(z),
+                        x.upper_bound_predicate((z_lower_bound - 1) as i32)
+                    ]
+                    .into(),
+                ));
+            }
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+impl ConstraintProgrammingPropagator for AbsoluteValuePropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let (x, z) = (self.x, self.z);
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            AbsoluteValuePropagator::propagate_one_pass(x, z, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "absolute value propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.z]
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.z]
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            AbsoluteValuePropagator::propagate_one_pass(self.x, self.z, domains, &mut reason_store)
+        })
+    }
+}
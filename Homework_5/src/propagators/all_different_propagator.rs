@@ -0,0 +1,156 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{propagate_until_fixed_point, return_if_conflict},
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the constraint that the variables x_1, ..., x_n take pairwise different values.
+///
+/// The propagation is based on Hall intervals: if the domains of k variables lie within an
+/// interval [a, b] of size k, then these variables take all values of the interval, and the bounds
+/// of the other variables are pushed out of the interval. If more than k variables lie within such
+/// an interval, there is a conflict. Holes in the domains are not taken into account.
+pub struct AllDifferentPropagator {
+    variables: Vec<AffineView>,
+    reason_store: ReasonStore,
+}
+
+impl AllDifferentPropagator {
+    pub fn new<View: Into<AffineView>>(variables: Vec<View>) -> AllDifferentPropagator {
+        AllDifferentPropagator {
+            variables: variables.into_iter().map(|x| x.into()).collect(),
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        variables: &[AffineView],
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        let mut interval_starts: Vec<i32> = variables
+            .iter()
+            .map(|x| domains.get_lower_bound(*x))
+            .collect();
+        interval_starts.sort_unstable();
+        interval_starts.dedup();
+
+        for a in interval_starts {
+            //the variables with a lower bound of at least a, by increasing upper bound
+            //  the first k of these variables lie within [a, b], where b is the upper bound of the k-th variable
+            let mut candidates: Vec<AffineView> = variables
+                .iter()
+                .copied()
+                .filter(|x| domains.get_lower_bound(*x) >= a)
+                .collect();
+            candidates.sort_by_key(|x| domains.get_upper_bound(*x));
+
+            for k in 1..=candidates.len() {
+                let b = domains.get_upper_bound(candidates[k - 1]);
+                //only consider the largest set of variables within [a, b]
+                if k < candidates.len() && domains.get_upper_bound(candidates[k]) == b {
+                    continue;
+                }
+
+                let interval_size = b as i64 - a as i64 + 1;
+                if (k as i64) < interval_size {
+                    continue;
+                }
+
+                let hall_set = &candidates[..k];
+                let hall_reason: PropositionalConjunction = hall_set
+                    .iter()
+                    .flat_map(|x| [x.lower_bound_predicate(a), x.upper_bound_predicate(b)])
+                    .collect::<Vec<Predicate>>()
+                    .into();
+
+                if (k as i64) > interval_size {
+                    return PropagationStatusCP::ConflictDetected {
+                        failure_reason: hall_reason,
+                    };
+                }
+
+                //the values of the Hall interval are taken by the Hall set, so the other variables cannot take them
+                for y in variables {
+                    if hall_set.contains(y) {
+                        continue;
+                    }
+
+                    if domains.get_lower_bound(*y) >= a && domains.get_lower_bound(*y) <= b {
+                        let mut reason = hall_reason.clone();
+                        reason.and(y.lower_bound_predicate(a));
+                        return_if_conflict!(reason_store.tighten_lower_bound(
+                            domains,
+                            *y,
+                            b as i64 + 1,
+                            reason,
+                        ));
+                    }
+
+                    if domains.get_upper_bound(*y) >= a && domains.get_upper_bound(*y) <= b {
+                        let mut reason = hall_reason.clone();
+                        reason.and(y.upper_bound_predicate(b));
+                        return_if_conflict!(reason_store.tighten_upper_bound(
+                            domains,
+                            *y,
+                            a as i64 - 1,
+                            reason,
+                        ));
+                    }
+                }
+            }
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+impl ConstraintProgrammingPropagator for AllDifferentPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let variables = &self.variables;
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            AllDifferentPropagator::propagate_one_pass(variables, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &str {
+        "all different propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        self.variables.clone()
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        self.variables.clone()
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            AllDifferentPropagator::propagate_one_pass(&self.variables, domains, &mut reason_store)
+        })
+    }
+}
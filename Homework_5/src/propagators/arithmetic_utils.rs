@@ -0,0 +1,92 @@
+//helper functions shared by the arithmetic propagators
+//  all arithmetic is done using i64 to avoid overflows when multiplying bounds
+
+use crate::{
+    basic_types::{AffineView, PropagationStatusCP},
+    engine::DomainManager,
+};
+
+use super::ReasonStore;
+
+//returns early from the enclosing function in case the propagation status reports a conflict
+macro_rules! return_if_conflict {
+    ($propagation_status: expr) => {
+        let propagation_status = $propagation_status;
+        if propagation_status.conflict_detected() {
+            return propagation_status;
+        }
+    };
+}
+
+pub(crate) use return_if_conflict;
+
+//repeatedly calls the one-pass propagation function until no more domain changes happen or a conflict is detected
+pub(crate) fn propagate_until_fixed_point(
+    domains: &mut DomainManager,
+    mut propagate_one_pass: impl FnMut(&mut DomainManager) -> PropagationStatusCP,
+) -> PropagationStatusCP {
+    loop {
+        let num_trail_entries_before = domains.num_trail_entries();
+        return_if_conflict!(propagate_one_pass(domains));
+        if domains.num_trail_entries() == num_trail_entries_before {
+            return PropagationStatusCP::NoConflictDetected;
+        }
+    }
+}
+
+pub(crate) fn div_floor(numerator: i64, denominator: i64) -> i64 {
+    let quotient = numerator / denominator;
+    if (numerator % denominator != 0) && ((numerator < 0) != (denominator < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+pub(crate) fn div_ceil(numerator: i64, denominator: i64) -> i64 {
+    let quotient = numerator / denominator;
+    if (numerator % denominator != 0) && ((numerator < 0) == (denominator < 0)) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+pub(crate) fn clamp_to_i32(value: i64) -> i32 {
+    value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+//returns the lower and upper bound of the view as i64 values
+pub(crate) fn get_bounds(domains: &DomainManager, view: AffineView) -> (i64, i64) {
+    (
+        domains.get_lower_bound(view) as i64,
+        domains.get_upper_bound(view) as i64,
+    )
+}
+
+//removes zero from the bounds of the divisor
+//  a zero in the middle of the domain is not removed, but the sign split ignores it
+pub(crate) fn exclude_zero_from_divisor(
+    y: AffineView,
+    domains: &mut DomainManager,
+    reason_store: &mut ReasonStore,
+) -> PropagationStatusCP {
+    if domains.get_lower_bound(y) == 0 {
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            y,
+            1,
+            vec![y.lower_bound_predicate(0)].into(),
+        ));
+    }
+
+    if domains.get_upper_bound(y) == 0 {
+        return_if_conflict!(reason_store.tighten_upper_bound(
+            domains,
+            y,
+            -1,
+            vec![y.upper_bound_predicate(0)].into(),
+        ));
+    }
+    PropagationStatusCP::NoConflictDetected
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::basic_types::estimate_nested_vec_memory_usage;
 use crate::basic_types::Clause;
 use crate::basic_types::ClauseReference;
 use crate::basic_types::Literal;
@@ -11,6 +12,14 @@ use crate::pumpkin_asserts::*;
 pub struct ClausalPropagator {
     pub watch_lists: Vec<Vec<ClauseWatcher>>,
     pub next_position_on_trail_to_propagate: usize,
+    //the number of times a clause was dereferenced during propagation, which contributes to the ticks of the solver
+    pub num_clause_visits: u64,
+}
+
+impl Default for ClausalPropagator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ClausalPropagator {
@@ -18,9 +27,14 @@ impl ClausalPropagator {
         ClausalPropagator {
             watch_lists: vec![],
             next_position_on_trail_to_propagate: 0,
+            num_clause_visits: 0,
         }
     }
 
+    pub fn estimate_memory_usage(&self) -> usize {
+        estimate_nested_vec_memory_usage(&self.watch_lists)
+    }
+
     pub fn grow(&mut self) {
         //increase the watch list, once for each polarity
         self.watch_lists.push(vec![]);
@@ -89,6 +103,7 @@ impl ClausalPropagator {
                     self.watch_lists[!true_literal][current_index].clause_reference;
 
                 let watched_clause = clause_manager.get_mutable_clause(watched_clause_reference);
+                self.num_clause_visits += 1;
 
                 //standard clause propagation starts here
 
@@ -151,8 +166,25 @@ impl ClausalPropagator {
 
                 //can propagate?
                 if assignments.is_literal_unassigned(watched_clause[0]) {
-                    assignments
-                        .enqueue_propagated_literal(watched_clause[0], watched_clause_reference.id);
+                    //the literal is propagated at the highest level of the other literals
+                    //  this is the current decision level unless the true literal was assigned out of order, see 'AssignmentsPropositional::synchronise'
+                    let propagation_level = if assignments
+                        .get_literal_assignment_level(true_literal)
+                        == assignments.get_decision_level()
+                    {
+                        assignments.get_decision_level()
+                    } else {
+                        watched_clause.get_literal_slice()[1..]
+                            .iter()
+                            .map(|literal| assignments.get_literal_assignment_level(*literal))
+                            .max()
+                            .unwrap()
+                    };
+                    assignments.enqueue_propagated_literal_at_level(
+                        watched_clause[0],
+                        watched_clause_reference.id,
+                        propagation_level,
+                    );
                 } else {
                     //conflict detected, stop any further propagation and report the conflict
                     //  pumpkin_assert_advanced(state_.assignments_.IsAssignedFalse(watched_clause[0]), "Sanity check.");
@@ -246,10 +278,7 @@ impl ClausalPropagator {
         assert!(
             self.watch_lists.iter().flatten().all(|x| {
                 let clause = clause_allocator.get_clause(x.clause_reference);
-                clause
-                    .get_literal_slice()
-                    .iter()
-                    .any(|lit| *lit == x.cached_literal)
+                clause.get_literal_slice().contains(&x.cached_literal)
             }),
             "There is a watcher with a cached literal that is not present in the clause."
         );
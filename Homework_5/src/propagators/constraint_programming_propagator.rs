@@ -1,11 +1,13 @@
 use crate::{
     basic_types::{
-        EnqueueStatus, IntegerVariable, Predicate, PropagationStatusCP, PropositionalConjunction,
+        AffineView, EnqueueStatus, IntegerVariable, Predicate, PropagationStatusCP,
+        PropositionalConjunction,
     },
     engine::DomainManager,
 };
 
-pub trait ConstraintProgrammingPropagator {
+//propagators are required to be Send, so that solvers can be moved to other threads, see 'PortfolioSolver'
+pub trait ConstraintProgrammingPropagator: Send {
     //Propagate method that will be called during search
     //	extends the current partial assignments with inferred domain changes
     //  in case no conflict has been detected, returns PropagationStatusCP::NoConflictDetected
@@ -13,8 +15,10 @@ pub trait ConstraintProgrammingPropagator {
     //      note that the failure (explanation) is given as a conjunction of predicates that lead to the failure
     fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP;
 
-    //Called each time the solver backtracks
+    //Called when the solver backtracks, if the propagator was notified of a domain change at one of the undone decision levels
     //  the propagator can then update its internal data structures given the new variable domains
+    //  the decision level of the domains is the backtrack level, so internal state can be restored from an undo log keyed on decision levels
+    //  integer state kept in 'TrailedInteger' or 'TrailedVec' is reverted automatically and does not need to be restored here
     fn synchronise(&mut self, domains: &DomainManager);
 
     //Notifies the propagator that a domain change occured with respect to the variable
@@ -22,28 +26,35 @@ pub trait ConstraintProgrammingPropagator {
     //	usually the propagator will update internal data structures to prepare for propagation
     //The return value indicates if the propagator should be enqueued for propagation
     //	note: the propagator registers which variables are relevant for it using 'get_integer_variables_to_watch_for_...' (see below)
+    //  by default the propagator is always enqueued
     fn notify_lower_bound_integer_variable_change(
         &mut self,
-        integer_variable: IntegerVariable,
-        old_lower_bound: i32,
-        new_lower_bound: i32,
-        domains: &DomainManager,
-    ) -> EnqueueStatus;
+        _integer_variable: IntegerVariable,
+        _old_lower_bound: i32,
+        _new_lower_bound: i32,
+        _domains: &DomainManager,
+    ) -> EnqueueStatus {
+        EnqueueStatus::ShouldEnqueue
+    }
 
     fn notify_upper_bound_integer_variable_change(
         &mut self,
-        integer_variable: IntegerVariable,
-        old_upper_bound: i32,
-        new_upper_bound: i32,
-        domains: &DomainManager,
-    ) -> EnqueueStatus;
+        _integer_variable: IntegerVariable,
+        _old_upper_bound: i32,
+        _new_upper_bound: i32,
+        _domains: &DomainManager,
+    ) -> EnqueueStatus {
+        EnqueueStatus::ShouldEnqueue
+    }
 
     fn notify_domain_hole_integer_variable_change(
         &mut self,
-        integer_variable: IntegerVariable,
-        removed_value_from_domain: i32,
-        domains: &DomainManager,
-    ) -> EnqueueStatus;
+        _integer_variable: IntegerVariable,
+        _removed_value_from_domain: i32,
+        _domains: &DomainManager,
+    ) -> EnqueueStatus {
+        EnqueueStatus::ShouldEnqueue
+    }
 
     //Returns the reason for propagation as a conjunction of predicates that imply the propagation
     //  reason -> predicate
@@ -62,12 +73,136 @@ pub trait ConstraintProgrammingPropagator {
     fn name(&self) -> &str;
 
     //These methods indicates for which variables and which events should the propagator be notified
-    fn get_integer_variables_to_watch_for_lower_bound_changes(&self) -> Vec<IntegerVariable>;
-    fn get_integer_variables_to_watch_for_upper_bound_changes(&self) -> Vec<IntegerVariable>;
-    fn get_integer_variables_to_watch_for_domain_hole_changes(&self) -> Vec<IntegerVariable>;
+    //  by default no variables are watched, propagators posted on views can instead override the view versions below
+    fn get_integer_variables_to_watch_for_lower_bound_changes(&self) -> Vec<IntegerVariable> {
+        vec![]
+    }
+
+    fn get_integer_variables_to_watch_for_upper_bound_changes(&self) -> Vec<IntegerVariable> {
+        vec![]
+    }
+
+    fn get_integer_variables_to_watch_for_domain_hole_changes(&self) -> Vec<IntegerVariable> {
+        vec![]
+    }
+
+    //Views: propagators may be posted on views of integer variables (see AffineView) rather than on plain variables
+    //  the methods below are the view counterparts of the watch and notify methods above
+    //  the watch list registers the views returned here and translates domain events of the underlying variable into events of the view,
+    //      e.g., a change in the upper bound of x is reported as a lower bound change of the view -x
+    //  the default implementations treat each watched integer variable as an identity view,
+    //      and forward the notifications to the integer variable versions above using the underlying variable and the bounds of the view
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        self.get_integer_variables_to_watch_for_lower_bound_changes()
+            .into_iter()
+            .map(AffineView::from)
+            .collect()
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        self.get_integer_variables_to_watch_for_upper_bound_changes()
+            .into_iter()
+            .map(AffineView::from)
+            .collect()
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        self.get_integer_variables_to_watch_for_domain_hole_changes()
+            .into_iter()
+            .map(AffineView::from)
+            .collect()
+    }
+
+    fn notify_lower_bound_view_change(
+        &mut self,
+        view: AffineView,
+        old_lower_bound: i32,
+        new_lower_bound: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.notify_lower_bound_integer_variable_change(
+            view.get_integer_variable(),
+            old_lower_bound,
+            new_lower_bound,
+            domains,
+        )
+    }
+
+    fn notify_upper_bound_view_change(
+        &mut self,
+        view: AffineView,
+        old_upper_bound: i32,
+        new_upper_bound: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.notify_upper_bound_integer_variable_change(
+            view.get_integer_variable(),
+            old_upper_bound,
+            new_upper_bound,
+            domains,
+        )
+    }
+
+    fn notify_domain_hole_view_change(
+        &mut self,
+        view: AffineView,
+        removed_value_from_domain: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.notify_domain_hole_integer_variable_change(
+            view.get_integer_variable(),
+            removed_value_from_domain,
+            domains,
+        )
+    }
+
+    //Local ids: each watched view has a local id, which is the position of the view in the list returned by the corresponding 'get_views_to_watch_for_...' method
+    //  e.g., the local id of a lower bound event is the position of the view in 'get_views_to_watch_for_lower_bound_changes'
+    //  the watch list notifies propagators through the methods below, so propagators can use the local id as an index into their own data structures
+    //      rather than searching for the view or integer variable
+    //  the default implementations forward the notifications to the view versions above
+    fn notify_lower_bound_change_with_local_id(
+        &mut self,
+        _local_id: usize,
+        view: AffineView,
+        old_lower_bound: i32,
+        new_lower_bound: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.notify_lower_bound_view_change(view, old_lower_bound, new_lower_bound, domains)
+    }
+
+    fn notify_upper_bound_change_with_local_id(
+        &mut self,
+        _local_id: usize,
+        view: AffineView,
+        old_upper_bound: i32,
+        new_upper_bound: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.notify_upper_bound_view_change(view, old_upper_bound, new_upper_bound, domains)
+    }
+
+    fn notify_domain_hole_change_with_local_id(
+        &mut self,
+        _local_id: usize,
+        view: AffineView,
+        removed_value_from_domain: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.notify_domain_hole_view_change(view, removed_value_from_domain, domains)
+    }
 
     //Initialises the propagator and does root propagation
     //	called only once by the solver when the propagator is added
     //The return value is the same as for the 'propagate' method
     fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP;
+
+    //Another propagation method that is used to help debugging
+    //	this method propagates until a fixed point without relying on internal data structures, hence immutable &self
+    //	it is usually best to implement this propagation method in the simplest but correct way
+    //  when the assert level is set to advanced or extreme (see pumpkin_asserts.rs), e.g., with the 'assert-advanced' feature
+    //      this method will be called to double check the reasons for failures and propagations that have been reported by this propagator, see 'DebugHelper'
+    //  note that the propagator will not be asked to provide reasons for propagations done by this method
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP;
 }
@@ -0,0 +1,291 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{propagate_until_fixed_point, return_if_conflict},
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the cumulative constraint: tasks with start times s_i, fixed durations d_i, and
+/// fixed resource usages r_i may never use more than the capacity of the resource at once.
+///
+/// The propagation is based on the time-table: a task whose latest start is before its earliest
+/// completion certainly runs in between, which is called its compulsory part. The sum of the
+/// compulsory parts forms the resource profile. If the profile exceeds the capacity there is a
+/// conflict, and otherwise a task cannot overlap with a part of the profile that leaves too little
+/// capacity for it. Explanations are given for single time points, e.g., task j runs at time t
+/// since [s_j >= t - d_j + 1] and [s_j <= t].
+pub struct CumulativePropagator {
+    tasks: Vec<Task>,
+    capacity: i64,
+    reason_store: ReasonStore,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Task {
+    start_time: AffineView,
+    duration: i64,
+    resource_usage: i64,
+}
+
+//a maximal interval [start, end) in which the same tasks have their compulsory part
+struct ProfileSegment {
+    start: i64,
+    end: i64,
+    height: i64,
+    task_indices: Vec<usize>,
+}
+
+impl CumulativePropagator {
+    pub fn new<View: Into<AffineView>>(
+        start_times: Vec<View>,
+        durations: Vec<i32>,
+        resource_usages: Vec<i32>,
+        capacity: i32,
+    ) -> CumulativePropagator {
+        assert!(
+            start_times.len() == durations.len() && start_times.len() == resource_usages.len(),
+            "Expect a duration and resource usage for each task of the cumulative propagator."
+        );
+        assert!(
+            durations.iter().chain(&resource_usages).all(|&v| v >= 0),
+            "Expect nonnegative durations and resource usages for the cumulative propagator."
+        );
+
+        //tasks without duration or resource usage never affect the profile
+        let tasks = start_times
+            .into_iter()
+            .zip(durations)
+            .zip(resource_usages)
+            .filter(|((_, duration), resource_usage)| *duration > 0 && *resource_usage > 0)
+            .map(|((start_time, duration), resource_usage)| Task {
+                start_time: start_time.into(),
+                duration: duration as i64,
+                resource_usage: resource_usage as i64,
+            })
+            .collect();
+
+        CumulativePropagator {
+            tasks,
+            capacity: capacity as i64,
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        tasks: &[Task],
+        capacity: i64,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        //a task that needs more than the capacity cannot be scheduled at all
+        if let Some(task) = tasks.iter().find(|task| task.resource_usage > capacity) {
+            let start_time = task.start_time;
+            return PropagationStatusCP::ConflictDetected {
+                failure_reason: vec![
+                    domains.get_lower_bound_predicate(start_time),
+                    domains.get_upper_bound_predicate(start_time),
+                ]
+                .into(),
+            };
+        }
+
+        let profile = CumulativePropagator::compute_profile(tasks, domains);
+
+        if let Some(segment) = profile.iter().find(|segment| segment.height > capacity) {
+            return PropagationStatusCP::ConflictDetected {
+                failure_reason: CumulativePropagator::explain_time_point(
+                    tasks,
+                    &segment.task_indices,
+                    segment.start,
+                ),
+            };
+        }
+
+        for (task_index, task) in tasks.iter().enumerate() {
+            let s = task.start_time;
+
+            //push the start of the task past the segments it cannot overlap with, in order of time
+            for segment in &profile {
+                if !CumulativePropagator::is_overloaded_by(segment, task_index, task, capacity) {
+                    continue;
+                }
+
+                //each step explains a single time point t of the segment at which the task would run if it started at its lower bound
+                //  the task then cannot start before t + 1
+                loop {
+                    let lower_bound = domains.get_lower_bound(s) as i64;
+                    if lower_bound >= segment.end || lower_bound + task.duration <= segment.start {
+                        break;
+                    }
+
+                    let t = (segment.end - 1).min(lower_bound + task.duration - 1);
+                    let mut reason =
+                        CumulativePropagator::explain_time_point(tasks, &segment.task_indices, t);
+                    reason.and(s.lower_bound_predicate((t - task.duration + 1) as i32));
+                    return_if_conflict!(reason_store.tighten_lower_bound(
+                        domains,
+                        s,
+                        t + 1,
+                        reason
+                    ));
+                }
+            }
+
+            //symmetrically, pull the start of the task before the segments it cannot overlap with, in reverse order of time
+            for segment in profile.iter().rev() {
+                if !CumulativePropagator::is_overloaded_by(segment, task_index, task, capacity) {
+                    continue;
+                }
+
+                loop {
+                    let upper_bound = domains.get_upper_bound(s) as i64;
+                    if upper_bound >= segment.end || upper_bound + task.duration <= segment.start {
+                        break;
+                    }
+
+                    let t = segment.start.max(upper_bound);
+                    let mut reason =
+                        CumulativePropagator::explain_time_point(tasks, &segment.task_indices, t);
+                    reason.and(s.upper_bound_predicate(t as i32));
+                    return_if_conflict!(reason_store.tighten_upper_bound(
+                        domains,
+                        s,
+                        t - task.duration,
+                        reason
+                    ));
+                }
+            }
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    //returns true if the segment leaves too little capacity for the task, not counting the compulsory part of the task itself
+    fn is_overloaded_by(
+        segment: &ProfileSegment,
+        task_index: usize,
+        task: &Task,
+        capacity: i64,
+    ) -> bool {
+        segment.height + task.resource_usage > capacity
+            && !segment.task_indices.contains(&task_index)
+    }
+
+    fn compute_profile(tasks: &[Task], domains: &DomainManager) -> Vec<ProfileSegment> {
+        //the compulsory part of a task is [ub(s), lb(s) + d)
+        let compulsory_parts: Vec<(usize, i64, i64)> = tasks
+            .iter()
+            .enumerate()
+            .map(|(i, task)| {
+                (
+                    i,
+                    domains.get_upper_bound(task.start_time) as i64,
+                    domains.get_lower_bound(task.start_time) as i64 + task.duration,
+                )
+            })
+            .filter(|(_, start, end)| start < end)
+            .collect();
+
+        let mut time_points: Vec<i64> = compulsory_parts
+            .iter()
+            .flat_map(|(_, start, end)| [*start, *end])
+            .collect();
+        time_points.sort_unstable();
+        time_points.dedup();
+
+        let mut profile = vec![];
+        for window in time_points.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            let task_indices: Vec<usize> = compulsory_parts
+                .iter()
+                .filter(|(_, task_start, task_end)| *task_start <= start && end <= *task_end)
+                .map(|(i, _, _)| *i)
+                .collect();
+            if task_indices.is_empty() {
+                continue;
+            }
+
+            profile.push(ProfileSegment {
+                start,
+                end,
+                height: task_indices.iter().map(|i| tasks[*i].resource_usage).sum(),
+                task_indices,
+            });
+        }
+        profile
+    }
+
+    //explains that the given tasks run at time t
+    fn explain_time_point(
+        tasks: &[Task],
+        task_indices: &[usize],
+        t: i64,
+    ) -> PropositionalConjunction {
+        task_indices
+            .iter()
+            .flat_map(|i| {
+                let task = &tasks[*i];
+                [
+                    task.start_time
+                        .lower_bound_predicate((t - task.duration + 1) as i32),
+                    task.start_time.upper_bound_predicate(t as i32),
+                ]
+            })
+            .collect::<Vec<Predicate>>()
+            .into()
+    }
+}
+
+impl ConstraintProgrammingPropagator for CumulativePropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let (tasks, capacity) = (&self.tasks, self.capacity);
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            CumulativePropagator::propagate_one_pass(tasks, capacity, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        3
+    }
+
+    fn name(&self) -> &str {
+        "cumulative propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        self.tasks.iter().map(|task| task.start_time).collect()
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        self.tasks.iter().map(|task| task.start_time).collect()
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            CumulativePropagator::propagate_one_pass(
+                &self.tasks,
+                self.capacity,
+                domains,
+                &mut reason_store,
+            )
+        })
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{
+        exclude_zero_from_divisor, get_bounds, propagate_until_fixed_point, return_if_conflict,
+    },
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the constraint x / y = z, where the division rounds towards zero.
+///
+/// The divisor y is never zero. Its domain is split into a negative and a positive part, and the
+/// bounds of z and x are computed separately for each part before taking their hull.
+pub struct DivisionPropagator {
+    x: AffineView,
+    y: AffineView,
+    z: AffineView,
+    reason_store: ReasonStore,
+}
+
+impl DivisionPropagator {
+    pub fn new(
+        x: impl Into<AffineView>,
+        y: impl Into<AffineView>,
+        z: impl Into<AffineView>,
+    ) -> DivisionPropagator {
+        DivisionPropagator {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        x: AffineView,
+        y: AffineView,
+        z: AffineView,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        return_if_conflict!(exclude_zero_from_divisor(y, domains, reason_store));
+
+        let (x_lower_bound, x_upper_bound) = get_bounds(domains, x);
+        let (y_lower_bound, y_upper_bound) = get_bounds(domains, y);
+        let divisor_parts = split_divisor_by_sign(y_lower_bound, y_upper_bound);
+
+        //bounds of z based on the bounds of x and y
+        //  within each part of the divisor the extreme values are attained at the bounds
+        let quotients: Vec<i64> = divisor_parts
+            .iter()
+            .flat_map(|&(a, b)| {
+                [
+                    x_lower_bound / a,
+                    x_lower_bound / b,
+                    x_upper_bound / a,
+                    x_upper_bound / b,
+                ]
+            })
+            .collect();
+
+        //when the dividend is nonnegative and the divisor positive, the bounds of z only depend on half of the bounds
+        //  otherwise all bounds of x and y are used in the explanation
+        let (lower_bound_reason, upper_bound_reason): (PropositionalConjunction, _) =
+            if x_lower_bound >= 0 && y_lower_bound >= 1 {
+                (
+                    vec![
+                        domains.get_lower_bound_predicate(x),
+                        domains.get_upper_bound_predicate(y),
+                        y.lower_bound_predicate(1),
+                    ]
+                    .into(),
+                    vec![
+                        domains.get_upper_bound_predicate(x),
+                        domains.get_lower_bound_predicate(y),
+                        x.lower_bound_predicate(0),
+                    ]
+                    .into(),
+                )
+            } else {
+                (
+                    domains.get_bound_predicates(&[x, y]).into(),
+                    domains.get_bound_predicates(&[x, y]).into(),
+                )
+            };
+
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            z,
+            *quotients.iter().min().unwrap(),
+            lower_bound_reason,
+        ));
+        return_if_conflict!(reason_store.tighten_upper_bound(
+            domains,
+            z,
+            *quotients.iter().max().unwrap(),
+            upper_bound_reason,
+        ));
+
+        //bounds of x based on the bounds of z and y
+        let (z_lower_bound, z_upper_bound) = get_bounds(domains, z);
+        let dividend_bounds: Vec<(i64, i64)> = divisor_parts
+            .iter()
+            .map(|&(a, b)| {
+                if a >= 1 {
+                    dividend_bounds_for_positive_divisor(z_lower_bound, z_upper_bound, a, b)
+                } else {
+                    //x / y = (-x) / (-y), so the negative part is handled by flipping the signs
+                    let (lower_bound, upper_bound) =
+                        dividend_bounds_for_positive_divisor(z_lower_bound, z_upper_bound, -b, -a);
+                    (-upper_bound, -lower_bound)
+                }
+            })
+            .collect();
+
+        let reason: PropositionalConjunction = domains.get_bound_predicates(&[z, y]).into();
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            x,
+            dividend_bounds.iter().map(|b| b.0).min().unwrap(),
+            reason.clone(),
+        ));
+        reason_store.tighten_upper_bound(
+            domains,
+            x,
+            dividend_bounds.iter().map(|b| b.1).max().unwrap(),
+            reason,
+        )
+    }
+}
+
+//returns the negative and positive parts of the divisor domain, excluding zero
+fn split_divisor_by_sign(lower_bound: i64, upper_bound: i64) -> Vec<(i64, i64)> {
+    let mut parts = vec![];
+    if lower_bound <= -1 {
+        parts.push((lower_bound, upper_bound.min(-1)));
+    }
+    if upper_bound >= 1 {
+        parts.push((lower_bound.max(1), upper_bound));
+    }
+    parts
+}
+
+//computes the bounds on x given that x / y lies in [z_lower_bound, z_upper_bound] and y lies in [a, b] with a >= 1
+fn dividend_bounds_for_positive_divisor(
+    z_lower_bound: i64,
+    z_upper_bound: i64,
+    a: i64,
+    b: i64,
+) -> (i64, i64) {
+    let lower_bound = if z_lower_bound >= 1 {
+        z_lower_bound * a
+    } else {
+        (z_lower_bound - 1) * b + 1
+    };
+
+    let upper_bound = if z_upper_bound >= 0 {
+        (z_upper_bound + 1) * b - 1
+    } else {
+        z_upper_bound * a
+    };
+
+    (lower_bound, upper_bound)
+}
+
+impl ConstraintProgrammingPropagator for DivisionPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let (x, y, z) = (self.x, self.y, self.z);
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            DivisionPropagator::propagate_one_pass(x, y, z, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "division propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.y, self.z]
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.y, self.z]
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            DivisionPropagator::propagate_one_pass(
+                self.x,
+                self.y,
+                self.z,
+                domains,
+                &mut reason_store,
+            )
+        })
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    basic_types::{
+        AffineView, EnqueueStatus, Predicate, PropagationStatusCP, PropositionalConjunction,
+    },
+    engine::DomainManager,
+};
+
+use super::ConstraintProgrammingPropagator;
+
+/// Wraps a propagator such that it only propagates when the 0-1 selector is one.
+///
+/// This is how the solver posts propagators inside a scope (see
+/// `ConstraintSatisfactionSolver::push_scope`). Every reason and failure of the wrapped propagator
+/// is extended with [selector >= 1], so that learned clauses depend on the scope. Once the selector
+/// is fixed to zero, the wrapped propagator never propagates again.
+///
+/// The selector may be a view, e.g., the view 1 - b activates the propagator when b is zero. This
+/// is used to half-reify constraints in both directions.
+pub struct GuardedPropagator {
+    propagator: Box<dyn ConstraintProgrammingPropagator>,
+    selector: AffineView,
+    selector_local_id: usize,
+    is_initialised: bool,
+    initialisation_level: u32,
+}
+
+impl GuardedPropagator {
+    pub fn new(
+        propagator: Box<dyn ConstraintProgrammingPropagator>,
+        selector: impl Into<AffineView>,
+    ) -> GuardedPropagator {
+        //the selector is watched after the lower bound views of the wrapped propagator
+        let selector_local_id = propagator
+            .get_views_to_watch_for_lower_bound_changes()
+            .len();
+        GuardedPropagator {
+            propagator,
+            selector: selector.into(),
+            selector_local_id,
+            is_initialised: false,
+            initialisation_level: 0,
+        }
+    }
+
+    fn selector_predicate(&self) -> Predicate {
+        self.selector.lower_bound_predicate(1)
+    }
+
+    fn is_active(&self, domains: &DomainManager) -> bool {
+        domains.get_lower_bound(self.selector) >= 1
+    }
+}
+
+impl ConstraintProgrammingPropagator for GuardedPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        if !self.is_active(domains) {
+            return PropagationStatusCP::NoConflictDetected;
+        }
+
+        //the wrapped propagator is initialised each time the selector is set,
+        //  since its root propagation is only valid when the selector holds
+        let status = if self.is_initialised {
+            self.propagator.propagate(domains)
+        } else {
+            self.is_initialised = true;
+            self.initialisation_level = domains.get_decision_level();
+            self.propagator.initialise_at_root(domains)
+        };
+
+        match status {
+            PropagationStatusCP::ConflictDetected { mut failure_reason } => {
+                failure_reason.and(self.selector_predicate());
+                PropagationStatusCP::ConflictDetected { failure_reason }
+            }
+            PropagationStatusCP::NoConflictDetected => PropagationStatusCP::NoConflictDetected,
+        }
+    }
+
+    //once the level at which the wrapped propagator was initialised is undone, its root propagation is undone as well,
+    //  so the wrapped propagator is initialised again the next time it propagates with the selector set
+    fn synchronise(&mut self, domains: &DomainManager) {
+        if self.is_initialised && self.initialisation_level > domains.get_decision_level() {
+            self.is_initialised = false;
+        }
+        self.propagator.synchronise(domains);
+    }
+
+    //notifications are forwarded with their local ids, which are the same for the wrapped propagator,
+    //  since the selector is watched after the views of the wrapped propagator
+    //if the wrapped propagator watches the selector itself, the watch list only keeps its watch, see 'WatchListCP::add_watches_for_propagator',
+    //  so the event is forwarded in addition to enqueueing the guarded propagator
+    fn notify_lower_bound_change_with_local_id(
+        &mut self,
+        local_id: usize,
+        view: AffineView,
+        old_lower_bound: i32,
+        new_lower_bound: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        if view == self.selector && local_id == self.selector_local_id {
+            return EnqueueStatus::ShouldEnqueue;
+        }
+
+        let enqueue_status = self.propagator.notify_lower_bound_change_with_local_id(
+            local_id,
+            view,
+            old_lower_bound,
+            new_lower_bound,
+            domains,
+        );
+
+        if view == self.selector {
+            EnqueueStatus::ShouldEnqueue
+        } else {
+            enqueue_status
+        }
+    }
+
+    fn notify_upper_bound_change_with_local_id(
+        &mut self,
+        local_id: usize,
+        view: AffineView,
+        old_upper_bound: i32,
+        new_upper_bound: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.propagator.notify_upper_bound_change_with_local_id(
+            local_id,
+            view,
+            old_upper_bound,
+            new_upper_bound,
+            domains,
+        )
+    }
+
+    fn notify_domain_hole_change_with_local_id(
+        &mut self,
+        local_id: usize,
+        view: AffineView,
+        removed_value_from_domain: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.propagator.notify_domain_hole_change_with_local_id(
+            local_id,
+            view,
+            removed_value_from_domain,
+            domains,
+        )
+    }
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        let mut reason = self.propagator.get_reason_for_propagation(predicate);
+        reason.and(self.selector_predicate());
+        reason
+    }
+
+    fn priority(&self) -> u32 {
+        self.propagator.priority()
+    }
+
+    fn name(&self) -> &str {
+        self.propagator.name()
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        let mut views = self.propagator.get_views_to_watch_for_lower_bound_changes();
+        views.push(self.selector);
+        views
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        self.propagator.get_views_to_watch_for_upper_bound_changes()
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        self.propagator.get_views_to_watch_for_domain_hole_changes()
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        if !self.is_active(domains) {
+            return PropagationStatusCP::NoConflictDetected;
+        }
+
+        match self.propagator.debug_propagate_from_scratch(domains) {
+            PropagationStatusCP::ConflictDetected { mut failure_reason } => {
+                failure_reason.and(self.selector_predicate());
+                PropagationStatusCP::ConflictDetected { failure_reason }
+            }
+            PropagationStatusCP::NoConflictDetected => PropagationStatusCP::NoConflictDetected,
+        }
+    }
+}
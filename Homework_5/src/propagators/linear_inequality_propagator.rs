@@ -3,11 +3,12 @@ use crate::{
     basic_types::{
         EnqueueStatus, IntegerVariable, Predicate, PropagationStatusCP, PropositionalConjunction,
     },
-    engine::DomainManager,
+    engine::{DomainManager, TrailedInteger},
 };
-use crate::engine::DomainOperationOutcome;
+use crate::basic_types::AffineView;
 
-use super::ConstraintProgrammingPropagator;
+use super::arithmetic_utils::return_if_conflict;
+use super::{ConstraintProgrammingPropagator, LinearLessOrEqualPropagator, ReasonStore};
 
 /// Propagator for the constraint \sum w_i * x_i >= c.
 ///
@@ -19,9 +20,15 @@ pub struct LinearInequalityPropagator {
     variables : Vec<IntegerVariable>,
     watchlist_ub: Vec<IntegerVariable>,
     watchlist_lb: Vec<IntegerVariable>,
+    // weights of the watched variables, indexed by the local ids of the watches
+    watchlist_ub_weights: Vec<i64>,
+    watchlist_lb_weights: Vec<i64>,
     c : i64,
-    slack: i64,
-    initialised : bool
+    // reverted automatically when backtracking
+    slack: TrailedInteger,
+    initialised : bool,
+    initialisation_level: u32,
+    reason_store : ReasonStore,
 }
 
 impl LinearInequalityPropagator {
@@ -36,13 +43,28 @@ impl LinearInequalityPropagator {
         // init watchlist
         let mut watchlist_lb = Vec :: new();
         let mut watchlist_ub = Vec :: new();
+        let mut watchlist_lb_weights = Vec :: new();
+        let mut watchlist_ub_weights = Vec :: new();
         for i in 0..variables.len() {
             assert_ne!(weights[i], 0);
 
+            // a variable that appears more than once is only watched once, so its weights are merged
             if weights[i] < 0 {
-                watchlist_lb.push(variables[i]);
+                match watchlist_lb.iter().position(|x| *x == variables[i]) {
+                    Some(j) => watchlist_lb_weights[j] += weights[i],
+                    None => {
+                        watchlist_lb.push(variables[i]);
+                        watchlist_lb_weights.push(weights[i]);
+                    }
+                }
             } else {
-                watchlist_ub.push(variables[i]);
+                match watchlist_ub.iter().position(|x| *x == variables[i]) {
+                    Some(j) => watchlist_ub_weights[j] += weights[i],
+                    None => {
+                        watchlist_ub.push(variables[i]);
+                        watchlist_ub_weights.push(weights[i]);
+                    }
+                }
             }
         }
 
@@ -51,10 +73,31 @@ impl LinearInequalityPropagator {
             variables,
             watchlist_ub,
             watchlist_lb,
+            watchlist_ub_weights,
+            watchlist_lb_weights,
             c,
-            slack: 0,
+            slack: TrailedInteger::new(0),
             initialised : false,
+            initialisation_level: 0,
+            reason_store : ReasonStore :: new(),
+        }
+    }
+
+    // the slack relies on the upper bounds of the positive terms and the lower bounds of the negative terms
+    //  so these bounds of all terms except the propagated one explain the propagation
+    fn get_reason_for_term(&self, index : usize, domains: &DomainManager) -> PropositionalConjunction {
+        let mut reason = PropositionalConjunction :: new();
+        for j in 0..self.variables.len() {
+            if j == index {
+                continue;
+            }
+            if self.weights[j] < 0 {
+                reason.and(domains.get_lower_bound_predicate(self.variables[j]));
+            } else {
+                reason.and(domains.get_upper_bound_predicate(self.variables[j]));
+            }
         }
+        reason
     }
 }
 
@@ -69,6 +112,8 @@ impl ConstraintProgrammingPropagator for LinearInequalityPropagator {
             };
         }
 
+        let slack = self.slack.read(domains);
+
         // update lower bounds
         for i in 0..self.variables.len() {
             let mut lb = domains.get_lower_bound(self.variables[i]) as i64;
@@ -81,83 +126,70 @@ impl ConstraintProgrammingPropagator for LinearInequalityPropagator {
             }
 
             // validate if any update can be applied
-            let diff = self.slack + (lb - ub) * self.weights[i];
-            let x_minsat = ((-1 * diff) + i64::abs(self.weights[i]) - 1) / self.weights[i];
+            let diff = slack + (lb - ub) * self.weights[i];
+            let x_minsat = (-diff + i64::abs(self.weights[i]) - 1) / self.weights[i];
             if diff < 0 {
 
-                let outcome;
-                let mut v = PropositionalConjunction :: new();
+                // a conflict is explained by the reason together with the opposite bound of the term itself
+                let reason = self.get_reason_for_term(i, domains);
                 if self.weights[i] < 0 {
-                    outcome = domains.tighten_upper_bound(self.variables[i], lb as i32 + x_minsat as i32 );
-
-                    // get predicate
-                    v.and(domains.get_lower_bound_predicates(&[self.variables[i]])[0]);
+                    return_if_conflict!(self.reason_store.tighten_upper_bound(domains, self.variables[i], lb + x_minsat, reason));
                 } else {
-                    outcome = domains.tighten_lower_bound(self.variables[i],  lb as i32 + x_minsat as i32);
-                    // get predicate
-                    v.and(domains.get_upper_bound_predicates(&[self.variables[i]])[0]);
+                    return_if_conflict!(self.reason_store.tighten_lower_bound(domains, self.variables[i], lb + x_minsat, reason));
                 }
-
-                // report
-                if lb + x_minsat < 0 {
-                    return PropagationStatusCP::ConflictDetected {
-                        failure_reason : v,
-                    }
-                }
-
-                match outcome {
-                    DomainOperationOutcome::Failure => return PropagationStatusCP::ConflictDetected {
-                        failure_reason : v,
-                    },
-                    _ => PropagationStatusCP::NoConflictDetected,
-                };
             }
         }
-        return PropagationStatusCP::NoConflictDetected;
+        PropagationStatusCP::NoConflictDetected
     }
 
-    fn synchronise(&mut self, _domains: &DomainManager) {
-        // Left blank, it is not necessary to use this. However, if you want to, you can.
+    fn synchronise(&mut self, domains: &DomainManager) {
+        let backtrack_level = domains.get_decision_level();
+
+        // the slack was computed from the domains at the initialisation level, so it is computed again if that level is undone
+        //  otherwise the slack is restored by the trail
+        if self.initialised && self.initialisation_level > backtrack_level {
+            self.initialised = false;
+        }
     }
 
-    fn notify_lower_bound_integer_variable_change(
+    fn notify_lower_bound_change_with_local_id(
         &mut self,
-        integer_variable: IntegerVariable,
+        local_id: usize,
+        _view: AffineView,
         old_lower_bound: i32,
         new_lower_bound: i32,
-        _domains: &DomainManager,
+        domains: &DomainManager,
     ) -> EnqueueStatus {
 
-        // get weight
-        let index = self.variables.iter().position(|n| n.id == integer_variable.id).unwrap();
-        let weight = self.weights[index];
+        // the local id is the position in the lower bound watchlist
+        let weight = self.watchlist_lb_weights[local_id];
 
-        assert!(weight < 0 as i64);
+        assert!(weight < 0_i64);
 
         // update slack
-        self.slack += (new_lower_bound as i64 - old_lower_bound as i64) * weight;
+        self.slack.add((new_lower_bound as i64 - old_lower_bound as i64) * weight, domains);
 
-        return EnqueueStatus::ShouldEnqueue;
+        EnqueueStatus::ShouldEnqueue
     }
 
-    fn notify_upper_bound_integer_variable_change(
+    fn notify_upper_bound_change_with_local_id(
         &mut self,
-        integer_variable: IntegerVariable,
+        local_id: usize,
+        _view: AffineView,
         old_upper_bound: i32,
         new_upper_bound: i32,
-        _domains: &DomainManager,
+        domains: &DomainManager,
     ) -> EnqueueStatus {
 
-        // get weight
-        let index = self.variables.iter().position(|n| n.id == integer_variable.id).unwrap();
-        let weight = self.weights[index];
+        // the local id is the position in the upper bound watchlist
+        let weight = self.watchlist_ub_weights[local_id];
 
-        assert!(weight > 0 as i64);
+        assert!(weight > 0_i64);
 
         // update slack
-        self.slack += (new_upper_bound as i64 - old_upper_bound as i64) * weight;
+        self.slack.add((new_upper_bound as i64 - old_upper_bound as i64) * weight, domains);
 
-        return EnqueueStatus::ShouldEnqueue;
+        EnqueueStatus::ShouldEnqueue
     }
 
     fn notify_domain_hole_integer_variable_change(
@@ -166,11 +198,11 @@ impl ConstraintProgrammingPropagator for LinearInequalityPropagator {
         _removed_value_from_domain: i32,
         _domains: &DomainManager,
     ) -> EnqueueStatus {
-        return EnqueueStatus::DoNotEnqueue;
+        EnqueueStatus::DoNotEnqueue
     }
 
-    fn get_reason_for_propagation(&mut self, _predicate: Predicate) -> PropositionalConjunction {
-        unreachable!()
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
     }
 
     fn priority(&self) -> u32 {
@@ -182,15 +214,15 @@ impl ConstraintProgrammingPropagator for LinearInequalityPropagator {
     }
 
     fn get_integer_variables_to_watch_for_lower_bound_changes(&self) -> Vec<IntegerVariable> {
-        return self.watchlist_lb.clone();
+        self.watchlist_lb.clone()
     }
 
     fn get_integer_variables_to_watch_for_upper_bound_changes(&self) -> Vec<IntegerVariable> {
-        return self.watchlist_ub.clone();
+        self.watchlist_ub.clone()
     }
 
     fn get_integer_variables_to_watch_for_domain_hole_changes(&self) -> Vec<IntegerVariable> {
-        return Vec :: new();
+        Vec :: new()
     }
 
     fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
@@ -206,12 +238,23 @@ impl ConstraintProgrammingPropagator for LinearInequalityPropagator {
             }
         }
 
-        self.slack = slack_lb + slack_ub - self.c;
+        self.slack.assign(slack_lb + slack_ub - self.c, domains);
 
         self.initialised = true;
+        self.initialisation_level = domains.get_decision_level();
 
         // check for satisfiability
         self.propagate(domains)
     }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+
+        // \sum w_i * x_i >= c is the same constraint as \sum -w_i * x_i <= -c, which does not rely on the slack
+        let terms : Vec<AffineView> = self.variables.iter()
+            .zip(&self.weights)
+            .map(|(x, w)| AffineView::new(*x, -*w as i32, 0))
+            .collect();
+        LinearLessOrEqualPropagator::new(terms, -self.c as i32).debug_propagate_from_scratch(domains)
+    }
 }
 
@@ -0,0 +1,124 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{arithmetic_utils::return_if_conflict, ConstraintProgrammingPropagator, ReasonStore};
+
+/// Propagator for the constraint x_1 + ... + x_n <= c.
+///
+/// Coefficients are expressed through the views, e.g., 3x - 2y <= 5 is posted on the views 3x and
+/// -2y. The slack c - (lb(x_1) + ... + lb(x_n)) bounds how much each x_i may exceed its lower
+/// bound, so ub(x_i) <= lb(x_i) + slack. The explanation of each propagation consists of the lower
+/// bounds of the other views. Since only upper bounds are tightened, a single pass reaches the
+/// fixed point.
+pub struct LinearLessOrEqualPropagator {
+    terms: Vec<AffineView>,
+    right_hand_side: i64,
+    reason_store: ReasonStore,
+}
+
+impl LinearLessOrEqualPropagator {
+    pub fn new<View: Into<AffineView>>(
+        terms: Vec<View>,
+        right_hand_side: i32,
+    ) -> LinearLessOrEqualPropagator {
+        LinearLessOrEqualPropagator {
+            terms: terms.into_iter().map(|x| x.into()).collect(),
+            right_hand_side: right_hand_side as i64,
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        terms: &[AffineView],
+        right_hand_side: i64,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        let lower_bound_sum: i64 = terms
+            .iter()
+            .map(|x| domains.get_lower_bound(*x) as i64)
+            .sum();
+        let slack = right_hand_side - lower_bound_sum;
+
+        if slack < 0 {
+            return PropagationStatusCP::ConflictDetected {
+                failure_reason: domains.get_lower_bound_predicates(terms).into(),
+            };
+        }
+
+        for (i, x) in terms.iter().enumerate() {
+            let new_upper_bound = domains.get_lower_bound(*x) as i64 + slack;
+            if new_upper_bound >= domains.get_upper_bound(*x) as i64 {
+                continue;
+            }
+
+            let reason: PropositionalConjunction = terms
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, y)| domains.get_lower_bound_predicate(*y))
+                .collect::<Vec<Predicate>>()
+                .into();
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                *x,
+                new_upper_bound,
+                reason
+            ));
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+impl ConstraintProgrammingPropagator for LinearLessOrEqualPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        LinearLessOrEqualPropagator::propagate_one_pass(
+            &self.terms,
+            self.right_hand_side,
+            domains,
+            &mut self.reason_store,
+        )
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "linear less or equal propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        self.terms.clone()
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        LinearLessOrEqualPropagator::propagate_one_pass(
+            &self.terms,
+            self.right_hand_side,
+            domains,
+            &mut reason_store,
+        )
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{propagate_until_fixed_point, return_if_conflict},
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the constraint max(x_1, ..., x_n) = z.
+///
+/// The bounds of z are the largest lower bound and the largest upper bound of the x_i. Each x_i is
+/// at most ub(z), and if only one x_i can take a value at least lb(z), then that variable must be
+/// the maximum and is bounded from below by lb(z).
+pub struct MaximumPropagator {
+    variables: Vec<AffineView>,
+    z: AffineView,
+    reason_store: ReasonStore,
+}
+
+impl MaximumPropagator {
+    pub fn new<View: Into<AffineView>>(
+        variables: Vec<View>,
+        z: impl Into<AffineView>,
+    ) -> MaximumPropagator {
+        assert!(
+            !variables.is_empty(),
+            "Expect at least one variable for the maximum propagator."
+        );
+
+        MaximumPropagator {
+            variables: variables.into_iter().map(|x| x.into()).collect(),
+            z: z.into(),
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        variables: &[AffineView],
+        z: AffineView,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        //the maximum is at most the largest upper bound
+        let largest_upper_bound = variables
+            .iter()
+            .map(|x| domains.get_upper_bound(*x))
+            .max()
+            .unwrap();
+        let reason: PropositionalConjunction = variables
+            .iter()
+            .map(|x| x.upper_bound_predicate(largest_upper_bound))
+            .collect::<Vec<Predicate>>()
+            .into();
+        return_if_conflict!(reason_store.tighten_upper_bound(
+            domains,
+            z,
+            largest_upper_bound as i64,
+            reason,
+        ));
+
+        //the maximum is at least the largest lower bound
+        let variable_with_largest_lower_bound = *variables
+            .iter()
+            .max_by_key(|x| domains.get_lower_bound(**x))
+            .unwrap();
+        let largest_lower_bound = domains.get_lower_bound(variable_with_largest_lower_bound);
+        let reason = vec![domains.get_lower_bound_predicate(variable_with_largest_lower_bound)];
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            z,
+            largest_lower_bound as i64,
+            reason.into(),
+        ));
+
+        //every variable is at most the maximum
+        let z_upper_bound_predicate = domains.get_upper_bound_predicate(z);
+        let z_upper_bound = domains.get_upper_bound(z);
+        for x in variables {
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                *x,
+                z_upper_bound as i64,
+                vec![z_upper_bound_predicate].into(),
+            ));
+        }
+
+        //if only one variable can support the lower bound of z, then it needs to be the maximum
+        let z_lower_bound = domains.get_lower_bound(z);
+        let mut supports = variables
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| domains.get_upper_bound(**x) >= z_lower_bound);
+        if let (Some((support_index, support)), None) = (supports.next(), supports.next()) {
+            let mut reason: PropositionalConjunction =
+                vec![domains.get_lower_bound_predicate(z)].into();
+            for (i, x) in variables.iter().enumerate() {
+                if i != support_index {
+                    reason.and(x.upper_bound_predicate(z_lower_bound - 1));
+                }
+            }
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                *support,
+                z_lower_bound as i64,
+                reason,
+            ));
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+impl ConstraintProgrammingPropagator for MaximumPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let (variables, z) = (&self.variables, self.z);
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            MaximumPropagator::propagate_one_pass(variables, z, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "maximum propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        let mut watched_variables = self.variables.clone();
+        watched_variables.push(self.z);
+        watched_variables
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        let mut watched_variables = self.variables.clone();
+        watched_variables.push(self.z);
+        watched_variables
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            MaximumPropagator::propagate_one_pass(
+                &self.variables,
+                self.z,
+                domains,
+                &mut reason_store,
+            )
+        })
+    }
+}
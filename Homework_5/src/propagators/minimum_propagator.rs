@@ -0,0 +1,168 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{propagate_until_fixed_point, return_if_conflict},
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the constraint min(x_1, ..., x_n) = z.
+///
+/// The bounds of z are the smallest lower bound and the smallest upper bound of the x_i. Each x_i
+/// is at least lb(z), and if only one x_i can take a value at most ub(z), then that variable must
+/// be the minimum and is bounded from above by ub(z).
+pub struct MinimumPropagator {
+    variables: Vec<AffineView>,
+    z: AffineView,
+    reason_store: ReasonStore,
+}
+
+impl MinimumPropagator {
+    pub fn new<View: Into<AffineView>>(
+        variables: Vec<View>,
+        z: impl Into<AffineView>,
+    ) -> MinimumPropagator {
+        assert!(
+            !variables.is_empty(),
+            "Expect at least one variable for the minimum propagator."
+        );
+
+        MinimumPropagator {
+            variables: variables.into_iter().map(|x| x.into()).collect(),
+            z: z.into(),
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        variables: &[AffineView],
+        z: AffineView,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        //the minimum is at least the smallest lower bound
+        let smallest_lower_bound = variables
+            .iter()
+            .map(|x| domains.get_lower_bound(*x))
+            .min()
+            .unwrap();
+        let reason: PropositionalConjunction = variables
+            .iter()
+            .map(|x| x.lower_bound_predicate(smallest_lower_bound))
+            .collect::<Vec<Predicate>>()
+            .into();
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            z,
+            smallest_lower_bound as i64,
+            reason,
+        ));
+
+        //the minimum is at most the smallest upper bound
+        let variable_with_smallest_upper_bound = *variables
+            .iter()
+            .min_by_key(|x| domains.get_upper_bound(**x))
+            .unwrap();
+        let smallest_upper_bound = domains.get_upper_bound(variable_with_smallest_upper_bound);
+        let reason = vec![domains.get_upper_bound_predicate(variable_with_smallest_upper_bound)];
+        return_if_conflict!(reason_store.tighten_upper_bound(
+            domains,
+            z,
+            smallest_upper_bound as i64,
+            reason.into(),
+        ));
+
+        //every variable is at least the minimum
+        let z_lower_bound_predicate = domains.get_lower_bound_predicate(z);
+        let z_lower_bound = domains.get_lower_bound(z);
+        for x in variables {
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                *x,
+                z_lower_bound as i64,
+                vec![z_lower_bound_predicate].into(),
+            ));
+        }
+
+        //if only one variable can support the upper bound of z, then it needs to be the minimum
+        let z_upper_bound = domains.get_upper_bound(z);
+        let mut supports = variables
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| domains.get_lower_bound(**x) <= z_upper_bound);
+        if let (Some((support_index, support)), None) = (supports.next(), supports.next()) {
+            let mut reason: PropositionalConjunction =
+                vec![domains.get_upper_bound_predicate(z)].into();
+            for (i, x) in variables.iter().enumerate() {
+                if i != support_index {
+                    reason.and(x.lower_bound_predicate(z_upper_bound + 1));
+                }
+            }
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                *support,
+                z_upper_bound as i64,
+                reason,
+            ));
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+impl ConstraintProgrammingPropagator for MinimumPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let (variables, z) = (&self.variables, self.z);
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            MinimumPropagator::propagate_one_pass(variables, z, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "minimum propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        let mut watched_variables = self.variables.clone();
+        watched_variables.push(self.z);
+        watched_variables
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        let mut watched_variables = self.variables.clone();
+        watched_variables.push(self.z);
+        watched_variables
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            MinimumPropagator::propagate_one_pass(
+                &self.variables,
+                self.z,
+                domains,
+                &mut reason_store,
+            )
+        })
+    }
+}
@@ -1,7 +1,30 @@
+mod absolute_value_propagator;
+mod all_different_propagator;
+mod arithmetic_utils;
 mod clausal_propagator;
 mod constraint_programming_propagator;
+mod cumulative_propagator;
+mod division_propagator;
+mod guarded_propagator;
 mod linear_inequality_propagator;
+mod linear_less_or_equal_propagator;
+mod maximum_propagator;
+mod minimum_propagator;
+mod modulo_propagator;
+mod reason_store;
+mod times_propagator;
 
+pub use absolute_value_propagator::AbsoluteValuePropagator;
+pub use all_different_propagator::AllDifferentPropagator;
 pub use clausal_propagator::ClausalPropagator;
 pub use constraint_programming_propagator::ConstraintProgrammingPropagator;
+pub use cumulative_propagator::CumulativePropagator;
+pub use division_propagator::DivisionPropagator;
+pub use guarded_propagator::GuardedPropagator;
 pub use linear_inequality_propagator::LinearInequalityPropagator;
+pub use linear_less_or_equal_propagator::LinearLessOrEqualPropagator;
+pub use maximum_propagator::MaximumPropagator;
+pub use minimum_propagator::MinimumPropagator;
+pub use modulo_propagator::ModuloPropagator;
+pub use reason_store::ReasonStore;
+pub use times_propagator::TimesPropagator;
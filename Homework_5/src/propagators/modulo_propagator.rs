@@ -0,0 +1,197 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{
+        exclude_zero_from_divisor, get_bounds, propagate_until_fixed_point, return_if_conflict,
+    },
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the constraint x % y = z, where the remainder takes the sign of x, i.e., the
+/// remainder of the division that rounds towards zero.
+///
+/// The divisor y is never zero. The magnitude of z is strictly smaller than the magnitude of y and
+/// never larger than the magnitude of x. Once x and y are fixed, z is fixed to the remainder.
+pub struct ModuloPropagator {
+    x: AffineView,
+    y: AffineView,
+    z: AffineView,
+    reason_store: ReasonStore,
+}
+
+impl ModuloPropagator {
+    pub fn new(
+        x: impl Into<AffineView>,
+        y: impl Into<AffineView>,
+        z: impl Into<AffineView>,
+    ) -> ModuloPropagator {
+        ModuloPropagator {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        x: AffineView,
+        y: AffineView,
+        z: AffineView,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        return_if_conflict!(exclude_zero_from_divisor(y, domains, reason_store));
+
+        let (x_lower_bound, x_upper_bound) = get_bounds(domains, x);
+        let (y_lower_bound, y_upper_bound) = get_bounds(domains, y);
+
+        //once x and y are fixed, the value of z is known
+        if x_lower_bound == x_upper_bound && y_lower_bound == y_upper_bound {
+            let remainder = x_lower_bound % y_lower_bound;
+            let reason: PropositionalConjunction = domains.get_bound_predicates(&[x, y]).into();
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                z,
+                remainder,
+                reason.clone()
+            ));
+            return reason_store.tighten_upper_bound(domains, z, remainder, reason);
+        }
+
+        //the magnitude of z is at most the largest magnitude of y minus one
+        let max_magnitude = y_lower_bound.abs().max(y_upper_bound.abs()) - 1;
+
+        //bounds of z based on the bounds of x and y
+        //  z is nonnegative for nonnegative x and nonpositive for nonpositive x, and lies between x and zero
+        let (new_upper_bound, upper_bound_reason): (i64, PropositionalConjunction) =
+            if x_upper_bound <= -1 {
+                (0, vec![x.upper_bound_predicate(-1)].into())
+            } else if x_upper_bound <= max_magnitude {
+                (
+                    x_upper_bound,
+                    vec![domains.get_upper_bound_predicate(x)].into(),
+                )
+            } else {
+                (max_magnitude, domains.get_bound_predicates(&[y]).into())
+            };
+        return_if_conflict!(reason_store.tighten_upper_bound(
+            domains,
+            z,
+            new_upper_bound,
+            upper_bound_reason
+        ));
+
+        let (new_lower_bound, lower_bound_reason): (i64, PropositionalConjunction) =
+            if x_lower_bound >= 1 {
+                (0, vec![x.lower_bound_predicate(1)].into())
+            } else if x_lower_bound >= -max_magnitude {
+                (
+                    x_lower_bound,
+                    vec![domains.get_lower_bound_predicate(x)].into(),
+                )
+            } else {
+                (-max_magnitude, domains.get_bound_predicates(&[y]).into())
+            };
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            z,
+            new_lower_bound,
+            lower_bound_reason
+        ));
+
+        //a nonzero z determines the sign of x, and its magnitude is a lower bound on the magnitude of x and y
+        let (z_lower_bound, z_upper_bound) = get_bounds(domains, z);
+        let (min_magnitude, z_nonzero_predicate) = if z_lower_bound >= 1 {
+            (z_lower_bound, domains.get_lower_bound_predicate(z))
+        } else if z_upper_bound <= -1 {
+            (-z_upper_bound, domains.get_upper_bound_predicate(z))
+        } else {
+            return PropagationStatusCP::NoConflictDetected;
+        };
+
+        if z_lower_bound >= 1 {
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                x,
+                z_lower_bound,
+                vec![z_nonzero_predicate].into(),
+            ));
+        } else {
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                x,
+                z_upper_bound,
+                vec![z_nonzero_predicate].into(),
+            ));
+        }
+
+        //the magnitude of y is strictly greater than the magnitude of z
+        //  only done if the sign of y is known
+        if domains.get_lower_bound(y) >= 1 {
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                y,
+                min_magnitude + 1,
+                vec![z_nonzero_predicate, y.lower_bound_predicate(1)].into(),
+            ));
+        } else if domains.get_upper_bound(y) <= -1 {
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                y,
+                -(min_magnitude + 1),
+                vec![z_nonzero_predicate, y.upper_bound_predicate(-1)].into(),
+            ));
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+impl ConstraintProgrammingPropagator for ModuloPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let (x, y, z) = (self.x, self.y, self.z);
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            ModuloPropagator::propagate_one_pass(x, y, z, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "modulo propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.y, self.z]
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.y, self.z]
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            ModuloPropagator::propagate_one_pass(self.x, self.y, self.z, domains, &mut reason_store)
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::arithmetic_utils::clamp_to_i32;
+
+/// Stores the explanations of the domain changes made by a propagator, so that they can be
+/// returned later on when the solver lazily asks for the reason of a propagation.
+///
+/// Reasons are keyed by the predicate that was placed on the trail. A propagator that uses the
+/// store should do all of its bound changes through it, which guarantees that every predicate the
+/// propagator places on the trail has a reason, and that conflicts are reported with an
+/// explanation rather than by putting the domains into a failed state.
+pub struct ReasonStore {
+    reasons: HashMap<Predicate, PropositionalConjunction>,
+}
+
+impl Default for ReasonStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReasonStore {
+    pub fn new() -> ReasonStore {
+        ReasonStore {
+            reasons: HashMap::new(),
+        }
+    }
+
+    pub fn get_reason(&self, predicate: Predicate) -> PropositionalConjunction {
+        self.reasons
+            .get(&predicate)
+            .unwrap_or_else(|| panic!("No reason stored for predicate {}.", predicate))
+            .clone()
+    }
+
+    pub fn clear(&mut self) {
+        self.reasons.clear();
+    }
+
+    /// Tightens the lower bound of the view if the new bound is stronger than the current one.
+    ///
+    /// If the new bound exceeds the upper bound, the domain is left untouched and a conflict is
+    /// returned, explained by the reason together with the current upper bound.
+    pub fn tighten_lower_bound(
+        &mut self,
+        domains: &mut DomainManager,
+        view: impl Into<AffineView>,
+        new_lower_bound: i64,
+        mut reason: PropositionalConjunction,
+    ) -> PropagationStatusCP {
+        let view = view.into();
+        if new_lower_bound <= domains.get_lower_bound(view) as i64 {
+            return PropagationStatusCP::NoConflictDetected;
+        }
+
+        if new_lower_bound > domains.get_upper_bound(view) as i64 {
+            reason.and(domains.get_upper_bound_predicate(view));
+            return PropagationStatusCP::ConflictDetected {
+                failure_reason: reason,
+            };
+        }
+
+        let new_lower_bound = clamp_to_i32(new_lower_bound);
+        domains.tighten_lower_bound(view, new_lower_bound);
+        self.reasons
+            .insert(view.lower_bound_predicate(new_lower_bound), reason);
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    /// Tightens the upper bound of the view, see 'tighten_lower_bound'.
+    pub fn tighten_upper_bound(
+        &mut self,
+        domains: &mut DomainManager,
+        view: impl Into<AffineView>,
+        new_upper_bound: i64,
+        mut reason: PropositionalConjunction,
+    ) -> PropagationStatusCP {
+        let view = view.into();
+        if new_upper_bound >= domains.get_upper_bound(view) as i64 {
+            return PropagationStatusCP::NoConflictDetected;
+        }
+
+        if new_upper_bound < domains.get_lower_bound(view) as i64 {
+            reason.and(domains.get_lower_bound_predicate(view));
+            return PropagationStatusCP::ConflictDetected {
+                failure_reason: reason,
+            };
+        }
+
+        let new_upper_bound = clamp_to_i32(new_upper_bound);
+        domains.tighten_upper_bound(view, new_upper_bound);
+        self.reasons
+            .insert(view.upper_bound_predicate(new_upper_bound), reason);
+        PropagationStatusCP::NoConflictDetected
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    basic_types::{AffineView, Predicate, PropagationStatusCP, PropositionalConjunction},
+    engine::DomainManager,
+};
+
+use super::{
+    arithmetic_utils::{
+        div_ceil, div_floor, get_bounds, propagate_until_fixed_point, return_if_conflict,
+    },
+    ConstraintProgrammingPropagator, ReasonStore,
+};
+
+/// Propagator for the constraint x * y = z.
+///
+/// The bounds of z are computed from the products of the bounds of x and y. The bounds of x
+/// (respectively y) are computed by dividing the bounds of z by the bounds of y (respectively x).
+/// If z cannot be zero, then neither can x and y, which is used to tighten bounds that are exactly
+/// zero, and to split a divisor that contains zero into its negative and positive part.
+pub struct TimesPropagator {
+    x: AffineView,
+    y: AffineView,
+    z: AffineView,
+    reason_store: ReasonStore,
+}
+
+impl TimesPropagator {
+    pub fn new(
+        x: impl Into<AffineView>,
+        y: impl Into<AffineView>,
+        z: impl Into<AffineView>,
+    ) -> TimesPropagator {
+        TimesPropagator {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+            reason_store: ReasonStore::new(),
+        }
+    }
+
+    fn propagate_one_pass(
+        x: AffineView,
+        y: AffineView,
+        z: AffineView,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        //bounds of z based on the bounds of x and y
+        let (x_lower_bound, x_upper_bound) = get_bounds(domains, x);
+        let (y_lower_bound, y_upper_bound) = get_bounds(domains, y);
+        let corners = [
+            x_lower_bound * y_lower_bound,
+            x_lower_bound * y_upper_bound,
+            x_upper_bound * y_lower_bound,
+            x_upper_bound * y_upper_bound,
+        ];
+
+        //when both factors are nonnegative, the bounds of z only depend on half of the bounds
+        //  otherwise all bounds of x and y are used in the explanation
+        let (lower_bound_reason, upper_bound_reason): (PropositionalConjunction, _) =
+            if x_lower_bound >= 0 && y_lower_bound >= 0 {
+                (
+                    domains.get_lower_bound_predicates(&[x, y]).into(),
+                    vec![
+                        domains.get_upper_bound_predicate(x),
+                        domains.get_upper_bound_predicate(y),
+                        x.lower_bound_predicate(0),
+                        y.lower_bound_predicate(0),
+                    ]
+                    .into(),
+                )
+            } else {
+                (
+                    domains.get_bound_predicates(&[x, y]).into(),
+                    domains.get_bound_predicates(&[x, y]).into(),
+                )
+            };
+
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            z,
+            *corners.iter().min().unwrap(),
+            lower_bound_reason,
+        ));
+        return_if_conflict!(reason_store.tighten_upper_bound(
+            domains,
+            z,
+            *corners.iter().max().unwrap(),
+            upper_bound_reason,
+        ));
+
+        //if z cannot be zero, then neither can x and y
+        let (z_lower_bound, z_upper_bound) = get_bounds(domains, z);
+        if z_lower_bound >= 1 || z_upper_bound <= -1 {
+            let z_nonzero_predicate = if z_lower_bound >= 1 {
+                z.lower_bound_predicate(1)
+            } else {
+                z.upper_bound_predicate(-1)
+            };
+
+            for factor in [x, y] {
+                return_if_conflict!(TimesPropagator::exclude_zero_from_bounds(
+                    factor,
+                    z_nonzero_predicate,
+                    domains,
+                    reason_store
+                ));
+            }
+        }
+
+        //bounds of each factor based on z and the other factor
+        return_if_conflict!(TimesPropagator::propagate_factor(
+            x,
+            y,
+            z,
+            domains,
+            reason_store
+        ));
+        TimesPropagator::propagate_factor(y, x, z, domains, reason_store)
+    }
+
+    fn exclude_zero_from_bounds(
+        factor: AffineView,
+        z_nonzero_predicate: Predicate,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        if domains.get_lower_bound(factor) == 0 {
+            return_if_conflict!(reason_store.tighten_lower_bound(
+                domains,
+                factor,
+                1,
+                vec![z_nonzero_predicate, factor.lower_bound_predicate(0)].into(),
+            ));
+        }
+
+        if domains.get_upper_bound(factor) == 0 {
+            return_if_conflict!(reason_store.tighten_upper_bound(
+                domains,
+                factor,
+                -1,
+                vec![z_nonzero_predicate, factor.upper_bound_predicate(0)].into(),
+            ));
+        }
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    //tightens the bounds of 'factor' using that factor = z / other_factor
+    //  if 'other_factor' contains zero, then it is split into its negative and positive part, which is
+    //  only done if z cannot be zero, and the bounds of 'factor' are the union of the bounds of both parts
+    fn propagate_factor(
+        factor: AffineView,
+        other_factor: AffineView,
+        z: AffineView,
+        domains: &mut DomainManager,
+        reason_store: &mut ReasonStore,
+    ) -> PropagationStatusCP {
+        let (other_lower_bound, other_upper_bound) = get_bounds(domains, other_factor);
+        let (z_lower_bound, z_upper_bound) = get_bounds(domains, z);
+
+        let divisor_parts = if other_lower_bound >= 1 || other_upper_bound <= -1 {
+            vec![(other_lower_bound, other_upper_bound)]
+        } else if z_lower_bound >= 1 || z_upper_bound <= -1 {
+            //the sign of z rules out that the divisor is zero, so the bounds of z are part of the reason
+            [(other_lower_bound, -1), (1, other_upper_bound)]
+                .into_iter()
+                .filter(|(lower_bound, upper_bound)| lower_bound <= upper_bound)
+                .collect()
+        } else {
+            return PropagationStatusCP::NoConflictDetected;
+        };
+
+        //within each part the divisor has a fixed sign, so the extreme values are attained at the bounds
+        let corners: Vec<(i64, i64)> = divisor_parts
+            .iter()
+            .flat_map(|&(lower_bound, upper_bound)| {
+                [
+                    (z_lower_bound, lower_bound),
+                    (z_lower_bound, upper_bound),
+                    (z_upper_bound, lower_bound),
+                    (z_upper_bound, upper_bound),
+                ]
+            })
+            .collect();
+        let new_lower_bound = corners.iter().map(|c| div_ceil(c.0, c.1)).min().unwrap();
+        let new_upper_bound = corners.iter().map(|c| div_floor(c.0, c.1)).max().unwrap();
+
+        let reason: PropositionalConjunction =
+            domains.get_bound_predicates(&[z, other_factor]).into();
+
+        return_if_conflict!(reason_store.tighten_lower_bound(
+            domains,
+            factor,
+            new_lower_bound,
+            reason.clone(),
+        ));
+        reason_store.tighten_upper_bound(domains, factor, new_upper_bound, reason)
+    }
+}
+
+impl ConstraintProgrammingPropagator for TimesPropagator {
+    fn propagate(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let (x, y, z) = (self.x, self.y, self.z);
+        let reason_store = &mut self.reason_store;
+        propagate_until_fixed_point(domains, |domains| {
+            TimesPropagator::propagate_one_pass(x, y, z, domains, reason_store)
+        })
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, predicate: Predicate) -> PropositionalConjunction {
+        self.reason_store.get_reason(predicate)
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "times propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.y, self.z]
+    }
+
+    fn get_views_to_watch_for_upper_bound_changes(&self) -> Vec<AffineView> {
+        vec![self.x, self.y, self.z]
+    }
+
+    fn get_views_to_watch_for_domain_hole_changes(&self) -> Vec<AffineView> {
+        vec![]
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.propagate(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        let mut reason_store = ReasonStore::new();
+        propagate_until_fixed_point(domains, |domains| {
+            TimesPropagator::propagate_one_pass(self.x, self.y, self.z, domains, &mut reason_store)
+        })
+    }
+}
@@ -1,4 +1,14 @@
-pub const PUMPKIN_ASSERT_LEVEL_DEFINITION: u8 = PUMPKIN_ASSERT_SIMPLE;
+//the level is simple by default, and can be raised with the 'assert-moderate', 'assert-advanced', and 'assert-extreme' features, where the highest enabled level is used
+//  e.g., 'cargo test --features assert-advanced' runs the tests with the checks of 'DebugHelper'
+pub const PUMPKIN_ASSERT_LEVEL_DEFINITION: u8 = if cfg!(feature = "assert-extreme") {
+    PUMPKIN_ASSERT_EXTREME
+} else if cfg!(feature = "assert-advanced") {
+    PUMPKIN_ASSERT_ADVANCED
+} else if cfg!(feature = "assert-moderate") {
+    PUMPKIN_ASSERT_MODERATE
+} else {
+    PUMPKIN_ASSERT_SIMPLE
+};
 
 pub const PUMPKIN_ASSERT_SIMPLE: u8 = 1;
 pub const PUMPKIN_ASSERT_MODERATE: u8 = 2;
@@ -0,0 +1,25 @@
+use crate::{basic_types::Solution, flatzinc::FlatZincInstance};
+
+//formats the solution as an XCSP3 instantiation of the variables of the instance, e.g.,
+//  <instantiation type="solution"> <list> x[0] x[1] y </list> <values> 2 0 5 </values> </instantiation>
+//  for optimisation problems, the type is 'optimum' and the cost is the value of the objective
+//the competition expects each line of the instantiation to be prefixed by 'v ', which is left to the caller
+pub fn format_instantiation(instance: &FlatZincInstance, solution: &Solution) -> String {
+    let output_values = instance.get_output_values(solution);
+    let names: Vec<&str> = output_values.iter().map(|(name, _)| *name).collect();
+    let values: Vec<String> = output_values
+        .iter()
+        .map(|(_, value)| value.to_string())
+        .collect();
+
+    let header = match instance.get_objective_value(solution) {
+        Some(cost) => format!("<instantiation type=\"optimum\" cost=\"{}\">", cost),
+        None => "<instantiation type=\"solution\">".to_owned(),
+    };
+    format!(
+        "{}\n  <list> {} </list>\n  <values> {} </values>\n</instantiation>",
+        header,
+        names.join(" "),
+        values.join(" ")
+    )
+}
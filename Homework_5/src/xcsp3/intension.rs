@@ -0,0 +1,105 @@
+use crate::basic_types::PumpkinError;
+
+//a functional expression of an intension constraint or objective, e.g., 'eq(add(x,y[2]),3)'
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Intension {
+    Int(i64),
+    Variable(String),
+    Call(String, Vec<Intension>),
+}
+
+pub(crate) fn parse_intension(text: &str) -> Result<Intension, PumpkinError> {
+    let tokens = tokenise(text)?;
+    let mut position = 0;
+    let expression = parse_expression(&tokens, &mut position)?;
+    if position != tokens.len() {
+        return Err(PumpkinError::invalid_model(format!(
+            "Unexpected content after the expression '{}'.",
+            text.trim()
+        )));
+    }
+    Ok(expression)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    //an identifier or integer, where identifiers include array indices such as 'x[2][3]'
+    Word(String),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenise(text: &str) -> Result<Vec<Token>, PumpkinError> {
+    let mut tokens = vec![];
+    let mut word = String::new();
+    for character in text.chars() {
+        let token = match character {
+            '(' => Some(Token::Open),
+            ')' => Some(Token::Close),
+            ',' => Some(Token::Comma),
+            _ if character.is_whitespace() => None,
+            _ if character.is_alphanumeric() || "_-[]%.".contains(character) => {
+                word.push(character);
+                continue;
+            }
+            _ => {
+                return Err(PumpkinError::invalid_model(format!(
+                    "Unexpected character '{}' in the expression '{}'.",
+                    character,
+                    text.trim()
+                )))
+            }
+        };
+        if !word.is_empty() {
+            tokens.push(Token::Word(std::mem::take(&mut word)));
+        }
+        tokens.extend(token);
+    }
+    if !word.is_empty() {
+        tokens.push(Token::Word(word));
+    }
+    Ok(tokens)
+}
+
+fn parse_expression(tokens: &[Token], position: &mut usize) -> Result<Intension, PumpkinError> {
+    let word = match tokens.get(*position) {
+        Some(Token::Word(word)) => word.clone(),
+        token => {
+            return Err(PumpkinError::invalid_model(format!(
+                "Expected an operand in the expression, found {:?}.",
+                token
+            )))
+        }
+    };
+    *position += 1;
+
+    if tokens.get(*position) != Some(&Token::Open) {
+        return Ok(match word.parse::<i64>() {
+            Ok(value) => Intension::Int(value),
+            Err(_) if word == "true" => Intension::Int(1),
+            Err(_) if word == "false" => Intension::Int(0),
+            Err(_) => Intension::Variable(word),
+        });
+    }
+    *position += 1;
+
+    let mut arguments = vec![];
+    if tokens.get(*position) != Some(&Token::Close) {
+        loop {
+            arguments.push(parse_expression(tokens, position)?);
+            match tokens.get(*position) {
+                Some(Token::Comma) => *position += 1,
+                Some(Token::Close) => break,
+                token => {
+                    return Err(PumpkinError::invalid_model(format!(
+                        "Expected ',' or ')' in the arguments of '{}', found {:?}.",
+                        word, token
+                    )))
+                }
+            }
+        }
+    }
+    *position += 1;
+    Ok(Intension::Call(word, arguments))
+}
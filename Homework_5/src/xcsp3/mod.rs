@@ -0,0 +1,7 @@
+mod instantiation;
+mod intension;
+mod reader;
+mod xml_parser;
+
+pub use instantiation::format_instantiation;
+pub use reader::read_xcsp3;
@@ -0,0 +1,1747 @@
+use std::collections::HashMap;
+
+use crate::basic_types::PumpkinError;
+use crate::flatzinc::{
+    Annotation, ConstraintItem, Expression, FlatZincModel, SetLiteral, SolveGoal, SolveItem,
+    VariableDeclaration, VariableType,
+};
+
+use super::{
+    intension::{parse_intension, Intension},
+    xml_parser::{parse_xml, XmlElement},
+};
+
+//reads an XCSP3-core instance and expresses it as a FlatZinc model, which is then translated and solved like any other FlatZinc model
+//  the variables of the instance are the output variables of the model, in order of declaration, where array elements are named as in XCSP3, e.g., 'x[2][3]'
+//  constructs without a FlatZinc counterpart, such as functional expressions and tables, are decomposed using introduced variables
+pub fn read_xcsp3(document: &str) -> Result<FlatZincModel, PumpkinError> {
+    let root = parse_xml(document)?;
+    if root.name != "instance" {
+        return Err(PumpkinError::invalid_model(
+            "The root element is expected to be 'instance'.",
+        ));
+    }
+    if let Some(format) = root.get_attribute("format") {
+        if format != "XCSP3" {
+            return Err(PumpkinError::unsupported_file_format(format!(
+                "The format '{}' is not supported.",
+                format
+            )));
+        }
+    }
+
+    let mut reader = Xcsp3Reader {
+        variables: vec![],
+        constraints: vec![],
+        domains: HashMap::new(),
+        arrays: HashMap::new(),
+        equality_variables: HashMap::new(),
+        num_introduced_variables: 0,
+    };
+
+    if let Some(variables) = root.get_child("variables") {
+        reader.read_variables(variables)?;
+    }
+
+    if let Some(constraints) = root.get_child("constraints") {
+        for constraint in &constraints.children {
+            reader.read_constraint(constraint)?;
+        }
+    }
+
+    let goal = match root.get_child("objectives") {
+        Some(objectives) => reader.read_objectives(objectives)?,
+        None => SolveGoal::Satisfy,
+    };
+
+    Ok(FlatZincModel {
+        parameters: vec![],
+        variables: reader.variables,
+        constraints: reader.constraints,
+        solve_item: SolveItem {
+            goal,
+            annotations: vec![],
+        },
+    })
+}
+
+//the linear expression 'terms[0].0 * terms[0].1 + ... + constant', where each term is a variable
+#[derive(Clone, Debug, Default)]
+struct LinearExpression {
+    terms: Vec<(i64, Expression)>,
+    constant: i64,
+}
+
+impl LinearExpression {
+    fn from_expression(expression: Expression) -> LinearExpression {
+        match expression {
+            Expression::Int(value) => LinearExpression {
+                terms: vec![],
+                constant: value,
+            },
+            expression => LinearExpression {
+                terms: vec![(1, expression)],
+                constant: 0,
+            },
+        }
+    }
+
+    fn add_scaled(&mut self, other: LinearExpression, scale: i64) {
+        self.terms.extend(
+            other
+                .terms
+                .into_iter()
+                .map(|(coefficient, variable)| (scale * coefficient, variable)),
+        );
+        self.constant += scale * other.constant;
+    }
+
+    //merges the terms of the same variable and removes terms with a zero coefficient
+    fn merged(self) -> LinearExpression {
+        let mut terms: Vec<(i64, Expression)> = vec![];
+        for (coefficient, variable) in self.terms {
+            match terms.iter_mut().find(|(_, other)| *other == variable) {
+                Some((other_coefficient, _)) => *other_coefficient += coefficient,
+                None => terms.push((coefficient, variable)),
+            }
+        }
+        terms.retain(|(coefficient, _)| *coefficient != 0);
+        LinearExpression {
+            terms,
+            constant: self.constant,
+        }
+    }
+}
+
+//the right-hand side of a condition such as '(le,10)' or '(in,1..5)'
+#[derive(Clone, Debug)]
+enum Operand {
+    Expression(Expression),
+    Range(i64, i64),
+    Set(Vec<i64>),
+}
+
+#[derive(Clone, Debug)]
+struct Condition {
+    operator: String,
+    operand: Operand,
+}
+
+struct Xcsp3Reader {
+    variables: Vec<VariableDeclaration>,
+    constraints: Vec<ConstraintItem>,
+    //the domains of all declared variables, including introduced variables
+    domains: HashMap<String, SetLiteral>,
+    //the sizes of the dimensions of each array
+    arrays: HashMap<String, Vec<usize>>,
+    //introduced boolean variables that are true exactly if a variable takes a value, used to encode tables
+    equality_variables: HashMap<(String, i64), Expression>,
+    num_introduced_variables: usize,
+}
+
+//methods for reading the variables and objectives of the instance
+impl Xcsp3Reader {
+    fn read_variables(&mut self, variables: &XmlElement) -> Result<(), PumpkinError> {
+        for element in &variables.children {
+            let id = get_id(element)?;
+            if let Some(variable_type) = element.get_attribute("type") {
+                if variable_type != "integer" {
+                    return Err(PumpkinError::unsupported_file_format(format!(
+                        "The variable '{}' has type '{}', only integer variables are supported.",
+                        id, variable_type
+                    )));
+                }
+            }
+
+            match element.name.as_str() {
+                "var" => {
+                    let domain = match element.get_attribute("as") {
+                        Some(other) => self
+                            .domains
+                            .get(other)
+                            .cloned()
+                            .ok_or_else(|| unknown_variable(other))?,
+                        None => parse_domain(&element.text)?,
+                    };
+                    self.declare_variable(id.to_owned(), domain, true);
+                }
+                "array" => self.read_array(id, element)?,
+                name => {
+                    return Err(PumpkinError::unsupported_file_format(format!(
+                        "The variable element '{}' is not supported.",
+                        name
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    //declares the elements of the array in row-major order
+    //  elements without a domain are left undeclared, as XCSP3 allows
+    fn read_array(&mut self, id: &str, element: &XmlElement) -> Result<(), PumpkinError> {
+        let size = element.get_attribute("size").ok_or_else(|| {
+            PumpkinError::invalid_model(format!("The array '{}' has no size.", id))
+        })?;
+        let dimensions = parse_size(size)?;
+
+        let mut element_domains: HashMap<Vec<usize>, SetLiteral> = HashMap::new();
+        let mut other_domain = None;
+        if element.get_child("domain").is_some() {
+            for domain_element in element.get_children("domain") {
+                let domain = parse_domain(&domain_element.text)?;
+                let targets = domain_element.get_attribute("for").unwrap_or("others");
+                for target in targets.split_whitespace() {
+                    if target == "others" {
+                        other_domain = Some(domain.clone());
+                        continue;
+                    }
+                    let (name, index_patterns) = parse_array_pattern(target)?;
+                    if name != id {
+                        return Err(PumpkinError::invalid_model(format!(
+                            "The domain of '{}' refers to '{}'.",
+                            id, target
+                        )));
+                    }
+                    for indices in expand_indices(&dimensions, &index_patterns)? {
+                        element_domains.insert(indices, domain.clone());
+                    }
+                }
+            }
+        } else {
+            other_domain = Some(parse_domain(&element.text)?);
+        }
+
+        let all_patterns = vec![IndexPattern::All; dimensions.len()];
+        for indices in expand_indices(&dimensions, &all_patterns)? {
+            if let Some(domain) = element_domains
+                .get(&indices)
+                .cloned()
+                .or_else(|| other_domain.clone())
+            {
+                self.declare_variable(get_element_name(id, &indices), domain, true);
+            }
+        }
+        self.arrays.insert(id.to_owned(), dimensions);
+        Ok(())
+    }
+
+    fn read_objectives(&mut self, objectives: &XmlElement) -> Result<SolveGoal, PumpkinError> {
+        let mut elements = objectives
+            .children
+            .iter()
+            .filter(|element| element.name == "minimize" || element.name == "maximize");
+        let objective = match (elements.next(), elements.next()) {
+            (None, _) => return Ok(SolveGoal::Satisfy),
+            (Some(objective), None) => objective,
+            (Some(_), Some(_)) => {
+                return Err(PumpkinError::unsupported_file_format(
+                    "Multiple objectives are not supported.",
+                ))
+            }
+        };
+
+        let default_type = if objective.get_child("list").is_some() {
+            "sum"
+        } else {
+            "expression"
+        };
+        let expression = match objective.get_attribute("type").unwrap_or(default_type) {
+            "expression" => {
+                let text = objective
+                    .get_child("function")
+                    .map_or(objective.text.as_str(), |function| function.text.as_str());
+                let expression = parse_intension(text)?;
+                self.integer(&expression)?
+            }
+            "sum" => {
+                let linear = self.read_weighted_list(objective)?;
+                self.linear_to_expression(linear)
+            }
+            objective_type @ ("maximum" | "minimum") => {
+                let list = self.read_list(objective)?;
+                self.extremum(objective_type == "maximum", list)
+            }
+            objective_type => {
+                return Err(PumpkinError::unsupported_file_format(format!(
+                    "Objectives of type '{}' are not supported.",
+                    objective_type
+                )))
+            }
+        };
+
+        if objective.name == "minimize" {
+            Ok(SolveGoal::Minimize(expression))
+        } else {
+            Ok(SolveGoal::Maximize(expression))
+        }
+    }
+}
+
+//methods for reading the constraints of the instance
+impl Xcsp3Reader {
+    fn read_constraint(&mut self, element: &XmlElement) -> Result<(), PumpkinError> {
+        self.read_constraint_element(element).map_err(|error| {
+            let constraint = match element.get_attribute("id") {
+                Some(id) => format!("{} '{}'", element.name, id),
+                None => element.name.clone(),
+            };
+            error.with_context(&format!("Constraint {}", constraint))
+        })
+    }
+
+    fn read_constraint_element(&mut self, element: &XmlElement) -> Result<(), PumpkinError> {
+        match element.name.as_str() {
+            "intension" => {
+                let text = element
+                    .get_child("function")
+                    .map_or(element.text.as_str(), |function| function.text.as_str());
+                let expression = parse_intension(text)?;
+                self.post_intension(&expression)
+            }
+            "extension" => self.read_extension(element),
+            "allDifferent" => {
+                if element.get_children("list").count() > 1
+                    || element.get_child("matrix").is_some()
+                    || element.get_child("except").is_some()
+                {
+                    return Err(PumpkinError::unsupported_file_format(
+                        "Only allDifferent over a single list without exceptions is supported.",
+                    ));
+                }
+                let list = self.read_list(element)?;
+                self.post("fzn_all_different_int", vec![Expression::Array(list)]);
+                Ok(())
+            }
+            "allEqual" => {
+                let list = self.read_list(element)?;
+                for x in list.iter().skip(1) {
+                    self.post("int_eq", vec![list[0].clone(), x.clone()]);
+                }
+                Ok(())
+            }
+            "ordered" => {
+                if element.get_child("lengths").is_some() {
+                    return Err(PumpkinError::unsupported_file_format(
+                        "Ordered constraints with lengths are not supported.",
+                    ));
+                }
+                let list = self.read_list(element)?;
+                let operator = get_child_text(element, "operator")?;
+                for pair in list.windows(2) {
+                    let (a, b) = (pair[0].clone(), pair[1].clone());
+                    match operator.trim() {
+                        "le" => self.post("int_le", vec![a, b]),
+                        "lt" => self.post("int_lt", vec![a, b]),
+                        "ge" => self.post("int_le", vec![b, a]),
+                        "gt" => self.post("int_lt", vec![b, a]),
+                        operator => return Err(unsupported_operator(operator)),
+                    }
+                }
+                Ok(())
+            }
+            "instantiation" => {
+                let list = self.read_list(element)?;
+                let values = self.expand_list(get_child_text(element, "values")?)?;
+                if list.len() != values.len() {
+                    return Err(PumpkinError::invalid_model(
+                        "The list and values differ in length.",
+                    ));
+                }
+                for (x, value) in list.into_iter().zip(values) {
+                    self.post("int_eq", vec![x, value]);
+                }
+                Ok(())
+            }
+            "sum" => {
+                let linear = self.read_weighted_list(element)?;
+                let condition = self.read_condition(element)?;
+                self.post_condition(linear, &condition)
+            }
+            extremum @ ("maximum" | "minimum") => {
+                if element.get_child("index").is_some() {
+                    return Err(PumpkinError::unsupported_file_format(
+                        "Maximum and minimum constraints with an index are not supported.",
+                    ));
+                }
+                let list = self.read_list(element)?;
+                let condition = self.read_condition(element)?;
+                let m = self.extremum(extremum == "maximum", list);
+                self.post_condition(LinearExpression::from_expression(m), &condition)
+            }
+            "element" => self.read_element(element),
+            "cumulative" => self.read_cumulative(element),
+            "noOverlap" => self.read_no_overlap(element),
+            "group" => {
+                let template = element
+                    .children
+                    .first()
+                    .ok_or_else(|| PumpkinError::invalid_model("The group has no constraint."))?;
+                let num_parameters = get_num_numbered_parameters(template);
+                for arguments in element.get_children("args") {
+                    let arguments: Vec<&str> = arguments.text.split_whitespace().collect();
+                    let constraint = instantiate_template(template, &arguments, num_parameters);
+                    self.read_constraint_element(&constraint)?;
+                }
+                Ok(())
+            }
+            "block" => {
+                for constraint in &element.children {
+                    self.read_constraint(constraint)?;
+                }
+                Ok(())
+            }
+            name => Err(PumpkinError::unsupported_file_format(format!(
+                "The constraint '{}' is not supported.",
+                name
+            ))),
+        }
+    }
+
+    fn read_extension(&mut self, element: &XmlElement) -> Result<(), PumpkinError> {
+        let list = self.read_list(element)?;
+        let (tuples, is_supports) = match (
+            element.get_child("supports"),
+            element.get_child("conflicts"),
+        ) {
+            (Some(supports), _) => (supports.text.as_str(), true),
+            (None, Some(conflicts)) => (conflicts.text.as_str(), false),
+            (None, None) => {
+                return Err(PumpkinError::invalid_model(
+                    "The extension constraint has no supports or conflicts.",
+                ))
+            }
+        };
+
+        //unary tables are given as a domain, e.g., '1 3..5'
+        if list.len() == 1 && !tuples.trim_start().starts_with('(') {
+            let set = parse_domain(tuples)?;
+            if is_supports {
+                self.post("set_in", vec![list[0].clone(), Expression::Set(set)]);
+            } else {
+                self.post(
+                    "set_in_reif",
+                    vec![
+                        list[0].clone(),
+                        Expression::Set(set),
+                        Expression::Bool(false),
+                    ],
+                );
+            }
+            return Ok(());
+        }
+
+        let tuples = parse_tuples(tuples, list.len())?;
+        if is_supports {
+            self.post_supports(&list, tuples);
+        } else {
+            self.post_conflicts(&list, tuples);
+        }
+        Ok(())
+    }
+
+    //each allowed tuple is selected by a boolean, where at least one tuple is selected
+    //  a selected tuple fixes its variables, and a value of a variable implies that one of the tuples with that value is selected
+    //  the latter clauses make unit propagation remove unsupported values, i.e., unit propagation establishes arc consistency
+    fn post_supports(&mut self, list: &[Expression], tuples: Vec<Vec<Option<i64>>>) {
+        let tuples: Vec<Vec<Option<i64>>> = tuples
+            .into_iter()
+            .filter(|tuple| {
+                tuple.iter().zip(list).all(|(value, x)| {
+                    value.is_none_or(|value| self.domain_values(x).contains(&value))
+                })
+            })
+            .collect();
+        let selectors: Vec<Expression> = tuples.iter().map(|_| self.new_bool()).collect();
+        self.post_clause(selectors.clone(), vec![]);
+
+        for (tuple, selector) in tuples.iter().zip(&selectors) {
+            for (value, x) in tuple.iter().zip(list) {
+                if let Some(value) = value {
+                    let equality = self.get_equality_variable(x, *value);
+                    self.post_clause(vec![equality], vec![selector.clone()]);
+                }
+            }
+        }
+
+        for (i, x) in list.iter().enumerate() {
+            for value in self.domain_values(x) {
+                let supports: Vec<Expression> = tuples
+                    .iter()
+                    .zip(&selectors)
+                    .filter(|(tuple, _)| tuple[i].is_none_or(|other| other == value))
+                    .map(|(_, selector)| selector.clone())
+                    .collect();
+                if supports.len() < selectors.len() {
+                    let equality = self.get_equality_variable(x, value);
+                    self.post_clause(supports, vec![equality]);
+                }
+            }
+        }
+    }
+
+    fn post_conflicts(&mut self, list: &[Expression], tuples: Vec<Vec<Option<i64>>>) {
+        for tuple in tuples {
+            let mut equalities = vec![];
+            for (value, x) in tuple.iter().zip(list) {
+                if let Some(value) = value {
+                    equalities.push(self.get_equality_variable(x, *value));
+                }
+            }
+            self.post_clause(vec![], equalities);
+        }
+    }
+
+    fn read_element(&mut self, element: &XmlElement) -> Result<(), PumpkinError> {
+        let list_element = element
+            .get_child("list")
+            .ok_or_else(|| PumpkinError::invalid_model("The element constraint has no list."))?;
+        let list = self.expand_list(&list_element.text)?;
+        let start_index = match list_element.get_attribute("startIndex") {
+            Some(start_index) => parse_integer(start_index)?,
+            None => 0,
+        };
+
+        let (value, condition) = match (element.get_child("value"), element.get_child("condition"))
+        {
+            (Some(value), _) => (self.read_single(&value.text)?, None),
+            (None, Some(condition)) => {
+                let (lower_bound, upper_bound) = self.get_union_of_bounds(&list);
+                let value = self.new_variable(lower_bound, upper_bound);
+                (value, Some(parse_condition(self, &condition.text)?))
+            }
+            (None, None) => {
+                return Err(PumpkinError::invalid_model(
+                    "The element constraint has no value or condition.",
+                ))
+            }
+        };
+
+        match element.get_child("index") {
+            //without an index, the value is one of the elements of the list
+            None => {
+                let equalities: Vec<Expression> = list
+                    .into_iter()
+                    .map(|x| {
+                        let equality = self.new_bool();
+                        self.post("int_eq_reif", vec![x, value.clone(), equality.clone()]);
+                        equality
+                    })
+                    .collect();
+                self.post_clause(equalities, vec![]);
+            }
+            Some(index) => {
+                if index
+                    .get_attribute("rank")
+                    .is_some_and(|rank| rank != "any")
+                {
+                    return Err(PumpkinError::unsupported_file_format(
+                        "Element constraints with a rank are not supported.",
+                    ));
+                }
+                //FlatZinc arrays start at one
+                let mut index = LinearExpression::from_expression(self.read_single(&index.text)?);
+                index.constant += 1 - start_index;
+                let index = self.linear_to_expression(index);
+
+                let name = if list.iter().all(|x| matches!(x, Expression::Int(_))) {
+                    "array_int_element"
+                } else {
+                    "array_var_int_element"
+                };
+                self.post(name, vec![index, Expression::Array(list), value.clone()]);
+            }
+        }
+
+        match condition {
+            Some(condition) => {
+                self.post_condition(LinearExpression::from_expression(value), &condition)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn read_cumulative(&mut self, element: &XmlElement) -> Result<(), PumpkinError> {
+        if element.get_child("ends").is_some() || element.get_child("machines").is_some() {
+            return Err(PumpkinError::unsupported_file_format(
+                "Cumulative constraints with ends or machines are not supported.",
+            ));
+        }
+        let origins = self.expand_list(get_child_text(element, "origins")?)?;
+        let lengths = self.read_constants(get_child_text(element, "lengths")?)?;
+        let heights = self.read_constants(get_child_text(element, "heights")?)?;
+        let condition = self.read_condition(element)?;
+        let capacity = match (condition.operator.as_str(), &condition.operand) {
+            ("le", Operand::Expression(Expression::Int(capacity))) => *capacity,
+            ("lt", Operand::Expression(Expression::Int(capacity))) => *capacity - 1,
+            _ => {
+                return Err(PumpkinError::unsupported_file_format(
+                    "Only cumulative constraints with a constant capacity are supported.",
+                ))
+            }
+        };
+        self.post_cumulative(origins, lengths, heights, capacity)
+    }
+
+    //tasks that do not overlap are tasks that each use the single unit of a resource
+    fn read_no_overlap(&mut self, element: &XmlElement) -> Result<(), PumpkinError> {
+        let origins = get_child_text(element, "origins")?;
+        if origins.contains('(') || element.get_attribute("zeroIgnored") == Some("false") {
+            return Err(PumpkinError::unsupported_file_format(
+                "Only one-dimensional noOverlap constraints that ignore tasks of length zero are supported.",
+            ));
+        }
+        let origins = self.expand_list(origins)?;
+        let lengths = self.read_constants(get_child_text(element, "lengths")?)?;
+        let heights = vec![1; origins.len()];
+        self.post_cumulative(origins, lengths, heights, 1)
+    }
+
+    fn post_cumulative(
+        &mut self,
+        origins: Vec<Expression>,
+        lengths: Vec<i64>,
+        heights: Vec<i64>,
+        capacity: i64,
+    ) -> Result<(), PumpkinError> {
+        if origins.len() != lengths.len() || origins.len() != heights.len() {
+            return Err(PumpkinError::invalid_model(
+                "The origins, lengths, and heights differ in length.",
+            ));
+        }
+        let to_array =
+            |values: Vec<i64>| Expression::Array(values.into_iter().map(Expression::Int).collect());
+        self.post(
+            "pumpkin_cumulative",
+            vec![
+                Expression::Array(origins),
+                to_array(lengths),
+                to_array(heights),
+                Expression::Int(capacity),
+            ],
+        );
+        Ok(())
+    }
+
+    //reads the list of the element, together with its coefficients, if any
+    fn read_weighted_list(
+        &mut self,
+        element: &XmlElement,
+    ) -> Result<LinearExpression, PumpkinError> {
+        let list = self.read_list(element)?;
+        let coefficients = match element.get_child("coeffs") {
+            Some(coefficients) => self.read_constants(&coefficients.text)?,
+            None => vec![1; list.len()],
+        };
+        if list.len() != coefficients.len() {
+            return Err(PumpkinError::invalid_model(
+                "The list and coefficients differ in length.",
+            ));
+        }
+
+        let mut linear = LinearExpression::default();
+        for (coefficient, x) in coefficients.into_iter().zip(list) {
+            linear.add_scaled(LinearExpression::from_expression(x), coefficient);
+        }
+        Ok(linear)
+    }
+
+    //the list of a constraint is either given in a 'list' element, or directly as the text of the constraint
+    fn read_list(&mut self, element: &XmlElement) -> Result<Vec<Expression>, PumpkinError> {
+        match element.get_child("list") {
+            Some(list) => self.expand_list(&list.text),
+            None => self.expand_list(&element.text),
+        }
+    }
+
+    fn read_constants(&mut self, text: &str) -> Result<Vec<i64>, PumpkinError> {
+        self.expand_list(text)?
+            .into_iter()
+            .map(|expression| match expression {
+                Expression::Int(value) => Ok(value),
+                _ => Err(PumpkinError::unsupported_file_format(format!(
+                    "Expected constants, found '{}'.",
+                    text.trim()
+                ))),
+            })
+            .collect()
+    }
+
+    fn read_single(&mut self, text: &str) -> Result<Expression, PumpkinError> {
+        let mut list = self.expand_list(text)?;
+        if list.len() != 1 {
+            return Err(PumpkinError::invalid_model(format!(
+                "Expected a single variable or value, found '{}'.",
+                text.trim()
+            )));
+        }
+        Ok(list.remove(0))
+    }
+
+    fn read_condition(&mut self, element: &XmlElement) -> Result<Condition, PumpkinError> {
+        let text = get_child_text(element, "condition")?;
+        parse_condition(self, text)
+    }
+
+    //expands a list of variables and values, where arrays may be referred to by patterns such as 'x[]' or 'x[1..3][0]'
+    fn expand_list(&self, text: &str) -> Result<Vec<Expression>, PumpkinError> {
+        let mut list = vec![];
+        for token in text.split_whitespace() {
+            if let Ok(value) = token.parse::<i64>() {
+                list.push(Expression::Int(value));
+            } else if self.domains.contains_key(token) {
+                list.push(Expression::Identifier(token.to_owned()));
+            } else if token.contains('[') {
+                let (name, index_patterns) = parse_array_pattern(token)?;
+                let dimensions = self
+                    .arrays
+                    .get(name)
+                    .ok_or_else(|| unknown_variable(token))?;
+                for indices in expand_indices(dimensions, &index_patterns)? {
+                    let element_name = get_element_name(name, &indices);
+                    if self.domains.contains_key(&element_name) {
+                        list.push(Expression::Identifier(element_name));
+                    }
+                }
+            } else {
+                return Err(unknown_variable(token));
+            }
+        }
+        Ok(list)
+    }
+}
+
+//methods for translating functional expressions
+//  integer expressions are turned into a variable or constant, and boolean expressions into a 0-1 variable or boolean constant
+//  linear parts of expressions are collected, so that e.g. 'le(add(x,mul(2,y)),z)' becomes a single linear inequality
+impl Xcsp3Reader {
+    fn post_intension(&mut self, expression: &Intension) -> Result<(), PumpkinError> {
+        let (operator, arguments) = match expression {
+            Intension::Call(operator, arguments) => (operator.as_str(), arguments.as_slice()),
+            _ => {
+                let b = self.boolean(expression)?;
+                self.post_clause(vec![b], vec![]);
+                return Ok(());
+            }
+        };
+
+        match (operator, arguments) {
+            ("and", _) => {
+                for argument in arguments {
+                    self.post_intension(argument)?;
+                }
+                Ok(())
+            }
+            ("lt" | "le" | "ge" | "gt" | "eq" | "ne", [a, b]) => {
+                let difference = self.difference(a, b)?;
+                self.post_linear(difference, operator, None)
+            }
+            ("not", [Intension::Call(inner_operator, inner_arguments)])
+                if inner_arguments.len() == 2 && get_negated_operator(inner_operator).is_some() =>
+            {
+                let difference = self.difference(&inner_arguments[0], &inner_arguments[1])?;
+                self.post_linear(
+                    difference,
+                    get_negated_operator(inner_operator).unwrap(),
+                    None,
+                )
+            }
+            ("or", _) => {
+                let mut positive = vec![];
+                let mut negative = vec![];
+                for argument in arguments {
+                    match argument {
+                        Intension::Call(operator, inner)
+                            if operator == "not" && inner.len() == 1 =>
+                        {
+                            negative.push(self.boolean(&inner[0])?)
+                        }
+                        _ => positive.push(self.boolean(argument)?),
+                    }
+                }
+                self.post_clause(positive, negative);
+                Ok(())
+            }
+            ("imp", [a, b]) => {
+                let a = self.boolean(a)?;
+                let b = self.boolean(b)?;
+                self.post_clause(vec![b], vec![a]);
+                Ok(())
+            }
+            ("iff", [a, b]) => {
+                let a = self.boolean(a)?;
+                let b = self.boolean(b)?;
+                self.post("bool_eq", vec![a, b]);
+                Ok(())
+            }
+            ("in", [x, set]) => {
+                let x = self.integer(x)?;
+                let set = get_set(set)?;
+                self.post("set_in", vec![x, Expression::Set(SetLiteral::Values(set))]);
+                Ok(())
+            }
+            _ => {
+                let b = self.boolean(expression)?;
+                self.post_clause(vec![b], vec![]);
+                Ok(())
+            }
+        }
+    }
+
+    fn boolean(&mut self, expression: &Intension) -> Result<Expression, PumpkinError> {
+        let (operator, arguments) = match expression {
+            Intension::Int(value) => return Ok(Expression::Bool(*value != 0)),
+            Intension::Variable(name) => {
+                let x = self.get_variable(name)?;
+                return Ok(self.integer_as_boolean(x));
+            }
+            Intension::Call(operator, arguments) => (operator.as_str(), arguments.as_slice()),
+        };
+
+        match (operator, arguments) {
+            ("lt" | "le" | "ge" | "gt" | "ne", [a, b]) => {
+                let difference = self.difference(a, b)?;
+                Ok(self.reify_linear(difference, operator))
+            }
+            ("eq", _) if arguments.len() >= 2 => {
+                let values = self.integers(arguments)?;
+                let equalities: Vec<Expression> = values[1..]
+                    .iter()
+                    .map(|value| {
+                        let mut difference = LinearExpression::from_expression(values[0].clone());
+                        difference.add_scaled(LinearExpression::from_expression(value.clone()), -1);
+                        self.reify_linear(difference, "eq")
+                    })
+                    .collect();
+                Ok(self.conjunction(equalities))
+            }
+            ("in" | "notin", [x, set]) => {
+                let x = self.integer(x)?;
+                let set = get_set(set)?;
+                let b = match x {
+                    Expression::Int(value) => Expression::Bool(set.contains(&value)),
+                    x => {
+                        let b = self.new_bool();
+                        let set = Expression::Set(SetLiteral::Values(set));
+                        self.post("set_in_reif", vec![x, set, b.clone()]);
+                        b
+                    }
+                };
+                if operator == "in" {
+                    Ok(b)
+                } else {
+                    Ok(self.negation(b))
+                }
+            }
+            ("not", [a]) => {
+                let a = self.boolean(a)?;
+                Ok(self.negation(a))
+            }
+            ("and", _) => {
+                let values = self.booleans(arguments)?;
+                Ok(self.conjunction(values))
+            }
+            ("or", _) => {
+                let values = self.booleans(arguments)?;
+                Ok(self.disjunction(values))
+            }
+            ("xor", _) if !arguments.is_empty() => {
+                let values = self.booleans(arguments)?;
+                let mut result = values[0].clone();
+                for value in values.into_iter().skip(1) {
+                    result = match (result, value) {
+                        (Expression::Bool(a), Expression::Bool(b)) => Expression::Bool(a != b),
+                        (Expression::Bool(false), b) | (b, Expression::Bool(false)) => b,
+                        (Expression::Bool(true), b) | (b, Expression::Bool(true)) => {
+                            self.negation(b)
+                        }
+                        (a, b) => {
+                            let r = self.new_bool();
+                            self.post("bool_xor", vec![a, b, r.clone()]);
+                            r
+                        }
+                    };
+                }
+                Ok(result)
+            }
+            ("iff", _) if arguments.len() >= 2 => {
+                let values = self.booleans(arguments)?;
+                let equivalences: Vec<Expression> = values[1..]
+                    .iter()
+                    .map(|value| match (&values[0], value) {
+                        (Expression::Bool(a), Expression::Bool(b)) => Expression::Bool(a == b),
+                        (a, b) => {
+                            let r = self.new_bool();
+                            self.post("bool_eq_reif", vec![a.clone(), b.clone(), r.clone()]);
+                            r
+                        }
+                    })
+                    .collect();
+                Ok(self.conjunction(equivalences))
+            }
+            ("imp", [a, b]) => {
+                let a = self.boolean(a)?;
+                let b = self.boolean(b)?;
+                let not_a = self.negation(a);
+                Ok(self.disjunction(vec![not_a, b]))
+            }
+            _ => {
+                let x = self.integer(expression)?;
+                Ok(self.integer_as_boolean(x))
+            }
+        }
+    }
+
+    fn integer(&mut self, expression: &Intension) -> Result<Expression, PumpkinError> {
+        let (operator, arguments) = match expression {
+            Intension::Int(value) => return Ok(Expression::Int(*value)),
+            Intension::Variable(name) => return self.get_variable(name),
+            Intension::Call(operator, arguments) => (operator.as_str(), arguments.as_slice()),
+        };
+
+        match (operator, arguments) {
+            ("add" | "sub" | "neg" | "mul", _) => {
+                let linear = self.linear(expression)?;
+                Ok(self.linear_to_expression(linear))
+            }
+            ("sqr", [a]) => {
+                let a = self.integer(a)?;
+                Ok(self.product(a.clone(), a))
+            }
+            ("pow", [a, Intension::Int(exponent)]) if *exponent >= 0 => {
+                let a = self.integer(a)?;
+                let mut result = Expression::Int(1);
+                for _ in 0..*exponent {
+                    result = self.product(result, a.clone());
+                }
+                Ok(result)
+            }
+            ("div" | "mod", [a, b]) => {
+                let a = self.integer(a)?;
+                let b = self.integer(b)?;
+                let (a_lower_bound, a_upper_bound) = self.get_bounds(&a);
+                let (b_lower_bound, b_upper_bound) = self.get_bounds(&b);
+                let largest_dividend = a_lower_bound.abs().max(a_upper_bound.abs());
+                let largest_divisor = b_lower_bound.abs().max(b_upper_bound.abs());
+                //the quotient is at most the dividend in absolute value, and the remainder is smaller than the divisor and has the sign of the dividend
+                let (lower_bound, upper_bound) = if operator == "div" {
+                    (-largest_dividend, largest_dividend)
+                } else {
+                    let largest_remainder = (largest_divisor - 1).max(0);
+                    (
+                        a_lower_bound.max(-largest_remainder).min(0),
+                        a_upper_bound.min(largest_remainder).max(0),
+                    )
+                };
+                let result = self.new_variable(lower_bound, upper_bound);
+                let name = if operator == "div" {
+                    "int_div"
+                } else {
+                    "int_mod"
+                };
+                self.post(name, vec![a, b, result.clone()]);
+                Ok(result)
+            }
+            ("abs", [a]) => {
+                let a = self.integer(a)?;
+                Ok(self.absolute_value(a))
+            }
+            ("dist", [a, b]) => {
+                let difference = self.difference(a, b)?;
+                let difference = self.linear_to_expression(difference);
+                Ok(self.absolute_value(difference))
+            }
+            ("min" | "max", _) if !arguments.is_empty() => {
+                let values = self.integers(arguments)?;
+                Ok(self.extremum(operator == "max", values))
+            }
+            ("if", [condition, a, b]) => {
+                let condition = self.boolean(condition)?;
+                let a = self.integer(a)?;
+                let b = self.integer(b)?;
+                match condition {
+                    Expression::Bool(true) => Ok(a),
+                    Expression::Bool(false) => Ok(b),
+                    condition => {
+                        let (lower_bound, upper_bound) =
+                            self.get_union_of_bounds(&[a.clone(), b.clone()]);
+                        let result = self.new_variable(lower_bound, upper_bound);
+                        let not_condition = self.negation(condition.clone());
+                        self.post("int_eq_imp", vec![result.clone(), a, condition]);
+                        self.post("int_eq_imp", vec![result.clone(), b, not_condition]);
+                        Ok(result)
+                    }
+                }
+            }
+            //boolean expressions are 0-1 variables, which can be used as integers directly
+            (
+                "lt" | "le" | "ge" | "gt" | "eq" | "ne" | "in" | "notin" | "not" | "and" | "or"
+                | "xor" | "iff" | "imp",
+                _,
+            ) => self.boolean(expression),
+            _ => Err(PumpkinError::unsupported_file_format(format!(
+                "The operator '{}' with {} arguments is not supported.",
+                operator,
+                arguments.len()
+            ))),
+        }
+    }
+
+    fn linear(&mut self, expression: &Intension) -> Result<LinearExpression, PumpkinError> {
+        let (operator, arguments) = match expression {
+            Intension::Call(operator, arguments) => (operator.as_str(), arguments.as_slice()),
+            _ => return Ok(LinearExpression::from_expression(self.integer(expression)?)),
+        };
+
+        match (operator, arguments) {
+            ("add", _) => {
+                let mut linear = LinearExpression::default();
+                for argument in arguments {
+                    let argument = self.linear(argument)?;
+                    linear.add_scaled(argument, 1);
+                }
+                Ok(linear)
+            }
+            ("sub", [a, b]) => self.difference(a, b),
+            ("neg", [a]) => {
+                let mut linear = LinearExpression::default();
+                linear.add_scaled(self.linear(a)?, -1);
+                Ok(linear)
+            }
+            //products with a constant factor are linear, other products are turned into variables
+            ("mul", _) if !arguments.is_empty() => {
+                let mut linear = self.linear(&arguments[0])?;
+                for argument in &arguments[1..] {
+                    let factor = self.linear(argument)?;
+                    linear = match (linear.terms.is_empty(), factor.terms.is_empty()) {
+                        (true, _) => {
+                            let mut product = LinearExpression::default();
+                            product.add_scaled(factor, linear.constant);
+                            product
+                        }
+                        (false, true) => {
+                            let mut product = LinearExpression::default();
+                            product.add_scaled(linear, factor.constant);
+                            product
+                        }
+                        (false, false) => {
+                            let a = self.linear_to_expression(linear);
+                            let b = self.linear_to_expression(factor);
+                            LinearExpression::from_expression(self.product(a, b))
+                        }
+                    };
+                }
+                Ok(linear)
+            }
+            _ => Ok(LinearExpression::from_expression(self.integer(expression)?)),
+        }
+    }
+
+    fn difference(
+        &mut self,
+        a: &Intension,
+        b: &Intension,
+    ) -> Result<LinearExpression, PumpkinError> {
+        let mut difference = self.linear(a)?;
+        let b = self.linear(b)?;
+        difference.add_scaled(b, -1);
+        Ok(difference)
+    }
+
+    fn integers(&mut self, arguments: &[Intension]) -> Result<Vec<Expression>, PumpkinError> {
+        arguments
+            .iter()
+            .map(|argument| self.integer(argument))
+            .collect()
+    }
+
+    fn booleans(&mut self, arguments: &[Intension]) -> Result<Vec<Expression>, PumpkinError> {
+        arguments
+            .iter()
+            .map(|argument| self.boolean(argument))
+            .collect()
+    }
+
+    //an integer is true if it is nonzero
+    fn integer_as_boolean(&mut self, x: Expression) -> Expression {
+        match x {
+            Expression::Int(value) => Expression::Bool(value != 0),
+            x => {
+                let (lower_bound, upper_bound) = self.get_bounds(&x);
+                if lower_bound >= 0 && upper_bound <= 1 {
+                    x
+                } else {
+                    self.reify_linear(LinearExpression::from_expression(x), "ne")
+                }
+            }
+        }
+    }
+}
+
+//methods for posting constraints and introducing variables
+impl Xcsp3Reader {
+    fn post(&mut self, name: &str, arguments: Vec<Expression>) {
+        self.constraints.push(ConstraintItem {
+            name: name.to_owned(),
+            arguments,
+            annotations: vec![],
+        });
+    }
+
+    fn post_clause(&mut self, positive: Vec<Expression>, negative: Vec<Expression>) {
+        self.post(
+            "bool_clause",
+            vec![Expression::Array(positive), Expression::Array(negative)],
+        );
+    }
+
+    //posts 'linear operator 0', optionally reified by the given boolean
+    fn post_linear(
+        &mut self,
+        linear: LinearExpression,
+        operator: &str,
+        reification: Option<Expression>,
+    ) -> Result<(), PumpkinError> {
+        let linear = linear.merged();
+        //every comparison is expressed as a FlatZinc constraint 'sum sign * terms' with the given right-hand side
+        let (sign, right_hand_side, name) = match operator {
+            "le" => (1, -linear.constant, "int_lin_le"),
+            "lt" => (1, -linear.constant - 1, "int_lin_le"),
+            "ge" => (-1, linear.constant, "int_lin_le"),
+            "gt" => (-1, linear.constant - 1, "int_lin_le"),
+            "eq" => (1, -linear.constant, "int_lin_eq"),
+            "ne" => (1, -linear.constant, "int_lin_ne"),
+            operator => return Err(unsupported_operator(operator)),
+        };
+
+        if linear.terms.is_empty() {
+            let holds = evaluate_comparison(operator, linear.constant);
+            match reification {
+                Some(r) => self.post("bool_eq", vec![r, Expression::Bool(holds)]),
+                None if !holds => self.post_clause(vec![], vec![]),
+                None => {}
+            }
+            return Ok(());
+        }
+
+        let (coefficients, variables): (Vec<Expression>, Vec<Expression>) = linear
+            .terms
+            .into_iter()
+            .map(|(coefficient, x)| (Expression::Int(sign * coefficient), x))
+            .unzip();
+        let mut arguments = vec![
+            Expression::Array(coefficients),
+            Expression::Array(variables),
+            Expression::Int(right_hand_side),
+        ];
+        match reification {
+            Some(r) => {
+                arguments.push(r);
+                self.post(&format!("{}_reif", name), arguments);
+            }
+            None => self.post(name, arguments),
+        }
+        Ok(())
+    }
+
+    fn reify_linear(&mut self, linear: LinearExpression, operator: &str) -> Expression {
+        let linear = linear.merged();
+        if linear.terms.is_empty() {
+            return Expression::Bool(evaluate_comparison(operator, linear.constant));
+        }
+        let r = self.new_bool();
+        //the operator is one of the comparisons, for which posting cannot fail
+        self.post_linear(linear, operator, Some(r.clone())).unwrap();
+        r
+    }
+
+    fn post_condition(
+        &mut self,
+        linear: LinearExpression,
+        condition: &Condition,
+    ) -> Result<(), PumpkinError> {
+        match (&condition.operand, condition.operator.as_str()) {
+            (Operand::Expression(operand), operator) => {
+                let mut difference = linear;
+                difference.add_scaled(LinearExpression::from_expression(operand.clone()), -1);
+                self.post_linear(difference, operator, None)
+            }
+            (Operand::Range(lower_bound, upper_bound), "in") => {
+                let mut below = linear.clone();
+                below.constant -= upper_bound;
+                self.post_linear(below, "le", None)?;
+                let mut above = linear;
+                above.constant -= lower_bound;
+                self.post_linear(above, "ge", None)
+            }
+            (Operand::Range(lower_bound, upper_bound), "notin") => {
+                let mut below = linear.clone();
+                below.constant -= lower_bound;
+                let below = self.reify_linear(below, "lt");
+                let mut above = linear;
+                above.constant -= upper_bound;
+                let above = self.reify_linear(above, "gt");
+                self.post_clause(vec![below, above], vec![]);
+                Ok(())
+            }
+            (Operand::Set(values), operator @ ("in" | "notin")) => {
+                let x = self.linear_to_expression(linear);
+                let set = Expression::Set(SetLiteral::Values(values.clone()));
+                if operator == "in" {
+                    self.post("set_in", vec![x, set]);
+                } else {
+                    self.post("set_in_reif", vec![x, set, Expression::Bool(false)]);
+                }
+                Ok(())
+            }
+            (_, operator) => Err(unsupported_operator(operator)),
+        }
+    }
+
+    //returns a variable or constant that is equal to the linear expression
+    fn linear_to_expression(&mut self, linear: LinearExpression) -> Expression {
+        let linear = linear.merged();
+        match linear.terms.as_slice() {
+            [] => return Expression::Int(linear.constant),
+            [(1, x)] if linear.constant == 0 => return x.clone(),
+            _ => {}
+        }
+
+        let (lower_bound, upper_bound) = self.get_linear_bounds(&linear);
+        let result = self.new_variable(lower_bound, upper_bound);
+        let mut definition = linear;
+        definition.terms.push((-1, result.clone()));
+        //posting an equality cannot fail
+        self.post_linear(definition, "eq", None).unwrap();
+        result
+    }
+
+    fn product(&mut self, a: Expression, b: Expression) -> Expression {
+        match (&a, &b) {
+            (Expression::Int(a), Expression::Int(b)) => return Expression::Int(a * b),
+            (Expression::Int(1), _) => return b,
+            (_, Expression::Int(1)) => return a,
+            _ => {}
+        }
+        let (a_lower_bound, a_upper_bound) = self.get_bounds(&a);
+        let (b_lower_bound, b_upper_bound) = self.get_bounds(&b);
+        let products = [
+            a_lower_bound * b_lower_bound,
+            a_lower_bound * b_upper_bound,
+            a_upper_bound * b_lower_bound,
+            a_upper_bound * b_upper_bound,
+        ];
+        let result = self.new_variable(
+            *products.iter().min().unwrap(),
+            *products.iter().max().unwrap(),
+        );
+        self.post("int_times", vec![a, b, result.clone()]);
+        result
+    }
+
+    fn absolute_value(&mut self, a: Expression) -> Expression {
+        let (lower_bound, upper_bound) = self.get_bounds(&a);
+        let (lower_bound, upper_bound) = if lower_bound >= 0 {
+            (lower_bound, upper_bound)
+        } else if upper_bound <= 0 {
+            (-upper_bound, -lower_bound)
+        } else {
+            (0, upper_bound.max(-lower_bound))
+        };
+        let result = self.new_variable(lower_bound, upper_bound);
+        self.post("int_abs", vec![a, result.clone()]);
+        result
+    }
+
+    fn extremum(&mut self, is_maximum: bool, values: Vec<Expression>) -> Expression {
+        if values.len() == 1 {
+            return values[0].clone();
+        }
+        let bounds: Vec<(i64, i64)> = values.iter().map(|x| self.get_bounds(x)).collect();
+        let (lower_bound, upper_bound) = if is_maximum {
+            (
+                bounds.iter().map(|bound| bound.0).max().unwrap(),
+                bounds.iter().map(|bound| bound.1).max().unwrap(),
+            )
+        } else {
+            (
+                bounds.iter().map(|bound| bound.0).min().unwrap(),
+                bounds.iter().map(|bound| bound.1).min().unwrap(),
+            )
+        };
+        let result = self.new_variable(lower_bound, upper_bound);
+        let name = if is_maximum {
+            "array_int_maximum"
+        } else {
+            "array_int_minimum"
+        };
+        self.post(name, vec![result.clone(), Expression::Array(values)]);
+        result
+    }
+
+    fn negation(&mut self, b: Expression) -> Expression {
+        match b {
+            Expression::Bool(value) => Expression::Bool(!value),
+            b => {
+                let not_b = self.new_bool();
+                self.post("bool_not", vec![b, not_b.clone()]);
+                not_b
+            }
+        }
+    }
+
+    fn conjunction(&mut self, values: Vec<Expression>) -> Expression {
+        if values.contains(&Expression::Bool(false)) {
+            return Expression::Bool(false);
+        }
+        let mut values: Vec<Expression> = values
+            .into_iter()
+            .filter(|value| *value != Expression::Bool(true))
+            .collect();
+        match values.len() {
+            0 => Expression::Bool(true),
+            1 => values.remove(0),
+            _ => {
+                let r = self.new_bool();
+                self.post("array_bool_and", vec![Expression::Array(values), r.clone()]);
+                r
+            }
+        }
+    }
+
+    fn disjunction(&mut self, values: Vec<Expression>) -> Expression {
+        if values.contains(&Expression::Bool(true)) {
+            return Expression::Bool(true);
+        }
+        let mut values: Vec<Expression> = values
+            .into_iter()
+            .filter(|value| *value != Expression::Bool(false))
+            .collect();
+        match values.len() {
+            0 => Expression::Bool(false),
+            1 => values.remove(0),
+            _ => {
+                let r = self.new_bool();
+                self.post("array_bool_or", vec![Expression::Array(values), r.clone()]);
+                r
+            }
+        }
+    }
+
+    //returns a boolean that is true exactly if x takes the value
+    fn get_equality_variable(&mut self, x: &Expression, value: i64) -> Expression {
+        let name = match x {
+            Expression::Identifier(name) => name.clone(),
+            x => return Expression::Bool(*x == Expression::Int(value)),
+        };
+        if let Some(equality) = self.equality_variables.get(&(name.clone(), value)) {
+            return equality.clone();
+        }
+
+        let equality = self.new_bool();
+        self.post(
+            "int_eq_reif",
+            vec![x.clone(), Expression::Int(value), equality.clone()],
+        );
+        self.equality_variables
+            .insert((name, value), equality.clone());
+        equality
+    }
+
+    fn declare_variable(&mut self, name: String, domain: SetLiteral, is_output: bool) {
+        let annotation_name = if is_output {
+            "output_var"
+        } else {
+            "var_is_introduced"
+        };
+        self.variables.push(VariableDeclaration {
+            name: name.clone(),
+            variable_type: VariableType::Int(Some(domain.clone())),
+            is_array: false,
+            assignment: None,
+            annotations: vec![Annotation {
+                name: annotation_name.to_owned(),
+                arguments: vec![],
+            }],
+        });
+        self.domains.insert(name, domain);
+    }
+
+    //introduced variables start with an underscore, which is not allowed for identifiers in XCSP3, so they never clash with variables of the instance
+    fn new_variable(&mut self, lower_bound: i64, upper_bound: i64) -> Expression {
+        if lower_bound == upper_bound {
+            return Expression::Int(lower_bound);
+        }
+        self.num_introduced_variables += 1;
+        let name = format!("_introduced_{}", self.num_introduced_variables);
+        self.declare_variable(
+            name.clone(),
+            SetLiteral::Range(lower_bound, upper_bound),
+            false,
+        );
+        Expression::Identifier(name)
+    }
+
+    fn new_bool(&mut self) -> Expression {
+        self.new_variable(0, 1)
+    }
+
+    fn get_variable(&self, name: &str) -> Result<Expression, PumpkinError> {
+        if self.domains.contains_key(name) {
+            Ok(Expression::Identifier(name.to_owned()))
+        } else {
+            Err(unknown_variable(name))
+        }
+    }
+
+    fn get_bounds(&self, x: &Expression) -> (i64, i64) {
+        match x {
+            Expression::Int(value) => (*value, *value),
+            Expression::Bool(value) => (*value as i64, *value as i64),
+            Expression::Identifier(name) => self.domains[name].bounds().unwrap_or((0, 0)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_union_of_bounds(&self, values: &[Expression]) -> (i64, i64) {
+        let bounds: Vec<(i64, i64)> = values.iter().map(|x| self.get_bounds(x)).collect();
+        (
+            bounds.iter().map(|bound| bound.0).min().unwrap_or(0),
+            bounds.iter().map(|bound| bound.1).max().unwrap_or(0),
+        )
+    }
+
+    fn get_linear_bounds(&self, linear: &LinearExpression) -> (i64, i64) {
+        let mut lower_bound = linear.constant;
+        let mut upper_bound = linear.constant;
+        for (coefficient, x) in &linear.terms {
+            let (x_lower_bound, x_upper_bound) = self.get_bounds(x);
+            if *coefficient > 0 {
+                lower_bound += coefficient * x_lower_bound;
+                upper_bound += coefficient * x_upper_bound;
+            } else {
+                lower_bound += coefficient * x_upper_bound;
+                upper_bound += coefficient * x_lower_bound;
+            }
+        }
+        (lower_bound, upper_bound)
+    }
+
+    fn domain_values(&self, x: &Expression) -> Vec<i64> {
+        match x {
+            Expression::Int(value) => vec![*value],
+            Expression::Identifier(name) => match &self.domains[name] {
+                SetLiteral::Range(lower_bound, upper_bound) => {
+                    (*lower_bound..=*upper_bound).collect()
+                }
+                SetLiteral::Values(values) => values.clone(),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IndexPattern {
+    All,
+    Range(usize, usize),
+}
+
+//splits e.g. 'x[1..3][]' into the name 'x' and the index patterns
+fn parse_array_pattern(token: &str) -> Result<(&str, Vec<IndexPattern>), PumpkinError> {
+    let invalid = || PumpkinError::invalid_model(format!("Invalid array reference '{}'.", token));
+    let (name, indices) = token.split_at(token.find('[').ok_or_else(invalid)?);
+    let indices = indices
+        .strip_prefix('[')
+        .and_then(|indices| indices.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+
+    let patterns = indices
+        .split("][")
+        .map(|index| {
+            if index.is_empty() {
+                return Ok(IndexPattern::All);
+            }
+            let (first, last) = index.split_once("..").unwrap_or((index, index));
+            match (first.parse(), last.parse()) {
+                (Ok(first), Ok(last)) => Ok(IndexPattern::Range(first, last)),
+                _ => Err(invalid()),
+            }
+        })
+        .collect::<Result<Vec<IndexPattern>, PumpkinError>>()?;
+    Ok((name, patterns))
+}
+
+//enumerates the indices that match the patterns in row-major order
+fn expand_indices(
+    dimensions: &[usize],
+    patterns: &[IndexPattern],
+) -> Result<Vec<Vec<usize>>, PumpkinError> {
+    if dimensions.len() != patterns.len() {
+        return Err(PumpkinError::invalid_model(format!(
+            "Expected {} indices for an array, found {}.",
+            dimensions.len(),
+            patterns.len()
+        )));
+    }
+
+    let mut all_indices = vec![vec![]];
+    for (size, pattern) in dimensions.iter().zip(patterns) {
+        let (first, last) = match pattern {
+            IndexPattern::All => (0, size.saturating_sub(1)),
+            IndexPattern::Range(first, last) => (*first, *last),
+        };
+        if last >= *size {
+            return Err(PumpkinError::invalid_model(format!(
+                "The index {} is out of bounds for an array dimension of size {}.",
+                last, size
+            )));
+        }
+        all_indices = all_indices
+            .into_iter()
+            .flat_map(|indices: Vec<usize>| {
+                (first..=last).map(move |index| {
+                    let mut extended_indices = indices.clone();
+                    extended_indices.push(index);
+                    extended_indices
+                })
+            })
+            .collect();
+    }
+    Ok(all_indices)
+}
+
+fn get_element_name(array: &str, indices: &[usize]) -> String {
+    let indices: String = indices.iter().map(|index| format!("[{}]", index)).collect();
+    format!("{}{}", array, indices)
+}
+
+//parses the size of an array, e.g., '[3][4]'
+fn parse_size(size: &str) -> Result<Vec<usize>, PumpkinError> {
+    size.trim()
+        .strip_prefix('[')
+        .and_then(|size| size.strip_suffix(']'))
+        .ok_or_else(|| PumpkinError::invalid_model(format!("Invalid array size '{}'.", size)))?
+        .split("][")
+        .map(|dimension| {
+            dimension
+                .trim()
+                .parse()
+                .map_err(|_| PumpkinError::invalid_model(format!("Invalid array size '{}'.", size)))
+        })
+        .collect()
+}
+
+//parses a domain given as values and ranges, e.g., '1 3..5 8'
+fn parse_domain(text: &str) -> Result<SetLiteral, PumpkinError> {
+    let mut ranges = vec![];
+    for token in text.split_whitespace() {
+        let (first, last) = token.split_once("..").unwrap_or((token, token));
+        if first.contains("infinity") || last.contains("infinity") {
+            return Err(PumpkinError::unsupported_file_format(
+                "Infinite domains are not supported.",
+            ));
+        }
+        ranges.push((parse_integer(first)?, parse_integer(last)?));
+    }
+
+    match ranges.as_slice() {
+        [(lower_bound, upper_bound)] => Ok(SetLiteral::Range(*lower_bound, *upper_bound)),
+        _ => {
+            let mut values: Vec<i64> = ranges
+                .into_iter()
+                .flat_map(|(first, last)| first..=last)
+                .collect();
+            values.sort_unstable();
+            values.dedup();
+            Ok(SetLiteral::Values(values))
+        }
+    }
+}
+
+//parses tuples such as '(1,2,*)(3,4,5)', where '*' stands for any value
+fn parse_tuples(text: &str, arity: usize) -> Result<Vec<Vec<Option<i64>>>, PumpkinError> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut tuples = vec![];
+    for tuple in text.split(')').filter(|tuple| !tuple.is_empty()) {
+        let tuple = tuple
+            .strip_prefix('(')
+            .ok_or_else(|| PumpkinError::invalid_model(format!("Invalid tuples '{}'.", text)))?;
+        let values = tuple
+            .split(',')
+            .map(|value| match value {
+                "*" => Ok(None),
+                value => parse_integer(value).map(Some),
+            })
+            .collect::<Result<Vec<Option<i64>>, PumpkinError>>()?;
+        if values.len() != arity {
+            return Err(PumpkinError::invalid_model(format!(
+                "The tuple '({})' does not have {} values.",
+                tuple, arity
+            )));
+        }
+        tuples.push(values);
+    }
+    Ok(tuples)
+}
+
+//parses a condition such as '(le,10)', '(eq,x)', '(in,1..5)', or '(notin,{1,3})'
+fn parse_condition(reader: &Xcsp3Reader, text: &str) -> Result<Condition, PumpkinError> {
+    let invalid = || PumpkinError::invalid_model(format!("Invalid condition '{}'.", text.trim()));
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let (operator, operand) = text
+        .strip_prefix('(')
+        .and_then(|text| text.strip_suffix(')'))
+        .and_then(|text| text.split_once(','))
+        .ok_or_else(invalid)?;
+
+    let operand = if let Some(values) = operand
+        .strip_prefix('{')
+        .and_then(|operand| operand.strip_suffix('}'))
+    {
+        Operand::Set(
+            values
+                .split(',')
+                .filter(|value| !value.is_empty())
+                .map(parse_integer)
+                .collect::<Result<Vec<i64>, PumpkinError>>()?,
+        )
+    } else if let Some((first, last)) = operand.split_once("..") {
+        Operand::Range(parse_integer(first)?, parse_integer(last)?)
+    } else {
+        let mut operand = reader.expand_list(operand)?;
+        if operand.len() != 1 {
+            return Err(invalid());
+        }
+        Operand::Expression(operand.remove(0))
+    };
+
+    Ok(Condition {
+        operator: operator.to_owned(),
+        operand,
+    })
+}
+
+fn parse_integer(text: &str) -> Result<i64, PumpkinError> {
+    text.trim()
+        .parse()
+        .map_err(|_| PumpkinError::invalid_model(format!("Expected an integer, found '{}'.", text)))
+}
+
+fn get_id(element: &XmlElement) -> Result<&str, PumpkinError> {
+    element.get_attribute("id").ok_or_else(|| {
+        PumpkinError::invalid_model(format!("The element '{}' has no id.", element.name))
+    })
+}
+
+fn get_child_text<'a>(element: &'a XmlElement, name: &str) -> Result<&'a str, PumpkinError> {
+    element
+        .get_child(name)
+        .map(|child| child.text.as_str())
+        .ok_or_else(|| PumpkinError::invalid_model(format!("Expected the element '{}'.", name)))
+}
+
+fn get_set(expression: &Intension) -> Result<Vec<i64>, PumpkinError> {
+    match expression {
+        Intension::Call(name, values) if name == "set" => values
+            .iter()
+            .map(|value| match value {
+                Intension::Int(value) => Ok(*value),
+                _ => Err(PumpkinError::unsupported_file_format(
+                    "Sets of variables are not supported.",
+                )),
+            })
+            .collect(),
+        _ => Err(PumpkinError::invalid_model(format!(
+            "Expected a set, found {:?}.",
+            expression
+        ))),
+    }
+}
+
+fn get_negated_operator(operator: &str) -> Option<&'static str> {
+    match operator {
+        "lt" => Some("ge"),
+        "le" => Some("gt"),
+        "ge" => Some("lt"),
+        "gt" => Some("le"),
+        "eq" => Some("ne"),
+        "ne" => Some("eq"),
+        _ => None,
+    }
+}
+
+//evaluates 'value operator 0'
+fn evaluate_comparison(operator: &str, value: i64) -> bool {
+    match operator {
+        "lt" => value < 0,
+        "le" => value <= 0,
+        "ge" => value >= 0,
+        "gt" => value > 0,
+        "eq" => value == 0,
+        _ => value != 0,
+    }
+}
+
+//the number of parameters %0, %1, ... used by the template of a group
+fn get_num_numbered_parameters(template: &XmlElement) -> usize {
+    let mut num_parameters = 0;
+    let mut texts = vec![template];
+    while let Some(element) = texts.pop() {
+        for (i, _) in element.text.match_indices('%') {
+            let digits: String = element.text[i + 1..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            if let Ok(index) = digits.parse::<usize>() {
+                num_parameters = num_parameters.max(index + 1);
+            }
+        }
+        texts.extend(&element.children);
+    }
+    num_parameters
+}
+
+//replaces the parameters %i of the template by the arguments, where %... stands for the arguments after the numbered parameters
+fn instantiate_template(
+    template: &XmlElement,
+    arguments: &[&str],
+    num_numbered_parameters: usize,
+) -> XmlElement {
+    let mut text = String::new();
+    let mut rest = template.text.as_str();
+    while let Some(i) = rest.find('%') {
+        text.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        if let Some(after) = rest.strip_prefix("...") {
+            let remaining = arguments.get(num_numbered_parameters..).unwrap_or(&[]);
+            text.push_str(&remaining.join(" "));
+            rest = after;
+        } else {
+            let num_digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+            let index: usize = rest[..num_digits].parse().unwrap_or(usize::MAX);
+            text.push_str(arguments.get(index).copied().unwrap_or(""));
+            rest = &rest[num_digits..];
+        }
+    }
+    text.push_str(rest);
+
+    XmlElement {
+        name: template.name.clone(),
+        attributes: template.attributes.clone(),
+        children: template
+            .children
+            .iter()
+            .map(|child| instantiate_template(child, arguments, num_numbered_parameters))
+            .collect(),
+        text,
+    }
+}
+
+fn unknown_variable(name: &str) -> PumpkinError {
+    PumpkinError::invalid_model(format!("Unknown variable '{}'.", name))
+}
+
+fn unsupported_operator(operator: &str) -> PumpkinError {
+    PumpkinError::unsupported_file_format(format!("The operator '{}' is not supported.", operator))
+}
@@ -0,0 +1,242 @@
+use crate::basic_types::PumpkinError;
+
+//an element of an XML document
+//  the text of an element is the concatenation of the text between its children, which suffices for XCSP3 where elements either contain text or children
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct XmlElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlElement>,
+    pub text: String,
+}
+
+impl XmlElement {
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn get_child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn get_children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+//parses the XML document and returns its root element
+//  this is a small parser for the subset of XML used by XCSP3: the prolog, comments, and CDATA sections are supported, but not DTDs or namespaces
+pub(crate) fn parse_xml(document: &str) -> Result<XmlElement, PumpkinError> {
+    let mut parser = XmlParser {
+        characters: document.chars().collect(),
+        position: 0,
+    };
+    parser.skip_misc()?;
+    let root = parser.parse_element()?;
+    parser.skip_misc()?;
+    if parser.position < parser.characters.len() {
+        return Err(parser.error("Unexpected content after the root element."));
+    }
+    Ok(root)
+}
+
+struct XmlParser {
+    characters: Vec<char>,
+    position: usize,
+}
+
+impl XmlParser {
+    //creates an error at the line and column of the current position, where columns are counted in characters, starting at one
+    fn error(&self, message: impl Into<String>) -> PumpkinError {
+        let preceding = &self.characters[..self.position.min(self.characters.len())];
+        let line_start = preceding
+            .iter()
+            .rposition(|character| *character == '\n')
+            .map_or(0, |newline| newline + 1);
+        let line = preceding
+            .iter()
+            .filter(|character| **character == '\n')
+            .count()
+            + 1;
+        PumpkinError::parse_error(line, preceding.len() - line_start + 1, message)
+    }
+
+    fn starts_with(&self, prefix: &str) -> bool {
+        prefix
+            .chars()
+            .enumerate()
+            .all(|(i, character)| self.characters.get(self.position + i) == Some(&character))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .characters
+            .get(self.position)
+            .is_some_and(|character| character.is_whitespace())
+        {
+            self.position += 1;
+        }
+    }
+
+    //skips until after the given terminator
+    fn skip_past(&mut self, terminator: &str) -> Result<(), PumpkinError> {
+        while self.position < self.characters.len() {
+            if self.starts_with(terminator) {
+                self.position += terminator.chars().count();
+                return Ok(());
+            }
+            self.position += 1;
+        }
+        Err(self.error(format!(
+            "Unexpected end of the document, expected '{}'.",
+            terminator
+        )))
+    }
+
+    //skips whitespace, comments, and processing instructions such as the XML declaration
+    fn skip_misc(&mut self) -> Result<(), PumpkinError> {
+        loop {
+            self.skip_whitespace();
+            if self.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.starts_with("<!DOCTYPE") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, PumpkinError> {
+        let start = self.position;
+        while self.characters.get(self.position).is_some_and(|character| {
+            character.is_alphanumeric() || matches!(character, '_' | '-' | '.' | ':')
+        }) {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(self.error("Expected a name."));
+        }
+        Ok(self.characters[start..self.position].iter().collect())
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), PumpkinError> {
+        if self.starts_with(expected) {
+            self.position += expected.chars().count();
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected '{}'.", expected)))
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<XmlElement, PumpkinError> {
+        self.expect("<")?;
+        let name = self.parse_name()?;
+        let mut element = XmlElement {
+            name,
+            attributes: vec![],
+            children: vec![],
+            text: String::new(),
+        };
+
+        loop {
+            self.skip_whitespace();
+            if self.starts_with("/>") {
+                self.position += 2;
+                return Ok(element);
+            } else if self.starts_with(">") {
+                self.position += 1;
+                break;
+            }
+
+            let attribute = self.parse_name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = match self.characters.get(self.position) {
+                Some(quote @ ('"' | '\'')) => *quote,
+                _ => {
+                    return Err(self.error(format!(
+                        "Expected a quoted value for the attribute '{}'.",
+                        attribute
+                    )))
+                }
+            };
+            self.position += 1;
+            let start = self.position;
+            while self
+                .characters
+                .get(self.position)
+                .is_some_and(|character| *character != quote)
+            {
+                self.position += 1;
+            }
+            let value: String = self.characters[start..self.position].iter().collect();
+            self.expect(&quote.to_string())?;
+            element
+                .attributes
+                .push((attribute, decode_entities(&value)));
+        }
+
+        //the content of the element up to its closing tag
+        loop {
+            if self.position >= self.characters.len() {
+                return Err(self.error(format!(
+                    "Unexpected end of the document, the element '{}' is not closed.",
+                    element.name
+                )));
+            } else if self.starts_with("</") {
+                self.position += 2;
+                let closing_name = self.parse_name()?;
+                if closing_name != element.name {
+                    return Err(self.error(format!(
+                        "The element '{}' is closed by '{}'.",
+                        element.name, closing_name
+                    )));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if self.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let start = self.position;
+                self.skip_past("]]>")?;
+                let text: String = self.characters[start..self.position - 3].iter().collect();
+                element.text.push_str(&text);
+            } else if self.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.starts_with("<") {
+                let child = self.parse_element()?;
+                element.children.push(child);
+                //children separate text, e.g., the tokens before and after a child are not glued together
+                element.text.push(' ');
+            } else {
+                let start = self.position;
+                while self
+                    .characters
+                    .get(self.position)
+                    .is_some_and(|character| *character != '<')
+                {
+                    self.position += 1;
+                }
+                let text: String = self.characters[start..self.position].iter().collect();
+                element.text.push_str(&decode_entities(&text));
+            }
+        }
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
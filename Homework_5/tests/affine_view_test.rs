@@ -0,0 +1,348 @@
+use std::sync::{Arc, Mutex};
+
+use pumpkin::{
+    basic_types::{
+        AffineView, EnqueueStatus, IntegerVariable, Predicate, PropagationStatusCP,
+        PropagatorIdentifier, PropositionalConjunction,
+    },
+    engine::{
+        AssignmentsInteger, CPEngineDataStructures, ConstraintSatisfactionSolver, DomainManager,
+        PropagatorQueue, SolverOptions, WatchListCP,
+    },
+    propagators::{ConstraintProgrammingPropagator, MaximumPropagator},
+};
+
+fn lower_bound(integer_variable: IntegerVariable, lower_bound: i32) -> Predicate {
+    Predicate::LowerBound {
+        integer_variable,
+        lower_bound,
+    }
+}
+
+fn upper_bound(integer_variable: IntegerVariable, upper_bound: i32) -> Predicate {
+    Predicate::UpperBound {
+        integer_variable,
+        upper_bound,
+    }
+}
+
+#[test]
+fn view_predicates_round_towards_the_domain_of_the_variable() {
+    let x = IntegerVariable { id: 0 };
+
+    //2x + 1 >= 4  <=>  x >= 2, and 2x + 1 <= 4  <=>  x <= 1
+    let view = AffineView::new(x, 2, 1);
+    assert_eq!(view.lower_bound_predicate(4), lower_bound(x, 2));
+    assert_eq!(view.upper_bound_predicate(4), upper_bound(x, 1));
+
+    //-2x + 1 >= -4  <=>  x <= 2, and -2x + 1 <= -4  <=>  x >= 3
+    let view = AffineView::new(x, -2, 1);
+    assert_eq!(view.lower_bound_predicate(-4), upper_bound(x, 2));
+    assert_eq!(view.upper_bound_predicate(-4), lower_bound(x, 3));
+
+    //-x - 3 >= -5  <=>  x <= 2
+    let view = (-AffineView::from(x)).offset_by(-3);
+    assert_eq!(view.lower_bound_predicate(-5), upper_bound(x, 2));
+    assert_eq!(view.transform_value(2), -5);
+    assert_eq!(view.inverse_value(-5), Some(2));
+}
+
+//the view computations are done with i64, so predicates over views with large offsets do not overflow
+#[test]
+fn view_predicates_do_not_overflow_for_large_offsets() {
+    let x = IntegerVariable { id: 0 };
+    let view = AffineView::new(x, -1, i32::MAX);
+
+    //-x + i32::MAX >= i32::MIN  <=>  x <= 2^32 - 1, which is clamped to i32::MAX
+    assert_eq!(
+        view.lower_bound_predicate(i32::MIN),
+        upper_bound(x, i32::MAX)
+    );
+    assert_eq!(view.inverse_value(i32::MIN), None);
+    assert_eq!(view.transform_value(10), i32::MAX - 10);
+    assert!(view.fits_domain(0, 10));
+    assert!(!AffineView::new(x, 1, i32::MAX).fits_domain(0, 10));
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn scaling_a_view_beyond_i32_panics() {
+    AffineView::new(IntegerVariable { id: 0 }, 1 << 20, 0).scaled(1 << 20);
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn offsetting_a_view_beyond_i32_panics() {
+    AffineView::new(IntegerVariable { id: 0 }, 1, i32::MAX).offset_by(1);
+}
+
+#[test]
+#[should_panic(expected = "do not fit in an i32")]
+fn propagators_on_views_that_do_not_fit_in_i32_are_rejected() {
+    let mut solver = ConstraintSatisfactionSolver::new(&SolverOptions::default());
+    let x = solver.create_new_integer_variable(0, 10).unwrap();
+    solver.add_propagator(Box::new(MaximumPropagator::new(
+        vec![AffineView::new(x, 1 << 30, 0)],
+        x,
+    )));
+}
+
+#[test]
+fn view_equality_predicates_only_exist_for_attainable_values() {
+    let x = IntegerVariable { id: 0 };
+    let view = AffineView::new(x, 3, 0);
+
+    assert_eq!(
+        view.equality_predicate(6),
+        Some(Predicate::Equal {
+            integer_variable: x,
+            equality_constant: 2
+        })
+    );
+    assert_eq!(view.equality_predicate(7), None);
+    assert_eq!(view.not_equal_predicate(7), None);
+}
+
+#[test]
+fn domain_manager_reports_and_tightens_bounds_of_views() {
+    let mut assignments_integer = AssignmentsInteger::new();
+    let x = assignments_integer.grow(2, 10);
+    let mut domains = DomainManager::new(0, &mut assignments_integer);
+
+    let minus_x = -AffineView::from(x);
+    assert_eq!(domains.get_lower_bound(minus_x), -10);
+    assert_eq!(domains.get_upper_bound(minus_x), -2);
+    assert_eq!(
+        domains.get_lower_bound_predicate(minus_x),
+        upper_bound(x, 10)
+    );
+    assert!(domains.is_value_in_domain(minus_x, -5));
+    assert!(!domains.is_value_in_domain(minus_x, 5));
+
+    //-x >= -7  <=>  x <= 7
+    domains.tighten_lower_bound(minus_x, -7);
+    assert_eq!(domains.get_upper_bound(x), 7);
+
+    //2x + 1 <= 10  <=>  x <= 4
+    domains.tighten_upper_bound(AffineView::new(x, 2, 1), 10);
+    assert_eq!(domains.get_upper_bound(x), 4);
+    assert_eq!(domains.get_lower_bound(minus_x), -4);
+}
+
+//records the lower bound events of the views it watches, together with the local ids of the events
+struct RecordingPropagator {
+    views: Vec<AffineView>,
+    lower_bound_events: Arc<Mutex<Vec<(AffineView, i32, i32)>>>,
+    local_ids: Arc<Mutex<Vec<usize>>>,
+}
+
+impl ConstraintProgrammingPropagator for RecordingPropagator {
+    fn propagate(&mut self, _domains: &mut DomainManager) -> PropagationStatusCP {
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, _predicate: Predicate) -> PropositionalConjunction {
+        PropositionalConjunction::new()
+    }
+
+    fn priority(&self) -> u32 {
+        0
+    }
+
+    fn name(&self) -> &str {
+        "recording propagator"
+    }
+
+    fn get_views_to_watch_for_lower_bound_changes(&self) -> Vec<AffineView> {
+        self.views.clone()
+    }
+
+    fn notify_lower_bound_view_change(
+        &mut self,
+        view: AffineView,
+        old_lower_bound: i32,
+        new_lower_bound: i32,
+        _domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.lower_bound_events
+            .lock()
+            .unwrap()
+            .push((view, old_lower_bound, new_lower_bound));
+        EnqueueStatus::ShouldEnqueue
+    }
+
+    fn notify_lower_bound_change_with_local_id(
+        &mut self,
+        local_id: usize,
+        view: AffineView,
+        old_lower_bound: i32,
+        new_lower_bound: i32,
+        domains: &DomainManager,
+    ) -> EnqueueStatus {
+        self.local_ids.lock().unwrap().push(local_id);
+        self.notify_lower_bound_view_change(view, old_lower_bound, new_lower_bound, domains)
+    }
+
+    fn initialise_at_root(&mut self, _domains: &mut DomainManager) -> PropagationStatusCP {
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    fn debug_propagate_from_scratch(&self, _domains: &mut DomainManager) -> PropagationStatusCP {
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+#[test]
+fn watch_list_translates_events_of_the_variable_into_events_of_the_view() {
+    let mut assignments_integer = AssignmentsInteger::new();
+    let x = assignments_integer.grow(0, 10);
+    let mut watch_list = WatchListCP::new();
+    watch_list.grow();
+
+    let minus_x = -AffineView::from(x);
+    let shifted_x = AffineView::from(x).offset_by(5);
+    let lower_bound_events = Arc::new(Mutex::new(vec![]));
+    let mut propagators: Vec<Box<dyn ConstraintProgrammingPropagator>> =
+        vec![Box::new(RecordingPropagator {
+            views: vec![minus_x, shifted_x],
+            lower_bound_events: lower_bound_events.clone(),
+            local_ids: Arc::new(Mutex::new(vec![])),
+        })];
+    watch_list.add_watches_for_propagator(propagators[0].as_ref(), PropagatorIdentifier { id: 0 });
+    let mut propagator_queue = PropagatorQueue::new(5);
+
+    //the upper bound of x decreasing from 10 to 7 means the lower bound of -x increased from -10 to -7
+    watch_list.notify_upper_bound_subscribed_propagators(
+        x,
+        10,
+        7,
+        &mut propagators,
+        &mut propagator_queue,
+        &mut assignments_integer,
+    );
+    assert_eq!(
+        *lower_bound_events.lock().unwrap(),
+        vec![(minus_x, -10, -7)]
+    );
+    assert!(!propagator_queue.is_empty());
+
+    //the lower bound of x increasing from 0 to 3 means the lower bound of x + 5 increased from 5 to 8
+    watch_list.notify_lower_bound_subscribed_propagators(
+        x,
+        0,
+        3,
+        &mut propagators,
+        &mut propagator_queue,
+        &mut assignments_integer,
+    );
+    assert_eq!(
+        *lower_bound_events.lock().unwrap(),
+        vec![(minus_x, -10, -7), (shifted_x, 5, 8)]
+    );
+}
+
+#[test]
+fn domain_events_are_merged_per_variable_before_notifying() {
+    let mut cp_data_structures = CPEngineDataStructures::new(&SolverOptions::default());
+    let x = cp_data_structures.assignments_integer.grow(0, 10);
+    cp_data_structures.watch_list_cp.grow();
+
+    let lower_bound_events = Arc::new(Mutex::new(vec![]));
+    let mut propagators: Vec<Box<dyn ConstraintProgrammingPropagator>> =
+        vec![Box::new(RecordingPropagator {
+            views: vec![AffineView::from(x)],
+            lower_bound_events: lower_bound_events.clone(),
+            local_ids: Arc::new(Mutex::new(vec![])),
+        })];
+    cp_data_structures
+        .watch_list_cp
+        .add_watches_for_propagator(propagators[0].as_ref(), PropagatorIdentifier { id: 0 });
+
+    //the lower bound is tightened twice without notification, as a propagator does through the domain manager
+    let mut domains = DomainManager::new(0, &mut cp_data_structures.assignments_integer);
+    domains.tighten_lower_bound(x, 2);
+    domains.tighten_lower_bound(x, 5);
+    cp_data_structures.notify_propagators_of_trail_entries(0, &mut propagators);
+
+    assert_eq!(
+        *lower_bound_events.lock().unwrap(),
+        vec![(AffineView::from(x), 0, 5)]
+    );
+    assert_eq!(
+        cp_data_structures.assignments_integer.num_trail_entries(),
+        2
+    );
+    assert!(!cp_data_structures.propagator_queue.is_empty());
+}
+
+//the local id of a watched view is its position in the list of views the propagator watches for the event
+#[test]
+fn watch_list_notifies_with_the_local_ids_of_the_views() {
+    let mut assignments_integer = AssignmentsInteger::new();
+    let x = assignments_integer.grow(0, 10);
+    let y = assignments_integer.grow(0, 10);
+    let mut watch_list = WatchListCP::new();
+    watch_list.grow();
+    watch_list.grow();
+
+    let local_ids = Arc::new(Mutex::new(vec![]));
+    let mut propagators: Vec<Box<dyn ConstraintProgrammingPropagator>> =
+        vec![Box::new(RecordingPropagator {
+            views: vec![AffineView::from(x), AffineView::from(y)],
+            lower_bound_events: Arc::new(Mutex::new(vec![])),
+            local_ids: local_ids.clone(),
+        })];
+    watch_list.add_watches_for_propagator(propagators[0].as_ref(), PropagatorIdentifier { id: 0 });
+    let mut propagator_queue = PropagatorQueue::new(5);
+
+    for (integer_variable, new_lower_bound) in [(y, 2), (x, 1), (y, 3)] {
+        watch_list.notify_lower_bound_subscribed_propagators(
+            integer_variable,
+            new_lower_bound - 1,
+            new_lower_bound,
+            &mut propagators,
+            &mut propagator_queue,
+            &mut assignments_integer,
+        );
+    }
+    assert_eq!(*local_ids.lock().unwrap(), vec![1, 0, 1]);
+}
+
+//a view that is listed twice for the same event is watched once, with the local id of its first occurrence
+#[test]
+fn watch_list_notifies_once_for_views_listed_more_than_once() {
+    let mut assignments_integer = AssignmentsInteger::new();
+    let x = assignments_integer.grow(0, 10);
+    let y = assignments_integer.grow(0, 10);
+    let mut watch_list = WatchListCP::new();
+    watch_list.grow();
+    watch_list.grow();
+
+    let local_ids = Arc::new(Mutex::new(vec![]));
+    let mut propagators: Vec<Box<dyn ConstraintProgrammingPropagator>> =
+        vec![Box::new(RecordingPropagator {
+            views: vec![
+                AffineView::from(x),
+                AffineView::from(x),
+                AffineView::from(y),
+            ],
+            lower_bound_events: Arc::new(Mutex::new(vec![])),
+            local_ids: local_ids.clone(),
+        })];
+    watch_list.add_watches_for_propagator(propagators[0].as_ref(), PropagatorIdentifier { id: 0 });
+    let mut propagator_queue = PropagatorQueue::new(5);
+
+    for integer_variable in [x, y] {
+        watch_list.notify_lower_bound_subscribed_propagators(
+            integer_variable,
+            0,
+            1,
+            &mut propagators,
+            &mut propagator_queue,
+            &mut assignments_integer,
+        );
+    }
+    assert_eq!(*local_ids.lock().unwrap(), vec![0, 2]);
+}
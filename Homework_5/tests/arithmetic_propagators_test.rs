@@ -0,0 +1,496 @@
+use pumpkin::{
+    basic_types::{AffineView, IntegerVariable, Predicate, PropagationStatusCP},
+    engine::{AssignmentsInteger, DomainManager, DomainOperationOutcome},
+    propagators::{
+        AbsoluteValuePropagator, AllDifferentPropagator, ConstraintProgrammingPropagator,
+        CumulativePropagator, DivisionPropagator, MaximumPropagator, MinimumPropagator,
+        ModuloPropagator, TimesPropagator,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const NUM_RANDOM_INSTANCES: usize = 300;
+
+fn evaluate_predicate(predicate: &Predicate, values: &[i32]) -> bool {
+    match *predicate {
+        Predicate::LowerBound {
+            integer_variable,
+            lower_bound,
+        } => values[integer_variable.id as usize] >= lower_bound,
+        Predicate::UpperBound {
+            integer_variable,
+            upper_bound,
+        } => values[integer_variable.id as usize] <= upper_bound,
+        Predicate::NotEqual {
+            integer_variable,
+            not_equal_constant,
+        } => values[integer_variable.id as usize] != not_equal_constant,
+        Predicate::Equal {
+            integer_variable,
+            equality_constant,
+        } => values[integer_variable.id as usize] == equality_constant,
+    }
+}
+
+fn enumerate_tuples(domains: &[(i32, i32)]) -> Vec<Vec<i32>> {
+    let mut tuples = vec![vec![]];
+    for &(lower_bound, upper_bound) in domains {
+        tuples = tuples
+            .into_iter()
+            .flat_map(|tuple| {
+                (lower_bound..=upper_bound).map(move |value| {
+                    let mut extended_tuple = tuple.clone();
+                    extended_tuple.push(value);
+                    extended_tuple
+                })
+            })
+            .collect();
+    }
+    tuples
+}
+
+fn create_assignment(domains: &[(i32, i32)]) -> (AssignmentsInteger, Vec<IntegerVariable>) {
+    let mut assignment = AssignmentsInteger::new();
+    let variables = domains
+        .iter()
+        .map(|&(lower_bound, upper_bound)| assignment.grow(lower_bound, upper_bound))
+        .collect();
+    (assignment, variables)
+}
+
+//propagates the constraint on the given domains and checks that:
+//  solutions of the constraint are never removed,
+//  a conflict is only reported if there are no solutions,
+//  the constraint is satisfied once all variables are fixed,
+//  each propagation and conflict is implied by its explanation, both semantically and by propagating from scratch
+fn check_propagator<P: ConstraintProgrammingPropagator>(
+    domains: &[(i32, i32)],
+    create_propagator: &impl Fn(&[IntegerVariable]) -> P,
+    propagate_from_scratch: &impl Fn(&P, &mut DomainManager) -> PropagationStatusCP,
+    is_solution: &impl Fn(&[i32]) -> bool,
+) {
+    let solutions: Vec<Vec<i32>> = enumerate_tuples(domains)
+        .into_iter()
+        .filter(|tuple| is_solution(tuple))
+        .collect();
+
+    let (mut assignment, variables) = create_assignment(domains);
+    let mut propagator = create_propagator(&variables);
+    let status = propagator.initialise_at_root(&mut DomainManager::new(0, &mut assignment));
+
+    match status {
+        PropagationStatusCP::ConflictDetected { failure_reason } => {
+            assert!(
+                solutions.is_empty(),
+                "Conflict reported for {:?} even though solutions exist.",
+                domains
+            );
+
+            let failure_reason: Vec<Predicate> = failure_reason.into_iter().collect();
+            for predicate in &failure_reason {
+                assert!(assignment.does_predicate_hold(predicate));
+            }
+
+            let (mut fresh_assignment, _) = create_assignment(domains);
+            if apply_predicates(&mut fresh_assignment, &failure_reason) {
+                let status = propagate_from_scratch(
+                    &propagator,
+                    &mut DomainManager::new(0, &mut fresh_assignment),
+                );
+                assert!(
+                    status.conflict_detected(),
+                    "The conflict explanation does not lead to a conflict for {:?}.",
+                    domains
+                );
+            }
+        }
+        PropagationStatusCP::NoConflictDetected => {
+            for solution in &solutions {
+                for (i, variable) in variables.iter().enumerate() {
+                    assert!(
+                        assignment.get_lower_bound(*variable) <= solution[i]
+                            && solution[i] <= assignment.get_upper_bound(*variable),
+                        "Solution {:?} removed for {:?}.",
+                        solution,
+                        domains
+                    );
+                }
+            }
+
+            if variables
+                .iter()
+                .all(|v| assignment.is_integer_variable_assigned(*v))
+            {
+                let values: Vec<i32> = variables
+                    .iter()
+                    .map(|v| assignment.get_assigned_value(*v))
+                    .collect();
+                assert!(
+                    is_solution(&values),
+                    "Fixed non-solution {:?} not detected.",
+                    values
+                );
+            }
+        }
+    }
+
+    for index in 0..assignment.num_trail_entries() {
+        if assignment
+            .get_propagator_identifier_on_trail(index)
+            .is_none()
+        {
+            continue;
+        }
+
+        let propagated_predicate = assignment.get_predicate_on_trail(index);
+        let reason: Vec<Predicate> = propagator
+            .get_reason_for_propagation(propagated_predicate)
+            .into_iter()
+            .collect();
+
+        for tuple in enumerate_tuples(domains) {
+            if is_solution(&tuple) && reason.iter().all(|p| evaluate_predicate(p, &tuple)) {
+                assert!(
+                    evaluate_predicate(&propagated_predicate, &tuple),
+                    "Reason {:?} does not imply {} for {:?}.",
+                    reason,
+                    propagated_predicate,
+                    domains
+                );
+            }
+        }
+
+        let (mut fresh_assignment, _) = create_assignment(domains);
+        assert!(apply_predicates(&mut fresh_assignment, &reason));
+        let status = propagate_from_scratch(
+            &propagator,
+            &mut DomainManager::new(0, &mut fresh_assignment),
+        );
+        assert!(
+            status.conflict_detected()
+                || fresh_assignment.does_predicate_hold(&propagated_predicate),
+            "Propagating {:?} from scratch does not reproduce {} for {:?}.",
+            reason,
+            propagated_predicate,
+            domains
+        );
+    }
+}
+
+//returns false if the predicates are inconsistent with the domains
+fn apply_predicates(assignment: &mut AssignmentsInteger, predicates: &[Predicate]) -> bool {
+    for predicate in predicates {
+        if let DomainOperationOutcome::Failure =
+            assignment.apply_predicate_no_notify(predicate, None)
+        {
+            return false;
+        }
+    }
+    true
+}
+
+//returns a view with a random sign and offset, so that the propagators also see negative values
+fn random_view(rng: &mut StdRng, integer_variable: IntegerVariable) -> AffineView {
+    let scale = if rng.gen_bool(0.5) { 1 } else { -1 };
+    AffineView::new(integer_variable, scale, rng.gen_range(-4..=4))
+}
+
+fn random_domain(rng: &mut StdRng, max_value: i32) -> (i32, i32) {
+    let lower_bound = rng.gen_range(0..max_value);
+    let upper_bound = rng.gen_range(lower_bound.max(1)..=max_value);
+    (lower_bound, upper_bound)
+}
+
+#[test]
+fn times_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains = [
+            random_domain(&mut rng, 6),
+            random_domain(&mut rng, 6),
+            random_domain(&mut rng, 30),
+        ];
+        check_propagator(
+            &domains,
+            &|v: &[IntegerVariable]| TimesPropagator::new(v[0], v[1], v[2]),
+            &|p: &TimesPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| t[0] * t[1] == t[2],
+        );
+    }
+}
+
+//the factor y contains zero while the product z does not, so x is bounded by splitting y by its sign
+//  the domains only support nonnegative values, so x and y are shifted views and z may be negated
+#[test]
+fn times_propagator_random_straddling_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let y_negative_part = rng.gen_range(1..=4);
+        let (z_lower_bound, z_upper_bound) = random_domain(&mut rng, 20);
+        let domains = [
+            (0, 50),
+            (0, y_negative_part + rng.gen_range(1..=4)),
+            (z_lower_bound.max(1), z_upper_bound),
+        ];
+        let views = [
+            AffineView::new(IntegerVariable { id: 0 }, 1, -25),
+            AffineView::new(IntegerVariable { id: 1 }, 1, -y_negative_part),
+            AffineView::new(
+                IntegerVariable { id: 2 },
+                if rng.gen_bool(0.5) { 1 } else { -1 },
+                0,
+            ),
+        ];
+        let value = |t: &[i32], i: usize| views[i].transform_value(t[i]);
+
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| TimesPropagator::new(views[0], views[1], views[2]),
+            &|p: &TimesPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| value(t, 0) * value(t, 1) == value(t, 2),
+        );
+    }
+}
+
+#[test]
+fn division_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains = [
+            random_domain(&mut rng, 25),
+            random_domain(&mut rng, 6),
+            random_domain(&mut rng, 10),
+        ];
+        check_propagator(
+            &domains,
+            &|v: &[IntegerVariable]| DivisionPropagator::new(v[0], v[1], v[2]),
+            &|p: &DivisionPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| t[1] != 0 && t[0] / t[1] == t[2],
+        );
+    }
+}
+
+#[test]
+fn modulo_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains = [
+            random_domain(&mut rng, 25),
+            random_domain(&mut rng, 8),
+            random_domain(&mut rng, 8),
+        ];
+        check_propagator(
+            &domains,
+            &|v: &[IntegerVariable]| ModuloPropagator::new(v[0], v[1], v[2]),
+            &|p: &ModuloPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| t[1] != 0 && t[0] % t[1] == t[2],
+        );
+    }
+}
+
+#[test]
+fn absolute_value_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains = [random_domain(&mut rng, 10), random_domain(&mut rng, 10)];
+        check_propagator(
+            &domains,
+            &|v: &[IntegerVariable]| AbsoluteValuePropagator::new(v[0], v[1]),
+            &|p: &AbsoluteValuePropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| t[0].abs() == t[1],
+        );
+    }
+}
+
+#[test]
+fn minimum_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains: Vec<(i32, i32)> = (0..4).map(|_| random_domain(&mut rng, 8)).collect();
+        check_propagator(
+            &domains,
+            &|v: &[IntegerVariable]| MinimumPropagator::new(v[0..3].to_vec(), v[3]),
+            &|p: &MinimumPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| *t[0..3].iter().min().unwrap() == t[3],
+        );
+    }
+}
+
+#[test]
+fn maximum_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains: Vec<(i32, i32)> = (0..4).map(|_| random_domain(&mut rng, 8)).collect();
+        check_propagator(
+            &domains,
+            &|v: &[IntegerVariable]| MaximumPropagator::new(v[0..3].to_vec(), v[3]),
+            &|p: &MaximumPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| *t[0..3].iter().max().unwrap() == t[3],
+        );
+    }
+}
+
+#[test]
+fn division_propagator_excludes_zero_divisor() {
+    let (mut assignment, variables) = create_assignment(&[(0, 10), (0, 3), (0, 10)]);
+    let mut propagator = DivisionPropagator::new(variables[0], variables[1], variables[2]);
+
+    let status = propagator.initialise_at_root(&mut DomainManager::new(0, &mut assignment));
+
+    assert!(status.no_conflict());
+    assert_eq!(1, assignment.get_lower_bound(variables[1]));
+}
+
+#[test]
+fn times_propagator_fixed_factors_fix_product() {
+    let (mut assignment, variables) = create_assignment(&[(3, 3), (4, 4), (0, 20)]);
+    let mut propagator = TimesPropagator::new(variables[0], variables[1], variables[2]);
+
+    let status = propagator.initialise_at_root(&mut DomainManager::new(0, &mut assignment));
+
+    assert!(status.no_conflict());
+    assert!(assignment.is_integer_variable_assigned_to_value(variables[2], 12));
+}
+
+#[test]
+fn times_propagator_bounds_factor_when_divisor_contains_zero() {
+    //x in [-20, 20] and y in [-2, 2] as shifted views, z in [6, 8]
+    let (mut assignment, variables) = create_assignment(&[(0, 40), (0, 4), (6, 8)]);
+    let x = AffineView::new(variables[0], 1, -20);
+    let y = AffineView::new(variables[1], 1, -2);
+    let mut propagator = TimesPropagator::new(x, y, variables[2]);
+
+    let mut domains = DomainManager::new(0, &mut assignment);
+    let status = propagator.initialise_at_root(&mut domains);
+
+    assert!(status.no_conflict());
+    assert_eq!(-8, domains.get_lower_bound(x));
+    assert_eq!(8, domains.get_upper_bound(x));
+}
+
+#[test]
+fn arithmetic_propagators_random_views() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains: Vec<(i32, i32)> = (0..4).map(|_| random_domain(&mut rng, 6)).collect();
+        let views: Vec<AffineView> = (0..4)
+            .map(|i| random_view(&mut rng, IntegerVariable { id: i }))
+            .collect();
+        let value = |t: &[i32], i: usize| views[i].transform_value(t[i]);
+
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| TimesPropagator::new(views[0], views[1], views[2]),
+            &|p: &TimesPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| value(t, 0) * value(t, 1) == value(t, 2),
+        );
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| DivisionPropagator::new(views[0], views[1], views[2]),
+            &|p: &DivisionPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| value(t, 1) != 0 && value(t, 0) / value(t, 1) == value(t, 2),
+        );
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| ModuloPropagator::new(views[0], views[1], views[2]),
+            &|p: &ModuloPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| value(t, 1) != 0 && value(t, 0) % value(t, 1) == value(t, 2),
+        );
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| AbsoluteValuePropagator::new(views[0], views[1]),
+            &|p: &AbsoluteValuePropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| value(t, 0).abs() == value(t, 1),
+        );
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| MinimumPropagator::new(views[0..3].to_vec(), views[3]),
+            &|p: &MinimumPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| (0..3).map(|i| value(t, i)).min().unwrap() == value(t, 3),
+        );
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| MaximumPropagator::new(views[0..3].to_vec(), views[3]),
+            &|p: &MaximumPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| (0..3).map(|i| value(t, i)).max().unwrap() == value(t, 3),
+        );
+    }
+}
+
+#[test]
+fn all_different_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains: Vec<(i32, i32)> = (0..4).map(|_| random_domain(&mut rng, 5)).collect();
+        let views: Vec<AffineView> = (0..4)
+            .map(|i| random_view(&mut rng, IntegerVariable { id: i }))
+            .collect();
+        let value = |t: &[i32], i: usize| views[i].transform_value(t[i]);
+
+        check_propagator(
+            &domains,
+            &|_: &[IntegerVariable]| AllDifferentPropagator::new(views.clone()),
+            &|p: &AllDifferentPropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &|t: &[i32]| (0..4).all(|i| (0..i).all(|j| value(t, i) != value(t, j))),
+        );
+    }
+}
+
+#[test]
+fn all_different_propagator_detects_hall_interval() {
+    let (mut assignment, variables) = create_assignment(&[(1, 2), (1, 2), (1, 3), (1, 5)]);
+    let mut propagator = AllDifferentPropagator::new(variables.clone());
+
+    let status = propagator.initialise_at_root(&mut DomainManager::new(0, &mut assignment));
+
+    assert!(status.no_conflict());
+    assert!(assignment.is_integer_variable_assigned_to_value(variables[2], 3));
+    assert_eq!(4, assignment.get_lower_bound(variables[3]));
+}
+
+#[test]
+fn cumulative_propagator_random_domains() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..NUM_RANDOM_INSTANCES {
+        let domains: Vec<(i32, i32)> = (0..4).map(|_| random_domain(&mut rng, 6)).collect();
+        let durations: Vec<i32> = (0..4).map(|_| rng.gen_range(0..=4)).collect();
+        let resource_usages: Vec<i32> = (0..4).map(|_| rng.gen_range(0..=3)).collect();
+        let capacity = rng.gen_range(1..=4);
+
+        let is_solution = |t: &[i32]| {
+            (0..=10).all(|time| {
+                (0..4)
+                    .filter(|&i| t[i] <= time && time < t[i] + durations[i])
+                    .map(|i| resource_usages[i])
+                    .sum::<i32>()
+                    <= capacity
+            })
+        };
+
+        check_propagator(
+            &domains,
+            &|v: &[IntegerVariable]| {
+                CumulativePropagator::new(
+                    v.to_vec(),
+                    durations.clone(),
+                    resource_usages.clone(),
+                    capacity,
+                )
+            },
+            &|p: &CumulativePropagator, d: &mut DomainManager| p.debug_propagate_from_scratch(d),
+            &is_solution,
+        );
+    }
+}
+
+#[test]
+fn cumulative_propagator_pushes_task_after_compulsory_part() {
+    //the first task certainly runs during [2, 4), so the second task cannot start before 4
+    let (mut assignment, variables) = create_assignment(&[(1, 2), (1, 10)]);
+    let mut propagator = CumulativePropagator::new(variables.clone(), vec![3, 2], vec![2, 1], 2);
+
+    let status = propagator.initialise_at_root(&mut DomainManager::new(0, &mut assignment));
+
+    assert!(status.no_conflict());
+    assert_eq!(4, assignment.get_lower_bound(variables[1]));
+}
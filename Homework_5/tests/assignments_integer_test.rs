@@ -0,0 +1,32 @@
+use pumpkin::engine::{AssignmentsInteger, DomainOperationOutcome};
+
+#[test]
+fn removing_a_bound_moves_it_past_removed_values() {
+    let mut assignments = AssignmentsInteger::new();
+    let x = assignments.grow(0, 6);
+
+    assignments.increase_decision_level();
+    for value in [2, 1, 4, 5] {
+        assignments.remove_value_from_domain_no_notify(x, value, None);
+    }
+    assert_eq!(assignments.get_lower_bound(x), 0);
+    assert_eq!(assignments.get_upper_bound(x), 6);
+
+    //the bounds skip the values that were removed before
+    assignments.remove_value_from_domain_no_notify(x, 0, None);
+    assert_eq!(assignments.get_lower_bound(x), 3);
+    assignments.remove_value_from_domain_no_notify(x, 6, None);
+    assert_eq!(assignments.get_upper_bound(x), 3);
+
+    //removing the last value is a conflict
+    assert!(matches!(
+        assignments.remove_value_from_domain_no_notify(x, 3, None),
+        DomainOperationOutcome::Failure
+    ));
+    assert!(assignments.is_conflict());
+
+    assignments.synchronise(0);
+    assert_eq!(assignments.get_lower_bound(x), 0);
+    assert_eq!(assignments.get_upper_bound(x), 6);
+    assert!((0..=6).all(|value| assignments.is_value_in_domain(x, value)));
+}
@@ -0,0 +1,161 @@
+use pumpkin::{
+    basic_types::{Predicate, PropagationStatusCP, PropagatorIdentifier, PropositionalConjunction},
+    engine::{AssignmentsInteger, DebugHelper, DomainManager},
+    propagators::{
+        ConstraintProgrammingPropagator, LinearInequalityPropagator, LinearLessOrEqualPropagator,
+    },
+};
+
+const PROPAGATOR_ID: PropagatorIdentifier = PropagatorIdentifier { id: 0 };
+
+//x + y <= 5 with x, y in [0, 5], where x >= 3 is decided, so that the propagator sets y <= 2
+fn propagate_sum_after_decision() -> (AssignmentsInteger, LinearLessOrEqualPropagator, Predicate) {
+    let mut assignments = AssignmentsInteger::new();
+    let x = assignments.grow(0, 5);
+    let y = assignments.grow(0, 5);
+    let mut propagator = LinearLessOrEqualPropagator::new(vec![x, y], 5);
+
+    assignments.increase_decision_level();
+    let mut domains = DomainManager::new(0, &mut assignments);
+    domains.tighten_lower_bound(x, 3);
+    assert!(propagator.propagate(&mut domains).no_conflict());
+    assert_eq!(assignments.get_upper_bound(y), 2);
+
+    let propagated_predicate = Predicate::UpperBound {
+        integer_variable: y,
+        upper_bound: 2,
+    };
+    (assignments, propagator, propagated_predicate)
+}
+
+#[test]
+fn reasons_that_reproduce_the_propagation_pass_the_check() {
+    let (assignments, mut propagator, propagated_predicate) = propagate_sum_after_decision();
+    let reason = propagator.get_reason_for_propagation(propagated_predicate);
+
+    assert!(DebugHelper::debug_propagator_reason(
+        propagated_predicate,
+        &reason,
+        &assignments,
+        &propagator,
+        PROPAGATOR_ID
+    ));
+}
+
+//the root domains of y are restored in the clone, so an empty reason does not lead to y <= 2
+#[test]
+#[should_panic(expected = "could not obtain the propagated predicate")]
+fn reasons_that_do_not_reproduce_the_propagation_are_detected() {
+    let (assignments, propagator, propagated_predicate) = propagate_sum_after_decision();
+
+    DebugHelper::debug_propagator_reason(
+        propagated_predicate,
+        &PropositionalConjunction::new(),
+        &assignments,
+        &propagator,
+        PROPAGATOR_ID,
+    );
+}
+
+//2x + 3y >= 12 with x, y in [0, 3]
+#[test]
+fn linear_inequality_propagates_from_scratch_and_checks_failures() {
+    let mut assignments = AssignmentsInteger::new();
+    let x = assignments.grow(0, 3);
+    let y = assignments.grow(0, 3);
+    let propagator = LinearInequalityPropagator::new(vec![2, 3], vec![x, y], 12);
+
+    let mut clone = assignments.clone();
+    let status = propagator.debug_propagate_from_scratch(&mut DomainManager::new(0, &mut clone));
+    assert!(status.no_conflict());
+    assert_eq!(clone.get_lower_bound(x), 2);
+    assert_eq!(clone.get_lower_bound(y), 2);
+
+    let failure_reason: PropositionalConjunction = vec![
+        Predicate::UpperBound {
+            integer_variable: x,
+            upper_bound: 1,
+        },
+        Predicate::UpperBound {
+            integer_variable: y,
+            upper_bound: 2,
+        },
+    ]
+    .into();
+    assert!(DebugHelper::debug_reported_failure(
+        &assignments,
+        &failure_reason,
+        &propagator,
+        PROPAGATOR_ID
+    ));
+}
+
+//y <= 2 alone does not make 2x + 3y >= 12 infeasible
+#[test]
+#[should_panic(expected = "could not reproduce the conflict")]
+fn failures_that_do_not_reproduce_the_conflict_are_detected() {
+    let mut assignments = AssignmentsInteger::new();
+    let x = assignments.grow(0, 3);
+    let y = assignments.grow(0, 3);
+    let propagator = LinearInequalityPropagator::new(vec![2, 3], vec![x, y], 12);
+
+    let failure_reason: PropositionalConjunction = vec![Predicate::UpperBound {
+        integer_variable: y,
+        upper_bound: 2,
+    }]
+    .into();
+    DebugHelper::debug_reported_failure(&assignments, &failure_reason, &propagator, PROPAGATOR_ID);
+}
+
+//2x + 3y >= 12 with x, y in [0, 3], where x <= 1 and y <= 2 are decided before the first propagation
+#[test]
+fn linear_inequality_failures_pass_the_check() {
+    let mut assignments = AssignmentsInteger::new();
+    let x = assignments.grow(0, 3);
+    let y = assignments.grow(0, 3);
+    let mut propagator = LinearInequalityPropagator::new(vec![2, 3], vec![x, y], 12);
+
+    assignments.increase_decision_level();
+    let mut domains = DomainManager::new(0, &mut assignments);
+    domains.tighten_upper_bound(x, 1);
+    domains.tighten_upper_bound(y, 2);
+    let failure_reason = match propagator.propagate(&mut domains) {
+        PropagationStatusCP::ConflictDetected { failure_reason } => failure_reason,
+        PropagationStatusCP::NoConflictDetected => panic!("Expected a conflict."),
+    };
+
+    assert!(DebugHelper::debug_reported_failure(
+        &assignments,
+        &failure_reason,
+        &propagator,
+        PROPAGATOR_ID
+    ));
+}
+
+//2x + 3y >= 12 with x, y in [0, 3], where deciding x <= 2 leads to y >= 3
+#[test]
+fn linear_inequality_reasons_pass_the_check() {
+    let mut assignments = AssignmentsInteger::new();
+    let x = assignments.grow(0, 3);
+    let y = assignments.grow(0, 3);
+    let mut propagator = LinearInequalityPropagator::new(vec![2, 3], vec![x, y], 12);
+
+    assignments.increase_decision_level();
+    let mut domains = DomainManager::new(0, &mut assignments);
+    domains.tighten_upper_bound(x, 2);
+    assert!(propagator.propagate(&mut domains).no_conflict());
+    assert_eq!(assignments.get_lower_bound(y), 3);
+
+    let propagated_predicate = Predicate::LowerBound {
+        integer_variable: y,
+        lower_bound: 3,
+    };
+    let reason = propagator.get_reason_for_propagation(propagated_predicate);
+    assert!(DebugHelper::debug_propagator_reason(
+        propagated_predicate,
+        &reason,
+        &assignments,
+        &propagator,
+        PROPAGATOR_ID
+    ));
+}
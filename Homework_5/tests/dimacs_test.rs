@@ -0,0 +1,168 @@
+use pumpkin::{
+    basic_types::{FileFormat, Instance, Literal, PumpkinExecutionFlag},
+    engine::{Pumpkin, SolverOptions},
+};
+
+//writes the contents to a file in the temporary directory, reads it into an instance, and removes the file
+fn read_instance(name: &str, contents: &str, file_format: FileFormat) -> Instance {
+    let file_location = std::env::temp_dir().join(format!(
+        "pumpkin_dimacs_test_{}_{}",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&file_location, contents).unwrap();
+    let mut instance = Instance::new();
+    let result = instance.read_file(file_location.to_str().unwrap(), file_format);
+    std::fs::remove_file(file_location).unwrap();
+    result.unwrap();
+    instance
+}
+
+//the clauses in the DIMACS notation, e.g., [1, -2] for the clause 'x1 or not x2'
+fn to_dimacs(clauses: &[Vec<Literal>]) -> Vec<Vec<i64>> {
+    clauses
+        .iter()
+        .map(|clause| {
+            clause
+                .iter()
+                .map(|literal| {
+                    let index = literal.get_propositional_variable().index() as i64;
+                    if literal.is_positive() {
+                        index
+                    } else {
+                        -index
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn irregular_whitespace_is_accepted() {
+    let instance = read_instance(
+        "whitespace.cnf",
+        "c comment\r\np cnf\t3 2\r\n\t1  -2 0\r\n\r\n  2\t3   -1 0   \r\n",
+        FileFormat::CnfDimacsPLine,
+    );
+    assert_eq!(
+        to_dimacs(&instance.hard_clauses),
+        vec![vec![1, -2], vec![2, 3, -1]]
+    );
+}
+
+#[test]
+fn clauses_may_span_lines_and_share_lines() {
+    let instance = read_instance(
+        "spanning.cnf",
+        "p cnf 4 3\n1 2\n-3 0 4 0\nc comment between clauses\n-1\n-4 0\n",
+        FileFormat::CnfDimacsPLine,
+    );
+    assert_eq!(
+        to_dimacs(&instance.hard_clauses),
+        vec![vec![1, 2, -3], vec![4], vec![-1, -4]]
+    );
+}
+
+#[test]
+fn last_clause_may_omit_the_zero() {
+    let instance = read_instance(
+        "trailing.cnf",
+        "p cnf 3 2\n1 2 0\n-2 3",
+        FileFormat::CnfDimacsPLine,
+    );
+    assert_eq!(
+        to_dimacs(&instance.hard_clauses),
+        vec![vec![1, 2], vec![-2, 3]]
+    );
+}
+
+#[test]
+fn percent_terminates_satlib_files() {
+    let instance = read_instance(
+        "uf3-01.cnf",
+        "c This Formular is generated by mcnf\nc\np cnf 3  2 \n 1 -3 2 0\n-1 3 -2 0\n%\n0\n\n",
+        FileFormat::CnfDimacsPLine,
+    );
+    assert_eq!(
+        to_dimacs(&instance.hard_clauses),
+        vec![vec![1, -3, 2], vec![-1, 3, -2]]
+    );
+}
+
+#[test]
+fn header_counts_that_do_not_match_the_file_are_accepted() {
+    let instance = read_instance(
+        "under_reported.cnf",
+        "p cnf 2 1\n1 -2 0\n3 4 0\n-4 0\n",
+        FileFormat::CnfDimacsPLine,
+    );
+    assert_eq!(
+        to_dimacs(&instance.hard_clauses),
+        vec![vec![1, -2], vec![3, 4], vec![-4]]
+    );
+
+    let instance = read_instance(
+        "over_reported.cnf",
+        "p cnf 10 5\n1 -2 0\n",
+        FileFormat::CnfDimacsPLine,
+    );
+    assert_eq!(to_dimacs(&instance.hard_clauses), vec![vec![1, -2]]);
+}
+
+#[test]
+fn weighted_clauses_may_span_lines() {
+    let instance = read_instance(
+        "spanning.wcnf",
+        "p wcnf 3 3 100\n100 1\n 2 0 5 -1 0\n100\t-2 3 0\n",
+        FileFormat::WcnfDimacsPLine,
+    );
+    assert_eq!(
+        to_dimacs(&instance.hard_clauses),
+        vec![vec![1, 2], vec![-2, 3]]
+    );
+    assert_eq!(instance.soft_clauses.len(), 1);
+    assert_eq!(instance.soft_clauses[0].weight, 5);
+    assert_eq!(
+        to_dimacs(&[instance.soft_clauses[0].literals.clone()]),
+        vec![vec![-1]]
+    );
+}
+
+#[test]
+fn pumpkin_creates_variables_missing_from_the_header() {
+    let file_location = std::env::temp_dir().join(format!(
+        "pumpkin_dimacs_test_{}_missing_variables.cnf",
+        std::process::id()
+    ));
+    std::fs::write(&file_location, "p cnf 1 1\n1 2 0\n-1 0\n").unwrap();
+    let mut pumpkin = Pumpkin::new(&SolverOptions::default());
+    let result = pumpkin.read_file(file_location.to_str().unwrap(), FileFormat::CnfDimacsPLine);
+    std::fs::remove_file(file_location).unwrap();
+    result.unwrap();
+
+    assert!(matches!(
+        pumpkin.solve(),
+        PumpkinExecutionFlag::Feasible { .. }
+    ));
+}
+
+#[test]
+fn solutions_are_formatted_as_dimacs_value_lines() {
+    let file_location = std::env::temp_dir().join(format!(
+        "pumpkin_dimacs_test_{}_value_line.cnf",
+        std::process::id()
+    ));
+    std::fs::write(&file_location, "p cnf 3 3\n1 0\n-2 0\n3 -1 0\n").unwrap();
+    let mut pumpkin = Pumpkin::new(&SolverOptions::default());
+    let result = pumpkin.read_file(file_location.to_str().unwrap(), FileFormat::CnfDimacsPLine);
+    std::fs::remove_file(file_location).unwrap();
+    result.unwrap();
+
+    match pumpkin.solve() {
+        PumpkinExecutionFlag::Feasible { feasible_solution } => {
+            assert_eq!(feasible_solution.format_dimacs_value_line(), "v 1 -2 3 0")
+        }
+        _ => panic!("Expected a feasible solution."),
+    }
+}
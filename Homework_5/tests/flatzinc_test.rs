@@ -0,0 +1,418 @@
+use pumpkin::{
+    basic_types::{PumpkinError, SolutionEnumerationFlag},
+    engine::SolverOptions,
+    flatzinc::{parse_flatzinc, Expression, FlatZincSolver, SetLiteral, SolveGoal, VariableType},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+//solves the model and returns the printed solutions together with the enumeration flag
+fn solve(
+    model: &str,
+    max_num_solutions: u64,
+    report_intermediate_solutions: bool,
+) -> (Vec<String>, SolutionEnumerationFlag) {
+    let model = parse_flatzinc(model).unwrap();
+    let mut solver = FlatZincSolver::new(&model, &SolverOptions::default()).unwrap();
+    let mut solutions = vec![];
+    let flag = solver.solve(
+        max_num_solutions,
+        report_intermediate_solutions,
+        |instance, solution| solutions.push(instance.format_solution(solution)),
+    );
+    (solutions, flag)
+}
+
+#[test]
+fn model_is_parsed() {
+    let model = parse_flatzinc(
+        "% a comment
+        predicate my_predicate(var int: x, array [int] of var bool: y);
+        array [1..3] of int: a = [1, -2, 3];
+        set of int: s = {1, 3, 5};
+        var -5..5: x :: output_var;
+        var {1, 3}: y :: is_defined_var;
+        var bool: b :: output_var = true;
+        array [1..2] of var int: xs :: output_array([1..2]) = [x, y];
+        constraint int_lin_le(a, [x, y, x], 4) :: domain;
+        constraint set_in(x, s);
+        solve :: seq_search([int_search(xs, first_fail, indomain_min, complete)]) minimize x;",
+    )
+    .unwrap();
+
+    assert_eq!(model.parameters.len(), 2);
+    assert_eq!(
+        model.parameters[1].value,
+        Expression::Set(SetLiteral::Values(vec![1, 3, 5]))
+    );
+    assert_eq!(model.variables.len(), 4);
+    assert_eq!(
+        model.variables[0].variable_type,
+        VariableType::Int(Some(SetLiteral::Range(-5, 5)))
+    );
+    assert_eq!(model.variables[2].assignment, Some(Expression::Bool(true)));
+    assert!(model.variables[3].is_array);
+    assert_eq!(model.variables[3].annotations[0].name, "output_array");
+    assert_eq!(model.constraints.len(), 2);
+    assert_eq!(model.constraints[0].name, "int_lin_le");
+    assert_eq!(model.constraints[0].arguments[2], Expression::Int(4));
+    assert_eq!(
+        model.solve_item.goal,
+        SolveGoal::Minimize(Expression::Identifier("x".to_owned()))
+    );
+    assert_eq!(model.solve_item.annotations[0].name, "seq_search");
+}
+
+#[test]
+fn syntax_errors_report_the_line_and_column() {
+    let error =
+        parse_flatzinc("var 1..3: x;\nconstraint int_le(x 3);\nsolve satisfy;").unwrap_err();
+    assert!(matches!(
+        error,
+        PumpkinError::Parse {
+            line: 2,
+            column: 21,
+            ..
+        }
+    ));
+
+    //errors at the end of the file are reported after the last character
+    let error = parse_flatzinc("var 1..3: x;").unwrap_err();
+    assert!(matches!(
+        &error,
+        PumpkinError::Parse { line: 1, column: 13, message } if message.contains("solve")
+    ));
+}
+
+#[test]
+fn unsupported_constraints_are_rejected() {
+    let model =
+        parse_flatzinc("var 1..3: x;\nconstraint int_pow(x, 2, 4);\nsolve satisfy;").unwrap();
+    let error = FlatZincSolver::new(&model, &SolverOptions::default())
+        .err()
+        .unwrap();
+    assert!(matches!(
+        &error,
+        PumpkinError::UnsupportedFileFormat { message } if message.contains("int_pow")
+    ));
+}
+
+#[test]
+fn solutions_are_printed_in_minizinc_format() {
+    let (solutions, flag) = solve(
+        "var 1..3: x :: output_var;
+        var bool: b :: output_var;
+        array [1..2] of var int: xs :: output_array([1..2]) = [x, 7];
+        constraint int_le_reif(x, 1, b);
+        constraint bool_clause([b], []);
+        solve satisfy;",
+        1,
+        false,
+    );
+
+    assert_eq!(
+        solutions,
+        vec!["x = 1;\nb = true;\nxs = array1d(1..2, [1, 7]);\n----------"]
+    );
+    assert!(matches!(
+        flag,
+        SolutionEnumerationFlag::SolutionLimitReached { num_solutions: 1 }
+    ));
+}
+
+#[test]
+fn optimisation_reports_the_optimal_solution() {
+    //maximise x + y subject to 2x + 3y <= 12, x - y != 1, and x * y <= 5
+    let model = "var 0..6: x :: output_var;
+        var 0..6: y :: output_var;
+        var 0..12: objective :: output_var;
+        var 0..36: product;
+        constraint int_lin_le([2, 3], [x, y], 12);
+        constraint int_lin_ne([1, -1], [x, y], 1);
+        constraint int_times(x, y, product);
+        constraint int_le(product, 5);
+        constraint int_lin_eq([1, 1, -1], [x, y, objective], 0);
+        solve maximize objective;";
+
+    let (solutions, flag) = solve(model, 1, false);
+    assert_eq!(
+        solutions,
+        vec!["x = 6;\ny = 0;\nobjective = 6;\n----------"]
+    );
+    assert!(matches!(
+        flag,
+        SolutionEnumerationFlag::AllSolutionsFound { .. }
+    ));
+
+    //intermediate solutions strictly improve the objective
+    let (solutions, _) = solve(model, 1, true);
+    let objective_values: Vec<i32> = solutions
+        .iter()
+        .map(|solution| {
+            let line = solution.lines().nth(2).unwrap();
+            line["objective = ".len()..line.len() - 1].parse().unwrap()
+        })
+        .collect();
+    assert!(objective_values.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(*objective_values.last().unwrap(), 6);
+}
+
+#[test]
+fn infeasible_models_have_no_solutions() {
+    for model in [
+        //infeasible at the root
+        "var 1..3: x;\nconstraint int_lin_le([1], [x], 0);\nsolve satisfy;",
+        //infeasible during search
+        "var bool: a;\nvar bool: b;\nvar bool: c;
+        constraint bool_xor(a, b, c);\nconstraint bool_eq(a, c);\nconstraint bool_eq(b, c);
+        constraint array_bool_or([a, b], true);\nsolve satisfy;",
+    ] {
+        let (solutions, flag) = solve(model, u64::MAX, true);
+        assert!(solutions.is_empty());
+        assert!(matches!(
+            flag,
+            SolutionEnumerationFlag::AllSolutionsFound { num_solutions: 0 }
+        ));
+    }
+}
+
+#[test]
+fn element_constraints_match_their_definition() {
+    //c == a[i] and d == [x, y, 2][i]
+    let (solutions, flag) = solve(
+        "array [1..4] of int: a = [3, -1, 3, 0];
+        var -2..8: i :: output_var;
+        var -5..5: c :: output_var;
+        var 0..2: x :: output_var;
+        var 1..2: y :: output_var;
+        var -5..5: d :: output_var;
+        constraint array_int_element(i, a, c);
+        constraint array_var_int_element(i, [x, y, 2, x], d);
+        solve satisfy;",
+        u64::MAX,
+        true,
+    );
+
+    let values: Vec<Vec<i32>> = solutions
+        .iter()
+        .map(|solution| {
+            solution
+                .lines()
+                .filter(|line| line.contains('='))
+                .map(|line| {
+                    line[line.find('=').unwrap() + 2..line.len() - 1]
+                        .parse()
+                        .unwrap()
+                })
+                .collect()
+        })
+        .collect();
+
+    let a = [3, -1, 3, 0];
+    let mut expected_num_solutions = 0;
+    for i in 1..=4 {
+        for x in 0..=2 {
+            for y in 1..=2 {
+                expected_num_solutions += 1;
+                let d = [x, y, 2, x][i as usize - 1];
+                assert!(values.contains(&vec![i, a[i as usize - 1], x, y, d]));
+            }
+        }
+    }
+    assert_eq!(values.len(), expected_num_solutions);
+    assert!(matches!(
+        flag,
+        SolutionEnumerationFlag::AllSolutionsFound { .. }
+    ));
+}
+
+//a random constraint over the integer variables x_1..x_n and boolean variables b_1..b_m
+//  rendered as FlatZinc and evaluated directly to compare against brute force
+enum RandomConstraint {
+    Linear {
+        name: &'static str,
+        coefficients: Vec<i32>,
+        right_hand_side: i32,
+        reification: Option<(&'static str, usize)>,
+    },
+    Comparison {
+        name: &'static str,
+        x: usize,
+        y: usize,
+        reification: Option<(&'static str, usize)>,
+    },
+    Clause {
+        positive: Vec<usize>,
+        negative: Vec<usize>,
+    },
+}
+
+impl RandomConstraint {
+    fn generate(rng: &mut StdRng, num_integers: usize, num_bools: usize) -> RandomConstraint {
+        let mut reification = || match rng.gen_range(0..3) {
+            0 => None,
+            1 => Some(("_reif", rng.gen_range(0..num_bools))),
+            _ => Some(("_imp", rng.gen_range(0..num_bools))),
+        };
+        let reification = reification();
+        match rng.gen_range(0..3) {
+            0 => RandomConstraint::Linear {
+                name: ["int_lin_le", "int_lin_eq", "int_lin_ne"][rng.gen_range(0..3)],
+                coefficients: (0..num_integers).map(|_| rng.gen_range(-3..=3)).collect(),
+                right_hand_side: rng.gen_range(-4..=4),
+                reification,
+            },
+            1 => RandomConstraint::Comparison {
+                name: ["int_eq", "int_ne", "int_le", "int_lt"][rng.gen_range(0..4)],
+                x: rng.gen_range(0..num_integers),
+                y: rng.gen_range(0..num_integers),
+                reification,
+            },
+            _ => RandomConstraint::Clause {
+                positive: (0..rng.gen_range(0..3))
+                    .map(|_| rng.gen_range(0..num_bools))
+                    .collect(),
+                negative: (0..rng.gen_range(1..3))
+                    .map(|_| rng.gen_range(0..num_bools))
+                    .collect(),
+            },
+        }
+    }
+
+    fn to_flatzinc(&self) -> String {
+        let reified = |name: &str, arguments: String, reification: &Option<(&str, usize)>| {
+            match reification {
+                Some((suffix, b)) => format!("constraint {name}{suffix}({arguments}, b{b});"),
+                None => format!("constraint {name}({arguments});"),
+            }
+        };
+        let variables = |indices: &mut dyn Iterator<Item = String>| {
+            format!("[{}]", indices.collect::<Vec<String>>().join(", "))
+        };
+
+        match self {
+            RandomConstraint::Linear {
+                name,
+                coefficients,
+                right_hand_side,
+                reification,
+            } => {
+                let arguments = format!(
+                    "{:?}, {}, {}",
+                    coefficients,
+                    variables(&mut (0..coefficients.len()).map(|i| format!("x{i}"))),
+                    right_hand_side
+                );
+                reified(name, arguments, reification)
+            }
+            RandomConstraint::Comparison {
+                name,
+                x,
+                y,
+                reification,
+            } => reified(name, format!("x{x}, x{y}"), reification),
+            RandomConstraint::Clause { positive, negative } => format!(
+                "constraint bool_clause({}, {});",
+                variables(&mut positive.iter().map(|b| format!("b{b}"))),
+                variables(&mut negative.iter().map(|b| format!("b{b}")))
+            ),
+        }
+    }
+
+    fn is_satisfied(&self, integers: &[i32], bools: &[bool]) -> bool {
+        let with_reification = |holds: bool, reification: &Option<(&str, usize)>| match reification
+        {
+            Some(("_reif", b)) => bools[*b] == holds,
+            Some((_, b)) => !bools[*b] || holds,
+            None => holds,
+        };
+
+        match self {
+            RandomConstraint::Linear {
+                name,
+                coefficients,
+                right_hand_side,
+                reification,
+            } => {
+                let sum: i32 = coefficients.iter().zip(integers).map(|(a, x)| a * x).sum();
+                let holds = match *name {
+                    "int_lin_le" => sum <= *right_hand_side,
+                    "int_lin_eq" => sum == *right_hand_side,
+                    _ => sum != *right_hand_side,
+                };
+                with_reification(holds, reification)
+            }
+            RandomConstraint::Comparison {
+                name,
+                x,
+                y,
+                reification,
+            } => {
+                let (x, y) = (integers[*x], integers[*y]);
+                let holds = match *name {
+                    "int_eq" => x == y,
+                    "int_ne" => x != y,
+                    "int_le" => x <= y,
+                    _ => x < y,
+                };
+                with_reification(holds, reification)
+            }
+            RandomConstraint::Clause { positive, negative } => {
+                positive.iter().any(|b| bools[*b]) || negative.iter().any(|b| !bools[*b])
+            }
+        }
+    }
+}
+
+#[test]
+fn random_reified_models_have_as_many_solutions_as_brute_force() {
+    let mut rng = StdRng::seed_from_u64(31);
+    let (num_integers, num_bools) = (3, 3);
+    let (lower_bound, upper_bound) = (-2, 2);
+
+    for _ in 0..60 {
+        let constraints: Vec<RandomConstraint> = (0..rng.gen_range(1..5))
+            .map(|_| RandomConstraint::generate(&mut rng, num_integers, num_bools))
+            .collect();
+
+        let mut model = String::new();
+        for i in 0..num_integers {
+            model.push_str(&format!(
+                "var {lower_bound}..{upper_bound}: x{i} :: output_var;\n"
+            ));
+        }
+        for b in 0..num_bools {
+            model.push_str(&format!("var bool: b{b} :: output_var;\n"));
+        }
+        for constraint in &constraints {
+            model.push_str(&constraint.to_flatzinc());
+            model.push('\n');
+        }
+        model.push_str("solve satisfy;\n");
+
+        let domain_size = (upper_bound - lower_bound + 1) as u32;
+        let mut expected_num_solutions = 0;
+        for assignment in 0..domain_size.pow(num_integers as u32) * (1 << num_bools) {
+            let integers: Vec<i32> = (0..num_integers)
+                .map(|i| {
+                    (assignment / domain_size.pow(i as u32) % domain_size) as i32 + lower_bound
+                })
+                .collect();
+            let bools: Vec<bool> = (0..num_bools)
+                .map(|b| (assignment / domain_size.pow(num_integers as u32)) >> b & 1 == 1)
+                .collect();
+            if constraints
+                .iter()
+                .all(|constraint| constraint.is_satisfied(&integers, &bools))
+            {
+                expected_num_solutions += 1;
+            }
+        }
+
+        let (_, flag) = solve(&model, u64::MAX, true);
+        assert!(
+            matches!(flag, SolutionEnumerationFlag::AllSolutionsFound { num_solutions } if num_solutions == expected_num_solutions),
+            "Wrong number of solutions for the model\n{}",
+            model
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use pumpkin::{
+    basic_types::{
+        CSPSolverExecutionFlag, IntegerVariable, Literal, Predicate, PropagationStatusCP,
+        PropositionalConjunction, PropositionalVariable,
+    },
+    engine::{ConstraintSatisfactionSolver, DomainManager, SolverOptions},
+    propagators::{ConstraintProgrammingPropagator, MaximumPropagator, TimesPropagator},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn create_solver() -> ConstraintSatisfactionSolver {
+    ConstraintSatisfactionSolver::new(&SolverOptions::default())
+}
+
+fn random_clause(rng: &mut StdRng, variables: &[PropositionalVariable]) -> Vec<Literal> {
+    let mut clause: Vec<Literal> = vec![];
+    while clause.len() < 3 {
+        let variable = variables[rng.gen_range(0..variables.len())];
+        if !clause
+            .iter()
+            .any(|l| l.get_propositional_variable() == variable)
+        {
+            clause.push(Literal::new(variable, rng.gen_bool(0.5)));
+        }
+    }
+    clause
+}
+
+fn is_satisfiable_brute_force(
+    variables: &[PropositionalVariable],
+    clauses: &[Vec<Literal>],
+) -> bool {
+    (0..(1u32 << variables.len())).any(|assignment| {
+        clauses.iter().all(|clause| {
+            clause.iter().any(|literal| {
+                let index = variables
+                    .iter()
+                    .position(|v| *v == literal.get_propositional_variable())
+                    .unwrap();
+                ((assignment >> index) & 1 == 1) == literal.is_positive()
+            })
+        })
+    })
+}
+
+fn is_feasible(flag: CSPSolverExecutionFlag) -> bool {
+    match flag {
+        CSPSolverExecutionFlag::Feasible => true,
+        CSPSolverExecutionFlag::Infeasible => false,
+        _ => panic!("Unexpected solver outcome."),
+    }
+}
+
+#[test]
+fn solving_under_assumptions_respects_the_assumptions() {
+    let mut solver = create_solver();
+    let a = Literal::new(solver.create_new_propositional_variable(), true);
+    let b = Literal::new(solver.create_new_propositional_variable(), true);
+    let c = Literal::new(solver.create_new_propositional_variable(), true);
+    solver.add_permanent_clause(vec![!a, b]);
+    solver.add_permanent_clause(vec![!b, c]);
+
+    assert!(matches!(
+        solver.solve_under_assumptions(&[a], i64::MAX),
+        CSPSolverExecutionFlag::Feasible
+    ));
+    assert!(solver
+        .get_propositional_assignments()
+        .is_literal_assigned_true(c));
+    solver.restore_state_at_root();
+
+    assert!(matches!(
+        solver.solve_under_assumptions(&[a, !c], i64::MAX),
+        CSPSolverExecutionFlag::InfeasibleUnderAssumptions
+    ));
+    solver.restore_state_at_root();
+
+    assert!(matches!(
+        solver.solve_under_assumptions(&[!c], i64::MAX),
+        CSPSolverExecutionFlag::Feasible
+    ));
+    assert!(solver
+        .get_propositional_assignments()
+        .is_literal_assigned_false(a));
+}
+
+//the second assumption is falsified by the decision on the first assumption rather than by propagation
+#[test]
+fn contradicting_assumptions_are_infeasible_under_assumptions() {
+    let mut solver = create_solver();
+    let a = Literal::new(solver.create_new_propositional_variable(), true);
+
+    assert!(matches!(
+        solver.solve_under_assumptions(&[a, !a], i64::MAX),
+        CSPSolverExecutionFlag::InfeasibleUnderAssumptions
+    ));
+    let core = solver.extract_core();
+    assert!(core.contains(&a) && core.contains(&!a));
+    solver.restore_state_at_root();
+
+    assert!(matches!(
+        solver.solve_under_assumptions(&[a], i64::MAX),
+        CSPSolverExecutionFlag::Feasible
+    ));
+}
+
+#[test]
+fn popping_a_scope_retracts_its_clauses() {
+    let mut solver = create_solver();
+    let a = Literal::new(solver.create_new_propositional_variable(), true);
+    let b = Literal::new(solver.create_new_propositional_variable(), true);
+    solver.add_permanent_clause(vec![a, b]);
+
+    solver.push_scope();
+    solver.add_unit_clause(!a);
+    assert!(is_feasible(solver.solve(i64::MAX)));
+    assert!(solver
+        .get_propositional_assignments()
+        .is_literal_assigned_true(b));
+    solver.restore_state_at_root();
+
+    solver.push_scope();
+    solver.add_permanent_clause(vec![!b, !a]);
+    solver.add_unit_clause(b);
+    solver.add_permanent_implication_unchecked(b, a);
+    assert!(!is_feasible(solver.solve(i64::MAX)));
+    solver.restore_state_at_root();
+
+    solver.pop_scope();
+    assert_eq!(solver.num_open_scopes(), 1);
+    assert!(is_feasible(solver.solve(i64::MAX)));
+    solver.restore_state_at_root();
+
+    solver.pop_scope();
+    solver.add_unit_clause(!b);
+    assert!(is_feasible(solver.solve(i64::MAX)));
+    assert!(solver
+        .get_propositional_assignments()
+        .is_literal_assigned_true(a));
+}
+
+#[test]
+fn popping_a_scope_retracts_its_propagators() {
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let y = solver.create_new_integer_variable(0, 5).unwrap();
+    let z = solver.create_new_integer_variable(7, 9).unwrap();
+    let w = solver.create_new_integer_variable(0, 5).unwrap();
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x, y], w)));
+
+    //x * y = z is feasible, but not together with max(x, y) <= 2
+    solver.push_scope();
+    solver.add_propagator(Box::new(TimesPropagator::new(x, y, z)));
+    assert!(is_feasible(solver.solve(i64::MAX)));
+    let assignments = solver.get_integer_assignments();
+    assert_eq!(
+        assignments.get_assigned_value(x) * assignments.get_assigned_value(y),
+        assignments.get_assigned_value(z)
+    );
+    solver.restore_state_at_root();
+
+    solver.push_scope();
+    let limit = solver.create_new_integer_variable(0, 2).unwrap();
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x, y, limit], limit)));
+    assert!(!is_feasible(solver.solve(i64::MAX)));
+    solver.restore_state_at_root();
+
+    //without the times constraint, the limit can be respected
+    solver.pop_scope();
+    solver.pop_scope();
+    solver.push_scope();
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x, y, limit], limit)));
+    assert!(is_feasible(solver.solve(i64::MAX)));
+    let assignments = solver.get_integer_assignments();
+    assert!(assignments.get_assigned_value(x) <= 2 && assignments.get_assigned_value(y) <= 2);
+}
+
+//enforces x >= 1 only in its root propagation, and counts how often it is initialised
+struct RootOnlyPropagator {
+    x: IntegerVariable,
+    num_initialisations: Arc<AtomicUsize>,
+}
+
+impl ConstraintProgrammingPropagator for RootOnlyPropagator {
+    fn propagate(&mut self, _domains: &mut DomainManager) -> PropagationStatusCP {
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {}
+
+    fn get_reason_for_propagation(&mut self, _predicate: Predicate) -> PropositionalConjunction {
+        PropositionalConjunction::new()
+    }
+
+    fn priority(&self) -> u32 {
+        0
+    }
+
+    fn name(&self) -> &str {
+        "root only propagator"
+    }
+
+    fn initialise_at_root(&mut self, domains: &mut DomainManager) -> PropagationStatusCP {
+        self.num_initialisations.fetch_add(1, Ordering::Relaxed);
+        self.debug_propagate_from_scratch(domains)
+    }
+
+    fn debug_propagate_from_scratch(&self, domains: &mut DomainManager) -> PropagationStatusCP {
+        domains.tighten_lower_bound(self.x, 1);
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+//the selector of the scope is an assumption, so restoring the root undoes the root propagation of the guarded propagator
+#[test]
+fn guarded_propagators_are_initialised_again_after_backtracking_past_the_selector() {
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let num_initialisations = Arc::new(AtomicUsize::new(0));
+
+    solver.push_scope();
+    solver.add_propagator(Box::new(RootOnlyPropagator {
+        x,
+        num_initialisations: Arc::clone(&num_initialisations),
+    }));
+    for expected_num_initialisations in 1..=2 {
+        assert!(is_feasible(solver.solve(i64::MAX)));
+        assert!(solver.get_integer_assignments().get_assigned_value(x) >= 1);
+        assert_eq!(
+            expected_num_initialisations,
+            num_initialisations.load(Ordering::Relaxed)
+        );
+        solver.restore_state_at_root();
+    }
+
+    //once the scope is popped, the propagator is not initialised again
+    solver.pop_scope();
+    assert!(is_feasible(solver.solve(i64::MAX)));
+    assert_eq!(2, num_initialisations.load(Ordering::Relaxed));
+}
+
+#[test]
+fn random_push_and_pop_sequences_match_brute_force() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for _ in 0..20 {
+        let mut solver = create_solver();
+        let variables: Vec<PropositionalVariable> = (0..10)
+            .map(|_| solver.create_new_propositional_variable())
+            .collect();
+
+        //the clauses of each open scope, where the zero-th entry contains the clauses outside of any scope
+        let mut scopes: Vec<Vec<Vec<Literal>>> = vec![vec![]];
+        for _ in 0..15 {
+            let clause = random_clause(&mut rng, &variables);
+            solver.add_permanent_clause(clause.clone());
+            scopes[0].push(clause);
+        }
+
+        for _ in 0..30 {
+            match rng.gen_range(0..3) {
+                0 => {
+                    solver.push_scope();
+                    scopes.push(vec![]);
+                }
+                1 if scopes.len() > 1 => {
+                    solver.pop_scope();
+                    scopes.pop();
+                }
+                _ => {
+                    for _ in 0..rng.gen_range(1..8) {
+                        let clause = random_clause(&mut rng, &variables);
+                        solver.add_permanent_clause(clause.clone());
+                        scopes.last_mut().unwrap().push(clause);
+                    }
+                }
+            }
+
+            let flag = solver.solve(i64::MAX);
+            let active_clauses: Vec<Vec<Literal>> = scopes.iter().flatten().cloned().collect();
+            if is_feasible(flag) {
+                let assignments = solver.get_propositional_assignments();
+                assert!(active_clauses.iter().all(|clause| clause
+                    .iter()
+                    .any(|l| assignments.is_literal_assigned_true(*l))));
+                solver.restore_state_at_root();
+            } else if solver.get_state().is_infeasible_under_assumptions() {
+                assert!(!is_satisfiable_brute_force(&variables, &active_clauses));
+                solver.restore_state_at_root();
+            } else {
+                //infeasible without any scope, from now on everything is infeasible
+                assert!(!is_satisfiable_brute_force(&variables, &active_clauses));
+                break;
+            }
+        }
+    }
+}
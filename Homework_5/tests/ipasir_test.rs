@@ -0,0 +1,150 @@
+use pumpkin::ipasir::{
+    ipasir_add, ipasir_failed, ipasir_init, ipasir_release, ipasir_solve, ipasir_val, IpasirResult,
+    IpasirSolver,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn is_satisfied(clause: &[i32], assignment: &[bool]) -> bool {
+    clause
+        .iter()
+        .any(|literal| assignment[literal.unsigned_abs() as usize - 1] == (*literal > 0))
+}
+
+//returns true if some assignment of the variables satisfies the clauses and the assumptions
+fn brute_force_is_satisfiable(
+    num_variables: usize,
+    clauses: &[Vec<i32>],
+    assumptions: &[i32],
+) -> bool {
+    (0..1u32 << num_variables).any(|bits| {
+        let assignment: Vec<bool> = (0..num_variables).map(|i| bits & (1 << i) != 0).collect();
+        clauses
+            .iter()
+            .all(|clause| is_satisfied(clause, &assignment))
+            && assumptions
+                .iter()
+                .all(|assumption| is_satisfied(&[*assumption], &assignment))
+    })
+}
+
+fn random_literal(rng: &mut StdRng, num_variables: usize) -> i32 {
+    let variable = rng.gen_range(1..=num_variables as i32);
+    if rng.gen_bool(0.5) {
+        variable
+    } else {
+        -variable
+    }
+}
+
+#[test]
+fn incremental_solving_matches_brute_force() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let num_variables = 8;
+    for _ in 0..20 {
+        let mut solver = IpasirSolver::new();
+        let mut clauses: Vec<Vec<i32>> = vec![];
+        for _ in 0..12 {
+            //a few clauses are added between calls to solve
+            for _ in 0..3 {
+                let clause: Vec<i32> = (0..rng.gen_range(1..=3))
+                    .map(|_| random_literal(&mut rng, num_variables))
+                    .collect();
+                for literal in &clause {
+                    solver.add(*literal);
+                }
+                solver.add(0);
+                clauses.push(clause);
+            }
+
+            let assumptions: Vec<i32> = (0..rng.gen_range(0..=4))
+                .map(|_| random_literal(&mut rng, num_variables))
+                .collect();
+            for assumption in &assumptions {
+                solver.assume(*assumption);
+            }
+
+            let is_satisfiable = brute_force_is_satisfiable(num_variables, &clauses, &assumptions);
+            match solver.solve() {
+                IpasirResult::Satisfiable => {
+                    assert!(is_satisfiable);
+                    let assignment: Vec<bool> = (1..=num_variables as i32)
+                        .map(|variable| solver.value(variable) == variable)
+                        .collect();
+                    assert!(clauses
+                        .iter()
+                        .all(|clause| is_satisfied(clause, &assignment)));
+                    assert!(assumptions.iter().all(|a| solver.value(*a) == *a));
+                }
+                IpasirResult::Unsatisfiable => {
+                    assert!(!is_satisfiable);
+                    //the failed assumptions alone are already unsatisfiable
+                    let failed_assumptions: Vec<i32> = assumptions
+                        .iter()
+                        .copied()
+                        .filter(|assumption| solver.failed(*assumption))
+                        .collect();
+                    assert!(!brute_force_is_satisfiable(
+                        num_variables,
+                        &clauses,
+                        &failed_assumptions
+                    ));
+                }
+                IpasirResult::Interrupted => panic!("Solving was not expected to be interrupted."),
+            }
+        }
+    }
+}
+
+#[test]
+fn failed_assumptions_exclude_irrelevant_assumptions() {
+    let mut solver = IpasirSolver::new();
+    for clause in [[-1, 2], [-2, 3]] {
+        for literal in clause {
+            solver.add(literal);
+        }
+        solver.add(0);
+    }
+
+    for assumption in [1, 4, -3] {
+        solver.assume(assumption);
+    }
+    assert_eq!(solver.solve(), IpasirResult::Unsatisfiable);
+    assert!(solver.failed(1));
+    assert!(solver.failed(-3));
+    assert!(!solver.failed(4));
+
+    //the assumptions were cleared by the previous call
+    assert_eq!(solver.solve(), IpasirResult::Satisfiable);
+}
+
+#[test]
+fn terminate_callback_interrupts_solving() {
+    let mut solver = IpasirSolver::new();
+    solver.add(1);
+    solver.add(2);
+    solver.add(0);
+    solver.set_terminate(Some(Box::new(|| true)));
+    assert_eq!(solver.solve(), IpasirResult::Interrupted);
+
+    solver.set_terminate(None);
+    assert_eq!(solver.solve(), IpasirResult::Satisfiable);
+}
+
+#[test]
+fn c_interface_solves_incrementally() {
+    unsafe {
+        let solver = ipasir_init();
+        for literal in [1, -2, 0, 2, 0] {
+            ipasir_add(solver, literal);
+        }
+        assert_eq!(ipasir_solve(solver), 10);
+        assert_eq!(ipasir_val(solver, 1), 1);
+        assert_eq!(ipasir_val(solver, -2), 2);
+
+        ipasir_add(solver, -1);
+        ipasir_add(solver, 0);
+        assert_eq!(ipasir_solve(solver), 20);
+        assert_eq!(ipasir_failed(solver, 1), 0);
+        ipasir_release(solver);
+    }
+}
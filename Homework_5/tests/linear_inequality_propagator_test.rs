@@ -30,6 +30,7 @@ fn test_variables_are_registered_for_bounds_changes() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_mut_passed, clippy::useless_vec)]
 fn test_lower_bound_changes_causes_enqueue() {
     let mut assignment = AssignmentsInteger::new();
 
@@ -124,6 +125,47 @@ fn test_conflict_is_detected_3() {
     test_propagation_scenario_with_conflict(weights, c, initial_domains);
 }
 
+#[test]
+fn test_slack_is_restored_when_backtracking() {
+    let mut assignment = AssignmentsInteger::new();
+    let x = assignment.grow(0, 10);
+    let y = assignment.grow(0, 2);
+    let mut propagator = LinearInequalityPropagator::new(vec![2, 5], vec![x, y], 12);
+
+    let mut domains = DomainManager::new(0, &mut assignment);
+    assert_eq!(
+        PropagationStatusCP::NoConflictDetected,
+        propagator.initialise_at_root(&mut domains)
+    );
+    assert_eq!(1, assignment.get_lower_bound(x));
+
+    // decreasing the upper bound of x at the first decision level forces y to be at least one
+    assignment.increase_decision_level();
+    let _ = assignment.tighten_upper_bound_no_notify(x, 5, None);
+    let mut domains = DomainManager::new(0, &mut assignment);
+    // x and y are the first and second variable watched for upper bound changes
+    propagator.notify_upper_bound_change_with_local_id(0, x.into(), 10, 5, &domains);
+    assert_eq!(
+        PropagationStatusCP::NoConflictDetected,
+        propagator.propagate(&mut domains)
+    );
+    assert_eq!(1, assignment.get_lower_bound(y));
+
+    // after backtracking to the root, the trailed slack no longer includes the change of x
+    assignment.synchronise(0);
+
+    assignment.increase_decision_level();
+    let _ = assignment.tighten_upper_bound_no_notify(y, 1, None);
+    let mut domains = DomainManager::new(0, &mut assignment);
+    propagator.notify_upper_bound_change_with_local_id(1, y.into(), 2, 1, &domains);
+    assert_eq!(
+        PropagationStatusCP::NoConflictDetected,
+        propagator.propagate(&mut domains)
+    );
+    assert_eq!(4, assignment.get_lower_bound(x));
+    assert_eq!(10, assignment.get_upper_bound(x));
+}
+
 fn test_propagation_scenario_no_conflict(
     weights: Vec<i64>,
     c: i64,
@@ -0,0 +1,183 @@
+use pumpkin::{
+    basic_types::{FileFormat, Instance, PumpkinExecutionFlag},
+    engine::{LnsNeighbourhood, LnsOptions, Pumpkin, SolverOptions},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+//a random instance with hard clauses, which are satisfied by a hidden assignment, and weighted soft clauses
+//  returns the contents of the wcnf file, and the optimal objective value computed by enumerating all assignments
+fn create_random_wcnf(rng: &mut StdRng, num_variables: usize) -> (String, u64) {
+    let top_weight = 1000;
+    let random_clause = |rng: &mut StdRng, length: usize| -> Vec<i64> {
+        (0..length)
+            .map(|_| {
+                let variable = rng.gen_range(1..=num_variables) as i64;
+                if rng.gen_bool(0.5) {
+                    variable
+                } else {
+                    -variable
+                }
+            })
+            .collect()
+    };
+    let is_satisfied = |clause: &[i64], assignment: u32| {
+        clause.iter().any(|literal| {
+            ((assignment >> (literal.unsigned_abs() - 1)) & 1 == 1) == (*literal > 0)
+        })
+    };
+
+    let hidden_assignment: u32 = rng.gen_range(0..(1 << num_variables));
+    let mut hard_clauses = vec![];
+    while hard_clauses.len() < num_variables {
+        let clause = random_clause(rng, 3);
+        if is_satisfied(&clause, hidden_assignment) {
+            hard_clauses.push(clause);
+        }
+    }
+    let soft_clauses: Vec<(u64, Vec<i64>)> = (0..2 * num_variables)
+        .map(|_| {
+            let length = rng.gen_range(1..=2);
+            (rng.gen_range(1..10), random_clause(rng, length))
+        })
+        .collect();
+
+    let optimum = (0..(1u32 << num_variables))
+        .filter(|assignment| {
+            hard_clauses
+                .iter()
+                .all(|clause| is_satisfied(clause, *assignment))
+        })
+        .map(|assignment| {
+            soft_clauses
+                .iter()
+                .filter(|(_, clause)| !is_satisfied(clause, assignment))
+                .map(|(weight, _)| weight)
+                .sum::<u64>()
+        })
+        .min()
+        .unwrap();
+
+    let mut contents = format!(
+        "p wcnf {} {} {}\n",
+        num_variables,
+        hard_clauses.len() + soft_clauses.len(),
+        top_weight
+    );
+    let clauses = hard_clauses
+        .iter()
+        .map(|clause| (top_weight, clause))
+        .chain(
+            soft_clauses
+                .iter()
+                .map(|(weight, clause)| (*weight, clause)),
+        );
+    for (weight, clause) in clauses {
+        contents.push_str(&format!("{} ", weight));
+        for literal in clause {
+            contents.push_str(&format!("{} ", literal));
+        }
+        contents.push_str("0\n");
+    }
+    (contents, optimum)
+}
+
+//reads the contents into both a solver and an instance, where the instance is used to check the objective value of solutions
+fn read_wcnf(name: &str, contents: &str) -> (Pumpkin, Instance) {
+    let file_location = std::env::temp_dir().join(format!(
+        "pumpkin_lns_test_{}_{}.wcnf",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&file_location, contents).unwrap();
+    let file_location = file_location.to_str().unwrap();
+
+    let mut pumpkin = Pumpkin::new(&SolverOptions::default());
+    pumpkin.disable_logging();
+    let mut instance = Instance::new();
+    let results = (
+        pumpkin.read_file(file_location, FileFormat::WcnfDimacsPLine),
+        instance.read_file(file_location, FileFormat::WcnfDimacsPLine),
+    );
+    std::fs::remove_file(file_location).unwrap();
+    results.0.unwrap();
+    results.1.unwrap();
+    (pumpkin, instance)
+}
+
+#[test]
+fn lns_finds_the_optimum_of_random_instances() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let lns_options = [
+        LnsOptions::complete_search(),
+        LnsOptions {
+            num_conflicts_per_iteration: 5,
+            ..Default::default()
+        },
+        LnsOptions {
+            neighbourhood: LnsNeighbourhood::Sequential,
+            num_conflicts_per_iteration: 5,
+            ..Default::default()
+        },
+    ];
+
+    for iteration in 0..10 {
+        let (contents, optimum) = create_random_wcnf(&mut rng, 12);
+        for (index, lns_options) in lns_options.iter().enumerate() {
+            let (mut pumpkin, instance) =
+                read_wcnf(&format!("random_{}_{}", iteration, index), &contents);
+
+            //improving solutions are reported with strictly decreasing objective values
+            //  the selector variables of soft clauses may be true for satisfied clauses, so the values bound the violation from above
+            let mut objective_values = vec![];
+            let execution_flag = pumpkin
+                .solve_with_lns(lns_options, |solution, objective_value| {
+                    assert!(!instance.are_hard_clauses_violated(solution));
+                    assert!(instance.compute_soft_clause_violation(solution) <= objective_value);
+                    objective_values.push(objective_value);
+                })
+                .unwrap();
+
+            let PumpkinExecutionFlag::Optimal { optimal_solution } = execution_flag else {
+                panic!("Expected the search to prove optimality.");
+            };
+            assert_eq!(
+                instance.compute_soft_clause_violation(&optimal_solution),
+                optimum
+            );
+            assert_eq!(*objective_values.last().unwrap(), optimum);
+            assert!(objective_values.windows(2).all(|pair| pair[0] > pair[1]));
+
+            //the objective is bounded by a single propagator, however many improvements are found
+            let num_propagators: u64 = pumpkin
+                .get_statistics()
+                .propagators
+                .iter()
+                .map(|propagator| propagator.num_propagators)
+                .sum();
+            assert_eq!(num_propagators, 1);
+        }
+    }
+}
+
+#[test]
+fn infeasible_hard_clauses_are_reported() {
+    let (mut pumpkin, _) = read_wcnf(
+        "infeasible",
+        "p wcnf 2 5 10\n10 1 2 0\n10 1 -2 0\n10 -1 2 0\n10 -1 -2 0\n3 1 0\n",
+    );
+    assert!(matches!(
+        pumpkin.solve_with_lns(&LnsOptions::default(), |_, _| {}),
+        Ok(PumpkinExecutionFlag::Infeasible)
+    ));
+}
+
+#[test]
+fn weights_beyond_the_supported_range_are_rejected() {
+    let (mut pumpkin, _) = read_wcnf(
+        "large_weights",
+        "p wcnf 2 2 4000000000\n2000000000 1 0\n2000000000 2 0\n",
+    );
+    assert!(pumpkin
+        .solve_with_lns(&LnsOptions::default(), |_, _| {})
+        .is_err());
+}
@@ -0,0 +1,146 @@
+use pumpkin::{
+    basic_types::{CSPSolverExecutionFlag, Literal, PropositionalVariable, Random},
+    engine::{AssignmentsPropositional, ConstraintSatisfactionSolver, LocalSearch, SolverOptions},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+//random 3-SAT clauses that are satisfied by a hidden assignment, given as indices of variables starting at one, negative for negated literals
+fn create_planted_3_sat_clauses(
+    rng: &mut StdRng,
+    num_variables: usize,
+    num_clauses: usize,
+) -> Vec<Vec<i32>> {
+    let hidden_assignment: Vec<bool> = (0..num_variables).map(|_| rng.gen_bool(0.5)).collect();
+    let mut clauses = vec![];
+    while clauses.len() < num_clauses {
+        let clause: Vec<i32> = (0..3)
+            .map(|_| {
+                let variable = rng.gen_range(1..=num_variables as i32);
+                if rng.gen_bool(0.5) {
+                    variable
+                } else {
+                    -variable
+                }
+            })
+            .collect();
+        if clause
+            .iter()
+            .any(|v| hidden_assignment[v.unsigned_abs() as usize - 1] == (*v > 0))
+        {
+            clauses.push(clause);
+        }
+    }
+    clauses
+}
+
+//variable zero is left out, like the root variable of the solver
+fn to_literals(clause: &[i32]) -> Vec<Literal> {
+    clause
+        .iter()
+        .map(|v| Literal::new(PropositionalVariable::new(v.unsigned_abs()), *v > 0))
+        .collect()
+}
+
+fn is_satisfied(clauses: &[Vec<i32>], values: &[bool]) -> bool {
+    clauses.iter().all(|clause| {
+        clause
+            .iter()
+            .any(|v| values[v.unsigned_abs() as usize] == (*v > 0))
+    })
+}
+
+fn create_assignments(num_variables: usize) -> AssignmentsPropositional {
+    let mut assignments = AssignmentsPropositional::new();
+    for _ in 0..=num_variables {
+        assignments.grow();
+    }
+    assignments
+}
+
+#[test]
+fn local_search_satisfies_planted_instances() {
+    let mut rng = StdRng::seed_from_u64(44);
+    let mut random = Random::new(0);
+    let num_variables = 100;
+    for _ in 0..5 {
+        let clauses = create_planted_3_sat_clauses(&mut rng, num_variables, 400);
+        let literals: Vec<Vec<Literal>> =
+            clauses.iter().map(|clause| to_literals(clause)).collect();
+        let assignments = create_assignments(num_variables);
+        let mut local_search = LocalSearch::new(
+            literals.iter().map(|clause| clause.as_slice()),
+            &assignments,
+        );
+
+        let result = local_search.run(&vec![false; num_variables + 1], 1_000_000, &mut random);
+        assert_eq!(result.num_unsatisfied_clauses, 0);
+        assert!(is_satisfied(&clauses, &result.best_values));
+    }
+}
+
+#[test]
+fn local_search_keeps_root_assignments() {
+    //x1 is true at the root, so the first clause is left out and the second clause can only be satisfied by x3
+    let clauses = [vec![1, 2], vec![-1, 3], vec![-2, -3]];
+    let literals: Vec<Vec<Literal>> = clauses.iter().map(|clause| to_literals(clause)).collect();
+    let mut assignments = create_assignments(3);
+    assignments.enqueue_decision_literal(to_literals(&[1])[0]);
+    let mut local_search = LocalSearch::new(
+        literals.iter().map(|clause| clause.as_slice()),
+        &assignments,
+    );
+    assert_eq!(local_search.num_clauses(), 2);
+
+    let result = local_search.run(&[false, true, true, false], 1000, &mut Random::new(3));
+    assert_eq!(result.num_unsatisfied_clauses, 0);
+    assert!(result.best_values[3]);
+    assert!(!result.best_values[2]);
+
+    //a clause falsified at the root can never be satisfied
+    let mut local_search =
+        LocalSearch::new([to_literals(&[-1]).as_slice()].into_iter(), &assignments);
+    let result = local_search.run(&[false; 4], 1000, &mut Random::new(3));
+    assert_eq!(result.num_unsatisfied_clauses, 1);
+}
+
+#[test]
+fn solutions_found_by_local_search_need_no_conflicts() {
+    let mut rng = StdRng::seed_from_u64(45);
+    let num_variables = 150;
+    let clauses = create_planted_3_sat_clauses(&mut rng, num_variables, 600);
+
+    let options = SolverOptions::builder()
+        .local_search(true)
+        .num_local_search_flips(1_000_000)
+        .build();
+    let mut solver = ConstraintSatisfactionSolver::new(&options);
+    let variables: Vec<PropositionalVariable> = (0..num_variables)
+        .map(|_| solver.create_new_propositional_variable())
+        .collect();
+    for clause in &clauses {
+        solver.add_permanent_clause(
+            clause
+                .iter()
+                .map(|v| Literal::new(variables[v.unsigned_abs() as usize - 1], *v > 0))
+                .collect(),
+        );
+    }
+
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Feasible
+    ));
+    let statistics = solver.get_statistics();
+    assert_eq!(statistics.num_conflicts, 0);
+    assert!(statistics.num_local_search_flips > 0);
+
+    let assignments = solver.get_propositional_assignments();
+    let values: Vec<bool> = std::iter::once(true)
+        .chain(
+            variables
+                .iter()
+                .map(|variable| assignments.is_variable_assigned_true(*variable)),
+        )
+        .collect();
+    assert!(is_satisfied(&clauses, &values));
+}
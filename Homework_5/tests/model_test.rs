@@ -0,0 +1,239 @@
+use pumpkin::{
+    basic_types::{Function, ModelExecutionFlag},
+    engine::{LearnedClauseSortingStrategy, Model, Pumpkin, SolverOptions},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn create_model() -> Model {
+    Model::new(SolverOptions::builder().time_limit_in_seconds(10).build())
+}
+
+#[test]
+fn builder_overrides_only_the_given_options() {
+    let options = SolverOptions::builder()
+        .time_limit_in_seconds(5)
+        .learned_clause_sorting_strategy(LearnedClauseSortingStrategy::Activity)
+        .build();
+    let default_options = SolverOptions::default();
+
+    assert_eq!(options.time_limit_in_seconds, 5);
+    assert_eq!(
+        options.learned_clause_sorting_strategy,
+        LearnedClauseSortingStrategy::Activity
+    );
+    assert_eq!(
+        options.num_conflicts_per_restart,
+        default_options.num_conflicts_per_restart
+    );
+    assert_eq!(options.random_seed, default_options.random_seed);
+}
+
+#[test]
+fn options_follow_the_command_line_arguments() {
+    let mut argument_handler = Pumpkin::create_argument_handler();
+    let default_options = SolverOptions::from_argument_handler(&argument_handler);
+    assert_eq!(
+        default_options.threshold_learned_clauses,
+        SolverOptions::default().threshold_learned_clauses
+    );
+
+    argument_handler
+        .parse_arguments(["-random-seed=7", "-time-limit=3"])
+        .unwrap();
+    let options = SolverOptions::from_argument_handler(&argument_handler);
+    assert_eq!(options.random_seed, 7);
+    assert_eq!(options.time_limit_in_seconds, 3);
+}
+
+#[test]
+fn clauses_and_linear_constraints_are_satisfied() {
+    let mut model = create_model();
+    let a = model.new_bool();
+    let b = model.new_bool();
+    let x = model.new_int(0, 10).unwrap();
+    let y = model.new_int(2, 8).unwrap();
+
+    model.add_clause(vec![a, b]);
+    model.add_clause(vec![!a]);
+    //x + y >= 12 and x - y <= -1
+    model.add_linear(vec![(-1, x), (-1, y)], -12);
+    model.add_linear(vec![(1, x), (-1, y)], -1);
+
+    let ModelExecutionFlag::Feasible { feasible_solution } = model.solve() else {
+        panic!("Expected a feasible solution.");
+    };
+    assert!(!feasible_solution.get_literal_value(a));
+    assert!(feasible_solution.get_literal_value(b));
+    assert!(feasible_solution[x] + feasible_solution[y] >= 12);
+    assert!(feasible_solution[x] < feasible_solution[y]);
+}
+
+#[test]
+fn pigeons_do_not_fit_in_fewer_holes() {
+    let mut model = create_model();
+    let pigeons: Vec<_> = (0..4).map(|_| model.new_int(0, 2).unwrap()).collect();
+    model.add_all_different(pigeons.clone());
+    assert!(matches!(model.solve(), ModelExecutionFlag::Infeasible));
+
+    let mut model = create_model();
+    let pigeons: Vec<_> = (0..3).map(|_| model.new_int(0, 2).unwrap()).collect();
+    model.add_all_different(pigeons.clone());
+    let ModelExecutionFlag::Feasible { feasible_solution } = model.solve() else {
+        panic!("Expected a feasible solution.");
+    };
+    let mut values: Vec<i32> = pigeons.iter().map(|p| feasible_solution[*p]).collect();
+    values.sort();
+    assert_eq!(values, vec![0, 1, 2]);
+}
+
+#[test]
+fn empty_clause_makes_the_model_infeasible() {
+    let mut model = create_model();
+    let a = model.new_bool();
+    model.add_clause(vec![a]);
+    model.add_clause(vec![]);
+    assert!(matches!(model.solve(), ModelExecutionFlag::Infeasible));
+}
+
+#[test]
+fn minimize_is_accepted_once_the_model_is_infeasible() {
+    //the last clause leads to a conflict while propagating at the root
+    let mut model = create_model();
+    let a = model.new_bool();
+    let b = model.new_bool();
+    let x = model.new_int(0, 5).unwrap();
+    model.add_clause(vec![a, b]);
+    model.add_clause(vec![a, !b]);
+    model.add_clause(vec![!a]);
+
+    let mut objective = Function::new();
+    objective.add_weighted_integer(x, 2);
+    objective.add_weighted_literal(model.get_lower_bound_literal(x, 3), 1);
+    model.minimize(objective).unwrap();
+    assert!(matches!(model.solve(), ModelExecutionFlag::Infeasible));
+}
+
+#[test]
+fn minimize_finds_the_optimum_of_random_models() {
+    let mut rng = StdRng::seed_from_u64(36);
+    for _ in 0..20 {
+        let num_bools = 4;
+        let weights: Vec<u64> = (0..num_bools).map(|_| rng.gen_range(0..5)).collect();
+        let integer_weight: u64 = rng.gen_range(1..4);
+        let clauses: Vec<Vec<(usize, bool)>> = (0..5)
+            .map(|_| {
+                (0..2)
+                    .map(|_| (rng.gen_range(0..num_bools), rng.gen_bool(0.5)))
+                    .collect()
+            })
+            .collect();
+        let is_feasible = |assignment: u32| {
+            clauses.iter().all(|clause| {
+                clause
+                    .iter()
+                    .any(|(index, is_positive)| ((assignment >> index) & 1 == 1) == *is_positive)
+            })
+        };
+        let expected_optimum = (0..(1u32 << num_bools))
+            .filter(|assignment| is_feasible(*assignment))
+            .map(|assignment| {
+                let bool_cost: u64 = (0..num_bools)
+                    .filter(|index| (assignment >> index) & 1 == 1)
+                    .map(|index| weights[index])
+                    .sum();
+                let integer_value = ((assignment & 1) + ((assignment >> 1) & 1)).max(1) as u64;
+                bool_cost + integer_weight * integer_value + 3
+            })
+            .min();
+
+        let mut model = create_model();
+        let bools: Vec<_> = (0..num_bools).map(|_| model.new_bool()).collect();
+        let integer = model.new_int(1, 5).unwrap();
+        for clause in &clauses {
+            model.add_clause(
+                clause
+                    .iter()
+                    .map(|(index, is_positive)| {
+                        if *is_positive {
+                            bools[*index]
+                        } else {
+                            !bools[*index]
+                        }
+                    })
+                    .collect(),
+            );
+        }
+        //the integer is at least two when the first two literals are true
+        let at_least_two = model.get_lower_bound_literal(integer, 2);
+        model.add_clause(vec![!bools[0], !bools[1], at_least_two]);
+
+        let mut objective = Function::new();
+        for (literal, weight) in bools.iter().zip(&weights) {
+            objective.add_weighted_literal(*literal, *weight);
+        }
+        objective.add_weighted_integer(integer, integer_weight);
+        //a literal that was not created with 'new_bool', which never holds in an optimal solution
+        objective.add_weighted_literal(model.get_lower_bound_literal(integer, 3), 2);
+        objective.add_constant_term(3);
+        model.minimize(objective).unwrap();
+
+        match model.solve() {
+            ModelExecutionFlag::Optimal { optimal_solution } => {
+                assert_eq!(
+                    model.get_objective_value(&optimal_solution),
+                    expected_optimum
+                );
+            }
+            ModelExecutionFlag::Infeasible => assert_eq!(expected_optimum, None),
+            _ => panic!("Expected the search to complete."),
+        }
+    }
+}
+
+#[test]
+fn optimisation_models_can_be_solved_again() {
+    //x + y >= 3 over [0, 5], minimising x
+    let mut model = create_model();
+    let x = model.new_int(0, 5).unwrap();
+    let y = model.new_int(0, 5).unwrap();
+    model.add_linear(vec![(-1, x), (-1, y)], -3);
+    let mut objective = Function::new();
+    objective.add_weighted_integer(x, 1);
+    model.minimize(objective).unwrap();
+
+    for _ in 0..2 {
+        match model.solve() {
+            ModelExecutionFlag::Optimal { optimal_solution } => {
+                assert_eq!(model.get_objective_value(&optimal_solution), Some(0));
+            }
+            _ => panic!("Expected an optimal solution."),
+        }
+    }
+
+    //the bounds found by earlier calls do not restrict constraints added later
+    let at_least_one = model.get_lower_bound_literal(x, 1);
+    model.add_clause(vec![at_least_one]);
+    match model.solve() {
+        ModelExecutionFlag::Optimal { optimal_solution } => {
+            assert_eq!(model.get_objective_value(&optimal_solution), Some(1));
+        }
+        _ => panic!("Expected an optimal solution."),
+    }
+}
+
+#[test]
+fn constant_objectives_are_optimal() {
+    let mut model = create_model();
+    let x = model.new_int(0, 5).unwrap();
+    let mut objective = Function::new();
+    objective.add_weighted_integer(x, 0);
+    objective.add_constant_term(4);
+    model.minimize(objective).unwrap();
+
+    match model.solve() {
+        ModelExecutionFlag::Optimal { optimal_solution } => {
+            assert_eq!(model.get_objective_value(&optimal_solution), Some(4));
+        }
+        _ => panic!("Expected an optimal solution."),
+    }
+}
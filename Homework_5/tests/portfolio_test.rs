@@ -0,0 +1,193 @@
+use pumpkin::{
+    basic_types::{CSPSolverExecutionFlag, FileFormat, Instance, Literal, PumpkinExecutionFlag},
+    engine::{
+        ClauseSharing, ConstraintSatisfactionSolver, LearnedClauseSortingStrategy, PortfolioSolver,
+        Pumpkin, SolverOptions,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+//writes the clauses in the DIMACS format to a file in the temporary directory and returns its location
+fn write_cnf_file(name: &str, num_variables: usize, clauses: &[Vec<i64>]) -> String {
+    let file_location = std::env::temp_dir().join(format!(
+        "pumpkin_portfolio_test_{}_{}.cnf",
+        std::process::id(),
+        name
+    ));
+    let mut contents = format!("p cnf {} {}\n", num_variables, clauses.len());
+    for clause in clauses {
+        for literal in clause {
+            contents.push_str(&format!("{} ", literal));
+        }
+        contents.push_str("0\n");
+    }
+    std::fs::write(&file_location, contents).unwrap();
+    file_location.to_str().unwrap().to_string()
+}
+
+//the pigeons are placed in the holes, at most one per hole
+fn create_pigeon_hole_clauses(num_pigeons: i64, num_holes: i64) -> Vec<Vec<i64>> {
+    let variable = |pigeon: i64, hole: i64| pigeon * num_holes + hole + 1;
+    let mut clauses: Vec<Vec<i64>> = (0..num_pigeons)
+        .map(|pigeon| (0..num_holes).map(|hole| variable(pigeon, hole)).collect())
+        .collect();
+    for hole in 0..num_holes {
+        for first in 0..num_pigeons {
+            for second in (first + 1)..num_pigeons {
+                clauses.push(vec![-variable(first, hole), -variable(second, hole)]);
+            }
+        }
+    }
+    clauses
+}
+
+#[test]
+fn solvers_can_be_sent_to_other_threads() {
+    fn assert_send<T: Send>() {}
+    assert_send::<ConstraintSatisfactionSolver>();
+    assert_send::<Pumpkin>();
+    assert_send::<PortfolioSolver>();
+}
+
+#[test]
+fn portfolio_proves_infeasibility() {
+    let file_location = write_cnf_file("pigeon_hole", 42, &create_pigeon_hole_clauses(7, 6));
+    let mut portfolio = PortfolioSolver::new(&SolverOptions::default(), 4);
+    let result = portfolio.read_file(&file_location, FileFormat::CnfDimacsPLine);
+    std::fs::remove_file(&file_location).unwrap();
+    result.unwrap();
+
+    assert!(matches!(
+        portfolio.solve(),
+        PumpkinExecutionFlag::Infeasible
+    ));
+    assert!(portfolio.get_winner_index().unwrap() < 4);
+}
+
+#[test]
+fn portfolio_solutions_satisfy_the_clauses() {
+    let mut rng = StdRng::seed_from_u64(41);
+    for iteration in 0..5 {
+        //the clauses are satisfied by a hidden assignment, so the instance is satisfiable
+        let num_variables = 60;
+        let hidden_assignment: Vec<bool> = (0..num_variables).map(|_| rng.gen_bool(0.5)).collect();
+        let mut clauses = vec![];
+        while clauses.len() < 250 {
+            let clause: Vec<i64> = (0..3)
+                .map(|_| {
+                    let index = rng.gen_range(0..num_variables);
+                    if rng.gen_bool(0.5) {
+                        index as i64 + 1
+                    } else {
+                        -(index as i64 + 1)
+                    }
+                })
+                .collect();
+            if clause.iter().any(|literal| {
+                hidden_assignment[literal.unsigned_abs() as usize - 1] == (*literal > 0)
+            }) {
+                clauses.push(clause);
+            }
+        }
+
+        let file_location =
+            write_cnf_file(&format!("random_{}", iteration), num_variables, &clauses);
+        let mut instance = Instance::new();
+        instance
+            .read_file(&file_location, FileFormat::CnfDimacsPLine)
+            .unwrap();
+        let mut portfolio = PortfolioSolver::new(&SolverOptions::default(), 3);
+        let result = portfolio.read_file(&file_location, FileFormat::CnfDimacsPLine);
+        std::fs::remove_file(&file_location).unwrap();
+        result.unwrap();
+
+        let PumpkinExecutionFlag::Feasible { feasible_solution } = portfolio.solve() else {
+            panic!("Expected a feasible solution.");
+        };
+        assert!(!instance.are_hard_clauses_violated(&feasible_solution));
+    }
+}
+
+#[test]
+fn workers_use_different_options() {
+    let options = SolverOptions::builder().random_seed(-2).build();
+    let first_options = PortfolioSolver::get_worker_options(&options, 0);
+    assert_eq!(first_options.random_seed, options.random_seed);
+    assert_eq!(
+        first_options.num_conflicts_per_restart,
+        options.num_conflicts_per_restart
+    );
+
+    let worker_options: Vec<SolverOptions> = (1..4)
+        .map(|index| PortfolioSolver::get_worker_options(&options, index))
+        .collect();
+    for (index, options) in worker_options.iter().enumerate() {
+        assert!(options.random_seed >= 0);
+        assert!(worker_options[(index + 1)..]
+            .iter()
+            .all(|other_options| other_options.random_seed != options.random_seed));
+    }
+    assert_eq!(
+        worker_options[0].learned_clause_sorting_strategy,
+        LearnedClauseSortingStrategy::Activity
+    );
+    assert!(worker_options[1].initial_phase);
+}
+
+#[test]
+fn short_learned_clauses_are_shared_with_the_other_solvers() {
+    let mut group = ClauseSharing::create_group(3, 3, 2);
+    let literals: Vec<Literal> = (0..4)
+        .map(|index| Literal::u32_to_literal(2 * index + 2))
+        .collect();
+
+    group[0].export_learned_clause(&literals[..2], 2);
+    //too many literals or a too large LBD
+    group[0].export_learned_clause(&literals, 2);
+    group[0].export_learned_clause(&literals[..2], 3);
+    //unit clauses are shared regardless of their LBD
+    group[1].export_learned_clause(&literals[..1], 5);
+
+    assert_eq!(group[0].num_exported_clauses, 1);
+    //literals do not implement Debug, so the clauses are compared with '=='
+    assert!(group[0].take_imported_clauses() == vec![literals[..1].to_vec()]);
+    assert!(group[1].take_imported_clauses() == vec![literals[..2].to_vec()]);
+    assert!(
+        group[2].take_imported_clauses() == vec![literals[..2].to_vec(), literals[..1].to_vec()]
+    );
+    assert!(group[2].take_imported_clauses().is_empty());
+    assert_eq!(group[2].num_imported_clauses, 2);
+}
+
+#[test]
+fn shared_clauses_are_imported_when_solving() {
+    let mut group = ClauseSharing::create_group(2, 8, 2);
+    let mut solver = ConstraintSatisfactionSolver::new(&SolverOptions::default());
+    let variables: Vec<Literal> = (0..3)
+        .map(|_| Literal::new(solver.create_new_propositional_variable(), true))
+        .collect();
+    solver.set_clause_sharing(group.pop());
+
+    //the solver prefers false values, so it only assigns literals to true because of the shared clauses
+    group[0].export_learned_clause(&[variables[2]], 1);
+    group[0].export_learned_clause(&[variables[0], variables[1]], 1);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Feasible
+    ));
+    let assignments = solver.get_propositional_assignments();
+    assert!(assignments.is_literal_assigned_true(variables[2]));
+    assert!(
+        assignments.is_literal_assigned_true(variables[0])
+            || assignments.is_literal_assigned_true(variables[1])
+    );
+    assert_eq!(solver.get_statistics().num_imported_shared_clauses, 2);
+
+    //a shared clause that contradicts the root makes the solver infeasible
+    solver.restore_state_at_root();
+    group[0].export_learned_clause(&[!variables[2]], 1);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+}
@@ -0,0 +1,141 @@
+use pumpkin::{
+    basic_types::{FileFormat, Instance, PumpkinError, PumpkinExecutionFlag},
+    engine::{ConstraintSatisfactionSolver, Pumpkin, SolverOptions},
+};
+
+//writes the contents to a file in the temporary directory and returns its location
+fn write_file(name: &str, contents: &str) -> String {
+    let file_location = std::env::temp_dir().join(format!(
+        "pumpkin_error_test_{}_{}",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&file_location, contents).unwrap();
+    file_location.to_str().unwrap().to_string()
+}
+
+fn read_cnf(name: &str, contents: &str) -> Result<Pumpkin, PumpkinError> {
+    let file_location = write_file(name, contents);
+    let mut pumpkin = Pumpkin::new(&SolverOptions::default());
+    let result = pumpkin.read_file(&file_location, FileFormat::CnfDimacsPLine);
+    std::fs::remove_file(file_location).unwrap();
+    result.map(|_| pumpkin)
+}
+
+fn parse_error_position(result: Result<Pumpkin, PumpkinError>) -> (usize, usize) {
+    match result {
+        Err(PumpkinError::Parse { line, column, .. }) => (line, column),
+        Err(error) => panic!("Expected a parse error, found {:?}.", error),
+        Ok(_) => panic!("Expected a parse error, but the file was read."),
+    }
+}
+
+#[test]
+fn cnf_with_comments_and_irregular_spacing_is_read() {
+    let mut pumpkin = read_cnf(
+        "irregular.cnf",
+        "c a comment\n\np cnf 3  2\n1 -2   0\n\n  2 3 -1 0\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        pumpkin.solve(),
+        PumpkinExecutionFlag::Feasible { .. }
+    ));
+}
+
+#[test]
+fn malformed_literal_reports_line_and_column() {
+    let result = read_cnf("malformed.cnf", "c comment\np cnf 2 2\n1 -2 0\n2 x 0\n");
+    assert_eq!(parse_error_position(result), (4, 3));
+}
+
+#[test]
+fn literal_too_large_for_the_solver_is_rejected() {
+    let result = read_cnf("too_large.cnf", "p cnf 2 1\n1 -99999999999 0\n");
+    assert_eq!(parse_error_position(result), (2, 3));
+}
+
+#[test]
+fn malformed_header_is_rejected() {
+    let result = read_cnf("header.cnf", "p wcnf 2 1\n1 2 0\n");
+    assert_eq!(parse_error_position(result), (1, 1));
+
+    let result = read_cnf("header_fields.cnf", "p cnf 2\n1 2 0\n");
+    assert_eq!(parse_error_position(result), (1, 1));
+
+    let result = read_cnf("empty.cnf", "c only a comment\n");
+    assert_eq!(parse_error_position(result), (2, 1));
+}
+
+#[test]
+fn empty_clause_is_rejected() {
+    let result = read_cnf("empty_clause.cnf", "p cnf 2 2\n1 2 0\n  0\n");
+    assert_eq!(parse_error_position(result), (3, 3));
+}
+
+#[test]
+fn missing_file_is_reported() {
+    let mut pumpkin = Pumpkin::new(&SolverOptions::default());
+    let error = pumpkin
+        .read_file("/nonexistent/pumpkin.cnf", FileFormat::CnfDimacsPLine)
+        .unwrap_err();
+    assert!(matches!(error, PumpkinError::Io { .. }));
+}
+
+#[test]
+fn wcnf_instance_is_read() {
+    let file_location = write_file(
+        "instance.wcnf",
+        "c comment\np wcnf 3 3 10\n10 1 2 0\n3 -1 0\n10 -2 3 0\n",
+    );
+    let mut instance = Instance::new();
+    instance
+        .read_file(&file_location, FileFormat::WcnfDimacsPLine)
+        .unwrap();
+    assert_eq!(instance.hard_clauses.len(), 2);
+    assert_eq!(instance.soft_clauses.len(), 1);
+    assert_eq!(instance.soft_clauses[0].weight, 3);
+
+    let mut instance = Instance::new();
+    let error = instance
+        .read_file(&file_location, FileFormat::MaxSAT2022)
+        .unwrap_err();
+    assert!(matches!(error, PumpkinError::UnsupportedFileFormat { .. }));
+    std::fs::remove_file(file_location).unwrap();
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    let mut argument_handler = Pumpkin::create_argument_handler();
+    argument_handler
+        .parse_arguments(["-time-limit=10", "-file-location=a.cnf"])
+        .unwrap();
+    assert_eq!(argument_handler.get_integer_argument("time-limit"), 10);
+
+    for argument in [
+        "-unknown-argument=1",
+        "time-limit=10",
+        "-time-limit",
+        "-time-limit=ten",
+        "-time-limit=-5",
+    ] {
+        let error = argument_handler.parse_arguments([argument]).unwrap_err();
+        assert!(
+            matches!(error, PumpkinError::InvalidArgument { .. }),
+            "{argument}"
+        );
+    }
+    assert_eq!(argument_handler.get_integer_argument("time-limit"), 10);
+}
+
+#[test]
+fn invalid_integer_bounds_are_rejected() {
+    let mut solver = ConstraintSatisfactionSolver::new(&SolverOptions::default());
+    for (lower_bound, upper_bound) in [(3, 3), (5, 2), (-1, 4)] {
+        let error = solver
+            .create_new_integer_variable(lower_bound, upper_bound)
+            .unwrap_err();
+        assert!(matches!(error, PumpkinError::InvalidModel { .. }));
+    }
+    assert!(solver.create_new_integer_variable(2, 3).is_ok());
+}
@@ -0,0 +1,100 @@
+use pumpkin::{
+    basic_types::{Literal, PropositionalVariable},
+    engine::{
+        CPEngineDataStructures, ConstraintSatisfactionSolver, DomainManager, SATCPMediator,
+        SATEngineDataStructures, SolverOptions,
+    },
+    propagators::{ConstraintProgrammingPropagator, LinearLessOrEqualPropagator},
+};
+
+//bounds outside of the initial domain map to the true or false literal, including negative bounds
+#[test]
+fn lower_bound_literals_outside_of_the_domain_are_fixed() {
+    let mut solver = ConstraintSatisfactionSolver::new(&SolverOptions::default());
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let assignments = solver.get_propositional_assignments();
+
+    assert!(assignments.is_literal_assigned_true(solver.get_lower_bound_literal(x, -1)));
+    assert!(assignments.is_literal_assigned_true(solver.get_lower_bound_literal(x, -7)));
+    assert!(assignments.is_literal_assigned_true(solver.get_lower_bound_literal(x, 0)));
+    assert!(assignments.is_literal_assigned_false(solver.get_lower_bound_literal(x, 6)));
+}
+
+//after a chronological backtrack, [x >= 3] is kept on the trail at level 1 while the current level is 2
+//  the propagation y <= 2 from x + y <= 5 is then explained by a literal at level 1, so it is assigned at level 1
+#[test]
+fn cp_propagations_take_the_level_of_their_explanation_after_a_chronological_backtrack() {
+    let options = SolverOptions::default();
+    let mut sat_data_structures = SATEngineDataStructures::new(&options);
+    let mut cp_data_structures = CPEngineDataStructures::new(&options);
+    let mut mediator = SATCPMediator::new();
+
+    let root_variable = mediator.create_new_propositional_variable(&mut sat_data_structures);
+    let true_literal = Literal::new(root_variable, true);
+    let assignments = &mut sat_data_structures.assignments_propositional;
+    assignments.true_literal = true_literal;
+    assignments.false_literal = !true_literal;
+    assignments.enqueue_decision_literal(true_literal);
+    mediator.true_literal = true_literal;
+    mediator.false_literal = !true_literal;
+    assert!(sat_data_structures.propagate_clauses().no_conflict());
+
+    let x = mediator.create_new_integer_variable(
+        0,
+        5,
+        &mut sat_data_structures,
+        &mut cp_data_structures,
+    );
+    let y = mediator.create_new_integer_variable(
+        0,
+        5,
+        &mut sat_data_structures,
+        &mut cp_data_structures,
+    );
+    let decision_variables: Vec<PropositionalVariable> = (0..2)
+        .map(|_| mediator.create_new_propositional_variable(&mut sat_data_structures))
+        .collect();
+    let mut cp_propagators: Vec<Box<dyn ConstraintProgrammingPropagator>> =
+        vec![Box::new(LinearLessOrEqualPropagator::new(vec![x, y], 5))];
+
+    for decision_variable in decision_variables {
+        sat_data_structures
+            .assignments_propositional
+            .increase_decision_level();
+        sat_data_structures
+            .assignments_propositional
+            .enqueue_decision_literal(Literal::new(decision_variable, true));
+        cp_data_structures
+            .assignments_integer
+            .increase_decision_level();
+    }
+    let reason_literal = mediator.get_lower_bound_literal(x, 3);
+    sat_data_structures
+        .assignments_propositional
+        .enqueue_propagated_literal_at_level(reason_literal, 1, 1);
+    mediator.synchronise_integer_trail_based_on_propositional_trail(
+        &sat_data_structures.assignments_propositional,
+        &mut cp_data_structures,
+        &mut cp_propagators,
+    );
+
+    let status = cp_propagators[0].propagate(&mut DomainManager::new(
+        0,
+        &mut cp_data_structures.assignments_integer,
+    ));
+    assert!(status.no_conflict());
+    mediator.synchronise_propositional_trail_based_on_integer_trail(
+        &mut sat_data_structures.assignments_propositional,
+        &cp_data_structures.assignments_integer,
+        &mut cp_propagators,
+        true,
+    );
+
+    let propagated_literal = mediator.get_upper_bound_literal(y, 2);
+    let assignments = &sat_data_structures.assignments_propositional;
+    assert!(assignments.is_literal_assigned_true(propagated_literal));
+    assert_eq!(
+        1,
+        assignments.get_literal_assignment_level(propagated_literal)
+    );
+}
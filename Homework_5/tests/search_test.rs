@@ -0,0 +1,793 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use pumpkin::{
+    basic_types::{
+        AffineView, CSPSolverExecutionFlag, ClauseAdditionOutcome, IntegerValueSelectionStrategy,
+        IntegerVariable, IntegerVariableSelectionStrategy, KeyValueHeap, Literal, Predicate,
+        PropagationStatusCP, PropagatorIdentifier, PropositionalConjunction, PropositionalVariable,
+        Random, SearchAnnotation,
+    },
+    engine::{
+        AssignmentsInteger, AssignmentsPropositional, BranchingHeuristicType,
+        ConstraintSatisfactionSolver, DomainManager, IntegerVariableSelector,
+        PropositionalValueSelector, PropositionalVariableSelector, RephaseKind, RephasingStrategy,
+        SolverOptions,
+    },
+    propagators::{
+        AllDifferentPropagator, ConstraintProgrammingPropagator, LinearInequalityPropagator,
+        LinearLessOrEqualPropagator, MaximumPropagator, MinimumPropagator, TimesPropagator,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const VARIABLE_SELECTION_STRATEGIES: [IntegerVariableSelectionStrategy; 5] = [
+    IntegerVariableSelectionStrategy::InputOrder,
+    IntegerVariableSelectionStrategy::FirstFail,
+    IntegerVariableSelectionStrategy::DomWDeg,
+    IntegerVariableSelectionStrategy::Smallest,
+    IntegerVariableSelectionStrategy::Largest,
+];
+
+const VALUE_SELECTION_STRATEGIES: [IntegerValueSelectionStrategy; 5] = [
+    IntegerValueSelectionStrategy::Min,
+    IntegerValueSelectionStrategy::Max,
+    IntegerValueSelectionStrategy::Split,
+    IntegerValueSelectionStrategy::ReverseSplit,
+    IntegerValueSelectionStrategy::Median,
+];
+
+fn create_solver() -> ConstraintSatisfactionSolver {
+    ConstraintSatisfactionSolver::new(&SolverOptions::default())
+}
+
+fn create_clauses(
+    solver: &mut ConstraintSatisfactionSolver,
+    num_variables: usize,
+    clauses: &[Vec<i32>],
+) -> Vec<Literal> {
+    let variables: Vec<Literal> = (0..num_variables)
+        .map(|_| Literal::new(solver.create_new_propositional_variable(), true))
+        .collect();
+    for clause in clauses {
+        let literals = clause
+            .iter()
+            .map(|&v| {
+                let literal = variables[v.unsigned_abs() as usize - 1];
+                if v > 0 {
+                    literal
+                } else {
+                    !literal
+                }
+            })
+            .collect();
+        solver.add_permanent_clause(literals);
+    }
+    variables
+}
+
+fn create_random_3_sat_clauses(
+    rng: &mut StdRng,
+    num_variables: usize,
+    num_clauses: usize,
+) -> Vec<Vec<i32>> {
+    (0..num_clauses)
+        .map(|_| {
+            let mut variables: Vec<i32> = vec![];
+            while variables.len() < 3 {
+                let v = rng.gen_range(1..=num_variables as i32);
+                if !variables.contains(&v) {
+                    variables.push(v);
+                }
+            }
+            variables
+                .into_iter()
+                .map(|v| if rng.gen_bool(0.5) { v } else { -v })
+                .collect()
+        })
+        .collect()
+}
+
+//checks the solution of the solver if it reports one, and checks infeasibility by enumerating all assignments otherwise
+fn check_solver_outcome(
+    solver: &mut ConstraintSatisfactionSolver,
+    num_variables: usize,
+    clauses: &[Vec<i32>],
+) {
+    let variables = create_clauses(solver, num_variables, clauses);
+    match solver.solve(i64::MAX) {
+        CSPSolverExecutionFlag::Feasible => {
+            let assignments = solver.get_propositional_assignments();
+            assert!(clauses.iter().all(|clause| clause.iter().any(|&v| {
+                let literal = variables[v.unsigned_abs() as usize - 1];
+                assignments.is_literal_assigned_true(if v > 0 { literal } else { !literal })
+            })));
+        }
+        CSPSolverExecutionFlag::Infeasible => {
+            assert!(!is_satisfiable_brute_force(num_variables, clauses))
+        }
+        _ => panic!("Unexpected solver outcome."),
+    }
+}
+
+fn is_satisfiable_brute_force(num_variables: usize, clauses: &[Vec<i32>]) -> bool {
+    (0..(1u32 << num_variables)).any(|assignment| {
+        clauses.iter().all(|clause| {
+            clause.iter().any(|&v| {
+                let value = (assignment >> (v.unsigned_abs() - 1)) & 1 == 1;
+                value == (v > 0)
+            })
+        })
+    })
+}
+
+#[test]
+fn pigeon_hole_problem_is_infeasible() {
+    let num_pigeons = 5;
+    let num_holes = 4;
+    let index = |pigeon: i32, hole: i32| pigeon * num_holes + hole + 1;
+
+    let mut clauses = vec![];
+    for pigeon in 0..num_pigeons {
+        clauses.push((0..num_holes).map(|hole| index(pigeon, hole)).collect());
+    }
+    for hole in 0..num_holes {
+        for pigeon1 in 0..num_pigeons {
+            for pigeon2 in (pigeon1 + 1)..num_pigeons {
+                clauses.push(vec![-index(pigeon1, hole), -index(pigeon2, hole)]);
+            }
+        }
+    }
+
+    let mut solver = create_solver();
+    create_clauses(&mut solver, (num_pigeons * num_holes) as usize, &clauses);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+}
+
+#[test]
+fn random_3_sat_instances_are_solved_correctly() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let num_variables = 12;
+    for _ in 0..100 {
+        let clauses = create_random_3_sat_clauses(&mut rng, num_variables, 55);
+        check_solver_outcome(&mut create_solver(), num_variables, &clauses);
+    }
+}
+
+#[test]
+fn random_3_sat_instances_are_solved_correctly_with_all_phase_strategies() {
+    let mut rng = StdRng::seed_from_u64(43);
+    let num_variables = 14;
+    let rephasing_strategies = [
+        RephasingStrategy::None,
+        RephasingStrategy::Original,
+        RephasingStrategy::Inverted,
+        RephasingStrategy::Random,
+        RephasingStrategy::Best,
+        RephasingStrategy::Walk,
+        RephasingStrategy::Cycle,
+    ];
+    let mut num_rephases = 0;
+    for _ in 0..20 {
+        let clauses = create_random_3_sat_clauses(&mut rng, num_variables, 60);
+        for rephasing_strategy in rephasing_strategies {
+            for target_phases in [false, true] {
+                //frequent restarts and rephases, so that the small instances are rephased as well
+                let options = SolverOptions::builder()
+                    .num_conflicts_per_restart(2)
+                    .num_conflicts_per_rephase(2)
+                    .rephasing_strategy(rephasing_strategy)
+                    .target_phases(target_phases)
+                    .random_seed(7)
+                    .build();
+                let mut solver = ConstraintSatisfactionSolver::new(&options);
+                check_solver_outcome(&mut solver, num_variables, &clauses);
+                num_rephases += solver.get_statistics().num_rephases;
+            }
+        }
+    }
+    assert!(num_rephases > 0);
+}
+
+#[test]
+fn random_3_sat_instances_are_solved_correctly_with_all_branching_heuristics() {
+    let mut rng = StdRng::seed_from_u64(44);
+    let num_variables = 14;
+    for _ in 0..30 {
+        let clauses = create_random_3_sat_clauses(&mut rng, num_variables, 60);
+        for branching_heuristic in [
+            BranchingHeuristicType::Vsids,
+            BranchingHeuristicType::Chb,
+            BranchingHeuristicType::Lrb,
+        ] {
+            let options = SolverOptions::builder()
+                .branching_heuristic(branching_heuristic)
+                .num_conflicts_per_restart(5)
+                .build();
+            let mut solver = ConstraintSatisfactionSolver::new(&options);
+            check_solver_outcome(&mut solver, num_variables, &clauses);
+        }
+    }
+}
+
+#[test]
+fn random_3_sat_instances_are_solved_correctly_with_chronological_backtracking() {
+    let mut rng = StdRng::seed_from_u64(45);
+    let num_variables = 16;
+    let mut num_chronological_backtracks = 0;
+    for _ in 0..30 {
+        let clauses = create_random_3_sat_clauses(&mut rng, num_variables, 68);
+        for threshold in [0, 1, 3] {
+            let options = SolverOptions::builder()
+                .chronological_backtracking(true)
+                .chronological_backtracking_threshold(threshold)
+                .num_conflicts_per_restart(20)
+                .build();
+            let mut solver = ConstraintSatisfactionSolver::new(&options);
+            check_solver_outcome(&mut solver, num_variables, &clauses);
+            num_chronological_backtracks += solver.get_statistics().num_chronological_backtracks;
+        }
+    }
+    assert!(num_chronological_backtracks > 0);
+}
+
+//the literals kept on the propositional trail are synchronised with the integer trail again after backtracking, see 'SATCPMediator::synchronise'
+//  so the integer domains must agree with the propositional assignment after the search
+#[test]
+fn random_integer_models_are_solved_correctly_with_chronological_backtracking() {
+    let mut rng = StdRng::seed_from_u64(46);
+    let num_variables = 5;
+    let upper_bound: i32 = 4;
+    let mut num_chronological_backtracks = 0;
+    for _ in 0..40 {
+        //linear inequalities with nonzero weights, together with an all-different constraint
+        let constraints: Vec<(Vec<i32>, i32)> = (0..3)
+            .map(|_| {
+                let weights: Vec<i32> = (0..num_variables)
+                    .map(|_| rng.gen_range(1..=3) * if rng.gen_bool(0.5) { 1 } else { -1 })
+                    .collect();
+                (weights, rng.gen_range(-2..=12))
+            })
+            .collect();
+        let is_feasible = |values: &[i32]| {
+            constraints.iter().all(|(weights, rhs)| {
+                weights.iter().zip(values).map(|(w, v)| w * v).sum::<i32>() <= *rhs
+            }) && (0..values.len()).all(|i| !values[(i + 1)..].contains(&values[i]))
+        };
+        let has_solution = (0..(upper_bound + 1).pow(num_variables as u32)).any(|code| {
+            let values: Vec<i32> = (0..num_variables)
+                .map(|i| (code / (upper_bound + 1).pow(i as u32)) % (upper_bound + 1))
+                .collect();
+            is_feasible(&values)
+        });
+
+        let options = SolverOptions::builder()
+            .chronological_backtracking(true)
+            .chronological_backtracking_threshold(0)
+            .build();
+        let mut solver = ConstraintSatisfactionSolver::new(&options);
+        let mut is_root_infeasible = false;
+        let variables: Vec<IntegerVariable> = (0..num_variables)
+            .map(|_| solver.create_new_integer_variable(0, upper_bound).unwrap())
+            .collect();
+        for (weights, rhs) in &constraints {
+            let terms: Vec<AffineView> = variables
+                .iter()
+                .zip(weights)
+                .map(|(variable, weight)| AffineView::new(*variable, *weight, 0))
+                .collect();
+            is_root_infeasible |= solver
+                .add_propagator(Box::new(LinearLessOrEqualPropagator::new(terms, *rhs)))
+                == ClauseAdditionOutcome::Infeasible;
+        }
+        is_root_infeasible |= solver
+            .add_propagator(Box::new(AllDifferentPropagator::new(variables.clone())))
+            == ClauseAdditionOutcome::Infeasible;
+        if is_root_infeasible {
+            assert!(!has_solution);
+            continue;
+        }
+
+        match solver.solve(i64::MAX) {
+            CSPSolverExecutionFlag::Feasible => {
+                let assignments = solver.get_integer_assignments();
+                let values: Vec<i32> = variables
+                    .iter()
+                    .map(|variable| assignments.get_assigned_value(*variable))
+                    .collect();
+                assert!(is_feasible(&values));
+                for (variable, value) in variables.iter().zip(&values) {
+                    let literal = solver.get_predicate_literal(Predicate::Equal {
+                        integer_variable: *variable,
+                        equality_constant: *value,
+                    });
+                    assert!(solver
+                        .get_propositional_assignments()
+                        .is_literal_assigned_true(literal));
+                }
+            }
+            CSPSolverExecutionFlag::Infeasible => assert!(!has_solution),
+            _ => panic!("Unexpected solver outcome."),
+        }
+        num_chronological_backtracks += solver.get_statistics().num_chronological_backtracks;
+    }
+    assert!(num_chronological_backtracks > 0);
+}
+
+#[test]
+fn backtracking_keeps_literals_assigned_out_of_order() {
+    let mut assignments = AssignmentsPropositional::default();
+    let variables: Vec<PropositionalVariable> = (0..5)
+        .map(|index| {
+            assignments.grow();
+            PropositionalVariable::new(index)
+        })
+        .collect();
+
+    assignments.increase_decision_level();
+    assignments.enqueue_decision_literal(Literal::new(variables[0], true));
+    assignments.increase_decision_level();
+    assignments.enqueue_decision_literal(Literal::new(variables[1], true));
+    assignments.increase_decision_level();
+    assignments.enqueue_decision_literal(Literal::new(variables[2], false));
+    assignments.enqueue_propagated_literal_at_level(Literal::new(variables[3], true), 1, 1);
+    assignments.enqueue_propagated_literal(Literal::new(variables[4], true), 1);
+
+    let mut unassigned_literals = vec![];
+    assignments.synchronise(1, |literal| unassigned_literals.push(literal));
+
+    assert_eq!(assignments.get_decision_level(), 1);
+    assert_eq!(unassigned_literals.len(), 3);
+    assert!(unassigned_literals[0] == Literal::new(variables[4], true));
+    assert!(unassigned_literals[2] == Literal::new(variables[1], true));
+    assert_eq!(assignments.trail.len(), 2);
+    assert!(assignments.trail[1] == Literal::new(variables[3], true));
+    assert_eq!(assignments.get_variable_assignment_level(variables[3]), 1);
+    assert!(assignments.is_variable_unassigned(variables[2]));
+}
+
+//counts how often it is asked to synchronise, without ever propagating
+struct SynchronisationCountingPropagator {
+    variable: IntegerVariable,
+    num_synchronisations: Arc<AtomicU64>,
+}
+
+impl ConstraintProgrammingPropagator for SynchronisationCountingPropagator {
+    fn propagate(&mut self, _domains: &mut DomainManager) -> PropagationStatusCP {
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    fn synchronise(&mut self, _domains: &DomainManager) {
+        self.num_synchronisations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get_reason_for_propagation(&mut self, _predicate: Predicate) -> PropositionalConjunction {
+        PropositionalConjunction::new()
+    }
+
+    fn priority(&self) -> u32 {
+        0
+    }
+
+    fn name(&self) -> &str {
+        "synchronisation counting propagator"
+    }
+
+    fn get_integer_variables_to_watch_for_lower_bound_changes(&self) -> Vec<IntegerVariable> {
+        vec![self.variable]
+    }
+
+    fn get_integer_variables_to_watch_for_upper_bound_changes(&self) -> Vec<IntegerVariable> {
+        vec![self.variable]
+    }
+
+    fn initialise_at_root(&mut self, _domains: &mut DomainManager) -> PropagationStatusCP {
+        PropagationStatusCP::NoConflictDetected
+    }
+
+    fn debug_propagate_from_scratch(&self, _domains: &mut DomainManager) -> PropagationStatusCP {
+        PropagationStatusCP::NoConflictDetected
+    }
+}
+
+//a propagator is only asked to synchronise if it was notified of a domain change at a decision level that is undone
+//  the model of four different values with a sum of at most five is infeasible and requires search, whereas the fixed variable only changes at the root
+#[test]
+fn only_notified_propagators_synchronise_when_backtracking() {
+    let mut solver = create_solver();
+    let fixed_variable = solver.create_new_integer_variable(0, 1).unwrap();
+    let variables: Vec<IntegerVariable> = (0..4)
+        .map(|_| solver.create_new_integer_variable(0, 3).unwrap())
+        .collect();
+    let fixed_literal = solver.get_predicate_literal(Predicate::LowerBound {
+        integer_variable: fixed_variable,
+        lower_bound: 1,
+    });
+    solver.add_permanent_clause(vec![fixed_literal]);
+
+    let num_synchronisations: Vec<Arc<AtomicU64>> =
+        (0..2).map(|_| Arc::new(AtomicU64::new(0))).collect();
+    for (variable, counter) in [fixed_variable, variables[0]]
+        .into_iter()
+        .zip(&num_synchronisations)
+    {
+        let _ = solver.add_propagator(Box::new(SynchronisationCountingPropagator {
+            variable,
+            num_synchronisations: counter.clone(),
+        }));
+    }
+    let _ = solver.add_propagator(Box::new(LinearLessOrEqualPropagator::new(
+        variables
+            .iter()
+            .map(|variable| AffineView::from(*variable))
+            .collect(),
+        5,
+    )));
+    let _ = solver.add_propagator(Box::new(AllDifferentPropagator::new(variables)));
+
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+    assert_eq!(num_synchronisations[0].load(Ordering::Relaxed), 0);
+    assert!(num_synchronisations[1].load(Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn key_value_heap_keeps_maximum_after_setting_values() {
+    let mut heap = KeyValueHeap::new();
+    for value in [3.0, 1.0, 4.0, 1.5, 5.0, 9.0, 2.0] {
+        heap.grow(value);
+    }
+    assert_eq!(heap.peek_max(), Some(5));
+
+    heap.set_value(5, 0.5);
+    assert_eq!(heap.peek_max(), Some(4));
+    heap.set_value(1, 10.0);
+    assert_eq!(heap.peek_max(), Some(1));
+    assert_eq!(heap.get_value(5), 0.5);
+
+    //setting the value of a removed key does not put it back on the heap
+    assert_eq!(heap.pop_max(), Some(1));
+    heap.set_value(1, 20.0);
+    assert_eq!(heap.peek_max(), Some(4));
+    heap.restore_key(1);
+    assert_eq!(heap.peek_max(), Some(1));
+
+    let mut values = vec![];
+    while !heap.is_empty() {
+        let key = heap.pop_max().unwrap();
+        values.push(heap.get_value(key));
+    }
+    assert!(values.windows(2).all(|pair| pair[0] >= pair[1]));
+}
+
+#[test]
+fn learning_rate_heuristics_reward_variables_in_conflicts() {
+    for branching_heuristic in [BranchingHeuristicType::Chb, BranchingHeuristicType::Lrb] {
+        let mut assignments = AssignmentsPropositional::default();
+        let mut selector = PropositionalVariableSelector::with_heuristic(branching_heuristic);
+        let variables: Vec<PropositionalVariable> = (0..3)
+            .map(|index| {
+                selector.grow();
+                assignments.grow();
+                PropositionalVariable::new(index)
+            })
+            .collect();
+
+        for variable in &variables {
+            selector.on_assign(*variable);
+        }
+        selector.on_conflict_variable(variables[1]);
+        selector.on_conflict();
+        for variable in &variables {
+            selector.restore(*variable);
+        }
+
+        //only the variable in the conflict is rewarded, so it is selected next
+        assert!(selector.get_score(variables[1]) > selector.get_score(variables[0]));
+        assert_eq!(
+            selector.get_score(variables[0]),
+            selector.get_score(variables[2])
+        );
+        assert!(selector.peek_next_variable(&assignments) == Some(variables[1]));
+    }
+}
+
+#[test]
+fn cycle_rephasing_starts_with_original_and_inverted_phases() {
+    let kinds: Vec<RephaseKind> = (0..11)
+        .map(|index| RephasingStrategy::Cycle.get_rephase_kind(index).unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            RephaseKind::Original,
+            RephaseKind::Inverted,
+            RephaseKind::Best,
+            RephaseKind::Walk,
+            RephaseKind::Random,
+            RephaseKind::Best,
+            RephaseKind::Walk,
+            RephaseKind::Original,
+            RephaseKind::Best,
+            RephaseKind::Walk,
+            RephaseKind::Inverted,
+        ]
+    );
+    assert!(RephasingStrategy::None.get_rephase_kind(0).is_none());
+    assert_eq!(
+        RephasingStrategy::Random.get_rephase_kind(5),
+        Some(RephaseKind::Random)
+    );
+}
+
+#[test]
+fn value_selector_follows_target_and_best_phases() {
+    let variables: Vec<PropositionalVariable> = (0..4).map(PropositionalVariable::new).collect();
+    let mut selector =
+        PropositionalValueSelector::with_initial_value(false).with_target_phases(true);
+    for _ in &variables {
+        selector.grow();
+    }
+    selector.update_and_freeze(variables[3], false);
+
+    //the longest conflict-free trail becomes the target, shorter trails are ignored
+    selector.update_target_and_best_phases(&[
+        Literal::new(variables[0], true),
+        Literal::new(variables[1], false),
+        Literal::new(variables[3], true),
+    ]);
+    selector.update_target_and_best_phases(&[Literal::new(variables[0], false)]);
+    assert!(selector.select_value(variables[0]));
+    assert!(!selector.select_value(variables[1]));
+    assert!(!selector.select_value(variables[2]));
+    assert!(!selector.select_value(variables[3]));
+
+    //rephasing forgets the target phases, but keeps the best phases and frozen values
+    let mut random = Random::new(0);
+    selector.rephase(RephaseKind::Inverted, &mut random);
+    assert!(selector.select_value(variables[1]));
+    assert!(selector.select_value(variables[2]));
+    assert!(!selector.select_value(variables[3]));
+
+    selector.rephase(RephaseKind::Best, &mut random);
+    assert!(selector.select_value(variables[0]));
+    assert!(!selector.select_value(variables[1]));
+    assert!(selector.select_value(variables[2]));
+    assert!(!selector.select_value(variables[3]));
+}
+
+//x * y = z, max(x, y) = w, with z in [12, 13] and w in [0, 4]
+//  the only solutions are (x, y) = (3, 4) and (4, 3)
+fn create_integer_model(
+    solver: &mut ConstraintSatisfactionSolver,
+) -> (
+    IntegerVariable,
+    IntegerVariable,
+    IntegerVariable,
+    IntegerVariable,
+) {
+    let x = solver.create_new_integer_variable(0, 10).unwrap();
+    let y = solver.create_new_integer_variable(0, 10).unwrap();
+    let z = solver.create_new_integer_variable(12, 13).unwrap();
+    let w = solver.create_new_integer_variable(0, 4).unwrap();
+    solver.add_propagator(Box::new(TimesPropagator::new(x, y, z)));
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x, y], w)));
+    (x, y, z, w)
+}
+
+#[test]
+fn integer_model_is_solved_with_vsids() {
+    let mut solver = create_solver();
+    let (x, y, z, w) = create_integer_model(&mut solver);
+
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Feasible
+    ));
+    let assignments = solver.get_integer_assignments();
+    let value = |v| assignments.get_assigned_value(v);
+    assert!(value(x) * value(y) == value(z));
+    assert!(value(x).max(value(y)) == value(w));
+    assert!([(3, 4), (4, 3)].contains(&(value(x), value(y))));
+}
+
+#[test]
+fn conflicts_of_propagators_are_analysed_to_prove_infeasibility() {
+    let mut solver = create_solver();
+    let (x, _, _, _) = create_integer_model(&mut solver);
+    //x * x is in [10, 15], which excludes both x = 3 and x = 4
+    //  root propagation does not detect this, so the solver needs to learn from the conflicts of the propagators
+    let s = solver.create_new_integer_variable(10, 15).unwrap();
+    solver.add_propagator(Box::new(TimesPropagator::new(x, x, s)));
+
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+}
+
+#[test]
+fn root_propagations_of_added_propagators_notify_existing_propagators() {
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 10).unwrap();
+    let y = solver.create_new_integer_variable(0, 10).unwrap();
+    let z = solver.create_new_integer_variable(0, 10).unwrap();
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x, y], z)));
+
+    //min(x) = a and min(y) = b bound x and y from above when they are added
+    //  the maximum propagator needs to be notified of these changes to bound z from above
+    let a = solver.create_new_integer_variable(0, 3).unwrap();
+    let b = solver.create_new_integer_variable(0, 4).unwrap();
+    solver.add_propagator(Box::new(MinimumPropagator::new(vec![x], a)));
+    solver.add_propagator(Box::new(MinimumPropagator::new(vec![y], b)));
+
+    let assignments = solver.get_integer_assignments();
+    assert_eq!(assignments.get_upper_bound(x), 3);
+    assert_eq!(assignments.get_upper_bound(y), 4);
+    assert_eq!(assignments.get_upper_bound(z), 4);
+}
+
+//y - x - x >= 0 lists x twice, which is watched once with the merged weight
+//  raising the lower bound of x to one then raises the lower bound of y to two
+#[test]
+fn linear_inequalities_with_repeated_variables_update_their_slack() {
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let y = solver.create_new_integer_variable(0, 5).unwrap();
+    solver.add_propagator(Box::new(LinearInequalityPropagator::new(
+        vec![1, -1, -1],
+        vec![y, x, x],
+        0,
+    )));
+    assert_eq!(solver.get_integer_assignments().get_lower_bound(y), 0);
+
+    let b = solver.create_new_integer_variable(1, 5).unwrap();
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x], b)));
+
+    let assignments = solver.get_integer_assignments();
+    assert_eq!(assignments.get_lower_bound(x), 1);
+    assert_eq!(assignments.get_lower_bound(y), 2);
+}
+
+#[test]
+fn integer_search_finds_solutions_for_all_strategies() {
+    for variable_selection_strategy in VARIABLE_SELECTION_STRATEGIES {
+        for value_selection_strategy in VALUE_SELECTION_STRATEGIES {
+            let mut solver = create_solver();
+            let (x, y, z, w) = create_integer_model(&mut solver);
+            solver.add_search_annotation(SearchAnnotation::new(
+                vec![w, x, y],
+                variable_selection_strategy,
+                value_selection_strategy,
+            ));
+
+            assert!(matches!(
+                solver.solve(i64::MAX),
+                CSPSolverExecutionFlag::Feasible
+            ));
+            let assignments = solver.get_integer_assignments();
+            let value = |v| assignments.get_assigned_value(v);
+            assert!(value(x) * value(y) == value(z));
+            assert!(value(x).max(value(y)) == value(w));
+            assert!([(3, 4), (4, 3)].contains(&(value(x), value(y))));
+        }
+    }
+}
+
+#[test]
+fn integer_search_proves_infeasibility() {
+    let mut solver = create_solver();
+    let (x, _, _, _) = create_integer_model(&mut solver);
+    //x * x is in [10, 15], which excludes both x = 3 and x = 4
+    //  root propagation does not detect this, so the solver needs to search
+    let s = solver.create_new_integer_variable(10, 15).unwrap();
+    solver.add_propagator(Box::new(TimesPropagator::new(x, x, s)));
+    solver.add_search_annotation(SearchAnnotation::new(
+        vec![s, x],
+        IntegerVariableSelectionStrategy::InputOrder,
+        IntegerValueSelectionStrategy::Median,
+    ));
+
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+}
+
+#[test]
+fn value_selection_strategies_create_expected_predicates() {
+    let mut assignments = AssignmentsInteger::new();
+    let x = assignments.grow(2, 9);
+    assignments.remove_value_from_domain_no_notify(x, 3, None);
+    assignments.remove_value_from_domain_no_notify(x, 4, None);
+
+    let predicate = |strategy: IntegerValueSelectionStrategy| {
+        strategy.select_decision_predicate(x, &assignments)
+    };
+
+    assert_eq!(
+        predicate(IntegerValueSelectionStrategy::Min),
+        Predicate::UpperBound {
+            integer_variable: x,
+            upper_bound: 2
+        }
+    );
+    assert_eq!(
+        predicate(IntegerValueSelectionStrategy::Max),
+        Predicate::LowerBound {
+            integer_variable: x,
+            lower_bound: 9
+        }
+    );
+    assert_eq!(
+        predicate(IntegerValueSelectionStrategy::Split),
+        Predicate::UpperBound {
+            integer_variable: x,
+            upper_bound: 5
+        }
+    );
+    assert_eq!(
+        predicate(IntegerValueSelectionStrategy::ReverseSplit),
+        Predicate::LowerBound {
+            integer_variable: x,
+            lower_bound: 6
+        }
+    );
+    //the domain is {2, 5, 6, 7, 8, 9}
+    assert_eq!(
+        predicate(IntegerValueSelectionStrategy::Median),
+        Predicate::Equal {
+            integer_variable: x,
+            equality_constant: 6
+        }
+    );
+}
+
+#[test]
+fn variable_selection_strategies_select_expected_variables() {
+    let mut assignments = AssignmentsInteger::new();
+    let a = assignments.grow(0, 1);
+    let b = assignments.grow(3, 9);
+    let c = assignments.grow(1, 4);
+    let d = assignments.grow(5, 5);
+    let e = assignments.grow(0, 9);
+
+    let selected_variable = |strategy: IntegerVariableSelectionStrategy,
+                             selector: &mut IntegerVariableSelector| {
+        selector.add_search_annotation(SearchAnnotation::new(
+            vec![d, b, c, a],
+            strategy,
+            IntegerValueSelectionStrategy::Min,
+        ));
+        selector
+            .get_next_decision_predicate(&assignments)
+            .unwrap()
+            .get_integer_variable()
+    };
+
+    let selected = |strategy| selected_variable(strategy, &mut IntegerVariableSelector::new());
+    assert_eq!(selected(IntegerVariableSelectionStrategy::InputOrder), b);
+    assert_eq!(selected(IntegerVariableSelectionStrategy::FirstFail), a);
+    assert_eq!(selected(IntegerVariableSelectionStrategy::Smallest), a);
+    assert_eq!(selected(IntegerVariableSelectionStrategy::Largest), b);
+
+    //b is in a propagator that reported many conflicts, which outweighs its larger domain
+    //  e is not part of the annotation, but keeps the propagator of b relevant
+    let mut selector = IntegerVariableSelector::new();
+    selector.register_propagator(PropagatorIdentifier { id: 0 }, vec![b, e]);
+    selector.register_propagator(PropagatorIdentifier { id: 1 }, vec![a, d]);
+    for _ in 0..10 {
+        selector.bump_propagator_weight(PropagatorIdentifier { id: 0 });
+    }
+    assert_eq!(
+        selected_variable(IntegerVariableSelectionStrategy::DomWDeg, &mut selector),
+        b
+    );
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use pumpkin::{
+    basic_types::{CSPSolverExecutionFlag, ClauseAdditionOutcome, Literal, PropositionalVariable},
+    engine::{ConstraintSatisfactionSolver, SolverOptions},
+    propagators::{MaximumPropagator, TimesPropagator},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn create_solver() -> ConstraintSatisfactionSolver {
+    ConstraintSatisfactionSolver::new(&SolverOptions::default())
+}
+
+fn count_models_brute_force(num_variables: usize, clauses: &[Vec<i32>]) -> usize {
+    (0..(1u32 << num_variables))
+        .filter(|assignment| {
+            clauses.iter().all(|clause| {
+                clause.iter().any(|&v| {
+                    let value = (assignment >> (v.unsigned_abs() - 1)) & 1 == 1;
+                    value == (v > 0)
+                })
+            })
+        })
+        .count()
+}
+
+#[test]
+fn all_models_of_random_formulas_are_enumerated() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let num_variables = 8;
+    for _ in 0..50 {
+        let clauses: Vec<Vec<i32>> = (0..rng.gen_range(5..30))
+            .map(|_| {
+                let mut variables: Vec<i32> = vec![];
+                while variables.len() < 3 {
+                    let v = rng.gen_range(1..=num_variables as i32);
+                    if !variables.contains(&v) {
+                        variables.push(v);
+                    }
+                }
+                variables
+                    .into_iter()
+                    .map(|v| if rng.gen_bool(0.5) { v } else { -v })
+                    .collect()
+            })
+            .collect();
+
+        let mut solver = create_solver();
+        let variables: Vec<PropositionalVariable> = (0..num_variables)
+            .map(|_| solver.create_new_propositional_variable())
+            .collect();
+        for clause in &clauses {
+            solver.add_permanent_clause(
+                clause
+                    .iter()
+                    .map(|&v| Literal::new(variables[v.unsigned_abs() as usize - 1], v > 0))
+                    .collect(),
+            );
+        }
+
+        let mut solutions = solver.solutions(variables.clone(), vec![], i64::MAX);
+        let mut models = HashSet::new();
+        for solution in solutions.by_ref() {
+            let model: Vec<bool> = variables.iter().map(|v| solution[*v]).collect();
+            assert!(clauses.iter().all(|clause| clause
+                .iter()
+                .any(|&v| model[v.unsigned_abs() as usize - 1] == (v > 0))));
+            assert!(models.insert(model), "A solution was reported twice.");
+        }
+        assert!(solutions.has_enumerated_all_solutions());
+        assert_eq!(
+            models.len(),
+            count_models_brute_force(num_variables, &clauses)
+        );
+    }
+}
+
+#[test]
+fn integer_solutions_are_enumerated_over_the_projection() {
+    //x * y = z with z in [4, 6]
+    //  the (x, y) pairs are (1, 4), (4, 1), (1, 5), (5, 1), (2, 2), (2, 3), and (3, 2)
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let y = solver.create_new_integer_variable(0, 5).unwrap();
+    let z = solver.create_new_integer_variable(4, 6).unwrap();
+    solver.add_propagator(Box::new(TimesPropagator::new(x, y, z)));
+
+    let mut solutions = solver.solutions(vec![], vec![x, y], i64::MAX);
+    let pairs: HashSet<(i32, i32)> = solutions
+        .by_ref()
+        .map(|solution| (solution[x], solution[y]))
+        .collect();
+    assert!(solutions.has_enumerated_all_solutions());
+    assert_eq!(
+        pairs,
+        HashSet::from([(1, 4), (4, 1), (1, 5), (5, 1), (2, 2), (2, 3), (3, 2)])
+    );
+
+    //projecting onto z only distinguishes the three values of z
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let y = solver.create_new_integer_variable(0, 5).unwrap();
+    let z = solver.create_new_integer_variable(4, 6).unwrap();
+    solver.add_propagator(Box::new(TimesPropagator::new(x, y, z)));
+    let values: Vec<i32> = solver
+        .solutions(vec![], vec![z], i64::MAX)
+        .map(|solution| solution[z])
+        .collect();
+    assert_eq!(values.len(), 3);
+    assert_eq!(
+        values.into_iter().collect::<HashSet<i32>>(),
+        HashSet::from([4, 5, 6])
+    );
+}
+
+#[test]
+fn enumeration_can_be_stopped_and_handles_empty_projections() {
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let y = solver.create_new_integer_variable(0, 5).unwrap();
+    let z = solver.create_new_integer_variable(0, 5).unwrap();
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x, y], z)));
+
+    let mut solutions = solver.solutions(vec![], vec![x, y], i64::MAX);
+    assert_eq!(solutions.by_ref().take(10).count(), 10);
+    assert!(!solutions.has_enumerated_all_solutions());
+    assert_eq!(solutions.by_ref().count(), 26);
+    assert!(solutions.has_enumerated_all_solutions());
+
+    //with an empty projection all solutions are considered the same
+    let mut solver = create_solver();
+    let x = solver.create_new_integer_variable(0, 5).unwrap();
+    let y = solver.create_new_integer_variable(0, 5).unwrap();
+    let z = solver.create_new_integer_variable(0, 5).unwrap();
+    solver.add_propagator(Box::new(MaximumPropagator::new(vec![x, y], z)));
+    let mut solutions = solver.solutions(vec![], vec![], i64::MAX);
+    assert_eq!(solutions.by_ref().count(), 1);
+    assert!(solutions.has_enumerated_all_solutions());
+}
+
+//the blocking clause is falsified by the solution, so the solver backjumps to the level where the clause propagates rather than restarting
+//  with four unconstrained variables, each variable is a decision at its own level, so the first blocking clause propagates at level three
+#[test]
+fn blocking_a_solution_backjumps_to_the_level_where_the_clause_propagates() {
+    let mut solver = create_solver();
+    let variables: Vec<PropositionalVariable> = (0..4)
+        .map(|_| solver.create_new_propositional_variable())
+        .collect();
+
+    let mut num_solutions = 0;
+    while let CSPSolverExecutionFlag::Feasible = solver.solve(i64::MAX) {
+        let outcome = solver.add_blocking_clause_for_current_solution(&variables, &[]);
+        num_solutions += 1;
+        if num_solutions == 1 {
+            assert_eq!(outcome, ClauseAdditionOutcome::NoConflictDetected);
+            assert_eq!(
+                solver.get_propositional_assignments().get_decision_level(),
+                3
+            );
+
+            //the solver can still be restored at the root, e.g., to add clauses
+            solver.restore_state_at_root();
+            assert!(solver
+                .get_propositional_assignments()
+                .is_at_the_root_level());
+        }
+        if outcome == ClauseAdditionOutcome::Infeasible {
+            break;
+        }
+    }
+    assert_eq!(num_solutions, 16);
+}
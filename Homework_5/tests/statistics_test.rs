@@ -0,0 +1,95 @@
+use pumpkin::{
+    basic_types::CSPSolverExecutionFlag,
+    engine::{ConstraintSatisfactionSolver, SolverOptions},
+    propagators::{AllDifferentPropagator, LinearLessOrEqualPropagator},
+};
+
+//four different values sum to at least six, which the solver only detects through search
+fn create_infeasible_solver() -> ConstraintSatisfactionSolver {
+    let mut solver = ConstraintSatisfactionSolver::new(&SolverOptions::default());
+    let variables: Vec<_> = (0..4)
+        .map(|_| solver.create_new_integer_variable(0, 4).unwrap())
+        .collect();
+    solver.add_propagator(Box::new(AllDifferentPropagator::new(variables.clone())));
+    solver.add_propagator(Box::new(LinearLessOrEqualPropagator::new(
+        variables.clone(),
+        5,
+    )));
+    solver.add_propagator(Box::new(LinearLessOrEqualPropagator::new(
+        variables[..2].to_vec(),
+        4,
+    )));
+    solver
+}
+
+#[test]
+fn statistics_count_the_search() {
+    let mut solver = create_infeasible_solver();
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+
+    let statistics = solver.get_statistics();
+    assert!(statistics.num_decisions > 0);
+    assert!(statistics.num_conflicts > 0);
+    assert!(statistics.num_ticks > 0);
+    assert!(statistics.num_learned_clauses <= statistics.num_conflicts);
+    assert!(statistics.num_explanation_clauses > 0);
+    if statistics.num_learned_clauses > 0 {
+        assert!(statistics.average_learned_clause_lbd >= 1.0);
+    }
+
+    //propagators are aggregated by name and ordered by name
+    let names: Vec<&str> = statistics
+        .propagators
+        .iter()
+        .map(|propagator| propagator.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "all different propagator",
+            "linear less or equal propagator"
+        ]
+    );
+    assert_eq!(statistics.propagators[0].num_propagators, 1);
+    assert_eq!(statistics.propagators[1].num_propagators, 2);
+    assert!(statistics.propagators[0].num_calls > 0);
+    let num_propagator_conflicts: u64 = statistics
+        .propagators
+        .iter()
+        .map(|propagator| propagator.num_conflicts)
+        .sum();
+    assert!(num_propagator_conflicts > 0);
+}
+
+#[test]
+fn statistics_are_written_as_entries_and_json() {
+    let mut solver = create_infeasible_solver();
+    solver.solve(i64::MAX);
+    let statistics = solver.get_statistics();
+
+    let entries = statistics.get_entries();
+    let get_entry = |name: &str| {
+        entries
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(
+        get_entry("conflicts"),
+        Some(statistics.num_conflicts.to_string())
+    );
+    assert_eq!(
+        get_entry("linear_less_or_equal_propagator_propagators"),
+        Some("2".to_string())
+    );
+    assert!(entries.iter().all(|(name, _)| !name.contains(' ')));
+
+    let json = statistics.to_json();
+    assert!(json.starts_with('{') && json.trim_end().ends_with('}'));
+    assert!(json.contains(&format!("\"decisions\": {},", statistics.num_decisions)));
+    assert!(json.contains("{\"name\": \"all different propagator\", \"propagators\": 1,"));
+    assert_eq!(json.matches('{').count(), json.matches('}').count());
+}
@@ -0,0 +1,177 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use pumpkin::{
+    basic_types::{CSPSolverExecutionFlag, Literal},
+    engine::{ConstraintSatisfactionSolver, SolverOptions},
+};
+
+//adds the clauses stating that the pigeons fit in the holes, one per hole, which is infeasible if there are more pigeons than holes
+fn create_pigeon_hole_solver(
+    options: &SolverOptions,
+    num_pigeons: usize,
+    num_holes: usize,
+) -> ConstraintSatisfactionSolver {
+    let mut solver = ConstraintSatisfactionSolver::new(options);
+    let placements: Vec<Vec<Literal>> = (0..num_pigeons)
+        .map(|_| {
+            (0..num_holes)
+                .map(|_| Literal::new(solver.create_new_propositional_variable(), true))
+                .collect()
+        })
+        .collect();
+
+    for pigeon in &placements {
+        solver.add_permanent_clause(pigeon.clone());
+    }
+    for hole in 0..num_holes {
+        for (first, first_placements) in placements.iter().enumerate() {
+            for second_placements in &placements[(first + 1)..] {
+                solver
+                    .add_permanent_clause(vec![!first_placements[hole], !second_placements[hole]]);
+            }
+        }
+    }
+    solver
+}
+
+#[test]
+fn terminate_flag_stops_the_search() {
+    let terminate_flag = Arc::new(AtomicBool::new(true));
+    let options = SolverOptions::builder()
+        .terminate_flag(terminate_flag.clone())
+        .build();
+    let mut solver = create_pigeon_hole_solver(&options, 4, 3);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Timeout
+    ));
+
+    //the flag is not reset by the solver, so the search only resumes once the flag is cleared
+    solver.restore_state_at_root();
+    terminate_flag.store(false, Ordering::Relaxed);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+}
+
+#[test]
+fn terminate_flag_can_be_set_from_another_thread() {
+    let options = SolverOptions::default();
+    let terminate_flag = options.terminate_flag.clone();
+    let mut solver = create_pigeon_hole_solver(&options, 14, 13);
+
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        terminate_flag.store(true, Ordering::Relaxed);
+    });
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Timeout
+    ));
+    interrupter.join().unwrap();
+}
+
+#[test]
+fn budgets_stop_the_search() {
+    let budgeted_options = [
+        SolverOptions::builder().conflict_budget(10).build(),
+        SolverOptions::builder().decision_budget(10).build(),
+        SolverOptions::builder().propagation_budget(10).build(),
+    ];
+    for options in &budgeted_options {
+        let mut solver = create_pigeon_hole_solver(options, 7, 6);
+        assert!(matches!(
+            solver.solve(i64::MAX),
+            CSPSolverExecutionFlag::Timeout
+        ));
+    }
+
+    let mut solver = create_pigeon_hole_solver(&SolverOptions::default(), 7, 6);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+}
+
+#[test]
+fn budgets_count_over_all_calls_to_solve() {
+    //each call finds a solution within the budget, but the calls together exceed it
+    let options = SolverOptions::builder().decision_budget(50).build();
+    let mut solver = create_pigeon_hole_solver(&options, 3, 3);
+    let num_solved_calls = (0..100)
+        .take_while(|_| {
+            let execution_flag = solver.solve(i64::MAX);
+            solver.restore_state_at_root();
+            matches!(execution_flag, CSPSolverExecutionFlag::Feasible)
+        })
+        .count();
+    assert!(num_solved_calls > 0 && num_solved_calls < 100);
+}
+
+#[test]
+fn tick_limit_stops_the_search_at_the_same_point() {
+    let run = |tick_limit: u64| {
+        let options = SolverOptions::builder().tick_limit(tick_limit).build();
+        let mut solver = create_pigeon_hole_solver(&options, 7, 6);
+        let execution_flag = solver.solve(i64::MAX);
+        (execution_flag, solver.get_num_ticks())
+    };
+
+    let (execution_flag, num_ticks) = run(u64::MAX);
+    assert!(matches!(execution_flag, CSPSolverExecutionFlag::Infeasible));
+    assert!(num_ticks > 1000);
+
+    //the limit is checked between search steps, so the solver may exceed it slightly, but always by the same amount
+    let (first_flag, first_num_ticks) = run(1000);
+    let (second_flag, second_num_ticks) = run(1000);
+    assert!(matches!(first_flag, CSPSolverExecutionFlag::Timeout));
+    assert!(matches!(second_flag, CSPSolverExecutionFlag::Timeout));
+    assert!(first_num_ticks >= 1000);
+    assert_eq!(first_num_ticks, second_num_ticks);
+}
+
+#[test]
+fn memory_estimate_grows_with_the_instance() {
+    let options = SolverOptions::default();
+    let small_solver = create_pigeon_hole_solver(&options, 4, 3);
+    let large_solver = create_pigeon_hole_solver(&options, 12, 11);
+    assert!(small_solver.estimate_memory_usage() > 0);
+    assert!(large_solver.estimate_memory_usage() > small_solver.estimate_memory_usage());
+    assert_eq!(
+        large_solver
+            .get_statistics()
+            .estimated_memory_usage_in_bytes,
+        large_solver.estimate_memory_usage() as u64
+    );
+}
+
+#[test]
+fn memory_limit_stops_the_search() {
+    //the estimate of any instance exceeds zero megabytes, even after reducing the learned clauses
+    let options = SolverOptions::builder()
+        .memory_limit_in_megabytes(0)
+        .build();
+    let mut solver = create_pigeon_hole_solver(&options, 7, 6);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Timeout
+    ));
+
+    //a limit that is not reached does not affect the search
+    let options = SolverOptions::builder()
+        .memory_limit_in_megabytes(64)
+        .build();
+    let mut solver = create_pigeon_hole_solver(&options, 7, 6);
+    assert!(matches!(
+        solver.solve(i64::MAX),
+        CSPSolverExecutionFlag::Infeasible
+    ));
+}
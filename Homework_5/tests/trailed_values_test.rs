@@ -0,0 +1,48 @@
+use pumpkin::engine::{AssignmentsInteger, DomainManager, TrailedInteger, TrailedVec};
+
+#[test]
+fn trailed_values_revert_when_backtracking() {
+    let mut assignments = AssignmentsInteger::new();
+    let mut values = TrailedVec::new(vec![1, 2, 3]);
+
+    //changes at the root are kept
+    values.assign(0, 10, &DomainManager::new(0, &mut assignments));
+
+    assignments.increase_decision_level();
+    values.add(1, 5, &DomainManager::new(0, &mut assignments));
+    assignments.increase_decision_level();
+    values.assign(2, 0, &DomainManager::new(0, &mut assignments));
+    values.add(1, 1, &DomainManager::new(0, &mut assignments));
+    assert_eq!(values.read(1, &DomainManager::new(0, &mut assignments)), 8);
+    assert_eq!(values.read(2, &DomainManager::new(0, &mut assignments)), 0);
+
+    assignments.synchronise(1);
+    let domains = DomainManager::new(0, &mut assignments);
+    assert_eq!(values.read(1, &domains), 7);
+    assert_eq!(values.read(2, &domains), 3);
+
+    assignments.synchronise(0);
+    let domains = DomainManager::new(0, &mut assignments);
+    assert_eq!(values.read(0, &domains), 10);
+    assert_eq!(values.read(1, &domains), 2);
+}
+
+//a change is undone if its decision level was undone, even if the level was started again before the value is read
+#[test]
+fn trailed_values_revert_when_the_decision_level_is_started_again() {
+    let mut assignments = AssignmentsInteger::new();
+    let mut value = TrailedInteger::new(4);
+
+    assignments.increase_decision_level();
+    assignments.increase_decision_level();
+    value.add(3, &DomainManager::new(0, &mut assignments));
+
+    assignments.synchronise(0);
+    assignments.increase_decision_level();
+    assignments.increase_decision_level();
+    assert_eq!(value.read(&DomainManager::new(0, &mut assignments)), 4);
+
+    value.assign(9, &DomainManager::new(0, &mut assignments));
+    assignments.synchronise(1);
+    assert_eq!(value.read(&DomainManager::new(0, &mut assignments)), 4);
+}
@@ -0,0 +1,498 @@
+use pumpkin::{
+    basic_types::{PumpkinError, SolutionEnumerationFlag},
+    engine::SolverOptions,
+    flatzinc::{FlatZincSolver, SetLiteral, VariableType},
+    xcsp3::{format_instantiation, read_xcsp3},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn instance(variables: &str, constraints: &str, objectives: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+        <instance format=\"XCSP3\" type=\"CSP\">
+          <variables> {} </variables>
+          <constraints> {} </constraints>
+          {}
+        </instance>",
+        variables, constraints, objectives
+    )
+}
+
+//returns the values of the variables of the instance in all solutions, sorted
+fn get_all_solutions(document: &str) -> Vec<Vec<i64>> {
+    let model = read_xcsp3(document).unwrap();
+    let mut solver = FlatZincSolver::new(&model, &SolverOptions::default()).unwrap();
+    let mut solutions = vec![];
+    let flag = solver.solve(u64::MAX, false, |instance, solution| {
+        let values = instance.get_output_values(solution);
+        solutions.push(values.into_iter().map(|(_, value)| value).collect());
+    });
+    assert!(matches!(
+        flag,
+        SolutionEnumerationFlag::AllSolutionsFound { .. }
+    ));
+    solutions.sort();
+    solutions
+}
+
+//returns the assignments of the variables with the given domains that satisfy the predicate, sorted
+fn brute_force(domains: &[(i64, i64)], predicate: impl Fn(&[i64]) -> bool) -> Vec<Vec<i64>> {
+    let mut assignments = vec![vec![]];
+    for (lower_bound, upper_bound) in domains {
+        assignments = assignments
+            .into_iter()
+            .flat_map(|assignment: Vec<i64>| {
+                (*lower_bound..=*upper_bound).map(move |value| {
+                    let mut extended_assignment = assignment.clone();
+                    extended_assignment.push(value);
+                    extended_assignment
+                })
+            })
+            .collect();
+    }
+    assignments.retain(|assignment| predicate(assignment));
+    assignments
+}
+
+#[test]
+fn variables_and_arrays_are_declared() {
+    let model = read_xcsp3(&instance(
+        "<var id=\"a\"> 1 3..5 8 </var>
+        <var id=\"b\" as=\"a\"/>
+        <array id=\"x\" size=\"[2][2]\">
+          <domain for=\"x[0][]\"> 0..1 </domain>
+          <domain for=\"others\"> 2..3 </domain>
+        </array>
+        <array id=\"y\" size=\"[3]\">
+          <domain for=\"y[0] y[2]\"> 0..9 </domain>
+        </array>",
+        "",
+        "",
+    ))
+    .unwrap();
+
+    let names: Vec<&str> = model
+        .variables
+        .iter()
+        .map(|variable| variable.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec!["a", "b", "x[0][0]", "x[0][1]", "x[1][0]", "x[1][1]", "y[0]", "y[2]"]
+    );
+    assert_eq!(
+        model.variables[1].variable_type,
+        VariableType::Int(Some(SetLiteral::Values(vec![1, 3, 4, 5, 8])))
+    );
+    assert_eq!(
+        model.variables[3].variable_type,
+        VariableType::Int(Some(SetLiteral::Range(0, 1)))
+    );
+    assert_eq!(
+        model.variables[4].variable_type,
+        VariableType::Int(Some(SetLiteral::Range(2, 3)))
+    );
+    assert!(model
+        .variables
+        .iter()
+        .all(|variable| variable.annotations[0].name == "output_var"));
+}
+
+#[test]
+fn unsupported_features_are_flagged() {
+    let error = read_xcsp3(&instance(
+        "<array id=\"x\" size=\"[3]\"> 0..2 </array>",
+        "<circuit> x[] </circuit>",
+        "",
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        &error,
+        PumpkinError::UnsupportedFileFormat { message } if message.contains("circuit")
+    ));
+
+    let error = read_xcsp3(&instance(
+        "<var id=\"s\" type=\"symbolic\"> a b </var>",
+        "",
+        "",
+    ))
+    .unwrap_err();
+    assert!(matches!(error, PumpkinError::UnsupportedFileFormat { .. }));
+
+    //errors in the instance itself are not unsupported features
+    let error = read_xcsp3(&instance(
+        "<var id=\"x\"> 0..2 </var>",
+        "<intension> eq(x,y) </intension>",
+        "",
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        &error,
+        PumpkinError::InvalidModel { message } if message.contains("'y'")
+    ));
+
+    //malformed documents are reported at the position where the error was found
+    let error = read_xcsp3("<instance>\n  <variables> </instance>").unwrap_err();
+    assert!(matches!(
+        error,
+        PumpkinError::Parse {
+            line: 2,
+            column: 25,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn solutions_are_formatted_as_instantiations() {
+    let model = read_xcsp3(&instance(
+        "<array id=\"x\" size=\"[2]\"> 0..5 </array>
+        <var id=\"y\"> 0..5 </var>",
+        "<intension> eq(add(x[0],x[1]),y) </intension>
+        <instantiation> <list> x[] </list> <values> 2 3 </values> </instantiation>",
+        "<objectives> <maximize type=\"sum\"> <list> x[1] y </list> <coeffs> 1 2 </coeffs> </maximize> </objectives>",
+    ))
+    .unwrap();
+    let mut solver = FlatZincSolver::new(&model, &SolverOptions::default()).unwrap();
+    let mut instantiations = vec![];
+    let flag = solver.solve(1, false, |instance, solution| {
+        instantiations.push(format_instantiation(instance, solution))
+    });
+
+    assert!(matches!(
+        flag,
+        SolutionEnumerationFlag::AllSolutionsFound { .. }
+    ));
+    assert_eq!(
+        instantiations,
+        vec![
+            "<instantiation type=\"optimum\" cost=\"13\">\n  <list> x[0] x[1] y </list>\n  <values> 2 3 5 </values>\n</instantiation>"
+        ]
+    );
+}
+
+#[test]
+fn optimisation_finds_the_optimum() {
+    //minimise the largest end of three tasks that cannot overlap, where the second task must follow the first
+    let model = read_xcsp3(&instance(
+        "<array id=\"s\" size=\"[3]\"> 0..10 </array>",
+        "<noOverlap> <origins> s[] </origins> <lengths> 2 3 4 </lengths> </noOverlap>
+        <intension> le(add(s[0],2),s[1]) </intension>",
+        "<objectives> <minimize type=\"maximum\"> <list> s[0] s[1] s[2] </list> </minimize> </objectives>",
+    ))
+    .unwrap();
+    let mut solver = FlatZincSolver::new(&model, &SolverOptions::default()).unwrap();
+    let mut objective_values = vec![];
+    let flag = solver.solve(1, true, |instance, solution| {
+        objective_values.push(instance.get_objective_value(solution).unwrap())
+    });
+
+    //the latest start is smallest if the longest task comes last, i.e., starts at 5
+    assert!(matches!(
+        flag,
+        SolutionEnumerationFlag::AllSolutionsFound { .. }
+    ));
+    assert_eq!(*objective_values.last().unwrap(), 5);
+    assert!(objective_values.windows(2).all(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn global_constraints_have_as_many_solutions_as_brute_force() {
+    let variables = "<array id=\"x\" size=\"[4]\"> 0..3 </array>";
+    let domains = [(0, 3); 4];
+
+    let solutions = get_all_solutions(&instance(
+        variables,
+        "<allDifferent> x[0..2] </allDifferent>
+        <ordered> <list> x[2] x[3] </list> <operator> le </operator> </ordered>",
+        "",
+    ));
+    let expected = brute_force(&domains, |x| {
+        x[0] != x[1] && x[0] != x[2] && x[1] != x[2] && x[2] <= x[3]
+    });
+    assert_eq!(solutions, expected);
+
+    let solutions = get_all_solutions(&instance(
+        variables,
+        "<sum> <list> x[] </list> <coeffs> 1 -2 3 1 </coeffs> <condition> (in,2..4) </condition> </sum>
+        <sum> <list> x[0] x[3] </list> <condition> (ne,x[1]) </condition> </sum>",
+        "",
+    ));
+    let expected = brute_force(&domains, |x| {
+        (2..=4).contains(&(x[0] - 2 * x[1] + 3 * x[2] + x[3])) && x[0] + x[3] != x[1]
+    });
+    assert_eq!(solutions, expected);
+
+    let solutions = get_all_solutions(&instance(
+        variables,
+        "<element> <list startIndex=\"1\"> x[1] x[2] 2 </list> <index> x[0] </index> <value> x[3] </value> </element>
+        <maximum> <list> x[1] x[2] </list> <condition> (le,2) </condition> </maximum>",
+        "",
+    ));
+    let expected = brute_force(&domains, |x| {
+        let list = [x[1], x[2], 2];
+        (1..=3).contains(&x[0]) && list[x[0] as usize - 1] == x[3] && x[1].max(x[2]) <= 2
+    });
+    assert_eq!(solutions, expected);
+
+    let solutions = get_all_solutions(&instance(
+        variables,
+        "<element> <list> x[0] x[1] </list> <value> x[2] </value> </element>
+        <minimum> <list> x[0] x[1] x[3] </list> <condition> (notin,{0,2}) </condition> </minimum>",
+        "",
+    ));
+    let expected = brute_force(&domains, |x| {
+        (x[2] == x[0] || x[2] == x[1]) && ![0, 2].contains(&x[0].min(x[1]).min(x[3]))
+    });
+    assert_eq!(solutions, expected);
+
+    let solutions = get_all_solutions(&instance(
+        variables,
+        "<cumulative>
+          <origins> x[] </origins> <lengths> 2 1 3 0 </lengths> <heights> 1 2 1 3 </heights>
+          <condition> (le,2) </condition>
+        </cumulative>",
+        "",
+    ));
+    let tasks = [(2, 1), (1, 2), (3, 1)];
+    let expected = brute_force(&domains, |x| {
+        (0..6).all(|t| {
+            let usage: i64 = tasks
+                .iter()
+                .zip(x)
+                .filter(|((duration, _), start)| **start <= t && t < **start + duration)
+                .map(|((_, height), _)| height)
+                .sum();
+            usage <= 2
+        })
+    });
+    assert_eq!(solutions, expected);
+}
+
+#[test]
+fn tables_have_as_many_solutions_as_brute_force() {
+    let variables = "<array id=\"x\" size=\"[3]\"> 0..2 </array>";
+    let domains = [(0, 2); 3];
+    let tuples = [[0, 1, -1], [1, -1, 2], [2, 2, 2], [2, 0, 1], [3, 0, 0]];
+    let tuples_text: String = tuples
+        .iter()
+        .map(|tuple| {
+            let values: Vec<String> = tuple
+                .iter()
+                .map(|value| match value {
+                    -1 => "*".to_owned(),
+                    value => value.to_string(),
+                })
+                .collect();
+            format!("({})", values.join(","))
+        })
+        .collect();
+    let matches = |x: &[i64], tuple: &[i64; 3]| {
+        x.iter()
+            .zip(tuple)
+            .all(|(value, other)| *other == -1 || value == other)
+    };
+
+    let solutions = get_all_solutions(&instance(
+        variables,
+        &format!(
+            "<extension> <list> x[] </list> <supports> {} </supports> </extension>
+            <extension> <list> x[1] </list> <conflicts> 1 </conflicts> </extension>",
+            tuples_text
+        ),
+        "",
+    ));
+    let expected = brute_force(&domains, |x| {
+        tuples.iter().any(|tuple| matches(x, tuple)) && x[1] != 1
+    });
+    assert_eq!(solutions, expected);
+
+    let solutions = get_all_solutions(&instance(
+        variables,
+        &format!(
+            "<extension> <list> x[] </list> <conflicts> {} </conflicts> </extension>",
+            tuples_text
+        ),
+        "",
+    ));
+    let expected = brute_force(&domains, |x| !tuples.iter().any(|tuple| matches(x, tuple)));
+    assert_eq!(solutions, expected);
+}
+
+#[test]
+fn groups_and_blocks_are_expanded() {
+    let solutions = get_all_solutions(&instance(
+        "<array id=\"x\" size=\"[2][2]\"> 0..2 </array>",
+        "<block>
+          <group>
+            <intension> lt(%0,%1) </intension>
+            <args> x[0][0] x[0][1] </args>
+            <args> x[1][0] x[1][1] </args>
+          </group>
+          <group>
+            <allEqual> %... </allEqual>
+            <args> x[][0] </args>
+          </group>
+        </block>",
+        "",
+    ));
+    let expected = brute_force(&[(0, 2); 4], |x| x[0] < x[1] && x[2] < x[3] && x[0] == x[2]);
+    assert_eq!(solutions, expected);
+}
+
+//a random functional expression over the variables x, y, and z
+enum RandomExpression {
+    Variable(usize),
+    Int(i64),
+    Call(&'static str, Vec<RandomExpression>),
+}
+
+impl RandomExpression {
+    fn generate_integer(rng: &mut StdRng, depth: u32) -> RandomExpression {
+        if depth == 0 || rng.gen_bool(0.3) {
+            return if rng.gen_bool(0.8) {
+                RandomExpression::Variable(rng.gen_range(0..3))
+            } else {
+                RandomExpression::Int(rng.gen_range(-2..=2))
+            };
+        }
+
+        let integer = |rng: &mut StdRng| RandomExpression::generate_integer(rng, depth - 1);
+        match rng.gen_range(0..9) {
+            0 => RandomExpression::Call("add", vec![integer(rng), integer(rng), integer(rng)]),
+            1 => RandomExpression::Call("sub", vec![integer(rng), integer(rng)]),
+            2 => RandomExpression::Call("mul", vec![integer(rng), integer(rng)]),
+            3 => RandomExpression::Call("abs", vec![integer(rng)]),
+            4 => RandomExpression::Call("dist", vec![integer(rng), integer(rng)]),
+            5 => {
+                let name = if rng.gen_bool(0.5) { "min" } else { "max" };
+                RandomExpression::Call(name, vec![integer(rng), integer(rng)])
+            }
+            6 => {
+                let name = if rng.gen_bool(0.5) { "div" } else { "mod" };
+                let divisor = RandomExpression::Int(rng.gen_range(1..=3));
+                RandomExpression::Call(name, vec![integer(rng), divisor])
+            }
+            7 => RandomExpression::Call(
+                "if",
+                vec![
+                    RandomExpression::generate_boolean(rng, depth - 1),
+                    integer(rng),
+                    integer(rng),
+                ],
+            ),
+            _ => RandomExpression::Call("neg", vec![integer(rng)]),
+        }
+    }
+
+    fn generate_boolean(rng: &mut StdRng, depth: u32) -> RandomExpression {
+        let comparisons = ["lt", "le", "ge", "gt", "eq", "ne"];
+        if depth == 0 || rng.gen_bool(0.4) {
+            let name = comparisons[rng.gen_range(0..comparisons.len())];
+            return RandomExpression::Call(
+                name,
+                vec![
+                    RandomExpression::generate_integer(rng, depth.saturating_sub(1)),
+                    RandomExpression::generate_integer(rng, depth.saturating_sub(1)),
+                ],
+            );
+        }
+
+        let boolean = |rng: &mut StdRng| RandomExpression::generate_boolean(rng, depth - 1);
+        match rng.gen_range(0..7) {
+            0 => RandomExpression::Call("and", vec![boolean(rng), boolean(rng)]),
+            1 => RandomExpression::Call("or", vec![boolean(rng), boolean(rng), boolean(rng)]),
+            2 => RandomExpression::Call("not", vec![boolean(rng)]),
+            3 => RandomExpression::Call("xor", vec![boolean(rng), boolean(rng)]),
+            4 => RandomExpression::Call("iff", vec![boolean(rng), boolean(rng)]),
+            5 => RandomExpression::Call("imp", vec![boolean(rng), boolean(rng)]),
+            _ => {
+                let name = if rng.gen_bool(0.5) { "in" } else { "notin" };
+                let set = RandomExpression::Call(
+                    "set",
+                    vec![RandomExpression::Int(-1), RandomExpression::Int(2)],
+                );
+                RandomExpression::Call(
+                    name,
+                    vec![RandomExpression::generate_integer(rng, depth - 1), set],
+                )
+            }
+        }
+    }
+
+    fn format(&self) -> String {
+        match self {
+            RandomExpression::Variable(index) => ["x", "y", "z"][*index].to_owned(),
+            RandomExpression::Int(value) => value.to_string(),
+            RandomExpression::Call(name, arguments) => {
+                let arguments: Vec<String> = arguments.iter().map(|a| a.format()).collect();
+                format!("{}({})", name, arguments.join(","))
+            }
+        }
+    }
+
+    //evaluates the expression, where booleans are zero or one, and division and remainder truncate as in FlatZinc
+    fn evaluate(&self, values: &[i64]) -> i64 {
+        let (name, arguments) = match self {
+            RandomExpression::Variable(index) => return values[*index],
+            RandomExpression::Int(value) => return *value,
+            RandomExpression::Call(name, arguments) => (name, arguments),
+        };
+        //the only set is {-1,2}, which is left out of the evaluated arguments
+        let arguments: Vec<i64> = arguments
+            .iter()
+            .filter(|a| !matches!(a, RandomExpression::Call("set", _)))
+            .map(|a| a.evaluate(values))
+            .collect();
+        let is_true = |value: i64| value != 0;
+        match *name {
+            "add" => arguments.iter().sum(),
+            "sub" => arguments[0] - arguments[1],
+            "mul" => arguments[0] * arguments[1],
+            "abs" => arguments[0].abs(),
+            "dist" => (arguments[0] - arguments[1]).abs(),
+            "min" => arguments[0].min(arguments[1]),
+            "max" => arguments[0].max(arguments[1]),
+            "div" => arguments[0] / arguments[1],
+            "mod" => arguments[0] % arguments[1],
+            "if" if is_true(arguments[0]) => arguments[1],
+            "if" => arguments[2],
+            "neg" => -arguments[0],
+            "lt" => (arguments[0] < arguments[1]) as i64,
+            "le" => (arguments[0] <= arguments[1]) as i64,
+            "ge" => (arguments[0] >= arguments[1]) as i64,
+            "gt" => (arguments[0] > arguments[1]) as i64,
+            "eq" => (arguments[0] == arguments[1]) as i64,
+            "ne" => (arguments[0] != arguments[1]) as i64,
+            "and" => arguments.iter().all(|a| is_true(*a)) as i64,
+            "or" => arguments.iter().any(|a| is_true(*a)) as i64,
+            "not" => !is_true(arguments[0]) as i64,
+            "xor" => (is_true(arguments[0]) != is_true(arguments[1])) as i64,
+            "iff" => (is_true(arguments[0]) == is_true(arguments[1])) as i64,
+            "imp" => (!is_true(arguments[0]) || is_true(arguments[1])) as i64,
+            "in" | "notin" => {
+                let is_member = [-1, 2].contains(&arguments[0]);
+                (is_member == (*name == "in")) as i64
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn random_intension_constraints_have_as_many_solutions_as_brute_force() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let domains = [(-2, 2), (0, 3), (-3, 1)];
+    for _ in 0..60 {
+        let constraint = RandomExpression::generate_boolean(&mut rng, 3);
+        let document = instance(
+            "<var id=\"x\"> -2..2 </var> <var id=\"y\"> 0..3 </var> <var id=\"z\"> -3..1 </var>",
+            &format!("<intension> {} </intension>", constraint.format()),
+            "",
+        );
+
+        let solutions = get_all_solutions(&document);
+        let expected = brute_force(&domains, |values| constraint.evaluate(values) != 0);
+        assert_eq!(solutions, expected, "{}", constraint.format());
+    }
+}